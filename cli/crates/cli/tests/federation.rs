@@ -787,3 +787,30 @@ async fn test_batch_requests() {
     ]
     "###);
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[cfg(not(target_os = "windows"))] // tsconfig setup doesn't work on windows :(
+async fn test_unsupported_method_is_not_allowed() {
+    let mut env = Environment::init_async().await;
+
+    env.grafbase_init(GraphType::Federated);
+    env.set_typescript_config(
+        r#"
+        import { config, graph } from '@grafbase/sdk'
+
+        export default config({
+            graph: graph.Federated(),
+        })
+        "#,
+    );
+    env.prepare_ts_config_dependencies();
+    env.grafbase_dev_watch();
+
+    let client = env.create_async_client().with_api_key();
+    client.poll_endpoint(30, 300).await;
+
+    let response = reqwest::Client::new().put(&env.endpoint).send().await.unwrap();
+
+    assert_eq!(response.status(), reqwest::StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(response.headers()["allow"], "GET, POST, OPTIONS");
+}
@@ -6,7 +6,7 @@ use async_graphql::{EmptySubscription, Schema};
 use async_graphql_axum::GraphQL;
 use axum::{
     extract::{Query, State},
-    http::HeaderMap,
+    http::{header, HeaderMap, StatusCode},
     response::{Html, IntoResponse},
     routing::get,
     Json,
@@ -100,7 +100,7 @@ pub(super) async fn run(
 
     let app = axum::Router::new()
         .route("/admin", get(admin).post_service(GraphQL::new(admin_schema)))
-        .route("/graphql", get(engine_get).post(engine_post))
+        .route("/graphql", get(engine_get).post(engine_post).fallback(not_allowed))
         .route_service("/ws", WebsocketService::new(websocket_sender))
         .nest_service("/static", tower_http::services::ServeDir::new(static_asset_path))
         .layer(grafbase_telemetry::tower::layer(
@@ -166,6 +166,13 @@ async fn engine_post(
     handle_engine_request(request, gateway, headers).await
 }
 
+/// Any method other than `GET` and `POST` on the GraphQL endpoint, `OPTIONS` being answered by
+/// the CORS layer.
+#[allow(clippy::unused_async)]
+async fn not_allowed() -> impl IntoResponse {
+    (StatusCode::METHOD_NOT_ALLOWED, [(header::ALLOW, "GET, POST, OPTIONS")])
+}
+
 async fn handle_engine_request(
     request: engine::BatchRequest,
     engine: EngineWatcher,
@@ -139,6 +139,10 @@ impl engine_v2::Runtime for CliRuntime {
     fn sleep(&self, duration: std::time::Duration) -> BoxFuture<'static, ()> {
         tokio::time::sleep(duration).boxed()
     }
+
+    fn spawn(&self, future: BoxFuture<'static, ()>) {
+        tokio::spawn(future);
+    }
 }
 
 #[derive(Debug)]
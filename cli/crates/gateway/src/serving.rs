@@ -9,7 +9,7 @@ use axum::{
 use bytes::Bytes;
 use futures_util::future::{join_all, BoxFuture};
 use gateway_core::{encode_stream_response, StreamingFormat};
-use http::{HeaderMap, StatusCode};
+use http::{header, HeaderMap, StatusCode};
 use tokio::sync::mpsc::{self, UnboundedReceiver};
 use tower_http::cors::CorsLayer;
 
@@ -17,7 +17,13 @@ use crate::{Context, Gateway, Response};
 
 pub(super) fn router(gateway: Gateway) -> Router {
     Router::new()
-        .route("/graphql", post(post_graphql).options(options_any).get(get_graphql))
+        .route(
+            "/graphql",
+            post(post_graphql)
+                .options(options_any)
+                .get(get_graphql)
+                .fallback(not_allowed),
+        )
         .with_state(gateway)
         .layer(grafbase_telemetry::tower::layer(
             grafbase_telemetry::metrics::meter_from_global_provider(),
@@ -25,6 +31,10 @@ pub(super) fn router(gateway: Gateway) -> Router {
         .layer(CorsLayer::permissive())
 }
 
+async fn not_allowed() -> impl IntoResponse {
+    (StatusCode::METHOD_NOT_ALLOWED, [(header::ALLOW, "GET, POST, OPTIONS")])
+}
+
 async fn post_graphql(State(gateway): State<Gateway>, headers: HeaderMap, body: Bytes) -> crate::Response {
     use gateway_core::ConstructableResponse as _;
 
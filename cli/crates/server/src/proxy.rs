@@ -8,7 +8,7 @@ use async_tungstenite::tungstenite::client::IntoClientRequest;
 use axum::{
     body::Body,
     extract::{ws::WebSocket, State, WebSocketUpgrade},
-    http::HeaderValue,
+    http::{header, HeaderValue},
     response::{Html, IntoResponse, Response},
     routing::{get, head, post},
     Router,
@@ -89,9 +89,10 @@ async fn start_inner(listener: TcpListener) -> Result<(), ServerError> {
 
     let router = Router::new()
         .route("/", get(root))
-        .route("/graphql", get(graphql))
-        .route("/graphql", head(graphql))
-        .route("/graphql", post(graphql))
+        .route(
+            "/graphql",
+            get(graphql).head(graphql).post(graphql).fallback(not_allowed),
+        )
         .route("/admin", get(admin))
         .route("/admin", head(admin))
         .route("/admin", post(admin))
@@ -129,6 +130,12 @@ async fn graphql(
     graphql_inner(client, req, "graphql").await
 }
 
+/// The worker is only ever proxied the methods it supports.
+#[allow(clippy::unused_async)]
+async fn not_allowed() -> impl IntoResponse {
+    (StatusCode::METHOD_NOT_ALLOWED, [(header::ALLOW, "GET, HEAD, POST")])
+}
+
 async fn admin(
     State(ProxyState { client, .. }): State<ProxyState>,
     req: Request<Body>,
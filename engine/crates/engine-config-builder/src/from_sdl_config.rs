@@ -14,7 +14,7 @@ use engine_v2_config::{
 };
 use federated_graph::{FederatedGraph, FederatedGraphV3, FieldId, ObjectId, SubgraphId};
 use parser_sdl::federation::header::SubgraphHeaderRule;
-use parser_sdl::federation::{EntityCachingConfig, FederatedGraphConfig};
+use parser_sdl::federation::{EntityCachingConfig, FederatedGraphConfig, SubgraphCacheConfig};
 use parser_sdl::{AuthV2Provider, GlobalCacheTarget};
 
 pub fn build_with_sdl_config(config: &FederatedGraphConfig, graph: FederatedGraph) -> VersionedConfig {
@@ -30,6 +30,15 @@ pub fn build_with_sdl_config(config: &FederatedGraphConfig, graph: FederatedGrap
         context.insert_rate_limit(config);
     }
 
+    let nullable_fields = config
+        .nullable_fields
+        .iter()
+        .filter_map(|field| {
+            let (object_name, field_name) = field.split_once('.')?;
+            graph.find_object_field(object_name, field_name)
+        })
+        .collect();
+
     VersionedConfig::V5(config::Config {
         graph,
         strings: context.strings.into_vec(),
@@ -41,15 +50,147 @@ pub fn build_with_sdl_config(config: &FederatedGraphConfig, graph: FederatedGrap
         auth: build_auth_config(config),
         operation_limits: build_operation_limits(config),
         disable_introspection: config.disable_introspection,
+        introspection_cache_max_age: config.introspection_cache_max_age,
+        response_cache_ttl: config.response_cache_ttl,
+        response_cache_bypass_headers: config.response_cache_bypass_headers.clone(),
         rate_limit: context.rate_limit,
         timeout: config.timeout,
+        max_execution_depth: config.max_execution_depth,
+        request_deduplication: config.request_deduplication,
+        validate_response_nullability: config.validate_response_nullability,
+        max_error_extension_size: config.max_error_extension_size,
+        max_logged_subgraph_request_size: config.max_logged_subgraph_request_size,
+        max_forwarded_header_bytes: config.max_forwarded_header_bytes,
+        max_variable_values: config.max_variable_values,
+        max_variable_depth: config.max_variable_depth,
+        operation_cache_limit: config.operation_cache_limit,
+        max_subscriptions_per_connection: config.max_subscriptions_per_connection,
+        max_concurrent_subscriptions: config.max_concurrent_subscriptions,
+        max_retries_per_operation: config.max_retries_per_operation,
+        required_headers: config.required_headers.clone(),
+        pretty_responses: config.pretty_responses,
+        max_parse_time: config.max_parse_time,
+        max_serialization_time: config.max_serialization_time,
+        max_string_length: config.max_string_length,
+        oversized_strings: match config.oversized_strings {
+            parser_sdl::federation::OversizedStringPolicy::Truncate => config::OversizedStringPolicy::Truncate,
+            parser_sdl::federation::OversizedStringPolicy::Error => config::OversizedStringPolicy::Error,
+        },
+        max_error_path_depth: config.max_error_path_depth,
+        incompatible_accept: match config.incompatible_accept {
+            parser_sdl::federation::IncompatibleAcceptPolicy::Reject => config::IncompatibleAcceptPolicy::Reject,
+            parser_sdl::federation::IncompatibleAcceptPolicy::Ignore => config::IncompatibleAcceptPolicy::Ignore,
+        },
+        log_rejected_operations: config.log_rejected_operations,
+        max_entities_per_operation: config.max_entities_per_operation,
+        subgraph_trace_headers: config.subgraph_trace_headers.clone(),
+        nullable_fields,
+        scalar_formats: config
+            .scalar_formats
+            .iter()
+            .map(|(name, format)| {
+                let format = match format {
+                    parser_sdl::federation::ScalarFormat::DateTime => config::ScalarFormat::DateTime,
+                    parser_sdl::federation::ScalarFormat::Uuid => config::ScalarFormat::Uuid,
+                };
+                (name.clone(), format)
+            })
+            .collect(),
+        shadow_gateway_url: config.shadow_gateway_url.clone(),
+        shadow_gateway_headers: config.shadow_gateway_headers.clone(),
+        duplicate_operation_names: match config.duplicate_operation_names {
+            parser_sdl::federation::DuplicateOperationNames::Allow => config::DuplicateOperationNames::Allow,
+            parser_sdl::federation::DuplicateOperationNames::Reject => config::DuplicateOperationNames::Reject,
+            parser_sdl::federation::DuplicateOperationNames::Disambiguate => {
+                config::DuplicateOperationNames::Disambiguate
+            }
+        },
+        non_finite_floats: match config.non_finite_floats {
+            parser_sdl::federation::NonFiniteFloats::Null => config::NonFiniteFloats::Null,
+            parser_sdl::federation::NonFiniteFloats::String => config::NonFiniteFloats::String,
+            parser_sdl::federation::NonFiniteFloats::Error => config::NonFiniteFloats::Error,
+        },
         entity_caching: match config.entity_caching {
-            EntityCachingConfig::Enabled { ttl, .. } => EntityCaching::Enabled { ttl },
+            EntityCachingConfig::Enabled {
+                ttl, stale_if_error, ..
+            } => EntityCaching::Enabled { ttl, stale_if_error },
             _ => EntityCaching::Disabled,
         },
+        subgraph_cache: build_subgraph_cache(config.subgraph_cache),
+        clients: config
+            .clients
+            .iter()
+            .map(|(name, client)| {
+                (
+                    name.clone(),
+                    config::ClientConfig {
+                        allowed_operation_prefixes: client.allowed_operation_prefixes.clone(),
+                        response_transforms: client
+                            .response_transforms
+                            .iter()
+                            .map(build_response_transform)
+                            .collect(),
+                        max_subgraphs_per_operation: client.max_subgraphs_per_operation,
+                        cost_budget: client.cost_budget.map(|budget| config::CostBudget {
+                            capacity: budget.capacity,
+                            refill_per_second: budget.refill_per_second,
+                        }),
+                        response_key_casing: client.response_key_casing.map(|casing| match casing {
+                            parser_sdl::federation::ResponseKeyCasing::SnakeCase => {
+                                config::ResponseKeyCasing::SnakeCase
+                            }
+                            parser_sdl::federation::ResponseKeyCasing::CamelCase => {
+                                config::ResponseKeyCasing::CamelCase
+                            }
+                        }),
+                        min_version: client.min_version.clone(),
+                    },
+                )
+            })
+            .collect(),
+        client_identification: config::ClientIdentification {
+            name_headers: config.client_identification.name_headers.clone(),
+            version_headers: config.client_identification.version_headers.clone(),
+        },
+        tracked_operations: config
+            .tracked_operations
+            .as_ref()
+            .map(|tracked| config::TrackedOperations {
+                max: tracked.max,
+                allow_list: tracked.allow_list.clone(),
+            }),
+        tenants: config.tenants.as_ref().map(|tenants| config::Tenants {
+            header: tenants.header.clone(),
+            profiles: tenants.profiles.clone(),
+        }),
     })
 }
 
+fn build_subgraph_cache(config: SubgraphCacheConfig) -> config::SubgraphCaching {
+    match config {
+        SubgraphCacheConfig::Disabled => config::SubgraphCaching::Disabled,
+        SubgraphCacheConfig::Enabled { ttl } => config::SubgraphCaching::Enabled { ttl },
+    }
+}
+
+fn build_response_transform(transform: &parser_sdl::federation::ResponseTransform) -> config::ResponseTransform {
+    let split = |path: &str| -> Vec<String> { path.split('.').map(str::to_string).collect() };
+
+    match transform {
+        parser_sdl::federation::ResponseTransform::Rename { path, to } => config::ResponseTransform::Rename {
+            path: split(path),
+            to: to.clone(),
+        },
+        parser_sdl::federation::ResponseTransform::Flatten { path } => {
+            config::ResponseTransform::Flatten { path: split(path) }
+        }
+        parser_sdl::federation::ResponseTransform::Wrap { path, key } => config::ResponseTransform::Wrap {
+            path: split(path),
+            key: key.clone(),
+        },
+    }
+}
+
 fn build_operation_limits(config: &FederatedGraphConfig) -> OperationLimits {
     let parsed_operation_limits = &config.operation_limits;
     OperationLimits {
@@ -58,6 +199,10 @@ fn build_operation_limits(config: &FederatedGraphConfig) -> OperationLimits {
         aliases: parsed_operation_limits.aliases,
         root_fields: parsed_operation_limits.root_fields,
         complexity: parsed_operation_limits.complexity,
+        fragment_depth: parsed_operation_limits.fragment_depth,
+        fragment_spreads: parsed_operation_limits.fragment_spreads,
+        fields: parsed_operation_limits.fields,
+        aliased_fetches: parsed_operation_limits.aliased_fetches,
     }
 }
 
@@ -170,15 +315,37 @@ impl<'a> BuildContext<'a> {
 
             let parser_sdl::federation::SubgraphConfig {
                 websocket_url,
+                shadow_url,
+                shadow_danger_accept_invalid_certs,
                 header_rules,
                 rate_limit,
                 timeout,
                 entity_caching,
+                subgraph_cache,
+                extra_fields,
+                scalar_coercion,
+                null_data,
+                unknown_typename,
+                type_mismatch,
+                max_query_depth,
+                url_template,
+                danger_accept_invalid_certs,
+                entities_operation_prefix,
+                max_concurrent_requests,
+                concurrency_fairness,
+                response_transform,
+                cost_weight,
+                entity_batch_size,
+                field_timeouts,
                 ..
             } = config;
 
             let headers = self.insert_headers(header_rules.iter());
             let websocket_url = websocket_url.as_ref().map(|url| self.strings.intern(url));
+            let shadow_url = shadow_url.as_ref().map(|url| self.strings.intern(url));
+            let entities_operation_prefix = entities_operation_prefix
+                .as_ref()
+                .map(|prefix| self.strings.intern(prefix));
             let subgraph_name = self.strings.intern(name);
 
             let rate_limit = rate_limit.as_ref().map(|config| config::GraphRateLimit {
@@ -192,27 +359,121 @@ impl<'a> BuildContext<'a> {
                      ttl,
                      retry_percent,
                      retry_mutations,
+                     max_attempts,
+                     base_delay,
                  }| config::RetryConfig {
                     min_per_second: *min_per_second,
                     ttl: *ttl,
                     retry_percent: *retry_percent,
                     retry_mutations: *retry_mutations,
+                    max_attempts: *max_attempts,
+                    base_delay: *base_delay,
                 },
             );
 
+            let field_timeouts = field_timeouts
+                .iter()
+                .filter_map(|(field, timeout)| {
+                    let (object_name, field_name) = field.split_once('.')?;
+                    Some((graph.find_object_field(object_name, field_name)?, *timeout))
+                })
+                .collect();
+
+            let url_template = url_template.as_ref().map(|template| config::UrlTemplate {
+                url: self.strings.intern(&template.url),
+                variables: template
+                    .variables
+                    .iter()
+                    .map(|(name, variable)| config::UrlTemplateVariable {
+                        name: self.strings.intern(name),
+                        header: self.strings.intern(&variable.header),
+                        allowed_values: variable
+                            .allowed_values
+                            .iter()
+                            .map(|value| self.strings.intern(value))
+                            .collect(),
+                    })
+                    .collect(),
+            });
+
+            let response_transform = response_transform.as_ref().map(|transform| config::ResponseTransform {
+                unwrap: transform
+                    .unwrap
+                    .iter()
+                    .flat_map(|path| path.strip_prefix('$').unwrap_or(path).split('.'))
+                    .filter(|key| !key.is_empty())
+                    .map(|key| self.strings.intern(key))
+                    .collect(),
+                rename: transform
+                    .rename
+                    .iter()
+                    .map(|(from, to)| (self.strings.intern(from), self.strings.intern(to)))
+                    .collect(),
+            });
+
+            let request_compression =
+                config
+                    .request_compression
+                    .as_ref()
+                    .map(|config| config::RequestCompressionConfig {
+                        threshold: config.threshold,
+                    });
+
             self.subgraph_configs.insert(
                 subgraph_id,
                 config::SubgraphConfig {
                     name: subgraph_name,
                     headers,
                     websocket_url,
+                    shadow_url,
+                    shadow_danger_accept_invalid_certs: *shadow_danger_accept_invalid_certs,
                     rate_limit,
                     timeout: *timeout,
                     retry,
+                    request_compression,
                     entity_caching: entity_caching.as_ref().map(|config| match config {
                         EntityCachingConfig::Disabled => EntityCaching::Disabled,
-                        EntityCachingConfig::Enabled { ttl, .. } => EntityCaching::Enabled { ttl: *ttl },
+                        EntityCachingConfig::Enabled {
+                            ttl, stale_if_error, ..
+                        } => EntityCaching::Enabled {
+                            ttl: *ttl,
+                            stale_if_error: *stale_if_error,
+                        },
                     }),
+                    subgraph_cache: subgraph_cache.map(build_subgraph_cache),
+                    extra_fields: match extra_fields {
+                        parser_sdl::federation::ExtraFieldsPolicy::Ignore => config::ExtraFieldsPolicy::Ignore,
+                        parser_sdl::federation::ExtraFieldsPolicy::Warn => config::ExtraFieldsPolicy::Warn,
+                    },
+                    scalar_coercion: match scalar_coercion {
+                        parser_sdl::federation::ScalarCoercion::Strict => config::ScalarCoercion::Strict,
+                        parser_sdl::federation::ScalarCoercion::Lenient => config::ScalarCoercion::Lenient,
+                    },
+                    null_data: match null_data {
+                        parser_sdl::federation::NullDataPolicy::Allow => config::NullDataPolicy::Allow,
+                        parser_sdl::federation::NullDataPolicy::Error => config::NullDataPolicy::Error,
+                    },
+                    unknown_typename: match unknown_typename {
+                        parser_sdl::federation::UnknownTypenamePolicy::Ignore => config::UnknownTypenamePolicy::Ignore,
+                        parser_sdl::federation::UnknownTypenamePolicy::Error => config::UnknownTypenamePolicy::Error,
+                    },
+                    type_mismatch: match type_mismatch {
+                        parser_sdl::federation::TypeMismatchPolicy::Error => config::TypeMismatchPolicy::Error,
+                        parser_sdl::federation::TypeMismatchPolicy::Ignore => config::TypeMismatchPolicy::Ignore,
+                    },
+                    max_query_depth: *max_query_depth,
+                    url_template,
+                    danger_accept_invalid_certs: *danger_accept_invalid_certs,
+                    entities_operation_prefix,
+                    max_concurrent_requests: *max_concurrent_requests,
+                    concurrency_fairness: match concurrency_fairness {
+                        parser_sdl::federation::ConcurrencyFairness::Fifo => config::ConcurrencyFairness::Fifo,
+                        parser_sdl::federation::ConcurrencyFairness::Fair => config::ConcurrencyFairness::Fair,
+                    },
+                    response_transform,
+                    cost_weight: *cost_weight,
+                    entity_batch_size: *entity_batch_size,
+                    field_timeouts,
                 },
             );
         }
@@ -263,7 +524,7 @@ impl<'a> BuildContext<'a> {
     }
 }
 
-trait FederatedGraphExt {
+pub(crate) trait FederatedGraphExt {
     fn find_subgraph(&self, name: &str) -> Option<SubgraphId>;
     fn find_object(&self, name: &str) -> Option<ObjectId>;
     fn find_object_field(&self, object_name: &str, field_name: &str) -> Option<FieldId>;
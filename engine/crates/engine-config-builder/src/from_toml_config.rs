@@ -3,7 +3,9 @@ use federated_graph::FederatedGraph;
 use gateway_config::Config;
 use parser_sdl::federation::{header::SubgraphHeaderRule, FederatedGraphConfig};
 
-use crate::build_with_sdl_config;
+use crate::{build_with_sdl_config, from_sdl_config::FederatedGraphExt};
+
+const DEFAULT_RESPONSE_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(60);
 
 pub fn build_with_toml_config(config: &Config, graph: FederatedGraph) -> VersionedConfig {
     let mut graph_config = FederatedGraphConfig::default();
@@ -17,7 +19,62 @@ pub fn build_with_toml_config(config: &Config, graph: FederatedGraph) -> Version
     }
 
     graph_config.timeout = config.gateway.timeout;
+    graph_config.max_execution_depth = config.gateway.max_execution_depth;
+    graph_config.request_deduplication = config.gateway.request_deduplication;
+    graph_config.validate_response_nullability = config.gateway.validate_response_nullability;
+    graph_config.max_error_extension_size = config.gateway.max_error_extension_size;
+    graph_config.max_logged_subgraph_request_size = config.gateway.max_logged_subgraph_request_size;
+    graph_config.max_forwarded_header_bytes = config.gateway.max_forwarded_header_bytes;
+    graph_config.max_variable_values = config.gateway.max_variable_values;
+    graph_config.max_variable_depth = config.gateway.max_variable_depth;
+    graph_config.operation_cache_limit = config.gateway.operation_cache_limit;
+    graph_config.max_subscriptions_per_connection = config.gateway.max_subscriptions_per_connection;
+    graph_config.max_concurrent_subscriptions = config.gateway.max_concurrent_subscriptions;
+    graph_config.max_retries_per_operation = config.gateway.max_retries_per_operation;
+    graph_config.required_headers = config.gateway.required_headers.clone();
+    graph_config.pretty_responses = config.gateway.pretty_responses;
+    graph_config.max_parse_time = config.gateway.max_parse_time;
+    graph_config.max_serialization_time = config.gateway.max_serialization_time;
+    graph_config.max_string_length = config.gateway.max_string_length;
+    graph_config.oversized_strings = config.gateway.oversized_strings.into();
+    graph_config.max_error_path_depth = config.gateway.max_error_path_depth;
+    graph_config.incompatible_accept = config.gateway.incompatible_accept.into();
+    graph_config.log_rejected_operations = config.gateway.log_rejected_operations;
+    graph_config.max_entities_per_operation = config.gateway.max_entities_per_operation;
+    graph_config.nullable_fields = config.gateway.nullable_fields.clone();
+    graph_config.scalar_formats = config
+        .gateway
+        .scalar_formats
+        .iter()
+        .map(|(name, format)| (name.clone(), (*format).into()))
+        .collect();
+    graph_config.shadow_gateway_url = config.gateway.shadow_gateway.as_ref().map(|url| url.to_string());
+    graph_config.shadow_gateway_headers = config.gateway.shadow_gateway_headers.clone();
+    graph_config.duplicate_operation_names = config.gateway.batching.duplicate_operation_names.into();
+    graph_config.non_finite_floats = config.gateway.non_finite_floats.into();
+    graph_config.client_identification = config.gateway.client_identification.clone().into();
+    graph_config.tracked_operations = config
+        .telemetry
+        .as_ref()
+        .and_then(|telemetry| telemetry.metrics.as_ref())
+        .and_then(parser_sdl::federation::TrackedOperations::from_config);
+    graph_config.subgraph_trace_headers = config
+        .telemetry
+        .as_ref()
+        .map(|telemetry| telemetry.tracing.subgraph_headers.clone())
+        .unwrap_or_default();
+    graph_config.tenants = config.gateway.tenants.clone().map(Into::into);
     graph_config.disable_introspection = !config.graph.introspection;
+    graph_config.introspection_cache_max_age = config.graph.introspection_cache_max_age;
+    graph_config.response_cache_ttl = config
+        .response_cache
+        .enabled
+        .then(|| config.response_cache.ttl.unwrap_or(DEFAULT_RESPONSE_CACHE_TTL));
+    graph_config.response_cache_bypass_headers = config
+        .response_cache
+        .bypass_headers
+        .clone()
+        .unwrap_or_else(|| vec!["authorization".to_string()]);
     graph_config.header_rules = config
         .headers
         .clone()
@@ -27,6 +84,14 @@ pub fn build_with_toml_config(config: &Config, graph: FederatedGraph) -> Version
     graph_config.rate_limit = config.gateway.rate_limit.clone().map(Into::into);
 
     graph_config.entity_caching = config.entity_caching.clone().into();
+    graph_config.subgraph_cache = config.subgraph_cache.clone().into();
+
+    graph_config.clients = config
+        .clients
+        .clone()
+        .into_iter()
+        .map(|(name, client)| (name, client.into()))
+        .collect();
 
     graph_config.subgraphs = config
         .subgraphs
@@ -42,11 +107,55 @@ pub fn build_with_toml_config(config: &Config, graph: FederatedGraph) -> Version
             let config = parser_sdl::federation::SubgraphConfig {
                 name: name.clone(),
                 websocket_url: subgraph_config.websocket_url.map(|url| url.to_string()),
+                shadow_url: subgraph_config.shadow_subgraph.map(|url| url.to_string()),
+                shadow_danger_accept_invalid_certs: subgraph_config.shadow_danger_accept_invalid_certs,
                 header_rules,
                 development_url: None,
                 rate_limit: subgraph_config.rate_limit.map(Into::into),
                 timeout: subgraph_config.timeout,
                 entity_caching: subgraph_config.entity_caching.map(Into::into),
+                subgraph_cache: subgraph_config.subgraph_cache.map(Into::into),
+                extra_fields: subgraph_config.extra_fields.into(),
+                scalar_coercion: subgraph_config.scalar_coercion.into(),
+                null_data: subgraph_config.null_data.into(),
+                unknown_typename: subgraph_config.unknown_typename.into(),
+                type_mismatch: subgraph_config.type_mismatch.into(),
+                max_query_depth: subgraph_config.max_query_depth,
+                url_template: subgraph_config.url_template.map(|template| {
+                    parser_sdl::federation::SubgraphUrlTemplate {
+                        url: template.url,
+                        variables: template
+                            .variables
+                            .into_iter()
+                            .map(|(name, variable)| {
+                                (
+                                    name,
+                                    parser_sdl::federation::SubgraphUrlVariable {
+                                        header: variable.header,
+                                        allowed_values: variable.allowed_values,
+                                    },
+                                )
+                            })
+                            .collect(),
+                    }
+                }),
+                danger_accept_invalid_certs: subgraph_config.danger_accept_invalid_certs,
+                entities_operation_prefix: subgraph_config.entities_operation_prefix,
+                max_concurrent_requests: subgraph_config.max_concurrent_requests,
+                concurrency_fairness: subgraph_config.concurrency_fairness.into(),
+                response_transform: subgraph_config.response_transform.map(|transform| {
+                    parser_sdl::federation::SubgraphResponseTransform {
+                        unwrap: transform.unwrap,
+                        rename: transform.rename,
+                    }
+                }),
+                cost_weight: subgraph_config.cost_weight,
+                entity_batch_size: subgraph_config.entity_batch_size,
+                field_timeouts: subgraph_config
+                    .fields
+                    .into_iter()
+                    .filter_map(|(field, config)| Some((field, config.timeout?)))
+                    .collect(),
                 retry: subgraph_config
                     .retry
                     .enabled
@@ -55,7 +164,14 @@ pub fn build_with_toml_config(config: &Config, graph: FederatedGraph) -> Version
                         ttl: subgraph_config.retry.ttl,
                         retry_percent: subgraph_config.retry.retry_percent,
                         retry_mutations: subgraph_config.retry.retry_mutations,
+                        max_attempts: subgraph_config.retry.max_attempts,
+                        base_delay: subgraph_config.retry.base_delay,
                     }),
+                request_compression: subgraph_config.request_compression.enabled.then_some(
+                    parser_sdl::federation::RequestCompressionConfig {
+                        threshold: subgraph_config.request_compression.threshold,
+                    },
+                ),
             };
 
             (name, config)
@@ -64,3 +180,33 @@ pub fn build_with_toml_config(config: &Config, graph: FederatedGraph) -> Version
 
     build_with_sdl_config(&graph_config, graph)
 }
+
+/// Parts of the configuration which don't match the federated graph. They don't prevent the
+/// gateway from running, but are ignored and thus likely mistakes.
+pub fn config_warnings(config: &Config, graph: &FederatedGraph) -> Vec<String> {
+    let graph = graph.clone().into_latest();
+    let mut warnings = Vec::new();
+
+    for (name, subgraph) in &config.subgraphs {
+        if graph.find_subgraph(name).is_none() {
+            warnings.push(format!(
+                "Subgraph '{name}' is configured but isn't part of the federated graph"
+            ));
+            continue;
+        }
+
+        for field in subgraph.fields.keys() {
+            let exists = field
+                .split_once('.')
+                .and_then(|(object_name, field_name)| graph.find_object_field(object_name, field_name))
+                .is_some();
+            if !exists {
+                warnings.push(format!(
+                    "Field '{field}' configured for subgraph '{name}' doesn't exist in the federated graph"
+                ));
+            }
+        }
+    }
+
+    warnings
+}
@@ -12,6 +12,24 @@ pub fn bad_request_error(message: &str) -> axum::response::Response {
     into_response(HttpGraphqlResponse::bad_request_error(message))
 }
 
+/// Error response for requests with a body exceeding the configured limit, with a `413` status.
+pub fn request_too_large(message: &str) -> axum::response::Response {
+    (
+        axum::http::StatusCode::PAYLOAD_TOO_LARGE,
+        into_response(HttpGraphqlResponse::request_too_large(message)),
+    )
+        .into_response()
+}
+
+/// Error response for requests received while no engine is available yet, with a `503` status.
+pub fn service_unavailable(message: &str) -> axum::response::Response {
+    (
+        axum::http::StatusCode::SERVICE_UNAVAILABLE,
+        into_response(HttpGraphqlResponse::service_unavailable(message)),
+    )
+        .into_response()
+}
+
 pub fn into_response(response: HttpGraphqlResponse) -> axum::response::Response {
     let HttpGraphqlResponse { headers, body, .. } = response;
 
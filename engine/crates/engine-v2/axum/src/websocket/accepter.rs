@@ -2,7 +2,7 @@ use std::{collections::HashMap, sync::Arc};
 
 use ::axum::extract::ws::{self, WebSocket};
 use engine_v2::{websocket::InitPayload, Engine, Runtime, Session};
-use futures_util::{pin_mut, stream::SplitStream, SinkExt, Stream, StreamExt};
+use futures_util::{pin_mut, stream::SplitStream, Sink, SinkExt, Stream, StreamExt};
 use tokio::sync::{mpsc, watch};
 
 use super::service::MessageConvert;
@@ -116,7 +116,11 @@ async fn handle_incoming_event<R: Runtime>(
                 return Some(Message::close(4409, format!("Subscriber for {id} already exists")));
             }
 
-            let stream = session.execute_websocket(id.clone(), payload);
+            // Rejected subscriptions aren't registered, their id may be used again.
+            let stream = match session.execute_websocket(id.clone(), payload) {
+                Ok(stream) => stream,
+                Err(message) => return Some(message),
+            };
             let handle = tasks.spawn(subscription_loop(stream, id.clone(), sender.clone()));
             subscriptions.insert(id, handle);
 
@@ -149,45 +153,53 @@ async fn subscription_loop(stream: impl Stream<Item = Message>, id: String, send
 }
 
 async fn accept_websocket<R: Runtime>(websocket: &mut WebSocket, engine: &EngineWatcher<R>) -> Option<Session<R>> {
-    while let Some(text) = websocket.recv_message().await {
-        let event: Event = serde_json::from_str(&text).ok()?;
-        match event {
-            Event::ConnectionInit {
-                payload: InitPayload { headers },
-            } => {
-                let Some(engine) = engine.borrow().clone() else {
-                    websocket
-                        .send(
-                            Message::close(4995, "register a subgraph before connecting")
-                                .to_axum_message()
-                                .unwrap(),
-                        )
-                        .await
-                        .ok();
-                    return None;
-                };
+    let InitPayload { headers } = wait_for_connection_init(websocket).await?;
 
-                let Ok(session) = engine.create_session(headers).await else {
-                    websocket
-                        .send(Message::close(4403, "Forbidden").to_axum_message().unwrap())
-                        .await
-                        .ok();
-                    return None;
-                };
+    let Some(engine) = engine.borrow().clone() else {
+        websocket
+            .send(
+                Message::close(4995, "register a subgraph before connecting")
+                    .to_axum_message()
+                    .unwrap(),
+            )
+            .await
+            .ok();
+        return None;
+    };
 
-                websocket
-                    .send(Message::ConnectionAck { payload: None }.to_axum_message().unwrap())
-                    .await
-                    .ok()?;
+    let Ok(session) = engine.create_session(headers).await else {
+        websocket
+            .send(Message::close(4403, "Forbidden").to_axum_message().unwrap())
+            .await
+            .ok();
+        return None;
+    };
 
-                return Some(session);
-            }
+    websocket
+        .send(Message::ConnectionAck { payload: None }.to_axum_message().unwrap())
+        .await
+        .ok()?;
+
+    Some(session)
+}
+
+/// Reads messages until the client sends `connection_init`, answering the ones that are
+/// allowed beforehand.
+async fn wait_for_connection_init<S>(websocket: &mut S) -> Option<InitPayload>
+where
+    S: WebsocketExt + Sink<ws::Message> + Unpin,
+{
+    while let Some(text) = websocket.recv_message().await {
+        let event: Event = serde_json::from_str(&text).ok()?;
+        match event {
+            Event::ConnectionInit { payload } => return Some(payload),
+            // Clients may check the connection is alive before initialising it.
             Event::Ping { .. } => {
                 websocket
                     .send(
-                        Message::Ping { payload: None }
+                        Message::Pong { payload: None }
                             .to_axum_message()
-                            .expect("ping should always be serializable"),
+                            .expect("pong should always be serializable"),
                     )
                     .await
                     .ok()?;
@@ -237,3 +249,67 @@ impl WebsocketExt for SplitStream<WebSocket> {
         self.next().await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::VecDeque,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    use super::*;
+
+    #[derive(Default)]
+    struct FakeSocket {
+        incoming: VecDeque<ws::Message>,
+        sent: Vec<ws::Message>,
+    }
+
+    impl WebsocketExt for FakeSocket {
+        async fn recv(&mut self) -> Option<Result<ws::Message, ::axum::Error>> {
+            self.incoming.pop_front().map(Ok)
+        }
+    }
+
+    impl Sink<ws::Message> for FakeSocket {
+        type Error = ::axum::Error;
+
+        fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn start_send(self: Pin<&mut Self>, item: ws::Message) -> Result<(), Self::Error> {
+            self.get_mut().sent.push(item);
+            Ok(())
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn ping_before_connection_init_is_answered_with_pong() {
+        let mut socket = FakeSocket {
+            incoming: VecDeque::from([
+                ws::Message::Text(r#"{"type":"ping"}"#.into()),
+                ws::Message::Text(r#"{"type":"connection_init"}"#.into()),
+            ]),
+            ..Default::default()
+        };
+
+        let payload = wait_for_connection_init(&mut socket).await;
+
+        assert!(payload.is_some());
+        let [ws::Message::Text(pong)] = socket.sent.as_slice() else {
+            panic!("expected a single text message, got {:?}", socket.sent);
+        };
+        let pong: serde_json::Value = serde_json::from_str(pong).unwrap();
+        assert_eq!(pong, serde_json::json!({"type": "pong"}));
+    }
+}
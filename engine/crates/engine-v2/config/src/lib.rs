@@ -138,9 +138,46 @@ impl VersionedConfig {
                     auth,
                     operation_limits,
                     disable_introspection,
+                    introspection_cache_max_age: None,
+                    response_cache_ttl: None,
+                    response_cache_bypass_headers: Vec::new(),
                     rate_limit: Default::default(),
                     timeout: None,
+                    max_execution_depth: None,
+                    request_deduplication: false,
+                    validate_response_nullability: false,
+                    max_error_extension_size: None,
+                    max_logged_subgraph_request_size: None,
+                    max_forwarded_header_bytes: None,
+                    max_variable_values: None,
+                    max_variable_depth: None,
+                    operation_cache_limit: None,
+                    max_subscriptions_per_connection: None,
+                    max_concurrent_subscriptions: None,
+                    max_retries_per_operation: None,
+                    required_headers: Vec::new(),
+                    pretty_responses: false,
+                    max_parse_time: None,
+                    max_serialization_time: None,
+                    max_string_length: None,
+                    oversized_strings: Default::default(),
+                    max_error_path_depth: None,
+                    incompatible_accept: Default::default(),
+                    log_rejected_operations: false,
+                    max_entities_per_operation: None,
+                    subgraph_trace_headers: Default::default(),
+                    nullable_fields: Vec::new(),
+                    scalar_formats: Default::default(),
+                    shadow_gateway_url: None,
+                    shadow_gateway_headers: Vec::new(),
+                    duplicate_operation_names: Default::default(),
+                    non_finite_floats: Default::default(),
                     entity_caching: Default::default(),
+                    subgraph_cache: Default::default(),
+                    clients: Default::default(),
+                    client_identification: Default::default(),
+                    tracked_operations: None,
+                    tenants: None,
                 }
             }
             VersionedConfig::V5(latest) => latest,
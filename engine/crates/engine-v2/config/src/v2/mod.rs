@@ -4,7 +4,7 @@ use std::{collections::BTreeMap, time::Duration};
 
 use crate::latest::GraphRateLimit;
 pub use cache_config::{CacheConfig, CacheConfigTarget, CacheConfigs};
-use federated_graph::{FederatedGraphV1, SubgraphId};
+use federated_graph::{FederatedGraphV1, FieldId, SubgraphId};
 pub use gateway_auth_config::v2::*;
 
 use crate::v5::HeaderRuleId;
@@ -17,6 +17,10 @@ pub struct OperationLimits {
     pub aliases: Option<u16>,
     pub root_fields: Option<u16>,
     pub complexity: Option<u16>,
+    pub fragment_depth: Option<u16>,
+    pub fragment_spreads: Option<u16>,
+    pub fields: Option<u16>,
+    pub aliased_fetches: Option<u16>,
 }
 
 /// Configuration for a federated graph
@@ -47,6 +51,12 @@ pub struct Config {
 pub struct SubgraphConfig {
     pub name: StringId,
     pub websocket_url: Option<StringId>,
+    /// Subgraph receiving a copy of the queries, whose responses are only compared.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shadow_url: Option<StringId>,
+    /// Skips the verification of the shadow subgraph TLS certificates.
+    #[serde(default)]
+    pub shadow_danger_accept_invalid_certs: bool,
     pub headers: Vec<HeaderRuleId>,
     #[serde(default)]
     pub rate_limit: Option<GraphRateLimit>,
@@ -54,12 +64,125 @@ pub struct SubgraphConfig {
     pub timeout: Option<Duration>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub retry: Option<RetryConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_compression: Option<RequestCompressionConfig>,
     #[serde(default)]
     pub entity_caching: Option<EntityCaching>,
+    /// Overrides the global subgraph cache config if present.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subgraph_cache: Option<SubgraphCaching>,
+    #[serde(default)]
+    pub extra_fields: ExtraFieldsPolicy,
+    #[serde(default)]
+    pub scalar_coercion: ScalarCoercion,
+    #[serde(default)]
+    pub null_data: NullDataPolicy,
+    #[serde(default)]
+    pub unknown_typename: UnknownTypenamePolicy,
+    #[serde(default)]
+    pub type_mismatch: TypeMismatchPolicy,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_query_depth: Option<u16>,
+    /// URL resolved for each request, overriding the one of the federated graph.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url_template: Option<UrlTemplate>,
+    /// Skips the verification of the subgraph TLS certificates.
+    #[serde(default)]
+    pub danger_accept_invalid_certs: bool,
+    /// Prefix of the operation name given to `_entities` queries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entities_operation_prefix: Option<StringId>,
+    /// Maximum number of requests in flight to the subgraph.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_requests: Option<usize>,
+    #[serde(default)]
+    pub concurrency_fairness: ConcurrencyFairness,
+    /// Reshaping of the subgraph responses before they're ingested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_transform: Option<ResponseTransform>,
+    /// Weight of the subgraph fields when apportioning the cost of operations between subgraphs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_weight: Option<f64>,
+    /// Maximum number of entities resolved by a single request, larger sets being split into batches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity_batch_size: Option<usize>,
+    /// Timeouts for specific root fields, taking precedence over the subgraph and gateway ones.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub field_timeouts: BTreeMap<FieldId, Duration>,
+}
+
+/// How to handle fields returned by a subgraph that weren't part of the request
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ExtraFieldsPolicy {
+    #[default]
+    Ignore,
+    Warn,
+}
+
+/// How strictly scalars returned by a subgraph must match their type
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ScalarCoercion {
+    #[default]
+    Strict,
+    /// Booleans may also be returned as `0`/`1` or `"0"`/`"1"`.
+    Lenient,
+}
+
+/// How to handle subgraph responses with `data: null` and no errors
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum NullDataPolicy {
+    #[default]
+    Allow,
+    /// Treated as an invalid response if any of the requested fields is non-null.
+    Error,
+}
+
+/// How to handle objects whose `__typename` isn't part of the schema
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum UnknownTypenamePolicy {
+    /// Written as an empty object.
+    #[default]
+    Ignore,
+    /// Treated as an invalid value.
+    Error,
+}
+
+/// Order in which requests waiting for a subgraph concurrency slot get one
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ConcurrencyFairness {
+    /// First come, first served.
+    #[default]
+    Fifo,
+    /// Operations take turns.
+    Fair,
+}
+
+/// How to handle objects whose `__typename` is a type of the schema which isn't a possible type
+/// of their field
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TypeMismatchPolicy {
+    /// Raises a `SUBGRAPH_TYPE_MISMATCH` error.
+    #[default]
+    Error,
+    /// Written as an empty object.
+    Ignore,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone, Copy)]
 pub enum EntityCaching {
+    #[default]
+    Disabled,
+    Enabled {
+        ttl: Option<Duration>,
+        /// Expired entries may still be served for this long if the subgraph request fails.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        stale_if_error: Option<Duration>,
+    },
+}
+
+/// Caching of whole subgraph query responses, keyed by subgraph, query and variables
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone, Copy)]
+pub enum SubgraphCaching {
     #[default]
     Disabled,
     Enabled {
@@ -67,12 +190,28 @@ pub enum EntityCaching {
     },
 }
 
+impl SubgraphCaching {
+    pub fn ttl(&self) -> Option<Duration> {
+        match self {
+            Self::Enabled { ttl } => Some(ttl.unwrap_or(DEFAULT_ENTITY_CACHE_TTL)),
+            Self::Disabled => None,
+        }
+    }
+}
+
 const DEFAULT_ENTITY_CACHE_TTL: Duration = Duration::from_secs(60);
 
 impl EntityCaching {
     pub fn ttl(&self) -> Option<Duration> {
         match self {
-            Self::Enabled { ttl } => Some(ttl.unwrap_or(DEFAULT_ENTITY_CACHE_TTL)),
+            Self::Enabled { ttl, .. } => Some(ttl.unwrap_or(DEFAULT_ENTITY_CACHE_TTL)),
+            _ => None,
+        }
+    }
+
+    pub fn stale_if_error(&self) -> Option<Duration> {
+        match self {
+            Self::Enabled { stale_if_error, .. } => *stale_if_error,
             _ => None,
         }
     }
@@ -88,6 +227,42 @@ pub struct RetryConfig {
     pub retry_percent: Option<f32>,
     /// Whether mutations should be retried at all. False by default.
     pub retry_mutations: Option<bool>,
+    /// Maximum number of attempts, the first one included. Only bounded by the retry budget by default.
+    pub max_attempts: Option<u32>,
+    /// Delay before the first retry, doubled for every subsequent one and randomized by a jitter. Default: 100ms.
+    pub base_delay: Option<Duration>,
+}
+
+/// A subgraph URL with `{name}` placeholders substituted from the request headers
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct UrlTemplate {
+    pub url: StringId,
+    pub variables: Vec<UrlTemplateVariable>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct UrlTemplateVariable {
+    pub name: StringId,
+    /// The request header providing the value.
+    pub header: StringId,
+    /// Any other header value is rejected.
+    pub allowed_values: Vec<StringId>,
+}
+
+/// Applied to the subgraph responses before they're deserialized
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct ResponseTransform {
+    /// Keys leading to the GraphQL response within the subgraph response, empty for the whole
+    /// response.
+    pub unwrap: Vec<StringId>,
+    /// Top-level keys of the GraphQL response to rename, from and to.
+    pub rename: Vec<(StringId, StringId)>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct RequestCompressionConfig {
+    /// Bodies smaller than this size in bytes are sent uncompressed.
+    pub threshold: Option<usize>,
 }
 
 /// A header that should be sent to a subgraph
@@ -216,9 +391,13 @@ mod tests {
               },
               "headers": [],
               "operation_limits": {
+                "aliasedFetches": null,
                 "aliases": null,
                 "complexity": null,
                 "depth": null,
+                "fields": null,
+                "fragmentDepth": null,
+                "fragmentSpreads": null,
                 "height": null,
                 "rootFields": null
               },
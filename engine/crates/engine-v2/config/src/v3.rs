@@ -160,9 +160,13 @@ mod tests {
               },
               "headers": [],
               "operation_limits": {
+                "aliasedFetches": null,
                 "aliases": null,
                 "complexity": null,
                 "depth": null,
+                "fields": null,
+                "fragmentDepth": null,
+                "fragmentSpreads": null,
                 "height": null,
                 "rootFields": null
               },
@@ -7,11 +7,14 @@ use std::{
     time::Duration,
 };
 
-use federated_graph::{FederatedGraphV3, SubgraphId};
+use federated_graph::{FederatedGraphV3, FieldId, SubgraphId};
 
 use self::rate_limit::{RateLimitConfigRef, RateLimitRedisConfigRef, RateLimitRedisTlsConfigRef};
 
-pub use super::v2::EntityCaching;
+pub use super::v2::{
+    ConcurrencyFairness, EntityCaching, ExtraFieldsPolicy, NullDataPolicy, RequestCompressionConfig, ResponseTransform,
+    ScalarCoercion, SubgraphCaching, TypeMismatchPolicy, UnknownTypenamePolicy, UrlTemplate, UrlTemplateVariable,
+};
 pub use super::v4::{
     AuthConfig, AuthProviderConfig, CacheConfig, CacheConfigTarget, CacheConfigs, Header, HeaderId, HeaderValue,
     JwksConfig, JwtConfig, OperationLimits, RetryConfig, StringId, SubgraphConfig,
@@ -48,14 +51,260 @@ pub struct Config {
     #[serde(default)]
     pub disable_introspection: bool,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub introspection_cache_max_age: Option<Duration>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_cache_ttl: Option<Duration>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub response_cache_bypass_headers: Vec<String>,
+
     #[serde(default)]
     pub rate_limit: Option<RateLimitConfig>,
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub timeout: Option<Duration>,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_execution_depth: Option<u16>,
+
+    #[serde(default)]
+    pub request_deduplication: bool,
+
+    #[serde(default)]
+    pub validate_response_nullability: bool,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_error_extension_size: Option<usize>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_logged_subgraph_request_size: Option<usize>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_forwarded_header_bytes: Option<usize>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_variable_values: Option<usize>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_variable_depth: Option<usize>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operation_cache_limit: Option<usize>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_subscriptions_per_connection: Option<usize>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_subscriptions: Option<usize>,
+
+    /// Retries of subgraph requests allowed within a single operation, across all subgraphs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_retries_per_operation: Option<usize>,
+
+    /// Names of the headers every request must have.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_headers: Vec<String>,
+
+    /// Whether the `x-grafbase-pretty` header may switch responses to pretty-printed JSON.
+    #[serde(default)]
+    pub pretty_responses: bool,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_parse_time: Option<Duration>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_serialization_time: Option<Duration>,
+
+    /// Default maximum size in bytes of the string values returned by subgraphs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_string_length: Option<usize>,
+
+    #[serde(default)]
+    pub oversized_strings: OversizedStringPolicy,
+
+    /// Maximum number of elements in the path of serialized errors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_error_path_depth: Option<usize>,
+
+    #[serde(default)]
+    pub incompatible_accept: IncompatibleAcceptPolicy,
+
+    /// Whether operations rejected before execution are logged for security monitoring.
+    #[serde(default)]
+    pub log_rejected_operations: bool,
+
+    /// Entities fetched from subgraphs beyond it fail the entity requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_entities_per_operation: Option<usize>,
+
+    /// Headers added to subgraph requests, with values templated from the trace context.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub subgraph_trace_headers: BTreeMap<String, String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub nullable_fields: Vec<FieldId>,
+
+    /// Formats validated for the values of custom scalars, by scalar name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub scalar_formats: BTreeMap<String, ScalarFormat>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shadow_gateway_url: Option<String>,
+
+    /// Client headers forwarded to the shadow gateway.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shadow_gateway_headers: Vec<String>,
+
+    #[serde(default)]
+    pub duplicate_operation_names: DuplicateOperationNames,
+
+    #[serde(default)]
+    pub non_finite_floats: NonFiniteFloats,
+
     #[serde(default)]
     pub entity_caching: EntityCaching,
+
+    /// Caching of whole subgraph query responses, subgraphs may override it.
+    #[serde(default)]
+    pub subgraph_cache: SubgraphCaching,
+
+    /// Client specific configuration, keyed by client name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub clients: BTreeMap<String, ClientConfig>,
+
+    #[serde(default)]
+    pub client_identification: ClientIdentification,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tracked_operations: Option<TrackedOperations>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenants: Option<Tenants>,
+}
+
+/// How to handle operations sharing the same name within a batch
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateOperationNames {
+    #[default]
+    Allow,
+    /// The whole batch is rejected.
+    Reject,
+    /// Operation names are suffixed with their index in the batch in traces and metrics.
+    Disambiguate,
+}
+
+/// What happens to string values exceeding their maximum length
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OversizedStringPolicy {
+    /// Cut at the limit, with an indicator appended.
+    #[default]
+    Truncate,
+    /// Treated as an invalid value.
+    Error,
+}
+
+/// What happens to incrementally delivered operations when the client only accepts a single
+/// response
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum IncompatibleAcceptPolicy {
+    /// Rejected before execution.
+    #[default]
+    Reject,
+    /// Executed, all the data being returned at once.
+    Ignore,
+}
+
+/// Format validated for the values of a custom scalar
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalarFormat {
+    /// An RFC 3339 date-time.
+    DateTime,
+    /// A hyphenated UUID.
+    Uuid,
+}
+
+/// How `Infinity` and `NaN` Float values are written in responses, as JSON cannot represent them
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum NonFiniteFloats {
+    /// Written as `null`, with an error for the field.
+    #[default]
+    Null,
+    /// Written as the strings `"Infinity"`, `"-Infinity"` and `"NaN"`.
+    String,
+    /// The response fails to serialize.
+    Error,
+}
+
+/// Headers identifying the client, checked in order after `x-grafbase-client-name` and
+/// `x-grafbase-client-version`
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone)]
+pub struct ClientIdentification {
+    pub name_headers: Vec<String>,
+    pub version_headers: Vec<String>,
+}
+
+/// Bounds the distinct operation names used for the `gql.operation.name` metric attribute
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone)]
+pub struct TrackedOperations {
+    /// Maximum number of distinct operation names, allowed ones included.
+    pub max: Option<usize>,
+    /// Operation names always tracked.
+    pub allow_list: Vec<String>,
+}
+
+/// Per-tenant visibility of the fields annotated with `@tag(name:)`
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone)]
+pub struct Tenants {
+    /// Header holding the tenant identifier.
+    pub header: String,
+    /// Tags visible to each tenant. Fields without any tag are visible to all.
+    pub profiles: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone)]
+pub struct ClientConfig {
+    /// If present, only operations whose name starts with one of those prefixes can be executed.
+    pub allowed_operation_prefixes: Option<Vec<String>>,
+    /// Transformations applied to the response data returned to the client.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub response_transforms: Vec<ResponseTransform>,
+    /// If present, operations touching more distinct subgraphs are rejected during planning.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_subgraphs_per_operation: Option<usize>,
+    /// If present, the cost of each operation is taken from this token bucket.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_budget: Option<CostBudget>,
+    /// If present, response keys are converted to this casing, except aliases and `__typename`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_key_casing: Option<ResponseKeyCasing>,
+    /// If present, requests from older versions of the client are rejected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_version: Option<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseKeyCasing {
+    SnakeCase,
+    CamelCase,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy)]
+pub struct CostBudget {
+    pub capacity: u64,
+    pub refill_per_second: u64,
+}
+
+/// A transformation of the response data, for the field at the given path of response keys
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub enum ResponseTransform {
+    /// The field is renamed.
+    Rename { path: Vec<String>, to: String },
+    /// The field is replaced by the fields of its object value.
+    Flatten { path: Vec<String> },
+    /// The field value is replaced by an object holding it under the key.
+    Wrap { path: Vec<String>, key: String },
 }
 
 impl Config {
@@ -71,9 +320,46 @@ impl Config {
             auth: Default::default(),
             operation_limits: Default::default(),
             disable_introspection: Default::default(),
+            introspection_cache_max_age: None,
+            response_cache_ttl: None,
+            response_cache_bypass_headers: Vec::new(),
             rate_limit: Default::default(),
             timeout: None,
+            max_execution_depth: None,
+            request_deduplication: false,
+            validate_response_nullability: false,
+            max_error_extension_size: None,
+            max_logged_subgraph_request_size: None,
+            max_forwarded_header_bytes: None,
+            max_variable_values: None,
+            max_variable_depth: None,
+            operation_cache_limit: None,
+            max_subscriptions_per_connection: None,
+            max_concurrent_subscriptions: None,
+            max_retries_per_operation: None,
+            required_headers: Vec::new(),
+            pretty_responses: false,
+            max_parse_time: None,
+            max_serialization_time: None,
+            max_string_length: None,
+            oversized_strings: Default::default(),
+            max_error_path_depth: None,
+            incompatible_accept: Default::default(),
+            log_rejected_operations: false,
+            max_entities_per_operation: None,
+            subgraph_trace_headers: Default::default(),
+            nullable_fields: Vec::new(),
+            scalar_formats: BTreeMap::new(),
+            shadow_gateway_url: None,
+            shadow_gateway_headers: Vec::new(),
+            duplicate_operation_names: Default::default(),
+            non_finite_floats: Default::default(),
             entity_caching: EntityCaching::Disabled,
+            subgraph_cache: SubgraphCaching::Disabled,
+            clients: Default::default(),
+            client_identification: Default::default(),
+            tracked_operations: None,
+            tenants: None,
         }
     }
 
@@ -187,9 +473,46 @@ mod tests {
             auth: None,
             operation_limits: Default::default(),
             disable_introspection: Default::default(),
+            introspection_cache_max_age: None,
+            response_cache_ttl: None,
+            response_cache_bypass_headers: Vec::new(),
             rate_limit: Default::default(),
             timeout: None,
+            max_execution_depth: None,
+            request_deduplication: false,
+            validate_response_nullability: false,
+            max_error_extension_size: None,
+            max_logged_subgraph_request_size: None,
+            max_forwarded_header_bytes: None,
+            max_variable_values: None,
+            max_variable_depth: None,
+            operation_cache_limit: None,
+            max_subscriptions_per_connection: None,
+            max_concurrent_subscriptions: None,
+            max_retries_per_operation: None,
+            required_headers: Vec::new(),
+            pretty_responses: false,
+            max_parse_time: None,
+            max_serialization_time: None,
+            max_string_length: None,
+            oversized_strings: Default::default(),
+            max_error_path_depth: None,
+            incompatible_accept: Default::default(),
+            log_rejected_operations: false,
+            max_entities_per_operation: None,
+            subgraph_trace_headers: Default::default(),
+            nullable_fields: Vec::new(),
+            scalar_formats: BTreeMap::new(),
+            shadow_gateway_url: None,
+            shadow_gateway_headers: Vec::new(),
+            duplicate_operation_names: Default::default(),
+            non_finite_floats: Default::default(),
             entity_caching: Default::default(),
+            subgraph_cache: Default::default(),
+            clients: Default::default(),
+            client_identification: Default::default(),
+            tracked_operations: None,
+            tenants: None,
         };
 
         insta::with_settings!({sort_maps => true}, {
@@ -210,8 +533,13 @@ mod tests {
                   }
                 }
               },
+              "client_identification": {
+                "name_headers": [],
+                "version_headers": []
+              },
               "default_header_rules": [],
               "disable_introspection": false,
+              "duplicate_operation_names": "Allow",
               "entity_caching": "Disabled",
               "graph": {
                 "authorized_directives": [],
@@ -237,17 +565,29 @@ mod tests {
                 "unions": []
               },
               "header_rules": [],
+              "incompatible_accept": "Reject",
+              "log_rejected_operations": false,
+              "non_finite_floats": "Null",
               "operation_limits": {
+                "aliasedFetches": null,
                 "aliases": null,
                 "complexity": null,
                 "depth": null,
+                "fields": null,
+                "fragmentDepth": null,
+                "fragmentSpreads": null,
                 "height": null,
                 "rootFields": null
               },
+              "oversized_strings": "Truncate",
               "paths": [],
+              "pretty_responses": false,
               "rate_limit": null,
+              "request_deduplication": false,
               "strings": [],
-              "subgraph_configs": {}
+              "subgraph_cache": "Disabled",
+              "subgraph_configs": {},
+              "validate_response_nullability": false
             }
             "###);
         });
@@ -21,4 +21,6 @@ impl SchemaLocation {
 pub enum BuildError {
     #[error("At {location}, a required field argument is invalid: {err}")]
     RequiredFieldArgumentCoercionError { location: String, err: InputValueError },
+    #[error("The URL template of subgraph '{subgraph}' is invalid: {reason}")]
+    InvalidUrlTemplate { subgraph: String, reason: String },
 }
@@ -2,20 +2,20 @@ use std::{mem::take, time::Duration};
 
 use config::latest::Config;
 
-use crate::sources;
+use crate::{sources, StringId};
 
-use super::BuildContext;
+use super::{BuildContext, BuildError};
 
 pub struct ExternalDataSources {
     pub graphql: sources::graphql::GraphqlEndpoints,
 }
 
 impl ExternalDataSources {
-    pub(super) fn build(ctx: &mut BuildContext, config: &mut Config) -> Self {
+    pub(super) fn build(ctx: &mut BuildContext, config: &mut Config) -> Result<Self, BuildError> {
         let endpoints = take(&mut config.graph.subgraphs)
             .into_iter()
             .enumerate()
-            .map(|(index, subgraph)| {
+            .map(|(index, subgraph)| -> Result<_, BuildError> {
                 let subgraph_id = ctx.next_subgraph_id();
                 let name = subgraph.name.into();
                 let url = ctx
@@ -24,52 +24,208 @@ impl ExternalDataSources {
                 match config.subgraph_configs.remove(&federated_graph::SubgraphId(index)) {
                     Some(config::latest::SubgraphConfig {
                         websocket_url,
+                        shadow_url,
+                        shadow_danger_accept_invalid_certs,
                         headers,
                         timeout,
                         retry,
+                        request_compression,
                         entity_caching,
+                        subgraph_cache,
+                        extra_fields,
+                        scalar_coercion,
+                        null_data,
+                        unknown_typename,
+                        type_mismatch,
+                        max_query_depth,
+                        url_template,
+                        danger_accept_invalid_certs,
+                        entities_operation_prefix,
+                        max_concurrent_requests,
+                        concurrency_fairness,
+                        response_transform,
+                        cost_weight,
+                        entity_batch_size,
+                        field_timeouts,
                         ..
-                    }) => sources::graphql::GraphqlEndpoint {
+                    }) => Ok(sources::graphql::GraphqlEndpoint {
                         name,
                         subgraph_id,
                         url,
                         websocket_url: websocket_url
                             .map(|url| ctx.urls.insert(url::Url::parse(&config[url]).expect("valid url"))),
+                        shadow_url: shadow_url
+                            .map(|url| ctx.urls.insert(url::Url::parse(&config[url]).expect("valid url"))),
+                        shadow_danger_accept_invalid_certs,
                         header_rules: headers.into_iter().map(Into::into).collect(),
                         timeout: timeout.unwrap_or(DEFAULT_SUBGRAPH_TIMEOUT),
+                        field_timeouts: {
+                            let mut field_timeouts = field_timeouts
+                                .into_iter()
+                                .filter_map(|(field_id, timeout)| Some((ctx.idmaps.field.get(field_id)?, timeout)))
+                                .collect::<Vec<_>>();
+                            field_timeouts.sort_unstable_by_key(|(field_id, _)| *field_id);
+                            field_timeouts
+                        },
                         retry: retry.map(
                             |config::latest::RetryConfig {
                                  min_per_second,
                                  ttl,
                                  retry_percent,
                                  retry_mutations,
+                                 max_attempts,
+                                 base_delay,
                              }| sources::graphql::RetryConfig {
                                 min_per_second,
                                 ttl,
                                 retry_percent,
                                 retry_mutations,
+                                max_attempts,
+                                base_delay,
                             },
                         ),
+                        request_compression_threshold: request_compression
+                            .map(|config| config.threshold.unwrap_or(DEFAULT_REQUEST_COMPRESSION_THRESHOLD)),
                         entity_cache_ttl: entity_caching.as_ref().unwrap_or(&config.entity_caching).ttl(),
-                    },
+                        entity_cache_stale_if_error: entity_caching
+                            .as_ref()
+                            .unwrap_or(&config.entity_caching)
+                            .stale_if_error(),
+                        subgraph_cache_ttl: subgraph_cache.as_ref().unwrap_or(&config.subgraph_cache).ttl(),
+                        warn_on_extra_fields: extra_fields == config::latest::ExtraFieldsPolicy::Warn,
+                        lenient_scalar_coercion: scalar_coercion == config::latest::ScalarCoercion::Lenient,
+                        error_on_null_data: null_data == config::latest::NullDataPolicy::Error,
+                        error_on_unknown_typename: unknown_typename == config::latest::UnknownTypenamePolicy::Error,
+                        error_on_type_mismatch: type_mismatch == config::latest::TypeMismatchPolicy::Error,
+                        max_query_depth,
+                        url_template: url_template
+                            .map(|template| build_url_template(ctx, config, name, template))
+                            .transpose()?,
+                        danger_accept_invalid_certs,
+                        entities_operation_prefix: entities_operation_prefix
+                            .map(|prefix| ctx.strings.get_or_new(&config[prefix])),
+                        max_concurrent_requests,
+                        fair_concurrency: concurrency_fairness == config::latest::ConcurrencyFairness::Fair,
+                        response_transform: response_transform.map(|transform| sources::graphql::ResponseTransform {
+                            unwrap: transform.unwrap.into_iter().map(|key| config[key].clone()).collect(),
+                            rename: transform
+                                .rename
+                                .into_iter()
+                                .map(|(from, to)| (config[from].clone(), config[to].clone()))
+                                .collect(),
+                        }),
+                        cost_weight: cost_weight.unwrap_or(DEFAULT_SUBGRAPH_COST_WEIGHT),
+                        entity_batch_size: entity_batch_size.filter(|size| *size > 0),
+                    }),
 
-                    None => sources::graphql::GraphqlEndpoint {
+                    None => Ok(sources::graphql::GraphqlEndpoint {
                         name,
                         subgraph_id,
                         url,
                         websocket_url: None,
+                        shadow_url: None,
+                        shadow_danger_accept_invalid_certs: false,
                         header_rules: Vec::new(),
                         timeout: DEFAULT_SUBGRAPH_TIMEOUT,
+                        field_timeouts: Vec::new(),
                         retry: None,
+                        request_compression_threshold: None,
                         entity_cache_ttl: config.entity_caching.ttl(),
-                    },
+                        entity_cache_stale_if_error: config.entity_caching.stale_if_error(),
+                        subgraph_cache_ttl: config.subgraph_cache.ttl(),
+                        warn_on_extra_fields: false,
+                        lenient_scalar_coercion: false,
+                        error_on_null_data: false,
+                        error_on_unknown_typename: false,
+                        error_on_type_mismatch: true,
+                        max_query_depth: None,
+                        url_template: None,
+                        danger_accept_invalid_certs: false,
+                        entities_operation_prefix: None,
+                        max_concurrent_requests: None,
+                        fair_concurrency: false,
+                        response_transform: None,
+                        cost_weight: DEFAULT_SUBGRAPH_COST_WEIGHT,
+                        entity_batch_size: None,
+                    }),
                 }
             })
-            .collect();
-        ExternalDataSources {
+            .collect::<Result<_, _>>()?;
+        Ok(ExternalDataSources {
             graphql: sources::GraphqlEndpoints { endpoints },
+        })
+    }
+}
+
+/// Splits the URL template into its literal parts and placeholders. Templates that could never
+/// resolve to a valid URL are rejected, rather than failing every request to the subgraph.
+fn build_url_template(
+    ctx: &mut BuildContext,
+    config: &Config,
+    subgraph_name: StringId,
+    template: config::latest::UrlTemplate,
+) -> Result<sources::graphql::UrlTemplate, BuildError> {
+    let invalid = |ctx: &BuildContext, reason: String| BuildError::InvalidUrlTemplate {
+        subgraph: ctx.strings[subgraph_name].to_string(),
+        reason,
+    };
+
+    let mut segments = Vec::new();
+    // The URL with each placeholder replaced by its first allowed value, to check that it's valid.
+    let mut example = String::new();
+    let mut rest = config[template.url].as_str();
+    let is_brace = |c: char| c == '{' || c == '}';
+    while let Some(start) = rest.find(is_brace) {
+        let end = match rest[start + 1..].find(is_brace) {
+            Some(end) if rest[start..].starts_with('{') && rest[start + 1 + end..].starts_with('}') => start + 1 + end,
+            _ => {
+                let reason = format!("unbalanced braces in '{}'", config[template.url]);
+                return Err(invalid(ctx, reason));
+            }
+        };
+        let name = &rest[start + 1..end];
+        let Some(variable) = template.variables.iter().find(|variable| config[variable.name] == name) else {
+            return Err(invalid(ctx, format!("no variable for the placeholder '{{{name}}}'")));
+        };
+        let Some(first_allowed_value) = variable.allowed_values.first() else {
+            return Err(invalid(
+                ctx,
+                format!("no allowed value for the placeholder '{{{name}}}'"),
+            ));
+        };
+
+        if start > 0 {
+            segments.push(sources::graphql::UrlTemplateSegment::Literal(
+                ctx.strings.get_or_new(&rest[..start]),
+            ));
         }
+        segments.push(sources::graphql::UrlTemplateSegment::Placeholder(
+            sources::graphql::UrlTemplateVariable {
+                name: ctx.strings.get_or_new(name),
+                header: ctx.strings.get_or_new(&config[variable.header]),
+                allowed_values: variable
+                    .allowed_values
+                    .iter()
+                    .map(|value| ctx.strings.get_or_new(&config[*value]))
+                    .collect(),
+            },
+        ));
+        example.push_str(&rest[..start]);
+        example.push_str(&config[*first_allowed_value]);
+        rest = &rest[end + 1..];
+    }
+    if !rest.is_empty() {
+        segments.push(sources::graphql::UrlTemplateSegment::Literal(
+            ctx.strings.get_or_new(rest),
+        ));
     }
+    example.push_str(rest);
+
+    url::Url::parse(&example).map_err(|err| invalid(ctx, format!("'{example}' is not a valid URL: {err}")))?;
+
+    Ok(sources::graphql::UrlTemplate { segments })
 }
 
 const DEFAULT_SUBGRAPH_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_SUBGRAPH_COST_WEIGHT: f64 = 1.0;
+const DEFAULT_REQUEST_COMPRESSION_THRESHOLD: usize = 1024;
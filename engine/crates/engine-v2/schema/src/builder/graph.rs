@@ -228,6 +228,7 @@ impl<'a> GraphBuilder<'a> {
                 Scalar {
                     name,
                     ty: ScalarType::from_scalar_name(&self.ctx.strings[name]),
+                    format: config.scalar_formats.get(&self.ctx.strings[name]).copied(),
                     description: None,
                     specified_by_url: None,
                     directives: self.push_directives(
@@ -660,6 +661,58 @@ impl<'a> GraphBuilder<'a> {
                         reason: reason.map(Into::into),
                     })
                 }
+                federated_graph::Directive::Other { name, arguments }
+                    if self.ctx.strings[StringId::from(*name)] == "feature" =>
+                {
+                    let Some(flag) = arguments.iter().find_map(|(name, value)| match value {
+                        federated_graph::Value::String(flag) if self.ctx.strings[StringId::from(*name)] == "name" => {
+                            Some(*flag)
+                        }
+                        _ => None,
+                    }) else {
+                        continue;
+                    };
+                    TypeSystemDirective::Feature(flag.into())
+                }
+                federated_graph::Directive::Other { name, arguments }
+                    if self.ctx.strings[StringId::from(*name)] == "maxStringLength" =>
+                {
+                    let Some(length) = arguments.iter().find_map(|(name, value)| match value {
+                        federated_graph::Value::Int(length) if self.ctx.strings[StringId::from(*name)] == "length" => {
+                            usize::try_from(*length).ok()
+                        }
+                        _ => None,
+                    }) else {
+                        continue;
+                    };
+                    TypeSystemDirective::MaxStringLength(length)
+                }
+                federated_graph::Directive::Other { name, arguments }
+                    if self.ctx.strings[StringId::from(*name)] == "priority" =>
+                {
+                    let Some(level) = arguments.iter().find_map(|(name, value)| match value {
+                        federated_graph::Value::Int(level) if self.ctx.strings[StringId::from(*name)] == "level" => {
+                            Some(*level)
+                        }
+                        _ => None,
+                    }) else {
+                        continue;
+                    };
+                    TypeSystemDirective::Priority(level)
+                }
+                federated_graph::Directive::Other { name, arguments }
+                    if self.ctx.strings[StringId::from(*name)] == "tag" =>
+                {
+                    let Some(tag) = arguments.iter().find_map(|(name, value)| match value {
+                        federated_graph::Value::String(tag) if self.ctx.strings[StringId::from(*name)] == "name" => {
+                            Some(*tag)
+                        }
+                        _ => None,
+                    }) else {
+                        continue;
+                    };
+                    TypeSystemDirective::Tag(tag.into())
+                }
                 federated_graph::Directive::Other { .. }
                 | federated_graph::Directive::Inaccessible
                 | federated_graph::Directive::Policy(_) => continue,
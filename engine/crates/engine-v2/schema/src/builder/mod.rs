@@ -29,7 +29,7 @@ impl TryFrom<Config> for Schema {
 
     fn try_from(mut config: Config) -> Result<Self, Self::Error> {
         let mut ctx = BuildContext::new(&mut config);
-        let sources = ExternalDataSources::build(&mut ctx, &mut config);
+        let sources = ExternalDataSources::build(&mut ctx, &mut config)?;
         let (graph, introspection) = GraphBuilder::build(&mut ctx, &sources, &mut config)?;
         let data_sources = DataSources {
             graphql: sources.graphql,
@@ -210,6 +210,13 @@ impl BuildContext {
             })
             .collect();
 
+        let mut nullable_fields: Vec<_> = config
+            .nullable_fields
+            .iter()
+            .filter_map(|id| self.idmaps.field.get(*id))
+            .collect();
+        nullable_fields.sort_unstable();
+
         let default_header_rules = config
             .default_header_rules
             .into_iter()
@@ -225,10 +232,56 @@ impl BuildContext {
             header_rules,
             settings: Settings {
                 timeout: config.timeout.unwrap_or(DEFAULT_GATEWAY_TIMEOUT),
+                max_execution_depth: config.max_execution_depth,
+                request_deduplication: config.request_deduplication,
+                validate_response_nullability: config.validate_response_nullability,
+                max_error_extension_size: config
+                    .max_error_extension_size
+                    .unwrap_or(DEFAULT_MAX_ERROR_EXTENSION_SIZE),
+                max_logged_subgraph_request_size: config
+                    .max_logged_subgraph_request_size
+                    .unwrap_or(DEFAULT_MAX_LOGGED_SUBGRAPH_REQUEST_SIZE),
+                max_forwarded_header_bytes: config.max_forwarded_header_bytes,
+                max_variable_values: config.max_variable_values,
+                max_variable_depth: config.max_variable_depth,
+                operation_cache_limit: config.operation_cache_limit.unwrap_or(DEFAULT_OPERATION_CACHE_LIMIT),
+                max_subscriptions_per_connection: config.max_subscriptions_per_connection,
+                max_concurrent_subscriptions: config.max_concurrent_subscriptions,
+                max_retries_per_operation: config.max_retries_per_operation,
+                required_headers: config
+                    .required_headers
+                    .iter()
+                    .map(|name| name.to_ascii_lowercase())
+                    .collect(),
+                pretty_responses: config.pretty_responses,
+                max_parse_time: config.max_parse_time,
+                max_serialization_time: config.max_serialization_time,
+                max_string_length: config.max_string_length,
+                oversized_strings: config.oversized_strings,
+                max_error_path_depth: config.max_error_path_depth,
+                incompatible_accept: config.incompatible_accept,
+                log_rejected_operations: config.log_rejected_operations,
+                max_entities_per_operation: config.max_entities_per_operation,
+                subgraph_trace_headers: take(&mut config.subgraph_trace_headers).into_iter().collect(),
+                nullable_fields,
+                shadow_gateway_url: config
+                    .shadow_gateway_url
+                    .as_deref()
+                    .map(|url| url::Url::parse(url).expect("valid url")),
+                shadow_gateway_headers: take(&mut config.shadow_gateway_headers),
+                duplicate_operation_names: config.duplicate_operation_names,
+                non_finite_floats: config.non_finite_floats,
                 default_header_rules,
                 auth_config: take(&mut config.auth),
                 operation_limits: take(&mut config.operation_limits),
                 disable_introspection: config.disable_introspection,
+                introspection_cache_max_age: config.introspection_cache_max_age,
+                response_cache_ttl: config.response_cache_ttl,
+                response_cache_bypass_headers: take(&mut config.response_cache_bypass_headers),
+                clients: take(&mut config.clients),
+                client_identification: take(&mut config.client_identification),
+                tracked_operations: take(&mut config.tracked_operations),
+                tenants: take(&mut config.tenants),
             },
         })
     }
@@ -261,3 +314,6 @@ from_id_newtypes! {
 }
 
 const DEFAULT_GATEWAY_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_MAX_ERROR_EXTENSION_SIZE: usize = 64 * 1024;
+const DEFAULT_MAX_LOGGED_SUBGRAPH_REQUEST_SIZE: usize = 4 * 1024;
+const DEFAULT_OPERATION_CACHE_LIMIT: usize = 1000;
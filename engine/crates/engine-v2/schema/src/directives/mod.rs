@@ -15,6 +15,16 @@ pub enum TypeSystemDirective {
     RequiresScopes(RequiredScopesId),
     CacheControl(CacheControlId),
     Authorized(AuthorizedDirectiveId),
+    /// `@feature(name:)`, only selectable by requests enabling this feature flag.
+    Feature(StringId),
+    /// `@maxStringLength(length:)`, maximum size in bytes of the string values of the field.
+    MaxStringLength(usize),
+    /// `@priority(level:)`, subgraph requests for the field get a slot before those of lower
+    /// levels when the subgraph concurrency limit is reached. The default level is 0.
+    Priority(i64),
+    /// `@tag(name:)`, only visible to the tenants whose profile includes this tag when tenants
+    /// are configured.
+    Tag(StringId),
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -20,7 +20,7 @@ pub enum InputValue<'a> {
     String(&'a str),
     EnumValue(EnumValueId),
     Int(i32),
-    BigInt(i64),
+    BigInt(i128),
     Float(f64),
     Boolean(bool),
     // There is no guarantee on the ordering.
@@ -11,7 +11,7 @@ impl<'a> From<SchemaInputValueWalker<'a>> for InputValue<'a> {
             SchemaInputValue::String(id) => InputValue::String(&walker.schema[*id]),
             SchemaInputValue::EnumValue(id) => InputValue::EnumValue(*id),
             SchemaInputValue::Int(n) => InputValue::Int(*n),
-            SchemaInputValue::BigInt(n) => InputValue::BigInt(*n),
+            SchemaInputValue::BigInt(n) => InputValue::BigInt((*n).into()),
             SchemaInputValue::Float(f) => InputValue::Float(*f),
             SchemaInputValue::Boolean(b) => InputValue::Boolean(*b),
             SchemaInputValue::InputObject(ids) => {
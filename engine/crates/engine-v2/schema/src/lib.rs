@@ -1,4 +1,4 @@
-use std::{str::FromStr, sync::OnceLock};
+use std::{collections::BTreeMap, str::FromStr, sync::OnceLock};
 
 mod builder;
 mod directives;
@@ -68,9 +68,47 @@ pub struct Settings {
     default_header_rules: Vec<HeaderRuleId>,
 
     pub timeout: std::time::Duration,
+    pub max_execution_depth: Option<u16>,
+    pub request_deduplication: bool,
+    pub validate_response_nullability: bool,
+    pub max_error_extension_size: usize,
+    pub max_logged_subgraph_request_size: usize,
+    pub max_forwarded_header_bytes: Option<usize>,
+    pub max_variable_values: Option<usize>,
+    pub max_variable_depth: Option<usize>,
+    pub operation_cache_limit: usize,
+    pub max_subscriptions_per_connection: Option<usize>,
+    pub max_concurrent_subscriptions: Option<usize>,
+    pub max_retries_per_operation: Option<usize>,
+    /// Lowercased
+    pub required_headers: Vec<String>,
+    pub pretty_responses: bool,
+    pub max_parse_time: Option<std::time::Duration>,
+    pub max_serialization_time: Option<std::time::Duration>,
+    pub max_string_length: Option<usize>,
+    pub oversized_strings: config::latest::OversizedStringPolicy,
+    pub max_error_path_depth: Option<usize>,
+    pub incompatible_accept: config::latest::IncompatibleAcceptPolicy,
+    pub log_rejected_operations: bool,
+    pub max_entities_per_operation: Option<usize>,
+    /// Header names and value templates
+    pub subgraph_trace_headers: Vec<(String, String)>,
+    /// Sorted
+    pub nullable_fields: Vec<FieldDefinitionId>,
+    pub shadow_gateway_url: Option<url::Url>,
+    pub shadow_gateway_headers: Vec<String>,
+    pub duplicate_operation_names: config::latest::DuplicateOperationNames,
+    pub non_finite_floats: config::latest::NonFiniteFloats,
     pub auth_config: Option<config::latest::AuthConfig>,
     pub operation_limits: config::latest::OperationLimits,
     pub disable_introspection: bool,
+    pub introspection_cache_max_age: Option<std::time::Duration>,
+    pub response_cache_ttl: Option<std::time::Duration>,
+    pub response_cache_bypass_headers: Vec<String>,
+    pub clients: BTreeMap<String, config::latest::ClientConfig>,
+    pub client_identification: config::latest::ClientIdentification,
+    pub tracked_operations: Option<config::latest::TrackedOperations>,
+    pub tenants: Option<config::latest::Tenants>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -361,6 +399,8 @@ pub struct Scalar {
     pub description: Option<StringId>,
     pub specified_by_url: Option<StringId>,
     pub directives: IdRange<TypeSystemDirectiveId>,
+    /// Validated for custom scalars returned by subgraphs.
+    pub format: Option<config::latest::ScalarFormat>,
 }
 
 /// Defines how a scalar should be represented and validated by the engine. They're almost the same
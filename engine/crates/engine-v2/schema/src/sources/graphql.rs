@@ -2,7 +2,8 @@ use std::time::Duration;
 use url::Url;
 
 use crate::{
-    HeaderRuleId, HeaderRuleWalker, RequiredFieldSet, RequiredFieldSetId, SchemaWalker, StringId, SubgraphId, UrlId,
+    FieldDefinitionId, HeaderRuleId, HeaderRuleWalker, RequiredFieldSet, RequiredFieldSetId, SchemaWalker, StringId,
+    SubgraphId, UrlId,
 };
 
 #[derive(Default, serde::Serialize, serde::Deserialize)]
@@ -16,12 +17,81 @@ pub struct GraphqlEndpoint {
     pub(crate) name: StringId,
     pub(crate) url: UrlId,
     pub(crate) websocket_url: Option<UrlId>,
+    // Queries are also sent there, only to compare the responses.
+    pub(crate) shadow_url: Option<UrlId>,
+    // TLS certificates of the shadow subgraph aren't verified.
+    pub(crate) shadow_danger_accept_invalid_certs: bool,
     pub(crate) header_rules: Vec<HeaderRuleId>,
     pub(crate) timeout: Duration,
+    // Timeouts overriding the subgraph one for specific root fields, sorted by field id.
+    pub(crate) field_timeouts: Vec<(FieldDefinitionId, Duration)>,
     pub(crate) retry: Option<RetryConfig>,
+    // Bodies at least this large are gzip compressed, if set.
+    pub(crate) request_compression_threshold: Option<usize>,
     // The ttl to use for caching for this subgraph.
     // If None then caching is disabled for this subgraph
     pub(crate) entity_cache_ttl: Option<Duration>,
+    // How long expired cache entries may be served when the subgraph request fails.
+    pub(crate) entity_cache_stale_if_error: Option<Duration>,
+    // How long responses to queries are cached as a whole, disabled if None.
+    pub(crate) subgraph_cache_ttl: Option<Duration>,
+    // Whether fields in the subgraph response that weren't requested should be reported.
+    pub(crate) warn_on_extra_fields: bool,
+    // Whether booleans may be returned as 0/1.
+    pub(crate) lenient_scalar_coercion: bool,
+    // Whether `data: null` without errors is invalid when non-null fields were requested.
+    pub(crate) error_on_null_data: bool,
+    // Whether an object with a `__typename` unknown to the schema is invalid.
+    pub(crate) error_on_unknown_typename: bool,
+    // Whether an object with a `__typename` of the schema which isn't a possible type of its
+    // field raises an error.
+    pub(crate) error_on_type_mismatch: bool,
+    // Subgraph queries nested any deeper are rejected during planning.
+    pub(crate) max_query_depth: Option<u16>,
+    // Resolved for each request from its headers, used instead of `url` if present.
+    pub(crate) url_template: Option<UrlTemplate>,
+    // TLS certificates of the subgraph aren't verified.
+    pub(crate) danger_accept_invalid_certs: bool,
+    // `_entities` queries are named after it if present.
+    pub(crate) entities_operation_prefix: Option<StringId>,
+    // Requests beyond it wait for one in flight to complete.
+    pub(crate) max_concurrent_requests: Option<usize>,
+    // Whether waiting requests are served in turns across operations rather than in order.
+    pub(crate) fair_concurrency: bool,
+    // Applied to the response bodies before they're ingested.
+    pub(crate) response_transform: Option<ResponseTransform>,
+    // Multiplies the cost of the fields it resolves when apportioning the operation cost.
+    pub(crate) cost_weight: f64,
+    // Representations beyond it are sent in separate requests.
+    pub(crate) entity_batch_size: Option<usize>,
+}
+
+/// Reshapes a subgraph response body into a GraphQL response.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct ResponseTransform {
+    /// Keys leading to the GraphQL response within the body, empty for the whole body.
+    pub unwrap: Vec<String>,
+    /// Top-level keys of the GraphQL response to rename, from and to.
+    pub rename: Vec<(String, String)>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct UrlTemplate {
+    // Parsed and validated when building the schema.
+    pub(crate) segments: Vec<UrlTemplateSegment>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub enum UrlTemplateSegment {
+    Literal(StringId),
+    Placeholder(UrlTemplateVariable),
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct UrlTemplateVariable {
+    pub(crate) name: StringId,
+    pub(crate) header: StringId,
+    pub(crate) allowed_values: Vec<StringId>,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -34,6 +104,10 @@ pub struct RetryConfig {
     pub retry_percent: Option<f32>,
     /// Whether mutations should be retried at all. False by default.
     pub retry_mutations: Option<bool>,
+    /// Maximum number of attempts, the first one included. Only bounded by the retry budget by default.
+    pub max_attempts: Option<u32>,
+    /// Delay before the first retry, doubled for every subsequent one and randomized by a jitter. Default: 100ms.
+    pub base_delay: Option<Duration>,
 }
 
 id_newtypes::U8! {
@@ -150,6 +224,15 @@ impl<'a> GraphqlEndpointWalker<'a> {
         self.as_ref().timeout
     }
 
+    /// Timeout explicitly configured for a root field of this subgraph, if any.
+    pub fn field_timeout(self, id: FieldDefinitionId) -> Option<Duration> {
+        let field_timeouts = &self.as_ref().field_timeouts;
+        field_timeouts
+            .binary_search_by_key(&id, |(field_id, _)| *field_id)
+            .ok()
+            .map(|index| field_timeouts[index].1)
+    }
+
     pub fn url(&self) -> &'a Url {
         &self.schema[self.as_ref().url]
     }
@@ -161,6 +244,15 @@ impl<'a> GraphqlEndpointWalker<'a> {
         }
     }
 
+    pub fn shadow_url(&self) -> Option<&'a Url> {
+        self.as_ref().shadow_url.map(|id| &self.schema[id])
+    }
+
+    /// Whether requests to the shadow subgraph skip the TLS certificate verification.
+    pub fn shadow_danger_accept_invalid_certs(self) -> bool {
+        self.as_ref().shadow_danger_accept_invalid_certs
+    }
+
     pub fn header_rules(self) -> impl Iterator<Item = HeaderRuleWalker<'a>> {
         self.as_ref().header_rules.iter().map(move |id| self.walk(*id))
     }
@@ -169,9 +261,108 @@ impl<'a> GraphqlEndpointWalker<'a> {
         self.as_ref().entity_cache_ttl
     }
 
+    pub fn entity_cache_stale_if_error(self) -> Option<Duration> {
+        self.as_ref().entity_cache_stale_if_error
+    }
+
+    pub fn subgraph_cache_ttl(self) -> Option<Duration> {
+        self.as_ref().subgraph_cache_ttl
+    }
+
     pub fn retry_config(self) -> Option<&'a RetryConfig> {
         self.as_ref().retry.as_ref()
     }
+
+    pub fn request_compression_threshold(self) -> Option<usize> {
+        self.as_ref().request_compression_threshold
+    }
+
+    pub fn warn_on_extra_fields(self) -> bool {
+        self.as_ref().warn_on_extra_fields
+    }
+
+    pub fn lenient_scalar_coercion(self) -> bool {
+        self.as_ref().lenient_scalar_coercion
+    }
+
+    pub fn error_on_null_data(self) -> bool {
+        self.as_ref().error_on_null_data
+    }
+
+    pub fn error_on_unknown_typename(self) -> bool {
+        self.as_ref().error_on_unknown_typename
+    }
+
+    pub fn error_on_type_mismatch(self) -> bool {
+        self.as_ref().error_on_type_mismatch
+    }
+
+    pub fn max_query_depth(self) -> Option<u16> {
+        self.as_ref().max_query_depth
+    }
+
+    /// Segments of the URL to use instead of `url()`, placeholders being resolved from the
+    /// request headers.
+    pub fn url_template(self) -> Option<impl Iterator<Item = UrlTemplateSegmentWalker<'a>> + 'a> {
+        let template = self.as_ref().url_template.as_ref()?;
+        Some(template.segments.iter().map(move |segment| match segment {
+            UrlTemplateSegment::Literal(id) => UrlTemplateSegmentWalker::Literal(&self.schema[*id]),
+            UrlTemplateSegment::Placeholder(variable) => UrlTemplateSegmentWalker::Placeholder(self.walk(variable)),
+        }))
+    }
+
+    /// Whether requests to this subgraph skip the TLS certificate verification.
+    pub fn danger_accept_invalid_certs(self) -> bool {
+        self.as_ref().danger_accept_invalid_certs
+    }
+
+    pub fn entities_operation_prefix(self) -> Option<&'a str> {
+        self.as_ref()
+            .entities_operation_prefix
+            .map(|id| self.schema[id].as_str())
+    }
+
+    pub fn max_concurrent_requests(self) -> Option<usize> {
+        self.as_ref().max_concurrent_requests
+    }
+
+    pub fn fair_concurrency(self) -> bool {
+        self.as_ref().fair_concurrency
+    }
+
+    pub fn response_transform(self) -> Option<&'a ResponseTransform> {
+        self.as_ref().response_transform.as_ref()
+    }
+
+    pub fn cost_weight(self) -> f64 {
+        self.as_ref().cost_weight
+    }
+
+    pub fn entity_batch_size(self) -> Option<usize> {
+        self.as_ref().entity_batch_size
+    }
+}
+
+pub enum UrlTemplateSegmentWalker<'a> {
+    Literal(&'a str),
+    Placeholder(UrlTemplateVariableWalker<'a>),
+}
+
+pub type UrlTemplateVariableWalker<'a> = SchemaWalker<'a, &'a UrlTemplateVariable>;
+
+impl<'a> UrlTemplateVariableWalker<'a> {
+    pub fn name(&self) -> &'a str {
+        &self.schema[self.item.name]
+    }
+
+    /// The request header providing the value.
+    pub fn header(&self) -> &'a str {
+        &self.schema[self.item.header]
+    }
+
+    pub fn allows(&self, value: &str) -> bool {
+        self.item.allowed_values.iter().any(|id| self.schema[*id] == value)
+    }
 }
 
 impl<'a> std::fmt::Debug for GraphqlEndpointWalker<'a> {
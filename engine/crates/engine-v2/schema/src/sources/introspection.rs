@@ -785,6 +785,7 @@ impl<'a> IntrospectionBuilder<'a> {
                     description: None,
                     specified_by_url: None,
                     directives: IdRange::empty(),
+                    format: None,
                 });
                 ScalarId::from(self.scalar_definitions.len() - 1)
             }
@@ -41,6 +41,37 @@ impl<'a> TypeSystemDirectivesWalker<'a> {
         })
     }
 
+    /// The feature flag a request must enable for this element to be visible at all.
+    pub fn feature_flag(&self) -> Option<&'a str> {
+        self.as_ref().iter().find_map(|d| match d {
+            TypeSystemDirective::Feature(id) => Some(self.schema[*id].as_str()),
+            _ => None,
+        })
+    }
+
+    pub fn max_string_length(&self) -> Option<usize> {
+        self.as_ref().iter().find_map(|d| match d {
+            TypeSystemDirective::MaxStringLength(length) => Some(*length),
+            _ => None,
+        })
+    }
+
+    pub fn priority(&self) -> Option<i64> {
+        self.as_ref().iter().find_map(|d| match d {
+            TypeSystemDirective::Priority(level) => Some(*level),
+            _ => None,
+        })
+    }
+
+    /// Tags of the element, restricting its visibility to the tenants allowed to see any of them.
+    pub fn tags(&self) -> impl Iterator<Item = &'a str> + 'a {
+        let schema = self.schema;
+        self.as_ref().iter().filter_map(|d| match d {
+            TypeSystemDirective::Tag(id) => Some(schema[*id].as_str()),
+            _ => None,
+        })
+    }
+
     pub fn iter_required_fields(&self) -> impl Iterator<Item = &'a RequiredFieldSet> + 'a {
         let schema = self.schema;
         self.as_ref().iter().filter_map(|d| match d {
@@ -3,7 +3,7 @@ use std::borrow::Cow;
 use super::{resolver::ResolverWalker, SchemaWalker};
 use crate::{
     EntityWalker, FieldDefinitionId, InputValueDefinitionWalker, ProvidableFieldSet, RequiredFieldSet, SubgraphId,
-    TypeSystemDirectivesWalker, TypeWalker,
+    TypeSystemDirectivesWalker, TypeWalker, Wrapping,
 };
 
 pub type FieldDefinitionWalker<'a> = SchemaWalker<'a, FieldDefinitionId>;
@@ -74,6 +74,17 @@ impl<'a> FieldDefinitionWalker<'a> {
         self.walk(self.as_ref().ty)
     }
 
+    /// Wrapping expected from subgraph responses. It only differs from the declared one for
+    /// fields with a nullability override, which accept a null.
+    pub fn response_wrapping(&self) -> Wrapping {
+        let wrapping = self.ty().wrapping();
+        if self.schema.settings.nullable_fields.binary_search(&self.item).is_ok() {
+            wrapping.with_nullable_outermost()
+        } else {
+            wrapping
+        }
+    }
+
     pub fn directives(&self) -> TypeSystemDirectivesWalker<'a> {
         self.walk(self.as_ref().directives)
     }
@@ -1,4 +1,7 @@
-use crate::{FieldDefinitionId, Names, RequiredFieldSet, Resolver, ResolverId, SchemaWalker, SubgraphId};
+use crate::{
+    sources::graphql::GraphqlEndpointWalker, FieldDefinitionId, Names, RequiredFieldSet, Resolver, ResolverId,
+    SchemaWalker, SubgraphId,
+};
 
 pub type ResolverWalker<'a> = SchemaWalker<'a, ResolverId>;
 
@@ -40,6 +43,14 @@ impl<'a> ResolverWalker<'a> {
         }
     }
 
+    pub fn graphql_endpoint(&self) -> Option<GraphqlEndpointWalker<'a>> {
+        match self.as_ref() {
+            Resolver::Introspection(_) => None,
+            Resolver::GraphqlRootField(resolver) => Some(self.walk(resolver).endpoint()),
+            Resolver::GraphqlFederationEntity(resolver) => Some(self.walk(resolver).endpoint()),
+        }
+    }
+
     pub fn can_provide(&self, field_id: FieldDefinitionId) -> bool {
         self.walk(field_id).is_resolvable_in(self.subgraph_id())
     }
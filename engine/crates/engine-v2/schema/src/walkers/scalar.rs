@@ -11,6 +11,10 @@ impl<'a> ScalarWalker<'a> {
     pub fn directives(&self) -> TypeSystemDirectivesWalker<'a> {
         self.walk(self.as_ref().directives)
     }
+
+    pub fn format(&self) -> Option<config::latest::ScalarFormat> {
+        self.as_ref().format
+    }
 }
 
 impl<'a> std::fmt::Debug for ScalarWalker<'a> {
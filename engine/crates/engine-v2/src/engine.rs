@@ -1,25 +1,33 @@
 use ::runtime::{
     auth::AccessToken,
+    bytes::OwnedOrSharedBytes,
     hooks::Hooks,
     hot_cache::{CachedDataKind, HotCache, HotCacheFactory},
     rate_limiting::RateLimitKey,
 };
 use async_runtime::stream::StreamExt as _;
+use config::latest::{DuplicateOperationNames, IncompatibleAcceptPolicy};
 use engine::{BatchRequest, Request};
 use engine_parser::types::OperationType;
-use futures::{channel::mpsc, FutureExt, StreamExt};
+use futures::{channel::mpsc, StreamExt};
 use futures_util::{SinkExt, Stream};
 use gateway_core::StreamingFormat;
 use gateway_v2_auth::AuthService;
 use grafbase_telemetry::{
     gql_response_status::GraphqlResponseStatus,
-    grafbase_client::Client,
-    metrics::{GraphqlOperationMetrics, GraphqlRequestMetricsAttributes, OperationMetricsAttributes},
+    grafbase_client::{Client, ClientIdentification},
+    metrics::{
+        GraphqlOperationMetrics, GraphqlRequestMetricsAttributes, OperationMetricsAttributes,
+        RequestCancellationMetrics, ResponseDiffMetrics, SubgraphMetrics,
+    },
     span::{gql::GqlRequestSpan, GqlRecorderSpanExt, GRAFBASE_TARGET},
 };
 use headers::HeaderMapExt;
 use schema::Schema;
-use std::{borrow::Cow, sync::Arc};
+use std::{
+    borrow::Cow,
+    sync::{atomic::AtomicUsize, Arc},
+};
 use tower::retry::budget::Budget as RetryBudget;
 use tracing::Instrument;
 use trusted_documents::PreparedOperationDocument;
@@ -27,17 +35,44 @@ use web_time::Instant;
 
 use crate::{
     execution::{ExecutableOperation, PreExecutionContext},
-    http_response::{HttpGraphqlResponse, HttpGraphqlResponseExtraMetadata},
+    http_response::{HttpGraphqlResponse, HttpGraphqlResponseBody, HttpGraphqlResponseExtraMetadata},
     operation::{Operation, PreparedOperation, Variables},
     response::{ErrorCode, GraphqlError, Response},
     websocket,
 };
 
+mod batch;
 mod cache;
+mod cancellation;
+mod client_version;
+mod cost_attribution;
+mod cost_budget;
+mod deduplication;
+mod feature_flags;
+mod operation_cache;
+mod pretty;
+mod rejections;
+mod response_cache;
 mod runtime;
+mod shadow_gateway;
+mod subgraph_concurrency;
+mod subgraph_health;
+mod subscription_limits;
+mod tenant;
 mod trusted_documents;
 
+use cancellation::CancellationGuard;
+use deduplication::{deduplication_key, forwarded_headers, Flight, InFlightQueries};
+pub(crate) use feature_flags::FeatureFlags;
+use operation_cache::OperationCache;
+use pretty::wants_pretty_response;
+use response_cache::{CachedResponse, ResponseCache};
 pub use runtime::Runtime;
+use shadow_gateway::ShadowGateway;
+pub(crate) use subgraph_concurrency::{SubgraphConcurrencyLimiter, SubgraphPermit};
+pub use subgraph_health::SubgraphHealth;
+use subgraph_health::SubgraphHealthTracker;
+pub(crate) use tenant::Tenant;
 
 pub(crate) struct SchemaVersion(Vec<u8>);
 
@@ -56,10 +91,24 @@ pub struct Engine<R: Runtime> {
     pub(crate) schema_version: SchemaVersion,
     pub(crate) runtime: R,
     operation_metrics: GraphqlOperationMetrics,
+    pub(crate) subgraph_metrics: SubgraphMetrics,
+    cancellation_metrics: RequestCancellationMetrics,
     auth: AuthService,
     retry_budgets: Vec<Option<RetryBudget>>,
+    subgraph_concurrency_limiters: Vec<Option<Arc<SubgraphConcurrencyLimiter>>>,
     trusted_documents_cache: <R::CacheFactory as HotCacheFactory>::Cache<String>,
-    operation_cache: <R::CacheFactory as HotCacheFactory>::Cache<Arc<PreparedOperation>>,
+    operation_cache: OperationCache<Arc<PreparedOperation>>,
+    // Serialized JSON responses of introspection-only queries.
+    introspection_cache: <R::CacheFactory as HotCacheFactory>::Cache<Vec<u8>>,
+    response_cache: Option<ResponseCache>,
+    in_flight_queries: InFlightQueries,
+    client_identification: ClientIdentification,
+    pub(crate) subgraph_health: Option<SubgraphHealthTracker>,
+    shadow_gateway: Option<Arc<ShadowGateway>>,
+    // Subscriptions running over all WebSocket connections.
+    active_subscriptions: Arc<AtomicUsize>,
+    sdl: Option<String>,
+    schema_warnings: Vec<String>,
 }
 
 impl<R: Runtime> Engine<R> {
@@ -86,6 +135,58 @@ impl<R: Runtime> Engine<R> {
             })
             .collect();
 
+        let subgraph_concurrency_limiters = schema
+            .walker()
+            .graphql_endpoints()
+            .map(|endpoint| {
+                let max = endpoint.max_concurrent_requests()?;
+                Some(Arc::new(SubgraphConcurrencyLimiter::new(
+                    max,
+                    endpoint.fair_concurrency(),
+                )))
+            })
+            .collect();
+
+        for endpoint in schema.walker().graphql_endpoints() {
+            if endpoint.danger_accept_invalid_certs() {
+                tracing::warn!(
+                    "TLS certificates of subgraph '{}' are not verified, its traffic can be intercepted",
+                    endpoint.name()
+                );
+            }
+            if endpoint.shadow_url().is_some() && endpoint.shadow_danger_accept_invalid_certs() {
+                tracing::warn!(
+                    "TLS certificates of the shadow of subgraph '{}' are not verified, its traffic can be intercepted",
+                    endpoint.name()
+                );
+            }
+        }
+
+        let client_identification = ClientIdentification::new(
+            &schema.settings.client_identification.name_headers,
+            &schema.settings.client_identification.version_headers,
+        );
+
+        let mut operation_metrics = GraphqlOperationMetrics::build(runtime.meter());
+        if let Some(tracked_operations) = &schema.settings.tracked_operations {
+            operation_metrics = operation_metrics
+                .with_tracked_operations(tracked_operations.allow_list.clone(), tracked_operations.max);
+        }
+
+        let shadow_gateway = schema.settings.shadow_gateway_url.clone().map(|url| {
+            Arc::new(ShadowGateway::new(
+                url,
+                &schema.settings.shadow_gateway_headers,
+                ResponseDiffMetrics::build(runtime.meter()),
+            ))
+        });
+
+        let operation_cache = OperationCache::new(schema.settings.operation_cache_limit);
+        let response_cache = schema
+            .settings
+            .response_cache_ttl
+            .map(|ttl| ResponseCache::new(ttl, schema.settings.response_cache_bypass_headers.clone()));
+
         Self {
             schema,
             schema_version: SchemaVersion({
@@ -104,17 +205,95 @@ impl<R: Runtime> Engine<R> {
             }),
             auth,
             retry_budgets,
-            operation_metrics: GraphqlOperationMetrics::build(runtime.meter()),
+            subgraph_concurrency_limiters,
+            operation_metrics,
+            subgraph_metrics: SubgraphMetrics::build(runtime.meter()),
+            cancellation_metrics: RequestCancellationMetrics::build(runtime.meter()),
             trusted_documents_cache: runtime.cache_factory().create(CachedDataKind::PersistedQuery).await,
-            operation_cache: runtime.cache_factory().create(CachedDataKind::Operation).await,
+            operation_cache,
+            introspection_cache: runtime
+                .cache_factory()
+                .create(CachedDataKind::IntrospectionResponse)
+                .await,
+            response_cache,
+            in_flight_queries: InFlightQueries::default(),
+            client_identification,
             runtime,
+            subgraph_health: None,
+            shadow_gateway,
+            active_subscriptions: Default::default(),
+            sdl: None,
+            schema_warnings: Vec::new(),
         }
     }
 
+    /// Keeps the federated SDL the schema was built from, so it can be served back as-is.
+    pub fn with_sdl(mut self, sdl: impl Into<String>) -> Self {
+        self.sdl = Some(sdl.into());
+        self
+    }
+
+    /// The federated SDL this engine was built from, if it was provided with [Engine::with_sdl].
+    pub fn sdl(&self) -> Option<&str> {
+        self.sdl.as_deref()
+    }
+
+    /// Keeps the non-fatal problems found while building the schema, so they can be inspected
+    /// while the engine runs.
+    pub fn with_schema_warnings(mut self, warnings: Vec<String>) -> Self {
+        self.schema_warnings = warnings;
+        self
+    }
+
+    /// Warnings provided with [Engine::with_schema_warnings].
+    pub fn schema_warnings(&self) -> &[String] {
+        &self.schema_warnings
+    }
+
+    /// Tracks the error rate and latency of the requests sent to each subgraph over the given
+    /// rolling window.
+    pub fn with_subgraph_health_tracking(mut self, window: std::time::Duration) -> Self {
+        self.subgraph_health = Some(SubgraphHealthTracker::new(&self.schema, window));
+        self
+    }
+
+    /// Health of every subgraph, if tracking was enabled with [Engine::with_subgraph_health_tracking].
+    pub fn subgraph_health(&self) -> Option<Vec<SubgraphHealth>> {
+        self.subgraph_health
+            .as_ref()
+            .map(|tracker| tracker.snapshot(&self.schema))
+    }
+
+    /// Purges the cached responses tagged with the surrogate key, typically the name of a type
+    /// changed by a mutation. Returns how many were purged.
+    pub fn invalidate_by_key(&self, key: &str) -> usize {
+        self.response_cache
+            .as_ref()
+            .map(|cache| cache.invalidate(key))
+            .unwrap_or_default()
+    }
+
+    /// Dropping the returned future cancels the execution, subgraph requests in flight included.
     pub async fn execute(
         self: &Arc<Self>,
         headers: http::HeaderMap,
         batch_request: BatchRequest,
+    ) -> HttpGraphqlResponse {
+        let guard = CancellationGuard::new(&self.cancellation_metrics);
+        let pretty = self.schema.settings.pretty_responses && wants_pretty_response(&headers);
+        let response = self.execute_with_timeout(headers, batch_request).await;
+        guard.complete();
+        if pretty {
+            response.into_pretty()
+        } else {
+            response
+        }
+    }
+
+    async fn execute_with_timeout(
+        self: &Arc<Self>,
+        headers: http::HeaderMap,
+        batch_request: BatchRequest,
     ) -> HttpGraphqlResponse {
         use futures_util::{pin_mut, select, FutureExt};
 
@@ -125,11 +304,16 @@ impl<R: Runtime> Engine<R> {
         };
 
         if let Err(err) = self.runtime.rate_limiter().limit(&RateLimitKey::Global).await {
-            return HttpGraphqlResponse::build(
-                Response::pre_execution_error(GraphqlError::new(err.to_string(), ErrorCode::RateLimited)),
-                format,
-                Default::default(),
+            let response = Response::pre_execution_error(
+                GraphqlError::new(err.to_string(), ErrorCode::RateLimited).with_retryable(true),
+            );
+            self.log_rejection(
+                request_context.client.as_ref(),
+                request_context.source_ip.as_deref(),
+                None,
+                &response,
             );
+            return HttpGraphqlResponse::build(response, format, Default::default());
         }
 
         let mut timeout = match format {
@@ -140,7 +324,9 @@ impl<R: Runtime> Engine<R> {
             None => async move {
                 self.runtime.sleep(self.schema.settings.timeout).await;
                 HttpGraphqlResponse::build(
-                    Response::execution_error(GraphqlError::new("Gateway timeout", ErrorCode::GatewayTimeout)),
+                    Response::execution_error(
+                        GraphqlError::new("Gateway timeout", ErrorCode::GatewayTimeout).with_retryable(true),
+                    ),
                     format,
                     Default::default(),
                 )
@@ -160,11 +346,16 @@ impl<R: Runtime> Engine<R> {
 
     pub async fn create_session(self: &Arc<Self>, headers: http::HeaderMap) -> Result<Session<R>, Cow<'static, str>> {
         if let Err(err) = self.runtime.rate_limiter().limit(&RateLimitKey::Global).await {
-            return Err(
-                Response::pre_execution_error(GraphqlError::new(err.to_string(), ErrorCode::RateLimited))
-                    .first_error_message()
-                    .unwrap_or("Internal server error".into()),
+            let response = Response::pre_execution_error(
+                GraphqlError::new(err.to_string(), ErrorCode::RateLimited).with_retryable(true),
+            );
+            self.log_rejection(
+                self.client_identification.extract_from(&headers).as_ref(),
+                rejections::source_ip(&headers).as_deref(),
+                None,
+                &response,
             );
+            return Err(response.first_error_message().unwrap_or("Internal server error".into()));
         }
 
         let request_context = match self.create_request_context(headers).await {
@@ -175,6 +366,7 @@ impl<R: Runtime> Engine<R> {
         Ok(Session {
             engine: Arc::clone(self),
             request_context: Arc::new(request_context),
+            active_subscriptions: Default::default(),
         })
     }
 
@@ -182,29 +374,66 @@ impl<R: Runtime> Engine<R> {
         &self,
         headers: http::HeaderMap,
     ) -> Result<RequestContext<<R::Hooks as Hooks>::Context>, Response> {
-        let client = Client::extract_from(&headers);
+        let client = self.client_identification.extract_from(&headers);
+        let source_ip = rejections::source_ip(&headers);
+
+        if let Some(name) = self
+            .schema
+            .settings
+            .required_headers
+            .iter()
+            .find(|name| !headers.contains_key(name.as_str()))
+        {
+            let response = Response::pre_execution_error(GraphqlError::new(
+                format!("Missing required header '{name}'"),
+                ErrorCode::MissingRequiredHeader,
+            ));
+            self.log_rejection(client.as_ref(), source_ip.as_deref(), None, &response);
+            return Err(response);
+        }
+
+        if let Err(err) = self.ensure_supported_client_version(client.as_ref()) {
+            let response = Response::pre_execution_error(err);
+            self.log_rejection(client.as_ref(), source_ip.as_deref(), None, &response);
+            return Err(response);
+        }
+
+        let feature_flags = FeatureFlags::extract_from(&headers);
+        let tenant = self
+            .schema
+            .settings
+            .tenants
+            .as_ref()
+            .map(|tenants| Tenant::extract_from(tenants, &headers));
         let streaming_format = headers.typed_get::<StreamingFormat>();
+        let carries_credentials = response_cache::carries_credentials(&headers);
 
-        let (hooks_context, headers) = self
-            .runtime
-            .hooks()
-            .on_gateway_request(headers)
-            .await
-            .map_err(Response::pre_execution_error)?;
+        let (hooks_context, headers) = match self.runtime.hooks().on_gateway_request(headers).await {
+            Ok(result) => result,
+            Err(err) => {
+                let response = Response::pre_execution_error(err);
+                self.log_rejection(client.as_ref(), source_ip.as_deref(), None, &response);
+                return Err(response);
+            }
+        };
 
         if let Some(access_token) = self.auth.authenticate(&headers).await {
             Ok(RequestContext {
                 headers,
                 streaming_format,
                 client,
+                source_ip,
+                feature_flags,
+                tenant,
                 access_token,
+                carries_credentials,
                 hooks_context,
             })
         } else {
-            Err(Response::pre_execution_error(GraphqlError::new(
-                "Unauthenticated",
-                ErrorCode::Unauthenticated,
-            )))
+            let response =
+                Response::pre_execution_error(GraphqlError::new("Unauthenticated", ErrorCode::Unauthenticated));
+            self.log_rejection(client.as_ref(), source_ip.as_deref(), None, &response);
+            Err(response)
         }
     }
 
@@ -222,7 +451,7 @@ impl<R: Runtime> Engine<R> {
                     )
                     .await
                 } else {
-                    self.execute_single(&request_context, request).await
+                    self.execute_single(&request_context, request, None).await
                 }
             }
             BatchRequest::Batch(requests) => {
@@ -231,9 +460,29 @@ impl<R: Runtime> Engine<R> {
                         "batch requests can't use multipart or event-stream responses",
                     );
                 }
+                let name_suffixes = match self.schema.settings.duplicate_operation_names {
+                    DuplicateOperationNames::Allow => vec![None; requests.len()],
+                    DuplicateOperationNames::Reject => {
+                        if let Some(name) = batch::find_duplicate_operation_name(&requests) {
+                            let response = Response::pre_execution_error(GraphqlError::new(
+                                format!("Operation name '{name}' is used more than once in the batch"),
+                                ErrorCode::DuplicateOperationName,
+                            ));
+                            self.log_rejection(
+                                request_context.client.as_ref(),
+                                request_context.source_ip.as_deref(),
+                                None,
+                                &response,
+                            );
+                            return HttpGraphqlResponse::build(response, None, Default::default());
+                        }
+                        vec![None; requests.len()]
+                    }
+                    DuplicateOperationNames::Disambiguate => batch::disambiguation_suffixes(&requests),
+                };
                 HttpGraphqlResponse::from_batch(
-                    futures_util::stream::iter(requests.into_iter())
-                        .then(|request| self.execute_single(&request_context, request))
+                    futures_util::stream::iter(requests.into_iter().zip(name_suffixes))
+                        .then(|(request, name_suffix)| self.execute_single(&request_context, request, name_suffix))
                         .collect::<Vec<_>>()
                         .await,
                 )
@@ -245,18 +494,50 @@ impl<R: Runtime> Engine<R> {
         &self,
         request_context: &RequestContext<<R::Hooks as Hooks>::Context>,
         request: Request,
+        name_suffix: Option<String>,
     ) -> HttpGraphqlResponse {
         let start = Instant::now();
         let span = GqlRequestSpan::create();
         async {
+            // Serialized before the request is consumed, to be sent as is to the shadow gateway.
+            let shadow_request_body = self
+                .shadow_gateway
+                .as_ref()
+                .and_then(|_| serde_json::to_vec(&request).ok())
+                .map(bytes::Bytes::from);
+
+            let operation_hash = self
+                .schema
+                .settings
+                .log_rejected_operations
+                .then(|| rejections::operation_hash(&request))
+                .flatten();
+
             let ctx = PreExecutionContext::new(self, request_context);
-            let (operation_metrics_attributes, response) = ctx.execute_single(request).await;
+            let (mut operation_metrics_attributes, response) = ctx.execute_single(request).await;
+            if let SingleResponse::Executed { response, .. } = &response {
+                self.log_rejection(
+                    request_context.client.as_ref(),
+                    request_context.source_ip.as_deref(),
+                    operation_hash.as_deref(),
+                    response,
+                );
+            }
+            let is_query = operation_metrics_attributes
+                .as_ref()
+                .is_some_and(|attributes| matches!(attributes.ty, grafbase_telemetry::metrics::OperationType::Query));
+            if let Some((attributes, suffix)) = operation_metrics_attributes.as_mut().zip(name_suffix) {
+                if let Some(name) = attributes.name.as_mut() {
+                    name.push_str(&suffix);
+                }
+            }
             let status = response.status();
 
             let mut response_metadata = HttpGraphqlResponseExtraMetadata {
                 operation_name: None,
                 operation_type: None,
                 has_errors: !status.is_success(),
+                has_execution_failures: response.has_execution_failures(),
             };
 
             let elapsed = start.elapsed();
@@ -293,12 +574,101 @@ impl<R: Runtime> Engine<R> {
                 tracing::debug!(target: GRAFBASE_TARGET, "{message}")
             }
 
-            HttpGraphqlResponse::build(response, None, response_metadata)
+            let mut http_response = self.build_single_http_response(response, response_metadata).await;
+
+            // Compared in the background, the client doesn't wait for the shadow gateway.
+            if let (Some(shadow_gateway), Some(request_body), HttpGraphqlResponseBody::Bytes(body)) = (
+                &self.shadow_gateway,
+                shadow_request_body.filter(|_| is_query),
+                &mut http_response.body,
+            ) {
+                // Shared with the comparison rather than copied.
+                let shared = bytes::Bytes::from(std::mem::replace(body, OwnedOrSharedBytes::Owned(Vec::new())));
+                *body = OwnedOrSharedBytes::Shared(shared.clone());
+
+                let shadow_gateway = Arc::clone(shadow_gateway);
+                let fetcher = self.runtime.fetcher().clone();
+                let headers = shadow_gateway.forwarded_headers(&request_context.headers);
+                let timeout = self.schema.settings.timeout;
+                self.runtime.spawn(Box::pin(async move {
+                    shadow_gateway
+                        .compare(&fetcher, headers, request_body, &shared, timeout)
+                        .await;
+                }));
+            }
+
+            http_response
         }
         .instrument(span)
         .await
     }
 
+    async fn build_single_http_response(
+        &self,
+        response: SingleResponse,
+        metadata: HttpGraphqlResponseExtraMetadata,
+    ) -> HttpGraphqlResponse {
+        let mut http_response = match response {
+            SingleResponse::Executed {
+                response,
+                introspection_cache_key,
+                response_cache_key,
+                surrogate_keys,
+            } => {
+                let mut http_response = HttpGraphqlResponse::build_json(&response, metadata);
+                if response.served_stale() {
+                    http_response.headers.insert(
+                        http::HeaderName::from_static(::runtime::cache::X_GRAFBASE_CACHE),
+                        http::HeaderValue::from_static("STALE"),
+                    );
+                }
+                insert_surrogate_keys(&mut http_response, &surrogate_keys);
+                let cacheable = response.status().is_success() && !response.served_stale();
+                if let (Some(cache), Some(key), HttpGraphqlResponseBody::Bytes(bytes)) = (
+                    &self.response_cache,
+                    response_cache_key.filter(|_| cacheable),
+                    &http_response.body,
+                ) {
+                    cache.insert(key, bytes.to_vec(), surrogate_keys);
+                }
+                let Some(key) = introspection_cache_key.filter(|_| response.status().is_success()) else {
+                    return http_response;
+                };
+                if let HttpGraphqlResponseBody::Bytes(bytes) = &http_response.body {
+                    self.introspection_cache.insert(key, bytes.to_vec()).await;
+                }
+                http_response
+            }
+            SingleResponse::CachedIntrospection(bytes) => {
+                let mut http_response =
+                    HttpGraphqlResponse::from_json_bytes(GraphqlResponseStatus::Success, bytes.into());
+                http_response.metadata = metadata;
+                http_response
+            }
+            SingleResponse::Cached(CachedResponse {
+                bytes, surrogate_keys, ..
+            }) => {
+                let mut http_response =
+                    HttpGraphqlResponse::from_json_bytes(GraphqlResponseStatus::Success, bytes.into());
+                http_response.metadata = metadata;
+                http_response.headers.insert(
+                    http::HeaderName::from_static(::runtime::cache::X_GRAFBASE_CACHE),
+                    http::HeaderValue::from_static("HIT"),
+                );
+                insert_surrogate_keys(&mut http_response, &surrogate_keys);
+                return http_response;
+            }
+        };
+
+        if let Some(max_age) = self.schema.settings.introspection_cache_max_age {
+            http_response
+                .headers
+                .typed_insert(headers::CacheControl::new().with_public().with_max_age(max_age));
+        }
+
+        http_response
+    }
+
     fn execute_stream(
         self: &Arc<Self>,
         request_context: Arc<RequestContext<<R::Hooks as Hooks>::Context>>,
@@ -348,6 +718,28 @@ impl<R: Runtime> Engine<R> {
     ) -> Option<&RetryBudget> {
         self.retry_budgets[usize::from(subgraph_id)].as_ref()
     }
+
+    /// Waits for a slot if the subgraph limits its concurrent requests, `None` otherwise.
+    pub(crate) async fn acquire_subgraph_permit(
+        &self,
+        subgraph_id: schema::sources::graphql::GraphqlEndpointId,
+        operation: usize,
+        priority: i64,
+    ) -> Option<SubgraphPermit> {
+        let limiter = self.subgraph_concurrency_limiters[usize::from(subgraph_id)].as_ref()?;
+
+        let queue_depth = limiter.queue_depth();
+        let start = Instant::now();
+        let permit = limiter.acquire(operation, priority).await;
+
+        self.subgraph_metrics.record_permit_wait(
+            self.schema.walker().walk(subgraph_id).name(),
+            start.elapsed(),
+            queue_depth,
+        );
+
+        Some(permit)
+    }
 }
 
 async fn convert_stream_to_http_response(
@@ -367,23 +759,131 @@ async fn convert_stream_to_http_response(
 }
 
 impl<'ctx, R: Runtime> PreExecutionContext<'ctx, R> {
-    async fn execute_single(mut self, request: Request) -> (Option<OperationMetricsAttributes>, Response) {
+    async fn execute_single(mut self, request: Request) -> (Option<OperationMetricsAttributes>, SingleResponse) {
+        let introspection_cache_key = self.schema.settings.introspection_cache_max_age.map(|_| {
+            cache::Key::IntrospectionResponse {
+                request: &request,
+                client_name: self.request_context.client.as_ref().map(|client| client.name.as_str()),
+                feature_flags: &self.request_context.feature_flags,
+                tenant: self.request_context.tenant.as_ref(),
+                schema_version: &self.engine.schema_version,
+            }
+            .to_string()
+        });
+
+        // Identifies the request along with whoever sent it and the headers forwarded to the
+        // subgraphs, shared by the deduplication and the response cache. Other headers vary for
+        // every request and would prevent any cache hit.
+        let request_key = if self.schema.settings.request_deduplication || self.engine.response_cache.is_some() {
+            deduplication_key(
+                &request,
+                self.request_context.client.as_ref().map(|client| client.name.as_str()),
+                &self.request_context.feature_flags,
+                self.request_context.tenant.as_ref(),
+                self.access_token(),
+                &forwarded_headers(&self.schema, self.request_context),
+            )
+        } else {
+            None
+        };
+
+        let deduplication_key = request_key.filter(|_| self.schema.settings.request_deduplication);
+
         let operation_plan = match self.prepare_operation(request).await {
             Ok(operation_plan) => operation_plan,
-            Err((metadata, response)) => return (metadata, response),
+            Err((metadata, response)) => return (metadata, SingleResponse::executed(response)),
         };
 
         let metrics_attributes = Some(operation_plan.metrics_attributes.clone());
-        let response = if matches!(operation_plan.ty(), OperationType::Subscription) {
-            Response::pre_execution_error(GraphqlError::new(
+
+        // Streaming requests never get here, the client only accepts a single JSON response.
+        if operation_plan.uses_incremental_delivery
+            && self.schema.settings.incompatible_accept == IncompatibleAcceptPolicy::Reject
+        {
+            let error = GraphqlError::new(
+                "The operation uses @defer or @stream, which require a multipart or event stream response not allowed by the Accept header",
+                ErrorCode::NotAcceptable,
+            )
+            .with_retryable(false);
+            return (
+                metrics_attributes,
+                SingleResponse::executed(Response::pre_execution_error(error)),
+            );
+        }
+
+        if let Err(err) = self.consume_cost_budget(&operation_plan).await {
+            return (
+                metrics_attributes,
+                SingleResponse::executed(Response::pre_execution_error(err)),
+            );
+        }
+        self.record_subgraph_costs(&operation_plan);
+
+        let introspection_cache_key = introspection_cache_key.filter(|_| {
+            matches!(operation_plan.ty(), OperationType::Query) && operation_plan.is_introspection_only(&self.schema)
+        });
+        if let Some(key) = &introspection_cache_key {
+            if let Some(bytes) = self.engine.introspection_cache.get(key).await {
+                return (metrics_attributes, SingleResponse::CachedIntrospection(bytes));
+            }
+        }
+
+        let (response_cache_key, surrogate_keys) = match &self.engine.response_cache {
+            // Responses of authenticated users may depend on their identity, through the subgraphs
+            // or the hooks context, so only anonymous ones are shared.
+            Some(cache)
+                if matches!(operation_plan.ty(), OperationType::Query)
+                    && matches!(self.access_token(), AccessToken::Anonymous)
+                    && !self.request_context.carries_credentials
+                    && !cache.is_bypassed_by(self.headers()) =>
+            {
+                if let Some(cached) = request_key.and_then(|key| cache.get(&key)) {
+                    return (metrics_attributes, SingleResponse::Cached(cached));
+                }
+                (request_key, operation_plan.surrogate_keys(&self.schema))
+            }
+            _ => (None, Vec::new()),
+        };
+
+        let response = match (operation_plan.ty(), deduplication_key) {
+            (OperationType::Subscription, _) => Arc::new(Response::pre_execution_error(GraphqlError::new(
                 "Subscriptions are only suported on streaming transports. Try making a request with SSE or WebSockets",
                 ErrorCode::BadRequest,
-            ))
-        } else {
-            self.execute_query_or_mutation(operation_plan).await
+            )
+            .with_retryable(false))),
+            (OperationType::Query, Some(key)) => self.execute_deduplicated_query(key, operation_plan).await,
+            _ => Arc::new(self.execute_query_or_mutation(operation_plan).await),
         };
 
-        (metrics_attributes, response)
+        (
+            metrics_attributes,
+            SingleResponse::Executed {
+                response,
+                introspection_cache_key,
+                response_cache_key,
+                surrogate_keys,
+            },
+        )
+    }
+
+    /// Shares the execution of the query with any identical one received concurrently.
+    async fn execute_deduplicated_query(self, key: blake3::Hash, operation_plan: ExecutableOperation) -> Arc<Response> {
+        let engine = self.engine;
+        match engine.in_flight_queries.join(key) {
+            Flight::Leader(leader) => {
+                let response = Arc::new(self.execute_query_or_mutation(operation_plan).await);
+                leader.complete(Arc::clone(&response));
+                response
+            }
+            Flight::Follower(response) => match response.await {
+                Ok(response) => {
+                    self.execute_background_futures().await;
+                    response
+                }
+                // The identical query was cancelled before completing.
+                Err(_) => Arc::new(self.execute_query_or_mutation(operation_plan).await),
+            },
+        }
     }
 
     async fn execute_stream(
@@ -391,9 +891,16 @@ impl<'ctx, R: Runtime> PreExecutionContext<'ctx, R> {
         request: Request,
         mut sender: mpsc::Sender<Response>,
     ) -> (Option<OperationMetricsAttributes>, GraphqlResponseStatus) {
+        let operation_hash = self
+            .schema
+            .settings
+            .log_rejected_operations
+            .then(|| rejections::operation_hash(&request))
+            .flatten();
         let operation_plan = match self.prepare_operation(request).await {
             Ok(operation_plan) => operation_plan,
             Err((metadata, response)) => {
+                self.log_rejected_operation(operation_hash.as_deref(), &response);
                 let status = response.status();
                 sender.send(response).await.ok();
                 return (metadata, status);
@@ -401,13 +908,14 @@ impl<'ctx, R: Runtime> PreExecutionContext<'ctx, R> {
         };
         let operation_type = operation_plan.ty();
         let metrics_attributes = Some(operation_plan.metrics_attributes.clone());
-
-        if matches!(operation_type, OperationType::Query | OperationType::Mutation) {
-            let response = self.execute_query_or_mutation(operation_plan).await;
+        if let Err(err) = self.consume_cost_budget(&operation_plan).await {
+            let response = Response::pre_execution_error(err);
+            self.log_rejected_operation(operation_hash.as_deref(), &response);
             let status = response.status();
             sender.send(response).await.ok();
             return (metrics_attributes, status);
         }
+        self.record_subgraph_costs(&operation_plan);
 
         let mut status: GraphqlResponseStatus = GraphqlResponseStatus::Success;
         struct Sender<'a> {
@@ -423,6 +931,26 @@ impl<'ctx, R: Runtime> PreExecutionContext<'ctx, R> {
             }
         }
 
+        // Only queries with deferred fragments send their response in parts.
+        if operation_plan.incremental.is_some() {
+            self.execute_incremental(
+                operation_plan,
+                Sender {
+                    sender,
+                    status: &mut status,
+                },
+            )
+            .await;
+            return (metrics_attributes, status);
+        }
+
+        if matches!(operation_type, OperationType::Query | OperationType::Mutation) {
+            let response = self.execute_query_or_mutation(operation_plan).await;
+            let status = response.status();
+            sender.send(response).await.ok();
+            return (metrics_attributes, status);
+        }
+
         self.execute_subscription(
             operation_plan,
             Sender {
@@ -435,9 +963,33 @@ impl<'ctx, R: Runtime> PreExecutionContext<'ctx, R> {
     }
 
     async fn prepare_operation(
+        &mut self,
+        request: Request,
+    ) -> Result<ExecutableOperation, (Option<OperationMetricsAttributes>, Response)> {
+        self.build_executable_operation(request)
+            .await
+            .map_err(|(metrics_attributes, mut response)| {
+                if let Some(document) = &self.canonical_document {
+                    response.restore_original_document(document);
+                }
+                (metrics_attributes, response)
+            })
+    }
+
+    async fn build_executable_operation(
         &mut self,
         mut request: Request,
     ) -> Result<ExecutableOperation, (Option<OperationMetricsAttributes>, Response)> {
+        // Only a document sent as is can be empty, persisted ones are resolved later.
+        if request.document_id.is_none()
+            && request.extensions.persisted_query.is_none()
+            && request.query().trim().is_empty()
+        {
+            let error = GraphqlError::new("The request doesn't contain any query.", ErrorCode::EmptyQuery)
+                .with_retryable(false);
+            return Err((None, Response::pre_execution_error(error)));
+        }
+
         let result = {
             let PreparedOperationDocument {
                 cache_key,
@@ -447,7 +999,7 @@ impl<'ctx, R: Runtime> PreExecutionContext<'ctx, R> {
                 Err(err) => return Err((None, Response::pre_execution_error(err))),
             };
 
-            if let Some(operation) = self.operation_cache.get(&cache_key).await {
+            if let Some(operation) = self.operation_cache.get(&cache_key) {
                 Ok(operation)
             } else if let Some(persisted_query) = document_fut {
                 match persisted_query.await {
@@ -465,15 +1017,32 @@ impl<'ctx, R: Runtime> PreExecutionContext<'ctx, R> {
                 if let Some(query) = query {
                     request.query = query
                 }
-                let operation = Operation::build(&self.schema, &request)
-                    .map(Arc::new)
-                    .map_err(|mut err| (err.take_metrics_attributes(), Response::pre_execution_error(err)))?;
+                let client_name = self.request_context.client.as_ref().map(|client| client.name.as_str());
+                // Documents sent as is are built from their canonical form, shared by all the
+                // equivalent ones.
+                let document = self
+                    .canonical_document
+                    .as_ref()
+                    .map_or(request.query(), |document| document.as_str());
+                let operation = Operation::build(
+                    &self.schema,
+                    &request,
+                    document,
+                    client_name,
+                    &self.request_context.feature_flags,
+                    self.request_context.tenant.as_ref(),
+                )
+                .map(Arc::new)
+                .map_err(|mut err| (err.take_metrics_attributes(), Response::pre_execution_error(err)))?;
 
-                self.push_background_future(self.engine.operation_cache.insert(cache_key, operation.clone()).boxed());
+                self.engine.operation_cache.insert(cache_key, operation.clone());
                 operation
             }
         };
 
+        if let Some(document) = &self.canonical_document {
+            request.variables = document.canonical_variables(std::mem::take(&mut request.variables));
+        }
         let variables = Variables::build(self.schema.as_ref(), &operation, request.variables).map_err(|errors| {
             (
                 Some(operation.metrics_attributes.clone()),
@@ -492,9 +1061,70 @@ impl<'ctx, R: Runtime> PreExecutionContext<'ctx, R> {
     }
 }
 
+/// Response to a single operation, which may come from the introspection or response cache.
+enum SingleResponse {
+    Executed {
+        response: Arc<Response>,
+        /// Key under which the serialized response is cached, for introspection-only queries.
+        introspection_cache_key: Option<String>,
+        /// Key under which the serialized response is cached, for queries with the response
+        /// cache enabled.
+        response_cache_key: Option<blake3::Hash>,
+        surrogate_keys: Vec<String>,
+    },
+    CachedIntrospection(Vec<u8>),
+    Cached(CachedResponse),
+}
+
+impl SingleResponse {
+    fn executed(response: Response) -> Self {
+        SingleResponse::Executed {
+            response: Arc::new(response),
+            introspection_cache_key: None,
+            response_cache_key: None,
+            surrogate_keys: Vec::new(),
+        }
+    }
+
+    fn status(&self) -> GraphqlResponseStatus {
+        match self {
+            SingleResponse::Executed { response, .. } => response.status(),
+            SingleResponse::CachedIntrospection(_) | SingleResponse::Cached(_) => GraphqlResponseStatus::Success,
+        }
+    }
+
+    fn has_execution_failures(&self) -> bool {
+        match self {
+            SingleResponse::Executed { response, .. } => response.has_execution_failures(),
+            SingleResponse::CachedIntrospection(_) | SingleResponse::Cached(_) => false,
+        }
+    }
+
+    fn first_error_message(&self) -> Option<Cow<'static, str>> {
+        match self {
+            SingleResponse::Executed { response, .. } => response.first_error_message(),
+            SingleResponse::CachedIntrospection(_) | SingleResponse::Cached(_) => None,
+        }
+    }
+}
+
+/// Space separated, as expected by CDNs purging by surrogate key.
+fn insert_surrogate_keys(http_response: &mut HttpGraphqlResponse, surrogate_keys: &[String]) {
+    if surrogate_keys.is_empty() {
+        return;
+    }
+    if let Ok(value) = http::HeaderValue::from_str(&surrogate_keys.join(" ")) {
+        http_response
+            .headers
+            .insert(http::HeaderName::from_static("surrogate-key"), value);
+    }
+}
+
 pub struct Session<R: Runtime> {
     engine: Arc<Engine<R>>,
     request_context: Arc<RequestContext<<R::Hooks as Hooks>::Context>>,
+    // Subscriptions running over the WebSocket connection of this session.
+    active_subscriptions: Arc<AtomicUsize>,
 }
 
 impl<R: Runtime> Clone for Session<R> {
@@ -502,6 +1132,7 @@ impl<R: Runtime> Clone for Session<R> {
         Self {
             engine: Arc::clone(&self.engine),
             request_context: Arc::clone(&self.request_context),
+            active_subscriptions: Arc::clone(&self.active_subscriptions),
         }
     }
 }
@@ -510,23 +1141,54 @@ pub(crate) struct RequestContext<C> {
     pub headers: http::HeaderMap,
     pub streaming_format: Option<StreamingFormat>,
     pub client: Option<Client>,
+    /// Only used to log rejections.
+    pub source_ip: Option<String>,
+    pub feature_flags: FeatureFlags,
+    /// Only set when tenants are configured.
+    pub tenant: Option<Tenant>,
     pub access_token: AccessToken,
+    /// Whether the headers received, before the hooks, identified the user.
+    pub carries_credentials: bool,
     pub hooks_context: C,
 }
 
 impl<R: Runtime> Session<R> {
-    pub fn execute_websocket(&self, id: String, request: Request) -> impl Stream<Item = websocket::Message> {
-        self.engine
+    /// Starts a subscription received over the WebSocket connection of this session. If a
+    /// subscription limit is reached, nothing is started and the error to send back is returned.
+    pub fn execute_websocket(
+        &self,
+        id: String,
+        request: Request,
+    ) -> Result<impl Stream<Item = websocket::Message>, websocket::Message> {
+        let slot = subscription_limits::SubscriptionSlot::acquire(
+            &self.engine.schema.settings,
+            &self.active_subscriptions,
+            &self.engine.active_subscriptions,
+        )
+        .map_err(|message| websocket::Message::Error {
+            id: id.clone(),
+            payload: websocket::Payload(Response::pre_execution_error(GraphqlError::new(
+                message,
+                ErrorCode::TooManySubscriptions,
+            ))),
+        })?;
+
+        Ok(self
+            .engine
             .execute_stream(self.request_context.clone(), request)
-            .map(move |response| match response {
-                Response::PreExecutionError(_) => websocket::Message::Error {
-                    id: id.clone(),
-                    payload: websocket::Payload(response),
-                },
-                response => websocket::Message::Next {
-                    id: id.clone(),
-                    payload: websocket::Payload(response),
-                },
-            })
+            .map(move |response| {
+                // The slot is released once the subscription stream is dropped.
+                let _slot = &slot;
+                match response {
+                    Response::PreExecutionError(_) => websocket::Message::Error {
+                        id: id.clone(),
+                        payload: websocket::Payload(response),
+                    },
+                    response => websocket::Message::Next {
+                        id: id.clone(),
+                        payload: websocket::Payload(response),
+                    },
+                }
+            }))
     }
 }
@@ -0,0 +1,78 @@
+//! Duplicate operation names within a batch are detected from the `operationName` of each
+//! request, operations without one are never considered duplicates.
+
+use std::collections::HashMap;
+
+use engine::Request;
+
+pub(super) fn find_duplicate_operation_name(requests: &[Request]) -> Option<&str> {
+    let counts = count_operation_names(requests);
+    requests
+        .iter()
+        .filter_map(|request| request.operation_name.as_deref())
+        .find(|name| counts[name] > 1)
+}
+
+/// Operations sharing their name with another one get their index in the batch as suffix, so
+/// that they can be told apart in traces and metrics.
+pub(super) fn disambiguation_suffixes(requests: &[Request]) -> Vec<Option<String>> {
+    let counts = count_operation_names(requests);
+    requests
+        .iter()
+        .enumerate()
+        .map(|(index, request)| {
+            request
+                .operation_name
+                .as_deref()
+                .filter(|name| counts[name] > 1)
+                .map(|_| format!("#{index}"))
+        })
+        .collect()
+}
+
+fn count_operation_names(requests: &[Request]) -> HashMap<&str, usize> {
+    let mut counts = HashMap::new();
+    for name in requests.iter().filter_map(|request| request.operation_name.as_deref()) {
+        *counts.entry(name).or_default() += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use engine::Request;
+
+    use super::{disambiguation_suffixes, find_duplicate_operation_name};
+
+    fn requests(names: &[Option<&str>]) -> Vec<Request> {
+        names
+            .iter()
+            .map(|name| {
+                let request = Request::new("query { __typename }");
+                match name {
+                    Some(name) => request.with_operation_name(*name),
+                    None => request,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn only_duplicated_names_are_suffixed() {
+        let requests = requests(&[Some("A"), Some("B"), None, Some("A"), None]);
+
+        assert_eq!(find_duplicate_operation_name(&requests), Some("A"));
+        assert_eq!(
+            disambiguation_suffixes(&requests),
+            [Some("#0".to_string()), None, None, Some("#3".to_string()), None]
+        );
+    }
+
+    #[test]
+    fn anonymous_operations_are_never_duplicates() {
+        let requests = requests(&[None, None, Some("A")]);
+
+        assert_eq!(find_duplicate_operation_name(&requests), None);
+        assert_eq!(disambiguation_suffixes(&requests), [None, None, None]);
+    }
+}
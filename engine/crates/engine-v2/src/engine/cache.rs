@@ -1,13 +1,14 @@
 use base64::{display::Base64Display, engine::general_purpose::URL_SAFE_NO_PAD};
-use engine::PersistedQueryRequestExtension;
+use engine::{PersistedQueryRequestExtension, Request};
 use schema::Schema;
 
-use super::SchemaVersion;
+use super::{FeatureFlags, SchemaVersion, Tenant};
 
 mod namespaces {
     pub const OPERATION: &str = "op";
     pub const TRUSTED_DOCUMENT: &str = "tdoc";
     pub const APQ: &str = "apq";
+    pub const INTROSPECTION_RESPONSE: &str = "intro";
 }
 
 /// Unique cache key that generates a URL-safe string.
@@ -15,6 +16,9 @@ mod namespaces {
 pub(super) enum Key<'a> {
     Operation {
         name: Option<&'a str>,
+        client_name: Option<&'a str>,
+        feature_flags: &'a FeatureFlags,
+        tenant: Option<&'a Tenant>,
         schema_version: &'a SchemaVersion,
         document: Document<'a>,
     },
@@ -25,11 +29,19 @@ pub(super) enum Key<'a> {
     Apq {
         ext: &'a PersistedQueryRequestExtension,
     },
+    IntrospectionResponse {
+        request: &'a Request,
+        client_name: Option<&'a str>,
+        feature_flags: &'a FeatureFlags,
+        tenant: Option<&'a Tenant>,
+        schema_version: &'a SchemaVersion,
+    },
 }
 
 pub(super) enum Document<'a> {
     PersistedQueryExt(&'a PersistedQueryRequestExtension),
     Id(&'a str),
+    /// Canonical form of the document, see [`crate::operation::CanonicalDocument`].
     Text(&'a str),
 }
 
@@ -40,6 +52,9 @@ impl std::fmt::Display for Key<'_> {
             // backwards-compatibility
             Key::Operation {
                 name,
+                client_name,
+                feature_flags,
+                tenant,
                 schema_version,
                 document,
             } => {
@@ -55,6 +70,30 @@ impl std::fmt::Display for Key<'_> {
                 // NULL bytes acting as a separator as it cannot be present in the
                 // operation name.
                 hasher.update(&[0x00]);
+                if let Some(client_name) = client_name {
+                    hasher.update(b"client");
+                    hasher.update(&[0x00]);
+                    hasher.update(client_name.as_bytes());
+                    hasher.update(&[0x00]);
+                }
+                // Fields gated behind feature flags are bound differently depending on them.
+                for flag in feature_flags.iter() {
+                    hasher.update(b"flag");
+                    hasher.update(&[0x00]);
+                    hasher.update(flag.as_bytes());
+                    hasher.update(&[0x00]);
+                }
+                // Likewise for fields only visible to some tenants.
+                if let Some(tenant) = tenant {
+                    hasher.update(b"tenant");
+                    hasher.update(&[0x00]);
+                    for tag in tenant.tags() {
+                        hasher.update(b"tag");
+                        hasher.update(&[0x00]);
+                        hasher.update(tag.as_bytes());
+                        hasher.update(&[0x00]);
+                    }
+                }
                 match document {
                     Document::PersistedQueryExt(ext) => {
                         hasher.update(b"apq");
@@ -98,6 +137,77 @@ impl std::fmt::Display for Key<'_> {
                 version,
                 Base64Display::new(sha256_hash, &URL_SAFE_NO_PAD)
             )),
+            // The response depends on the schema, so a new schema version invalidates it.
+            Key::IntrospectionResponse {
+                request,
+                client_name,
+                feature_flags,
+                tenant,
+                schema_version,
+            } => {
+                let mut hasher = blake3::Hasher::new();
+                hasher.update(&Schema::build_identifier().len().to_ne_bytes());
+                hasher.update(Schema::build_identifier());
+                hasher.update(&schema_version.len().to_ne_bytes());
+                hasher.update(schema_version);
+
+                let parts = [
+                    serde_json::to_vec(&request.query),
+                    serde_json::to_vec(&request.operation_name),
+                    serde_json::to_vec(&request.document_id),
+                    serde_json::to_vec(&request.extensions.persisted_query),
+                    serde_json::to_vec(&request.variables),
+                    serde_json::to_vec(client_name),
+                    serde_json::to_vec(&feature_flags.iter().collect::<Vec<_>>()),
+                    serde_json::to_vec(&tenant.map(|tenant| tenant.tags().collect::<Vec<_>>())),
+                ];
+                for part in parts {
+                    let bytes = part.unwrap_or_default();
+                    // Length prefix, so that parts can't bleed into each other.
+                    hasher.update(&bytes.len().to_ne_bytes());
+                    hasher.update(&bytes);
+                }
+                let hash = hasher.finalize();
+
+                f.write_fmt(format_args!(
+                    "{}.blake3.{}",
+                    namespaces::INTROSPECTION_RESPONSE,
+                    Base64Display::new(hash.as_bytes(), &URL_SAFE_NO_PAD)
+                ))
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Document, Key};
+    use crate::{
+        engine::{FeatureFlags, SchemaVersion},
+        operation::CanonicalDocument,
+    };
+
+    fn operation_key(query: &str) -> String {
+        Key::Operation {
+            name: None,
+            client_name: None,
+            feature_flags: &FeatureFlags::default(),
+            tenant: None,
+            schema_version: &SchemaVersion(b"v1".to_vec()),
+            document: Document::Text(CanonicalDocument::new(query).as_str()),
+        }
+        .to_string()
+    }
+
+    #[test]
+    fn equivalent_documents_share_the_operation_key() {
+        let key = operation_key("query($id: ID!) {\n  user(id: $id) { id, name }\n}");
+
+        assert_eq!(
+            key,
+            operation_key("query($id: ID!) { # Some user\n  user(id: $id) { id, name },  \n}\n")
+        );
+        assert_eq!(key, operation_key("query($userId:ID!){user(id:$userId){id name}}"));
+        assert_ne!(key, operation_key("query($id: ID!) {\n  user(id: $id) { name, id }\n}"));
+    }
+}
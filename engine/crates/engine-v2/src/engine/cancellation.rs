@@ -0,0 +1,31 @@
+use grafbase_telemetry::{metrics::RequestCancellationMetrics, span::GRAFBASE_TARGET};
+
+/// Held for the whole execution of a request. Dropped before being completed, the execution was
+/// dropped with it, subgraph requests in flight included, which happens when the client
+/// disconnects.
+pub(super) struct CancellationGuard<'a> {
+    metrics: &'a RequestCancellationMetrics,
+    completed: bool,
+}
+
+impl<'a> CancellationGuard<'a> {
+    pub(super) fn new(metrics: &'a RequestCancellationMetrics) -> Self {
+        Self {
+            metrics,
+            completed: false,
+        }
+    }
+
+    pub(super) fn complete(mut self) {
+        self.completed = true;
+    }
+}
+
+impl Drop for CancellationGuard<'_> {
+    fn drop(&mut self) {
+        if !self.completed {
+            tracing::debug!(target: GRAFBASE_TARGET, "request cancelled before completion");
+            self.metrics.record_cancelled();
+        }
+    }
+}
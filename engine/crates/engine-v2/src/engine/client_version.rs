@@ -0,0 +1,73 @@
+//! Rejection of clients older than the minimum version configured for them.
+
+use std::cmp::Ordering;
+
+use grafbase_telemetry::grafbase_client::Client;
+
+use crate::{
+    response::{ErrorCode, GraphqlError},
+    Engine, Runtime,
+};
+
+impl<R: Runtime> Engine<R> {
+    /// Clients with a minimum version must send an equal or newer one. Unknown clients and
+    /// clients without a minimum version aren't restricted.
+    pub(super) fn ensure_supported_client_version(&self, client: Option<&Client>) -> Result<(), GraphqlError> {
+        let Some((client, min_version)) = client.and_then(|client| {
+            let min_version = self.schema.settings.clients.get(&client.name)?.min_version.as_deref()?;
+            Some((client, min_version))
+        }) else {
+            return Ok(());
+        };
+
+        let message = match client.version.as_deref() {
+            Some(version) if compare_versions(version, min_version).is_ge() => return Ok(()),
+            Some(version) => format!(
+                "Version {version} of client '{}' is no longer supported, please upgrade to version {min_version} or later",
+                client.name
+            ),
+            None => format!(
+                "Client '{}' must send its version, please upgrade to version {min_version} or later",
+                client.name
+            ),
+        };
+
+        Err(GraphqlError::new(message, ErrorCode::ClientVersionUnsupported))
+    }
+}
+
+/// Compares the dot separated numbers of both versions, missing ones counting as 0. Pre-release
+/// and build suffixes are ignored.
+fn compare_versions(left: &str, right: &str) -> Ordering {
+    let (left, right) = (version_numbers(left), version_numbers(right));
+    let len = left.len().max(right.len());
+    let padded = |numbers: Vec<u64>| numbers.into_iter().chain(std::iter::repeat(0)).take(len);
+    padded(left).cmp(padded(right))
+}
+
+fn version_numbers(version: &str) -> Vec<u64> {
+    let version = version.trim().trim_start_matches('v');
+    let version = version.split(['-', '+']).next().unwrap_or_default();
+    version
+        .split('.')
+        .map(|number| {
+            let digits = number.find(|c: char| !c.is_ascii_digit()).unwrap_or(number.len());
+            number[..digits].parse().unwrap_or_default()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::compare_versions;
+
+    #[test]
+    fn versions_are_compared_numerically() {
+        assert_eq!(compare_versions("2.10.0", "2.9.1"), Ordering::Greater);
+        assert_eq!(compare_versions("1.2", "1.2.0"), Ordering::Equal);
+        assert_eq!(compare_versions("v1.4.0-beta.1", "1.4.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.3.9", "1.4"), Ordering::Less);
+    }
+}
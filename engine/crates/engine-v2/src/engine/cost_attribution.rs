@@ -0,0 +1,88 @@
+//! Attribution of the cost of operations to the subgraphs resolving their fields, for
+//! chargeback.
+
+use schema::sources::graphql::GraphqlEndpointId;
+
+use super::cost_budget::operation_cost;
+use crate::{
+    execution::{ExecutableOperation, PreExecutionContext},
+    operation::{Field, FieldId},
+    Runtime,
+};
+
+impl<'ctx, R: Runtime> PreExecutionContext<'ctx, R> {
+    /// Apportions the cost of the operation between the subgraphs resolving its fields,
+    /// recorded in the `subgraph_cost_total` metric.
+    pub(super) fn record_subgraph_costs(&self, operation: &ExecutableOperation) {
+        let walker = self.schema.walker();
+        let weighted_fields = operation
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| matches!(field, Field::Query(_)))
+            .filter_map(|(id, _)| {
+                let plan_id = operation.plan_id_for(FieldId::from(id));
+                let resolver = walker.walk(operation.plan[plan_id].resolver_id);
+                // Introspection fields aren't resolved by any subgraph.
+                let endpoint = resolver.graphql_endpoint()?;
+                Some((endpoint.id(), endpoint.cost_weight()))
+            });
+
+        for (endpoint_id, cost) in apportion(operation_cost(operation), weighted_fields) {
+            self.engine
+                .subgraph_metrics
+                .record_cost(walker.walk(endpoint_id).name(), cost);
+        }
+    }
+}
+
+/// Splits the cost between the subgraphs in proportion to the sum of the weights of the fields
+/// they resolve.
+fn apportion(
+    cost: u64,
+    weighted_fields: impl IntoIterator<Item = (GraphqlEndpointId, f64)>,
+) -> Vec<(GraphqlEndpointId, f64)> {
+    let mut weights = Vec::<(GraphqlEndpointId, f64)>::new();
+    for (endpoint_id, weight) in weighted_fields {
+        match weights.iter_mut().find(|(id, _)| *id == endpoint_id) {
+            Some((_, total)) => *total += weight,
+            None => weights.push((endpoint_id, weight)),
+        }
+    }
+
+    let total_weight = weights.iter().map(|(_, weight)| weight).sum::<f64>();
+    if total_weight <= 0.0 {
+        return Vec::new();
+    }
+
+    weights
+        .into_iter()
+        .map(|(endpoint_id, weight)| (endpoint_id, cost as f64 * weight / total_weight))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use schema::sources::graphql::GraphqlEndpointId;
+
+    use super::apportion;
+
+    #[test]
+    fn cost_is_apportioned_by_the_weight_of_the_resolved_fields() {
+        let products = GraphqlEndpointId::from(0);
+        let reviews = GraphqlEndpointId::from(1);
+
+        // Two fields from each subgraph, those of reviews weighing three times as much.
+        let fields = [(products, 1.0), (reviews, 3.0), (products, 1.0), (reviews, 3.0)];
+
+        assert_eq!(apportion(4, fields), [(products, 1.0), (reviews, 3.0)]);
+    }
+
+    #[test]
+    fn nothing_is_attributed_without_any_weight() {
+        let products = GraphqlEndpointId::from(0);
+
+        assert!(apportion(4, [(products, 0.0)]).is_empty());
+        assert!(apportion(4, []).is_empty());
+    }
+}
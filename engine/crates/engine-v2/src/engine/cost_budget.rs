@@ -0,0 +1,108 @@
+//! Admission of operations based on a per-client token bucket consumed by their cost.
+
+use std::time::Duration;
+
+use ::runtime::kv::KvStore;
+use config::latest::CostBudget;
+use web_time::{SystemTime, UNIX_EPOCH};
+
+use crate::{
+    execution::{ExecutableOperation, PreExecutionContext},
+    operation::Field,
+    response::{ErrorCode, GraphqlError},
+    Runtime,
+};
+
+/// State of a bucket, stored in the KV store to be shared by all gateway instances.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Bucket {
+    tokens: f64,
+    updated_at_ms: u64,
+}
+
+impl<'ctx, R: Runtime> PreExecutionContext<'ctx, R> {
+    /// Takes the cost of the operation from the budget of the client, if it has one. Rejected
+    /// operations don't consume anything.
+    pub(super) async fn consume_cost_budget(&self, operation: &ExecutableOperation) -> Result<(), GraphqlError> {
+        let Some((client_name, budget)) = self.request_context.client.as_ref().and_then(|client| {
+            let budget = self.schema.settings.clients.get(&client.name)?.cost_budget?;
+            Some((client.name.as_str(), budget))
+        }) else {
+            return Ok(());
+        };
+
+        let cost = operation_cost(operation);
+        match take_tokens(self.runtime.kv(), client_name, budget, cost).await {
+            Ok(()) => Ok(()),
+            Err(retry_after) => {
+                let error = GraphqlError::new(
+                    format!("Operation cost of {cost} exceeds the remaining budget of client '{client_name}'"),
+                    ErrorCode::CostBudgetExceeded,
+                );
+                Err(match retry_after {
+                    Some(retry_after) => error
+                        .with_extension("retryAfter", retry_after.as_secs())
+                        .with_retryable(true),
+                    // The operation costs more than the bucket can ever hold.
+                    None => error.with_retryable(false),
+                })
+            }
+        }
+    }
+}
+
+/// Each field selected by the client costs one token, like for the complexity limit.
+pub(super) fn operation_cost(operation: &ExecutableOperation) -> u64 {
+    operation
+        .fields
+        .iter()
+        .filter(|field| matches!(field, Field::Query(_)))
+        .count() as u64
+}
+
+/// On rejection, returns how long to wait until enough tokens are available, if ever.
+async fn take_tokens(kv: &KvStore, client_name: &str, budget: CostBudget, cost: u64) -> Result<(), Option<Duration>> {
+    let key = format!("cost_budget:{client_name}");
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default();
+    let capacity = budget.capacity as f64;
+    let refill_per_second = budget.refill_per_second as f64;
+
+    // The KV store has no atomic updates, concurrent operations may both consume the same
+    // tokens. The budget is enforced on a best effort basis and fails open.
+    let tokens = match kv.get_json_or_null::<Bucket>(&key, None).await {
+        Ok(Some(bucket)) => {
+            let elapsed = now_ms.saturating_sub(bucket.updated_at_ms) as f64 / 1000.0;
+            (bucket.tokens + elapsed * refill_per_second).min(capacity)
+        }
+        Ok(None) => capacity,
+        Err(err) => {
+            tracing::warn!("Failed to read the cost budget of client '{client_name}': {err}");
+            return Ok(());
+        }
+    };
+
+    let cost = cost as f64;
+    if cost > tokens {
+        if cost > capacity || budget.refill_per_second == 0 {
+            return Err(None);
+        }
+        let retry_after = ((cost - tokens) / refill_per_second).ceil();
+        return Err(Some(Duration::from_secs(retry_after as u64)));
+    }
+
+    let bucket = Bucket {
+        tokens: tokens - cost,
+        updated_at_ms: now_ms,
+    };
+    // Once full again the bucket is equivalent to a missing one.
+    let expiration_ttl = (budget.refill_per_second > 0)
+        .then(|| Duration::from_secs((budget.capacity / budget.refill_per_second).max(1) + 1));
+    if let Err(err) = kv.put_json(&key, &bucket, expiration_ttl).await {
+        tracing::warn!("Failed to update the cost budget of client '{client_name}': {err}");
+    }
+
+    Ok(())
+}
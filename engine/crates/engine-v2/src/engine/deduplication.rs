@@ -0,0 +1,150 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use engine::Request;
+use futures::{
+    channel::oneshot,
+    future::{FutureExt, Shared},
+};
+use runtime::auth::AccessToken;
+use schema::Schema;
+
+use super::{FeatureFlags, RequestContext, Tenant};
+use crate::{execution::create_subgraph_headers_with_rules, response::Response};
+
+type SharedResponse = Shared<oneshot::Receiver<Arc<Response>>>;
+
+/// Queries currently being executed, keyed by everything that may influence their response. Lets
+/// identical concurrent queries share a single execution.
+#[derive(Default)]
+pub(crate) struct InFlightQueries {
+    queries: Mutex<HashMap<blake3::Hash, SharedResponse>>,
+}
+
+pub(crate) enum Flight<'a> {
+    /// No identical query is running, the caller must execute it and complete the flight.
+    Leader(FlightLeader<'a>),
+    /// An identical query is running, its response can be awaited. If that query gets cancelled,
+    /// the receiver fails and the caller must execute the query itself.
+    Follower(SharedResponse),
+}
+
+pub(crate) struct FlightLeader<'a> {
+    in_flight: &'a InFlightQueries,
+    key: blake3::Hash,
+    sender: Option<oneshot::Sender<Arc<Response>>>,
+}
+
+impl InFlightQueries {
+    pub fn join(&self, key: blake3::Hash) -> Flight<'_> {
+        let mut queries = self.queries.lock().unwrap();
+        if let Some(response) = queries.get(&key) {
+            return Flight::Follower(response.clone());
+        }
+
+        let (sender, receiver) = oneshot::channel();
+        queries.insert(key, receiver.shared());
+
+        Flight::Leader(FlightLeader {
+            in_flight: self,
+            key,
+            sender: Some(sender),
+        })
+    }
+}
+
+impl FlightLeader<'_> {
+    pub fn complete(mut self, response: Arc<Response>) {
+        let sender = self.sender.take();
+        // Queries received from now on must be executed again.
+        drop(self);
+        if let Some(sender) = sender {
+            sender.send(response).ok();
+        }
+    }
+}
+
+impl Drop for FlightLeader<'_> {
+    fn drop(&mut self) {
+        // If the leader was cancelled, dropping the sender releases the followers.
+        if let Ok(mut queries) = self.in_flight.queries.lock() {
+            queries.remove(&self.key);
+        }
+    }
+}
+
+/// Returns the deduplication key of the request, or `None` if it cannot be deduplicated.
+///
+/// Besides the operation itself, the key includes the authentication of the client and the
+/// headers forwarded to the subgraphs, see [`forwarded_headers`].
+pub(crate) fn deduplication_key(
+    request: &Request,
+    client_name: Option<&str>,
+    feature_flags: &FeatureFlags,
+    tenant: Option<&Tenant>,
+    access_token: &AccessToken,
+    forwarded_headers: &[(http::HeaderName, http::HeaderValue)],
+) -> Option<blake3::Hash> {
+    if !request.uploads.is_empty() {
+        return None;
+    }
+
+    let mut hasher = blake3::Hasher::new();
+    update(&mut hasher, &request.query)?;
+    update(&mut hasher, &request.operation_name)?;
+    update(&mut hasher, &request.document_id)?;
+    update(&mut hasher, &request.variables)?;
+    update(&mut hasher, &request.extensions.persisted_query)?;
+    update(&mut hasher, &client_name)?;
+    update(&mut hasher, &feature_flags.iter().collect::<Vec<_>>())?;
+    update(&mut hasher, &tenant.map(|tenant| tenant.tags().collect::<Vec<_>>()))?;
+    update(&mut hasher, access_token)?;
+    update(
+        &mut hasher,
+        &forwarded_headers
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_bytes()))
+            .collect::<Vec<_>>(),
+    )?;
+
+    Some(hasher.finalize())
+}
+
+/// Headers any subgraph would receive once the header rules are applied, in a stable order as
+/// clients may send them in any order. Only those can change the subgraph responses, while
+/// headers such as `traceparent` or `x-request-id` differ for every request.
+pub(crate) fn forwarded_headers<C>(
+    schema: &Schema,
+    request_context: &RequestContext<C>,
+) -> Vec<(http::HeaderName, http::HeaderValue)> {
+    let default = create_subgraph_headers_with_rules(
+        request_context,
+        schema.walker().default_header_rules(),
+        http::HeaderMap::new(),
+    );
+
+    let mut pairs = default
+        .iter()
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect::<Vec<_>>();
+    for endpoint in schema.walker().graphql_endpoints() {
+        let headers = create_subgraph_headers_with_rules(request_context, endpoint.header_rules(), default.clone());
+        pairs.extend(headers.iter().map(|(name, value)| (name.clone(), value.clone())));
+    }
+
+    pairs.sort_unstable_by(|(a_name, a_value), (b_name, b_value)| {
+        (a_name.as_str(), a_value.as_bytes()).cmp(&(b_name.as_str(), b_value.as_bytes()))
+    });
+    pairs.dedup();
+    pairs
+}
+
+fn update(hasher: &mut blake3::Hasher, value: &impl serde::Serialize) -> Option<()> {
+    let bytes = serde_json::to_vec(value).ok()?;
+    // Length prefix, so that values can't bleed into each other.
+    hasher.update(&bytes.len().to_ne_bytes());
+    hasher.update(&bytes);
+    Some(())
+}
@@ -0,0 +1,61 @@
+pub(crate) static X_GRAFBASE_FLAGS: http::HeaderName = http::HeaderName::from_static("x-grafbase-flags");
+
+/// Feature flags enabled by the request through the `x-grafbase-flags` header, as a comma
+/// separated list. Fields annotated with `@feature(name:)` are only visible to requests
+/// enabling the matching flag.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct FeatureFlags(Vec<String>);
+
+impl FeatureFlags {
+    pub fn extract_from(headers: &http::HeaderMap) -> Self {
+        let mut flags = headers
+            .get_all(&X_GRAFBASE_FLAGS)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .filter(|flag| !flag.is_empty())
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+
+        // Sorted so that the same set of flags always produces the same operation cache key.
+        flags.sort_unstable();
+        flags.dedup();
+
+        FeatureFlags(flags)
+    }
+
+    pub fn contains(&self, flag: &str) -> bool {
+        self.0.binary_search_by(|probe| probe.as_str().cmp(flag)).is_ok()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FeatureFlags, X_GRAFBASE_FLAGS};
+
+    #[test]
+    fn flags_are_trimmed_sorted_and_deduplicated() {
+        let mut headers = http::HeaderMap::new();
+        headers.append(&X_GRAFBASE_FLAGS, "feature-b, feature-a,,".parse().unwrap());
+        headers.append(&X_GRAFBASE_FLAGS, "feature-a".parse().unwrap());
+
+        let flags = FeatureFlags::extract_from(&headers);
+
+        assert_eq!(flags.iter().collect::<Vec<_>>(), ["feature-a", "feature-b"]);
+        assert!(flags.contains("feature-b"));
+        assert!(!flags.contains("feature-c"));
+    }
+
+    #[test]
+    fn no_flags_without_header() {
+        assert_eq!(
+            FeatureFlags::extract_from(&http::HeaderMap::new()),
+            FeatureFlags::default()
+        );
+    }
+}
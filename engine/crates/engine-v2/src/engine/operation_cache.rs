@@ -0,0 +1,160 @@
+//! Prepared operations kept in memory, bounded to a maximum number of entries. Once full, an
+//! operation is evicted to make room for a new one: the least recently used among a few sampled
+//! entries, approximating an LRU in constant time.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        RwLock,
+    },
+};
+
+pub(crate) struct OperationCache<V> {
+    capacity: usize,
+    // Incremented on every access, each entry keeping the value of its latest one.
+    clock: AtomicU64,
+    entries: RwLock<Entries<V>>,
+}
+
+/// Entries are stored contiguously so that eviction can sample them by index.
+struct Entries<V> {
+    index: HashMap<String, usize>,
+    slots: Vec<Entry<V>>,
+}
+
+struct Entry<V> {
+    key: String,
+    value: V,
+    last_used: AtomicU64,
+}
+
+/// Number of entries considered for eviction. Caches no larger than that behave as an exact LRU.
+const EVICTION_SAMPLES: usize = 5;
+
+impl<V: Clone> OperationCache<V> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            clock: AtomicU64::new(0),
+            entries: RwLock::new(Entries {
+                index: HashMap::new(),
+                slots: Vec::new(),
+            }),
+        }
+    }
+
+    /// Hits only take the read lock, the recency of the entry being tracked with an atomic.
+    pub fn get(&self, key: &str) -> Option<V> {
+        let entries = self.entries.read().unwrap();
+        let entry = &entries.slots[*entries.index.get(key)?];
+        entry.last_used.fetch_max(self.tick(), Ordering::Relaxed);
+        Some(entry.value.clone())
+    }
+
+    pub fn insert(&self, key: String, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut entries = self.entries.write().unwrap();
+        let last_used = AtomicU64::new(self.tick());
+        if let Some(&i) = entries.index.get(&key) {
+            entries.slots[i] = Entry { key, value, last_used };
+            return;
+        }
+
+        if entries.slots.len() >= self.capacity {
+            entries.evict_one();
+        }
+
+        entries.index.insert(key.clone(), entries.slots.len());
+        entries.slots.push(Entry { key, value, last_used });
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+impl<V> Entries<V> {
+    fn evict_one(&mut self) {
+        let len = self.slots.len();
+        let Some(evicted) = rand::seq::index::sample(&mut rand::thread_rng(), len, EVICTION_SAMPLES.min(len))
+            .into_iter()
+            .min_by_key(|&i| self.slots[i].last_used.load(Ordering::Relaxed))
+        else {
+            return;
+        };
+
+        let entry = self.slots.swap_remove(evicted);
+        self.index.remove(&entry.key);
+        if let Some(moved) = self.slots.get(evicted) {
+            self.index.insert(moved.key.clone(), evicted);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OperationCache;
+
+    #[test]
+    fn least_recently_used_operation_is_evicted_first() {
+        let cache = OperationCache::new(3);
+        cache.insert("a".to_string(), 1);
+        cache.insert("b".to_string(), 2);
+        cache.insert("c".to_string(), 3);
+
+        assert_eq!(cache.get("a"), Some(1));
+
+        cache.insert("d".to_string(), 4);
+        assert_eq!(cache.get("b"), None);
+
+        cache.insert("e".to_string(), 5);
+        assert_eq!(cache.get("c"), None);
+
+        assert_eq!(cache.get("a"), Some(1));
+        assert_eq!(cache.get("d"), Some(4));
+        assert_eq!(cache.get("e"), Some(5));
+    }
+
+    #[test]
+    fn hot_operation_is_never_evicted_under_churn() {
+        let cache = OperationCache::new(10);
+        cache.insert("hot".to_string(), 0);
+
+        for i in 1..10_000 {
+            cache.insert(format!("cold-{i}"), i);
+            assert_eq!(cache.get("hot"), Some(0));
+        }
+
+        assert_eq!(cache.entries.read().unwrap().slots.len(), 10);
+    }
+
+    #[test]
+    fn entries_stay_reachable_after_evictions() {
+        let cache = OperationCache::new(50);
+        for i in 0..1_000 {
+            cache.insert(i.to_string(), i);
+        }
+
+        let entries = cache.entries.read().unwrap();
+        assert_eq!(entries.slots.len(), 50);
+        assert_eq!(entries.index.len(), 50);
+        for (i, entry) in entries.slots.iter().enumerate() {
+            assert_eq!(entries.index[&entry.key], i);
+        }
+        drop(entries);
+
+        assert_eq!(cache.get("999"), Some(999));
+    }
+
+    #[test]
+    fn nothing_is_cached_without_capacity() {
+        let cache = OperationCache::new(0);
+        cache.insert("a".to_string(), 1);
+
+        assert_eq!(cache.get("a"), None);
+    }
+}
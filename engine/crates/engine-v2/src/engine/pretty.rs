@@ -0,0 +1,10 @@
+pub(crate) static X_GRAFBASE_PRETTY: http::HeaderName = http::HeaderName::from_static("x-grafbase-pretty");
+
+/// Whether the client asked for a pretty-printed response with `x-grafbase-pretty: true`. Only
+/// honored if enabled in the configuration.
+pub(super) fn wants_pretty_response(headers: &http::HeaderMap) -> bool {
+    headers
+        .get(&X_GRAFBASE_PRETTY)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.trim().eq_ignore_ascii_case("true"))
+}
@@ -0,0 +1,91 @@
+//! Structured events for requests and operations rejected before execution, meant for security
+//! monitoring. They're logged under their own target, apart from the request logs, so that
+//! they can be filtered and shipped on their own.
+
+use engine::Request;
+use grafbase_telemetry::grafbase_client::Client;
+use tracing::Level;
+
+use crate::{
+    execution::PreExecutionContext,
+    response::{ErrorCode, Response},
+    Engine, Runtime,
+};
+
+pub(crate) const REJECTIONS_TARGET: &str = "grafbase::rejections";
+
+impl<R: Runtime> Engine<R> {
+    /// Every rejection goes through here. Nothing is logged unless enabled or if the response
+    /// isn't a rejection.
+    pub(super) fn log_rejection(
+        &self,
+        client: Option<&Client>,
+        source_ip: Option<&str>,
+        operation_hash: Option<&str>,
+        response: &Response,
+    ) {
+        if !self.schema.settings.log_rejected_operations {
+            return;
+        }
+
+        let Some(error) = response.rejection() else {
+            return;
+        };
+
+        // Part of the regular automatic persisted queries flow, not worth monitoring.
+        if matches!(error.code, ErrorCode::PersistedQueryNotFound) {
+            return;
+        }
+
+        tracing::event!(
+            target: REJECTIONS_TARGET,
+            Level::WARN,
+            reason = %error.code,
+            error = %error.message,
+            client.name = client.map(|client| client.name.as_str()),
+            client.version = client.and_then(|client| client.version.as_deref()),
+            source.ip = source_ip,
+            operation.hash = operation_hash,
+            "operation rejected",
+        );
+    }
+}
+
+impl<'ctx, R: Runtime> PreExecutionContext<'ctx, R> {
+    pub(super) fn log_rejected_operation(&self, operation_hash: Option<&str>, response: &Response) {
+        self.engine.log_rejection(
+            self.request_context.client.as_ref(),
+            self.request_context.source_ip.as_deref(),
+            operation_hash,
+            response,
+        );
+    }
+}
+
+/// Address of the client as reported by the proxies in front of the gateway, the first one of
+/// `X-Forwarded-For` or `X-Real-IP`.
+pub(super) fn source_ip(headers: &http::HeaderMap) -> Option<String> {
+    headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .or_else(|| headers.get("x-real-ip").and_then(|value| value.to_str().ok()))
+        .map(str::trim)
+        .filter(|ip| !ip.is_empty())
+        .map(str::to_string)
+}
+
+/// Identifies the operation document without logging it: the BLAKE3 hash of the query, or the
+/// trusted document id or persisted query hash if it wasn't sent.
+pub(super) fn operation_hash(request: &Request) -> Option<String> {
+    if !request.query.is_empty() {
+        return Some(blake3::hash(request.query.as_bytes()).to_hex().to_string());
+    }
+    request.document_id.clone().or_else(|| {
+        request
+            .extensions
+            .persisted_query
+            .as_ref()
+            .map(|ext| hex::encode(&ext.sha256_hash))
+    })
+}
@@ -0,0 +1,139 @@
+//! Whole responses of queries, kept in memory and tagged with surrogate keys so that they can be
+//! purged once a mutation changed the data they contain.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+};
+
+use web_time::{Duration, Instant};
+
+// Expired entries are only dropped on access or once the cache is full.
+const MAX_ENTRIES: usize = 10_000;
+
+// Responses to requests with any of them are specific to a user and never cached, whatever the
+// configured bypass headers.
+const CREDENTIAL_HEADERS: [http::HeaderName; 3] = [
+    http::header::AUTHORIZATION,
+    http::header::PROXY_AUTHORIZATION,
+    http::header::COOKIE,
+];
+
+/// Whether the headers identify a user. Checked on the headers received by the gateway, before
+/// the hooks had a chance to remove them and keep the user in their context instead.
+pub(crate) fn carries_credentials(headers: &http::HeaderMap) -> bool {
+    CREDENTIAL_HEADERS.iter().any(|name| headers.contains_key(name))
+}
+
+pub(crate) struct ResponseCache {
+    ttl: Duration,
+    // Requests with any of them, typically carrying credentials, skip the cache entirely.
+    bypass_headers: Vec<String>,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    entries: HashMap<blake3::Hash, CachedResponse>,
+    // Cache keys of the entries tagged with each surrogate key.
+    tagged: HashMap<String, HashSet<blake3::Hash>>,
+}
+
+#[derive(Clone)]
+pub(crate) struct CachedResponse {
+    pub bytes: Vec<u8>,
+    pub surrogate_keys: Vec<String>,
+    expires_at: Instant,
+}
+
+impl ResponseCache {
+    pub fn new(ttl: Duration, bypass_headers: Vec<String>) -> Self {
+        Self {
+            ttl,
+            bypass_headers,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    pub fn is_bypassed_by(&self, headers: &http::HeaderMap) -> bool {
+        carries_credentials(headers)
+            || self
+                .bypass_headers
+                .iter()
+                .any(|name| headers.contains_key(name.as_str()))
+    }
+
+    pub fn get(&self, key: &blake3::Hash) -> Option<CachedResponse> {
+        let mut state = self.state.lock().unwrap();
+        let response = state.entries.get(key)?;
+        if response.expires_at > Instant::now() {
+            return Some(response.clone());
+        }
+        state.remove(key);
+        None
+    }
+
+    pub fn insert(&self, key: blake3::Hash, bytes: Vec<u8>, surrogate_keys: Vec<String>) {
+        let mut state = self.state.lock().unwrap();
+        if state.entries.len() >= MAX_ENTRIES {
+            state.remove_expired();
+            if state.entries.len() >= MAX_ENTRIES {
+                return;
+            }
+        }
+
+        state.remove(&key);
+        for surrogate_key in &surrogate_keys {
+            state.tagged.entry(surrogate_key.clone()).or_default().insert(key);
+        }
+        state.entries.insert(
+            key,
+            CachedResponse {
+                bytes,
+                surrogate_keys,
+                expires_at: Instant::now() + self.ttl,
+            },
+        );
+    }
+
+    /// Purges all responses tagged with the surrogate key, returning how many there were.
+    pub fn invalidate(&self, surrogate_key: &str) -> usize {
+        let mut state = self.state.lock().unwrap();
+        let Some(keys) = state.tagged.remove(surrogate_key) else {
+            return 0;
+        };
+        for key in &keys {
+            state.remove(key);
+        }
+        keys.len()
+    }
+}
+
+impl State {
+    fn remove(&mut self, key: &blake3::Hash) {
+        let Some(response) = self.entries.remove(key) else {
+            return;
+        };
+        for surrogate_key in &response.surrogate_keys {
+            if let Some(keys) = self.tagged.get_mut(surrogate_key) {
+                keys.remove(key);
+                if keys.is_empty() {
+                    self.tagged.remove(surrogate_key);
+                }
+            }
+        }
+    }
+
+    fn remove_expired(&mut self) {
+        let now = Instant::now();
+        let expired = self
+            .entries
+            .iter()
+            .filter(|(_, response)| response.expires_at <= now)
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+        for key in &expired {
+            self.remove(key);
+        }
+    }
+}
@@ -14,4 +14,7 @@ pub trait Runtime: Send + Sync + 'static {
     fn cache_factory(&self) -> &Self::CacheFactory;
     fn rate_limiter(&self) -> &RateLimiter;
     fn sleep(&self, duration: std::time::Duration) -> BoxFuture<'static, ()>;
+    /// Runs the future in the background, detached from the request. Native runtimes spawn it on
+    /// their executor, wasm ones should hand it to `wait_until`.
+    fn spawn(&self, future: BoxFuture<'static, ()>);
 }
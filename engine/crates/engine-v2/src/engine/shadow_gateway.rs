@@ -0,0 +1,217 @@
+use std::time::Duration;
+
+use ::runtime::fetch::{FetchRequest, Fetcher};
+use bytes::Bytes;
+use grafbase_telemetry::{metrics::ResponseDiffMetrics, span::GRAFBASE_TARGET};
+use serde_json::Value;
+
+/// Differences beyond this are only counted in the logs.
+const MAX_LOGGED_DIFFS: usize = 10;
+
+/// Another gateway receiving a copy of the queries, typically a newer version being validated.
+/// Its responses are only compared with ours, never sent to the client.
+pub(crate) struct ShadowGateway {
+    url: url::Url,
+    /// Only these client headers are forwarded, the shadow gateway may not be trusted with the
+    /// others.
+    forwarded_headers: Vec<http::HeaderName>,
+    metrics: ResponseDiffMetrics,
+}
+
+impl ShadowGateway {
+    pub(crate) fn new(url: url::Url, forwarded_headers: &[String], metrics: ResponseDiffMetrics) -> Self {
+        Self {
+            url,
+            forwarded_headers: forwarded_headers
+                .iter()
+                .filter_map(|name| http::HeaderName::from_bytes(name.as_bytes()).ok())
+                .collect(),
+            metrics,
+        }
+    }
+
+    /// The client headers to send along the request.
+    pub(crate) fn forwarded_headers(&self, headers: &http::HeaderMap) -> http::HeaderMap {
+        let mut forwarded = http::HeaderMap::new();
+        for name in &self.forwarded_headers {
+            for value in headers.get_all(name) {
+                forwarded.append(name.clone(), value.clone());
+            }
+        }
+        forwarded
+    }
+
+    /// Sends the request to the shadow gateway and compares both responses. Failures are only
+    /// logged.
+    pub(crate) async fn compare(
+        &self,
+        fetcher: &Fetcher,
+        headers: http::HeaderMap,
+        request_body: Bytes,
+        response_body: &[u8],
+        timeout: Duration,
+    ) {
+        let request = FetchRequest {
+            url: &self.url,
+            headers,
+            json_body: request_body,
+            timeout,
+            danger_accept_invalid_certs: false,
+        };
+
+        let shadow_body = match fetcher.post(&request).await {
+            Ok(response) => response.bytes,
+            Err(err) => {
+                tracing::warn!(target: GRAFBASE_TARGET, "shadow gateway request failed: {err}");
+                return;
+            }
+        };
+
+        let (Ok(primary), Ok(shadow)) = (
+            serde_json::from_slice::<Value>(response_body),
+            serde_json::from_slice::<Value>(&shadow_body),
+        ) else {
+            tracing::warn!(target: GRAFBASE_TARGET, "shadow gateway response could not be compared, it isn't valid JSON");
+            return;
+        };
+
+        let mut diffs = Vec::new();
+        diff_values(&primary, &shadow, String::new(), &mut diffs);
+        if diffs.is_empty() {
+            return;
+        }
+
+        self.metrics.record_diff();
+
+        let mut summary = diffs
+            .iter()
+            .take(MAX_LOGGED_DIFFS)
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        if diffs.len() > MAX_LOGGED_DIFFS {
+            summary.push_str(&format!(" and {} more", diffs.len() - MAX_LOGGED_DIFFS));
+        }
+        tracing::warn!(target: GRAFBASE_TARGET, "shadow gateway response differs: {summary}");
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Diff {
+    path: String,
+    kind: DiffKind,
+}
+
+#[derive(Debug, PartialEq)]
+enum DiffKind {
+    Changed,
+    OnlyInPrimary,
+    OnlyInShadow,
+}
+
+impl std::fmt::Display for Diff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path = if self.path.is_empty() { "<root>" } else { &self.path };
+        match self.kind {
+            DiffKind::Changed => write!(f, "{path} changed"),
+            DiffKind::OnlyInPrimary => write!(f, "{path} missing from the shadow"),
+            DiffKind::OnlyInShadow => write!(f, "{path} only in the shadow"),
+        }
+    }
+}
+
+/// Object keys are compared regardless of their order, list items by position.
+fn diff_values(primary: &Value, shadow: &Value, path: String, diffs: &mut Vec<Diff>) {
+    let child = |key: &dyn std::fmt::Display| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{path}.{key}")
+        }
+    };
+
+    match (primary, shadow) {
+        (Value::Object(primary), Value::Object(shadow)) => {
+            for (key, value) in primary {
+                match shadow.get(key) {
+                    Some(shadow_value) => diff_values(value, shadow_value, child(key), diffs),
+                    None => diffs.push(Diff {
+                        path: child(key),
+                        kind: DiffKind::OnlyInPrimary,
+                    }),
+                }
+            }
+            for key in shadow.keys().filter(|key| !primary.contains_key(*key)) {
+                diffs.push(Diff {
+                    path: child(key),
+                    kind: DiffKind::OnlyInShadow,
+                });
+            }
+        }
+        (Value::Array(primary), Value::Array(shadow)) => {
+            for (index, (value, shadow_value)) in primary.iter().zip(shadow).enumerate() {
+                diff_values(value, shadow_value, child(&index), diffs);
+            }
+            for index in shadow.len()..primary.len() {
+                diffs.push(Diff {
+                    path: child(&index),
+                    kind: DiffKind::OnlyInPrimary,
+                });
+            }
+            for index in primary.len()..shadow.len() {
+                diffs.push(Diff {
+                    path: child(&index),
+                    kind: DiffKind::OnlyInShadow,
+                });
+            }
+        }
+        (primary, shadow) if primary != shadow => diffs.push(Diff {
+            path,
+            kind: DiffKind::Changed,
+        }),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn diff(primary: Value, shadow: Value) -> Vec<String> {
+        let mut diffs = Vec::new();
+        diff_values(&primary, &shadow, String::new(), &mut diffs);
+        let mut diffs = diffs.iter().map(ToString::to_string).collect::<Vec<_>>();
+        diffs.sort();
+        diffs
+    }
+
+    #[test]
+    fn key_order_is_ignored() {
+        let diffs = diff(
+            json!({"data": {"me": {"id": "1", "name": "Alice"}}}),
+            json!({"data": {"me": {"name": "Alice", "id": "1"}}}),
+        );
+        assert!(diffs.is_empty());
+    }
+
+    #[test]
+    fn diffs_are_reported_by_path() {
+        let diffs = diff(
+            json!({"data": {"me": {"id": "1", "name": "Alice", "friends": [{"id": "2"}]}}}),
+            json!({
+                "data": {"me": {"id": "1", "name": "Bob", "friends": [{"id": "2"}, {"id": "3"}]}},
+                "errors": []
+            }),
+        );
+        assert_eq!(
+            diffs,
+            [
+                "data.me.friends.1 only in the shadow",
+                "data.me.name changed",
+                "errors only in the shadow"
+            ]
+        );
+    }
+}
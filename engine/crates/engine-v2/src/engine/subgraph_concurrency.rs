@@ -0,0 +1,216 @@
+//! Bounds on the number of requests in flight to a subgraph.
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use futures::channel::oneshot;
+
+/// Hands out a limited number of permits to send a request to a subgraph. Once exhausted,
+/// requests wait in a queue per operation and priority. Queues of the highest priority are served
+/// first, one permit at a time in turns when fair. Otherwise all requests of a same priority
+/// share a single queue and are served in order.
+pub(crate) struct SubgraphConcurrencyLimiter {
+    fair: bool,
+    state: Mutex<State>,
+}
+
+struct State {
+    available: usize,
+    // Operations with waiting requests, the next permit going to the first one of the highest
+    // priority.
+    queues: VecDeque<(QueueKey, VecDeque<oneshot::Sender<SubgraphPermit>>)>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct QueueKey {
+    priority: i64,
+    operation: usize,
+}
+
+impl SubgraphConcurrencyLimiter {
+    pub(crate) fn new(max_concurrent_requests: usize, fair: bool) -> Self {
+        Self {
+            fair,
+            state: Mutex::new(State {
+                available: max_concurrent_requests,
+                queues: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Waits for a permit, `operation` identifying the operation sending the request among the
+    /// ones currently running. Requests with a higher `priority` get a permit first.
+    pub(crate) async fn acquire(self: &Arc<Self>, operation: usize, priority: i64) -> SubgraphPermit {
+        let receiver = {
+            let mut state = self.state.lock().unwrap();
+            if state.available > 0 {
+                state.available -= 1;
+                return SubgraphPermit {
+                    limiter: Some(Arc::clone(self)),
+                };
+            }
+            let key = QueueKey {
+                priority,
+                operation: if self.fair { operation } else { 0 },
+            };
+            let (sender, receiver) = oneshot::channel();
+            match state.queues.iter_mut().find(|(queued, _)| *queued == key) {
+                Some((_, senders)) => senders.push_back(sender),
+                None => state.queues.push_back((key, VecDeque::from([sender]))),
+            }
+            receiver
+        };
+        // Senders are only dropped once they sent a permit or failed to because we stopped
+        // waiting.
+        receiver.await.expect("permit to be sent")
+    }
+
+    /// Number of requests currently waiting for a permit.
+    pub(crate) fn queue_depth(&self) -> usize {
+        let state = self.state.lock().unwrap();
+        state.queues.iter().map(|(_, senders)| senders.len()).sum()
+    }
+
+    fn release(self: &Arc<Self>) {
+        let mut state = self.state.lock().unwrap();
+        while let Some(position) = next_queue(&state.queues) {
+            let Some((key, mut senders)) = state.queues.remove(position) else {
+                break;
+            };
+            let sender = senders.pop_front();
+            if !senders.is_empty() {
+                state.queues.push_back((key, senders));
+            }
+            let Some(sender) = sender else {
+                continue;
+            };
+            // The permit is handed over directly, without ever becoming available. If the
+            // request stopped waiting, it goes to the next one instead.
+            match sender.send(SubgraphPermit {
+                limiter: Some(Arc::clone(self)),
+            }) {
+                Ok(()) => return,
+                Err(mut permit) => {
+                    permit.limiter = None;
+                }
+            }
+        }
+        state.available += 1;
+    }
+}
+
+/// Position of the first queue with the highest priority.
+fn next_queue<T>(queues: &VecDeque<(QueueKey, T)>) -> Option<usize> {
+    queues
+        .iter()
+        .enumerate()
+        .max_by_key(|(position, (key, _))| (key.priority, std::cmp::Reverse(*position)))
+        .map(|(position, _)| position)
+}
+
+/// Permit to send a request to the subgraph, given back when dropped.
+pub(crate) struct SubgraphPermit {
+    limiter: Option<Arc<SubgraphConcurrencyLimiter>>,
+}
+
+impl Drop for SubgraphPermit {
+    fn drop(&mut self) {
+        if let Some(limiter) = self.limiter.take() {
+            limiter.release();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use futures::FutureExt;
+
+    use super::SubgraphConcurrencyLimiter;
+
+    const BATCH: usize = 1;
+    const CHEAP: usize = 2;
+
+    /// Operations served one after the other with a single permit, while a batch operation has
+    /// five requests waiting ahead of a single one from a cheap operation.
+    fn served_operations(fair: bool) -> Vec<usize> {
+        let limiter = Arc::new(SubgraphConcurrencyLimiter::new(1, fair));
+        let mut permit = limiter.acquire(BATCH, 0).now_or_never().unwrap();
+
+        let mut waiting = Vec::new();
+        for operation in [BATCH; 5].into_iter().chain([CHEAP]) {
+            let mut acquire = Box::pin(limiter.acquire(operation, 0));
+            assert!(acquire.as_mut().now_or_never().is_none());
+            waiting.push((operation, acquire));
+        }
+
+        let mut served = Vec::new();
+        while !waiting.is_empty() {
+            drop(permit);
+            let (i, next) = waiting
+                .iter_mut()
+                .enumerate()
+                .find_map(|(i, (_, acquire))| acquire.as_mut().now_or_never().map(|permit| (i, permit)))
+                .expect("a waiting request to get the permit");
+            served.push(waiting.remove(i).0);
+            permit = next;
+        }
+
+        drop(permit);
+        assert!(limiter.acquire(CHEAP, 0).now_or_never().is_some());
+
+        served
+    }
+
+    #[test]
+    fn cheap_operation_is_not_starved_when_fair() {
+        assert_eq!(served_operations(true), [BATCH, CHEAP, BATCH, BATCH, BATCH, BATCH]);
+    }
+
+    #[test]
+    fn requests_are_served_in_order_by_default() {
+        assert_eq!(served_operations(false), [BATCH, BATCH, BATCH, BATCH, BATCH, CHEAP]);
+    }
+
+    #[test]
+    fn permit_of_a_request_which_stopped_waiting_goes_to_the_next_one() {
+        let limiter = Arc::new(SubgraphConcurrencyLimiter::new(1, true));
+        let permit = limiter.acquire(BATCH, 0).now_or_never().unwrap();
+
+        let mut abandoned = Box::pin(limiter.acquire(BATCH, 0));
+        assert!(abandoned.as_mut().now_or_never().is_none());
+        let mut next = Box::pin(limiter.acquire(CHEAP, 0));
+        assert!(next.as_mut().now_or_never().is_none());
+
+        drop(abandoned);
+        drop(permit);
+
+        assert!(next.as_mut().now_or_never().is_some());
+    }
+
+    #[test]
+    fn higher_priority_request_gets_the_permit_first() {
+        let limiter = Arc::new(SubgraphConcurrencyLimiter::new(1, false));
+        let permit = limiter.acquire(BATCH, 0).now_or_never().unwrap();
+
+        let mut low = Box::pin(limiter.acquire(BATCH, 0));
+        assert!(low.as_mut().now_or_never().is_none());
+        let mut high = Box::pin(limiter.acquire(CHEAP, 10));
+        assert!(high.as_mut().now_or_never().is_none());
+
+        drop(permit);
+
+        let permit = high
+            .as_mut()
+            .now_or_never()
+            .expect("high priority request to get the permit");
+        assert!(low.as_mut().now_or_never().is_none());
+
+        drop(permit);
+
+        assert!(low.as_mut().now_or_never().is_some());
+    }
+}
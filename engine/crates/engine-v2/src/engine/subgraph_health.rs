@@ -0,0 +1,105 @@
+use std::{collections::VecDeque, sync::Mutex, time::Duration};
+
+use schema::{sources::graphql::GraphqlEndpointId, Schema};
+use web_time::Instant;
+
+/// Requests kept per subgraph, bounds the memory used by busy subgraphs.
+const MAX_SAMPLES: usize = 1024;
+
+/// Rolling statistics of the requests sent to each subgraph, over a time window.
+pub(crate) struct SubgraphHealthTracker {
+    window: Duration,
+    samples: Vec<Mutex<VecDeque<Sample>>>,
+}
+
+struct Sample {
+    at: Instant,
+    latency: Duration,
+    failed: bool,
+}
+
+/// Health of a subgraph as observed by the gateway over the tracking window.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct SubgraphHealth {
+    pub name: String,
+    /// Requests sent within the window, retries included.
+    pub requests: usize,
+    /// Share of those requests which failed to get a response, between 0 and 1.
+    pub error_rate: f64,
+    /// 99th percentile of the request latency in milliseconds, if any request was sent.
+    pub p99_latency_ms: Option<u64>,
+}
+
+impl SubgraphHealthTracker {
+    pub fn new(schema: &Schema, window: Duration) -> Self {
+        Self {
+            window,
+            samples: schema
+                .walker()
+                .graphql_endpoints()
+                .map(|_| Mutex::new(VecDeque::new()))
+                .collect(),
+        }
+    }
+
+    pub fn record(&self, subgraph_id: GraphqlEndpointId, latency: Duration, failed: bool) {
+        let now = Instant::now();
+        let mut samples = self.samples[usize::from(subgraph_id)].lock().unwrap();
+
+        self.evict_expired(&mut samples, now);
+        if samples.len() == MAX_SAMPLES {
+            samples.pop_front();
+        }
+
+        samples.push_back(Sample {
+            at: now,
+            latency,
+            failed,
+        });
+    }
+
+    pub fn snapshot(&self, schema: &Schema) -> Vec<SubgraphHealth> {
+        let now = Instant::now();
+
+        schema
+            .walker()
+            .graphql_endpoints()
+            .map(|endpoint| {
+                let mut samples = self.samples[usize::from(endpoint.id())].lock().unwrap();
+                self.evict_expired(&mut samples, now);
+
+                let requests = samples.len();
+                let failures = samples.iter().filter(|sample| sample.failed).count();
+
+                let mut latencies = samples.iter().map(|sample| sample.latency).collect::<Vec<_>>();
+                latencies.sort_unstable();
+
+                // Nearest-rank percentile.
+                let p99_latency_ms = (requests * 99)
+                    .div_ceil(100)
+                    .checked_sub(1)
+                    .map(|rank| latencies[rank].as_millis() as u64);
+
+                SubgraphHealth {
+                    name: endpoint.name().to_string(),
+                    requests,
+                    error_rate: if requests == 0 {
+                        0.0
+                    } else {
+                        failures as f64 / requests as f64
+                    },
+                    p99_latency_ms,
+                }
+            })
+            .collect()
+    }
+
+    fn evict_expired(&self, samples: &mut VecDeque<Sample>, now: Instant) {
+        while samples
+            .front()
+            .is_some_and(|sample| now.duration_since(sample.at) > self.window)
+        {
+            samples.pop_front();
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Bounds on the number of subscriptions running over WebSocket connections.
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use schema::Settings;
+
+/// A subscription counted against both the limit of its connection and the gateway wide one.
+/// Released when dropped, along with the subscription stream holding it.
+pub(super) struct SubscriptionSlot {
+    connection: Arc<AtomicUsize>,
+    global: Arc<AtomicUsize>,
+}
+
+impl SubscriptionSlot {
+    /// Fails with the error message for the client if either limit is reached.
+    pub(super) fn acquire(
+        settings: &Settings,
+        connection: &Arc<AtomicUsize>,
+        global: &Arc<AtomicUsize>,
+    ) -> Result<Self, String> {
+        if let Some(max) = settings.max_subscriptions_per_connection {
+            if !try_increment(connection, max) {
+                return Err(format!(
+                    "Too many subscriptions on this connection, at most {max} are allowed"
+                ));
+            }
+        } else {
+            connection.fetch_add(1, Ordering::Relaxed);
+        }
+
+        if let Some(max) = settings.max_concurrent_subscriptions {
+            if !try_increment(global, max) {
+                connection.fetch_sub(1, Ordering::Relaxed);
+                return Err(format!("Too many concurrent subscriptions, at most {max} are allowed"));
+            }
+        } else {
+            global.fetch_add(1, Ordering::Relaxed);
+        }
+
+        Ok(SubscriptionSlot {
+            connection: Arc::clone(connection),
+            global: Arc::clone(global),
+        })
+    }
+}
+
+impl Drop for SubscriptionSlot {
+    fn drop(&mut self) {
+        self.connection.fetch_sub(1, Ordering::Relaxed);
+        self.global.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+fn try_increment(counter: &AtomicUsize, max: usize) -> bool {
+    counter
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| {
+            (count < max).then_some(count + 1)
+        })
+        .is_ok()
+}
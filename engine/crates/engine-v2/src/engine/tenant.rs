@@ -0,0 +1,72 @@
+use config::latest::Tenants;
+
+/// Tags visible to the tenant of the request, identified by the configured header. Fields
+/// annotated with `@tag(name:)` are only visible to tenants whose profile includes one of their
+/// tags. Requests without a known tenant only see fields without any tag.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct Tenant {
+    tags: Vec<String>,
+}
+
+impl Tenant {
+    pub fn extract_from(tenants: &Tenants, headers: &http::HeaderMap) -> Self {
+        let mut tags = headers
+            .get(tenants.header.as_str())
+            .and_then(|value| value.to_str().ok())
+            .and_then(|id| tenants.profiles.get(id.trim()))
+            .cloned()
+            .unwrap_or_default();
+
+        // Sorted so that tenants with the same profile share the same operation cache key.
+        tags.sort_unstable();
+        tags.dedup();
+
+        Tenant { tags }
+    }
+
+    /// Whether an element with those tags is visible to the tenant.
+    pub fn can_see<'a>(&self, tags: impl Iterator<Item = &'a str>) -> bool {
+        let mut tags = tags.peekable();
+        tags.peek().is_none() || tags.any(|tag| self.tags.binary_search_by(|probe| probe.as_str().cmp(tag)).is_ok())
+    }
+
+    pub fn tags(&self) -> impl Iterator<Item = &str> {
+        self.tags.iter().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use config::latest::Tenants;
+
+    use super::Tenant;
+
+    fn tenants() -> Tenants {
+        Tenants {
+            header: "x-tenant-id".into(),
+            profiles: BTreeMap::from([("acme".to_string(), vec!["public".to_string(), "acme".to_string()])]),
+        }
+    }
+
+    #[test]
+    fn untagged_elements_are_visible_to_all() {
+        let tenant = Tenant::extract_from(&tenants(), &http::HeaderMap::new());
+
+        assert!(tenant.can_see(std::iter::empty()));
+        assert!(!tenant.can_see(["public"].into_iter()));
+    }
+
+    #[test]
+    fn tagged_elements_are_visible_if_any_tag_is_in_the_profile() {
+        let mut headers = http::HeaderMap::new();
+        headers.insert("x-tenant-id", "acme".parse().unwrap());
+
+        let tenant = Tenant::extract_from(&tenants(), &headers);
+
+        assert_eq!(tenant.tags().collect::<Vec<_>>(), ["acme", "public"]);
+        assert!(tenant.can_see(["globex", "acme"].into_iter()));
+        assert!(!tenant.can_see(["globex"].into_iter()));
+    }
+}
@@ -2,6 +2,7 @@
 
 use crate::{
     execution::PreExecutionContext,
+    operation::CanonicalDocument,
     response::{ErrorCode, GraphqlError},
     Runtime,
 };
@@ -38,6 +39,18 @@ impl<'ctx, R: Runtime> PreExecutionContext<'ctx, R> {
         let document_id = request.document_id.as_ref();
         let name = request.operation_name();
         let schema_version = &self.engine.schema_version;
+        let feature_flags = &self.request_context.feature_flags;
+        let tenant = self.request_context.tenant.as_ref();
+        // Operations of clients restricted to specific operations are cached separately, so that
+        // they can't be served an operation that was only validated for another client.
+        let restricted_client_name = client_name.filter(|name| {
+            self.engine
+                .schema
+                .settings
+                .clients
+                .get(*name)
+                .is_some_and(|client| client.allowed_operation_prefixes.is_some())
+        });
 
         match (trusted_documents_enabled, persisted_query_extension, document_id) {
             (true, None, None) => {
@@ -48,13 +61,19 @@ impl<'ctx, R: Runtime> PreExecutionContext<'ctx, R> {
                     .map(|(name, value)| self.headers().get(name).and_then(|v| v.to_str().ok()) == Some(value))
                     .unwrap_or_default()
                 {
+                    let canonical_document = CanonicalDocument::new(request.query());
+                    let cache_key = Key::Operation {
+                        name,
+                        client_name: restricted_client_name,
+                        feature_flags,
+                        tenant,
+                        schema_version,
+                        document: Document::Text(canonical_document.as_str()),
+                    }
+                    .to_string();
+                    self.canonical_document = Some(canonical_document);
                     Ok(PreparedOperationDocument {
-                        cache_key: Key::Operation {
-                            name,
-                            schema_version,
-                            document: Document::Text(request.query()),
-                        }
-                        .to_string(),
+                        cache_key,
                         document_fut: None,
                     })
                 } else {
@@ -68,6 +87,9 @@ impl<'ctx, R: Runtime> PreExecutionContext<'ctx, R> {
             (true, Some(ext), _) => Ok(PreparedOperationDocument {
                 cache_key: Key::Operation {
                     name,
+                    client_name: restricted_client_name,
+                    feature_flags,
+                    tenant,
                     schema_version,
                     document: Document::PersistedQueryExt(ext),
                 }
@@ -77,24 +99,38 @@ impl<'ctx, R: Runtime> PreExecutionContext<'ctx, R> {
             (true, _, Some(document_id)) => Ok(PreparedOperationDocument {
                 cache_key: Key::Operation {
                     name,
+                    client_name: restricted_client_name,
+                    feature_flags,
+                    tenant,
                     schema_version,
                     document: Document::Id(document_id),
                 }
                 .to_string(),
                 document_fut: Some(self.handle_trusted_document_query(document_id.into(), client_name)?),
             }),
-            (false, None, _) => Ok(PreparedOperationDocument {
-                cache_key: Key::Operation {
+            (false, None, _) => {
+                let canonical_document = CanonicalDocument::new(request.query());
+                let cache_key = Key::Operation {
                     name,
+                    client_name: restricted_client_name,
+                    feature_flags,
+                    tenant,
                     schema_version,
-                    document: Document::Text(request.query()),
+                    document: Document::Text(canonical_document.as_str()),
                 }
-                .to_string(),
-                document_fut: None,
-            }),
+                .to_string();
+                self.canonical_document = Some(canonical_document);
+                Ok(PreparedOperationDocument {
+                    cache_key,
+                    document_fut: None,
+                })
+            }
             (false, Some(ext), _) => Ok(PreparedOperationDocument {
                 cache_key: Key::Operation {
                     name,
+                    client_name: restricted_client_name,
+                    feature_flags,
+                    tenant,
                     schema_version,
                     document: Document::PersistedQueryExt(ext),
                 }
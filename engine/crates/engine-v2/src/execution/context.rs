@@ -1,9 +1,15 @@
+use std::sync::atomic::Ordering;
+
 use ::runtime::hooks::Hooks;
 use futures::future::BoxFuture;
 use runtime::auth::AccessToken;
 use schema::{HeaderRuleWalker, Schema};
 
-use crate::{engine::RequestContext, Engine, Runtime};
+use crate::{
+    engine::{FeatureFlags, RequestContext, Tenant},
+    operation::CanonicalDocument,
+    Engine, Runtime,
+};
 
 use super::{header_rule::create_subgraph_headers_with_rules, ExecutableOperation, RequestHooks};
 
@@ -14,6 +20,8 @@ pub(crate) struct PreExecutionContext<'ctx, R: Runtime> {
     pub(crate) request_context: &'ctx RequestContext<<R::Hooks as Hooks>::Context>,
     // needs to be Send so that futures are Send.
     pub(super) background_futures: crossbeam_queue::SegQueue<BoxFuture<'ctx, ()>>,
+    /// Canonical form of the document sent as is, from which the operation is built.
+    pub(crate) canonical_document: Option<CanonicalDocument>,
 }
 
 impl<'ctx, R: Runtime> PreExecutionContext<'ctx, R> {
@@ -22,6 +30,7 @@ impl<'ctx, R: Runtime> PreExecutionContext<'ctx, R> {
             engine,
             request_context,
             background_futures: Default::default(),
+            canonical_document: None,
         }
     }
 
@@ -74,6 +83,45 @@ impl<'ctx, R: Runtime> ExecutionContext<'ctx, R> {
         &self.request_context.access_token
     }
 
+    pub fn feature_flags(&self) -> &'ctx FeatureFlags {
+        &self.request_context.feature_flags
+    }
+
+    pub fn tenant(&self) -> Option<&'ctx Tenant> {
+        self.request_context.tenant.as_ref()
+    }
+
+    pub fn client_name(&self) -> Option<String> {
+        self.request_context.client.as_ref().map(|client| client.name.clone())
+    }
+
+    pub fn headers(&self) -> &'ctx http::HeaderMap {
+        &self.request_context.headers
+    }
+
+    /// Takes one retry from the budget shared by all the subgraph requests of the operation.
+    pub fn withdraw_operation_retry(&self) -> bool {
+        let Some(remaining) = &self.operation.remaining_retries else {
+            return true;
+        };
+        remaining
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| count.checked_sub(1))
+            .is_ok()
+    }
+
+    /// Takes `count` entities from the limit shared by all the entity requests of the operation.
+    /// Once exceeded, the limit is exhausted and every further request fails.
+    pub fn withdraw_operation_entities(&self, count: usize) -> bool {
+        let Some(remaining) = &self.operation.remaining_entities else {
+            return true;
+        };
+        remaining
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |remaining| {
+                Some(remaining.saturating_sub(count))
+            })
+            .is_ok_and(|remaining| remaining >= count)
+    }
+
     pub fn subgraph_headers_with_rules(&self, rules: impl Iterator<Item = HeaderRuleWalker<'ctx>>) -> http::HeaderMap {
         create_subgraph_headers_with_rules(
             self.request_context,
@@ -12,6 +12,7 @@ use tracing::instrument;
 
 use crate::{
     execution::{ExecutableOperation, ExecutionContext, PlanWalker},
+    operation::{CanonicalDocument, DeferredFragmentId, Field, TypeNameField},
     response::{
         InputdResponseObjectSet, ObjectIdentifier, Response, ResponseBuilder, ResponseEdge, ResponseObjectField,
         ResponseValue, SubgraphResponse, SubgraphResponseRefMut,
@@ -26,6 +27,22 @@ pub(crate) trait ResponseSender: Send {
     fn send(&mut self, response: Response) -> impl Future<Output = Result<(), Self::Error>> + Send;
 }
 
+/// Restores the errors of each response to the document of the request before sending it.
+struct RestoringSender<'a, S> {
+    canonical_document: Option<&'a CanonicalDocument>,
+    responses: S,
+}
+
+impl<S: ResponseSender> ResponseSender for RestoringSender<'_, S> {
+    type Error = S::Error;
+    async fn send(&mut self, mut response: Response) -> Result<(), Self::Error> {
+        if let Some(document) = self.canonical_document {
+            response.restore_original_document(document);
+        }
+        self.responses.send(response).await
+    }
+}
+
 impl<'ctx, R: Runtime> PreExecutionContext<'ctx, R> {
     #[instrument(skip_all)]
     pub async fn execute_query_or_mutation(self, operation: ExecutableOperation) -> Response {
@@ -41,10 +58,20 @@ impl<'ctx, R: Runtime> PreExecutionContext<'ctx, R> {
         let response_fut = ctx.execute();
 
         tracing::trace!("Starting execution...");
-        let (response, _) = futures_util::join!(response_fut, background_fut);
+        let (mut response, _) = futures_util::join!(response_fut, background_fut);
+        if let Some(document) = &self.canonical_document {
+            response.restore_original_document(document);
+        }
         response
     }
 
+    /// Used when the response was produced by another request, so only the background work of this
+    /// one remains.
+    pub async fn execute_background_futures(self) {
+        let background_futures: FuturesUnordered<_> = self.background_futures.into_iter().collect();
+        background_futures.collect::<Vec<_>>().await;
+    }
+
     #[instrument(skip_all)]
     pub async fn execute_subscription(self, operation: ExecutableOperation, responses: impl ResponseSender) {
         let background_futures: FuturesUnordered<_> = self.background_futures.into_iter().collect();
@@ -55,11 +82,36 @@ impl<'ctx, R: Runtime> PreExecutionContext<'ctx, R> {
             request_context: self.request_context,
         };
 
-        let subscription_fut = ctx.execute_subscription(responses);
+        let subscription_fut = ctx.execute_subscription(RestoringSender {
+            canonical_document: self.canonical_document.as_ref(),
+            responses,
+        });
 
         tracing::trace!("Starting execution...");
         futures_util::join!(subscription_fut, background_fut);
     }
+
+    /// Executes a query with deferred fragments. The initial response is sent as soon as the plans
+    /// it needs are done, each deferred fragment following in a subsequent response once the
+    /// plans leading to it are.
+    #[instrument(skip_all)]
+    pub async fn execute_incremental(self, operation: ExecutableOperation, responses: impl ResponseSender) {
+        let background_futures: FuturesUnordered<_> = self.background_futures.into_iter().collect();
+        let background_fut = background_futures.collect::<Vec<_>>();
+        let ctx = ExecutionContext {
+            engine: self.engine,
+            operation: &operation,
+            request_context: self.request_context,
+        };
+
+        let responses_fut = ctx.execute_incremental(RestoringSender {
+            canonical_document: self.canonical_document.as_ref(),
+            responses,
+        });
+
+        tracing::trace!("Starting execution...");
+        futures_util::join!(responses_fut, background_fut);
+    }
 }
 
 impl<'ctx, R: Runtime> ExecutionContext<'ctx, R> {
@@ -86,16 +138,47 @@ impl<'ctx, R: Runtime> ExecutionContext<'ctx, R> {
             return response;
         }
 
+        if let Some(response) = self.response_if_nothing_to_execute() {
+            return response;
+        }
+
         OperationExecution {
             futures: ExecutorFutureSet::new(),
             state: self.new_execution_state(),
             response: ResponseBuilder::new(self.operation.root_object_id),
             ctx: self,
+            deferred_plan_ids: None,
+            running_plans_by_fragment: Vec::new(),
         }
         .run()
         .await
     }
 
+    async fn execute_incremental(self, mut responses: impl ResponseSender) {
+        assert!(matches!(self.operation.ty(), OperationType::Query));
+
+        if let Some(response) = self.response_if_root_errors() {
+            let _ = responses.send(response).await;
+            return;
+        }
+
+        if let Some(response) = self.response_if_nothing_to_execute() {
+            let _ = responses.send(response).await;
+            return;
+        }
+
+        OperationExecution {
+            futures: ExecutorFutureSet::new(),
+            state: self.new_execution_state(),
+            response: ResponseBuilder::new(self.operation.root_object_id),
+            running_plans_by_fragment: vec![0; self.operation.deferred_fragments.len()],
+            ctx: self,
+            deferred_plan_ids: Some(Vec::new()),
+        }
+        .run_incremental(responses)
+        .await
+    }
+
     async fn execute_subscription(self, mut responses: impl ResponseSender) {
         assert!(matches!(self.operation.ty(), OperationType::Subscription));
 
@@ -173,7 +256,33 @@ impl<'ctx, R: Runtime> ExecutionContext<'ctx, R> {
         );
         let schema = self.engine.schema.clone();
         let operation = self.operation.prepared.clone();
-        Some(response.build(schema, operation))
+        Some(response.build(schema, operation, self.client_name()))
+    }
+
+    /// Operations whose root selection set is empty, every field being excluded by `@skip` or
+    /// `@include`, or only has `__typename` are answered without any executor.
+    fn response_if_nothing_to_execute(&self) -> Option<Response> {
+        let operation = self.operation;
+        let mut typename_fields = Vec::new();
+        for &field_id in &operation[operation.root_selection_set_id].field_ids_ordered_by_parent_entity_id_then_position
+        {
+            let Field::TypeName(TypeNameField { bound_response_key, .. }) = operation[field_id] else {
+                return None;
+            };
+            typename_fields.push(ResponseObjectField {
+                edge: bound_response_key.into(),
+                required_field_id: None,
+                value: ResponseValue::StringId {
+                    id: self.engine.schema[operation.root_object_id].name,
+                    nullable: false,
+                },
+            });
+        }
+
+        let mut response = ResponseBuilder::new(operation.root_object_id);
+        response.insert_root_fields(typename_fields);
+        let schema = self.engine.schema.clone();
+        Some(response.build(schema, operation.prepared.clone(), self.client_name()))
     }
 }
 
@@ -235,6 +344,8 @@ where
                                 state: self.initial_state.clone(),
                                 ctx: self.ctx,
                                 response,
+                                deferred_plan_ids: None,
+                                running_plans_by_fragment: Vec::new(),
                             };
 
                             operation_execution.futures.push_result(ExecutorFutureResult {
@@ -278,6 +389,11 @@ struct OperationExecution<'ctx, 'exec, R: Runtime> {
     futures: ExecutorFutureSet<'exec>,
     state: OperationExecutionState<'ctx>,
     response: ResponseBuilder,
+    // Plans of deferred fragments held back while executing the plans of the initial response.
+    deferred_plan_ids: Option<Vec<ExecutionPlanId>>,
+    // Number of running plans leading to each deferred fragment, empty unless they're delivered
+    // separately.
+    running_plans_by_fragment: Vec<usize>,
 }
 
 impl<'ctx, 'exec, R: Runtime> std::ops::Deref for OperationExecution<'ctx, 'exec, R> {
@@ -291,50 +407,164 @@ impl<'ctx, 'exec, R: Runtime> OperationExecution<'ctx, 'exec, R>
 where
     'ctx: 'exec,
 {
-    /// Runs a single execution to completion, returning its response
+    /// Runs a single execution to completion, returning its response. Plans are executed
+    /// concurrently as soon as the plans they depend on have finished, so independent root fields
+    /// only take as long as the slowest subgraph. Mutation root fields are never independent, the
+    /// planner making each one depend on the previous one so they're executed in order.
     async fn run(mut self) -> Response {
         for plan_id in self.state.get_executable_plans() {
-            self.spawn_executor(plan_id);
-        }
-
-        while let Some(ExecutorFutureResult { plan_id, result }) = self.futures.next().await {
-            // Retrieving the first edge (response key) appearing in the query to provide a better
-            // error path if necessary.
-            let (any_edge, default_fields) = self.get_first_edge_and_default_object(plan_id);
-            match result {
-                Ok(subgraph_response) => {
-                    tracing::trace!(%plan_id, "Succeeded");
-                    let tracked_response_object_sets =
-                        self.response.ingest(subgraph_response, any_edge, default_fields);
-                    for (set_id, response_object_refs) in tracked_response_object_sets.into_iter() {
-                        self.state.push_response_objects(set_id, response_object_refs);
-                    }
+            self.start_executor(plan_id);
+        }
+        self.execute_started_plans().await;
+        self.into_response()
+    }
 
-                    let response_modifier_executor_ids = self.state.get_next_executable_response_modifiers(plan_id);
-                    for id in &response_modifier_executor_ids {
-                        self.ctx
-                            .execute_response_modifier(&mut self.state, &mut self.response, *id)
-                            .await;
-                    }
+    /// Runs a query with deferred fragments. Their plans are only started once the initial
+    /// response was sent, against the same response objects. So the deferred data is read from
+    /// the same response, without fetching any of its parents again. Each fragment is sent as
+    /// soon as no running plan leads to it anymore.
+    async fn run_incremental(mut self, mut responses: impl ResponseSender) {
+        for plan_id in self.state.get_executable_plans() {
+            self.start_executor(plan_id);
+        }
+        self.execute_started_plans().await;
 
-                    for plan_id in self
-                        .state
-                        .get_next_executable_plans(plan_id, response_modifier_executor_ids)
-                    {
-                        self.spawn_executor(plan_id);
-                    }
+        let schema = self.engine.schema.clone();
+        let operation = self.operation.prepared.clone();
+        let initial_response = self.response.snapshot(schema, operation, self.client_name());
+        // Deferred fragments have nowhere to go without the data of the initial response.
+        if !initial_response.has_data() {
+            let _ = responses.send(initial_response).await;
+            return;
+        }
+        if responses.send(initial_response.with_has_next(true)).await.is_err() {
+            return;
+        }
+
+        for plan_id in self.deferred_plan_ids.take().unwrap_or_default() {
+            self.start_executor(plan_id);
+        }
+
+        let mut sent = vec![false; self.running_plans_by_fragment.len()];
+        loop {
+            // Fragments resolved by the initial plans, or whose plans had nothing to resolve, are
+            // complete right away.
+            let complete = (0..sent.len())
+                .filter(|&i| !sent[i] && self.running_plans_by_fragment[i] == 0)
+                .collect::<Vec<_>>();
+            for &i in &complete {
+                sent[i] = true;
+            }
+            let has_next = sent.iter().any(|sent| !sent);
+            if !complete.is_empty() && !self.send_deferred_fragments(&mut responses, complete, has_next).await {
+                return;
+            }
+            if !has_next {
+                return;
+            }
+
+            if self.execute_next_plan().await.is_none() {
+                break;
+            }
+        }
+
+        // The request was aborted, the remaining fragments are sent with the error which aborted
+        // it.
+        let remaining = (0..sent.len()).filter(|&i| !sent[i]).collect();
+        self.send_deferred_fragments(&mut responses, remaining, false).await;
+    }
+
+    /// Returns whether the response could be sent.
+    async fn send_deferred_fragments(
+        &mut self,
+        responses: &mut impl ResponseSender,
+        fragment_ids: Vec<usize>,
+        has_next: bool,
+    ) -> bool {
+        // Deferred fields not resolved yet would all be reported as missing, so nullability is
+        // only validated once the last fragment is resolved.
+        if !has_next && self.schema().settings.validate_response_nullability {
+            self.response
+                .validate_nullability(self.ctx.schema(), self.ctx.operation);
+        }
+        let schema = self.engine.schema.clone();
+        let operation = self.operation.prepared.clone();
+        let response = self.response.subsequent_snapshot(
+            schema,
+            operation,
+            self.client_name(),
+            fragment_ids.into_iter().map(DeferredFragmentId::from).collect(),
+            has_next,
+        );
+        responses.send(response).await.is_ok()
+    }
+
+    async fn execute_started_plans(&mut self) {
+        while self.execute_next_plan().await.is_some() {}
+    }
+
+    /// Waits for the next running plan to finish and ingests its response, starting the plans
+    /// which can be executed afterwards. Returns None once no plan is running or the request was
+    /// aborted.
+    async fn execute_next_plan(&mut self) -> Option<ExecutionPlanId> {
+        let ExecutorFutureResult { plan_id, result } = self.futures.next().await?;
+        // Retrieving the first edge (response key) appearing in the query to provide a better
+        // error path if necessary.
+        let (any_edge, default_fields) = self.get_first_edge_and_default_object(plan_id);
+        match result {
+            Ok(subgraph_response) => {
+                tracing::trace!(%plan_id, "Succeeded");
+                let tracked_response_object_sets = self.response.ingest(subgraph_response, any_edge, default_fields);
+                if self.response.is_aborted() {
+                    tracing::trace!(%plan_id, "Aborted the request");
+                    return None;
                 }
-                Err((root_response_object_set, error)) => {
-                    tracing::trace!(%plan_id, "Failed");
-                    self.response
-                        .propagate_execution_error(root_response_object_set, error, any_edge, default_fields);
+                for (set_id, response_object_refs) in tracked_response_object_sets.into_iter() {
+                    self.state.push_response_objects(set_id, response_object_refs);
+                }
+
+                let response_modifier_executor_ids = self.state.get_next_executable_response_modifiers(plan_id);
+                for id in &response_modifier_executor_ids {
+                    self.ctx
+                        .execute_response_modifier(&mut self.state, &mut self.response, *id)
+                        .await;
+                }
+
+                for next_plan_id in self
+                    .state
+                    .get_next_executable_plans(plan_id, response_modifier_executor_ids)
+                {
+                    self.start_executor(next_plan_id);
                 }
             }
+            Err((root_response_object_set, error)) => {
+                tracing::trace!(%plan_id, "Failed");
+                self.response
+                    .propagate_execution_error(root_response_object_set, error, any_edge, default_fields);
+            }
+        }
+
+        // After the plans depending on it were started, which lead to the same fragments.
+        if !self.running_plans_by_fragment.is_empty() {
+            let logical_plan_id = self.ctx.operation[plan_id].logical_plan_id;
+            for &fragment_id in self.ctx.operation.deferred_fragments_led_to_by(logical_plan_id) {
+                self.running_plans_by_fragment[usize::from(fragment_id)] -= 1;
+            }
+        }
+
+        Some(plan_id)
+    }
+
+    fn into_response(mut self) -> Response {
+        if self.schema().settings.validate_response_nullability {
+            self.response
+                .validate_nullability(self.ctx.schema(), self.ctx.operation);
         }
 
         let schema = self.engine.schema.clone();
         let operation = self.operation.prepared.clone();
-        self.response.build(schema, operation)
+        let client_name = self.client_name();
+        self.response.build(schema, operation, client_name)
     }
 
     fn get_first_edge_and_default_object(
@@ -383,6 +613,21 @@ where
         (first_edge, Some(fields))
     }
 
+    fn start_executor(&mut self, plan_id: ExecutionPlanId) {
+        if let Some(deferred_plan_ids) = &mut self.deferred_plan_ids {
+            if self
+                .ctx
+                .operation
+                .is_deferred(self.ctx.operation[plan_id].logical_plan_id)
+            {
+                tracing::trace!(%plan_id, "Deferring plan");
+                deferred_plan_ids.push(plan_id);
+                return;
+            }
+        }
+        self.spawn_executor(plan_id);
+    }
+
     fn spawn_executor(&mut self, plan_id: ExecutionPlanId) {
         tracing::trace!(%plan_id, "Starting plan");
         let root_response_object_set = Arc::new(self.state.get_input(&self.response, plan_id));
@@ -392,6 +637,13 @@ where
             return;
         }
 
+        if !self.running_plans_by_fragment.is_empty() {
+            let logical_plan_id = self.ctx.operation[plan_id].logical_plan_id;
+            for &fragment_id in self.ctx.operation.deferred_fragments_led_to_by(logical_plan_id) {
+                self.running_plans_by_fragment[usize::from(fragment_id)] += 1;
+            }
+        }
+
         self.futures.push_fut({
             let plan = self.ctx.plan_walker(plan_id);
             let subgraph_response = self.response.new_subgraph_response(
@@ -8,6 +8,12 @@ pub(crate) type PlanningResult<T> = Result<T, PlanningError>;
 pub(crate) enum PlanningError {
     #[error("Internal error: {0}")]
     InternalError(String),
+    #[error("Query for subgraph '{subgraph_name}' would have a depth of {depth}, exceeding its limit of {max_depth}")]
+    SubgraphQueryTooDeep {
+        subgraph_name: String,
+        depth: usize,
+        max_depth: u16,
+    },
 }
 
 impl From<PlanningError> for GraphqlError {
@@ -53,14 +59,18 @@ impl From<ExecutionError> for GraphqlError {
             return err;
         }
         let message = err.to_string();
-        let code = match &err {
-            ExecutionError::Internal(_) => ErrorCode::InternalServerError,
-            ExecutionError::DeserializationError(_) => ErrorCode::SubgraphInvalidResponseError,
-            ExecutionError::Fetch { .. } => ErrorCode::SubgraphRequestError,
-            ExecutionError::RateLimit(_) => ErrorCode::RateLimited,
-            ExecutionError::Graphql(err) => err.code,
-        };
-        GraphqlError::new(message, code)
+        match &err {
+            ExecutionError::Internal(_) => GraphqlError::new(message, ErrorCode::InternalServerError),
+            ExecutionError::DeserializationError(_) => {
+                GraphqlError::new(message, ErrorCode::SubgraphInvalidResponseError)
+            }
+            // Timeouts, connection failures and server errors of the subgraph are all transient.
+            ExecutionError::Fetch { .. } => {
+                GraphqlError::new(message, ErrorCode::SubgraphRequestError).with_retryable(true)
+            }
+            ExecutionError::RateLimit(_) => GraphqlError::new(message, ErrorCode::RateLimited).with_retryable(true),
+            ExecutionError::Graphql(err) => GraphqlError::new(message, err.code),
+        }
     }
 }
 
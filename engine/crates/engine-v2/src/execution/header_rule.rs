@@ -5,7 +5,7 @@ use schema::{HeaderRuleWalker, NameOrPatternRef};
 
 use crate::engine::RequestContext;
 
-pub(super) fn create_subgraph_headers_with_rules<'ctx, C>(
+pub(crate) fn create_subgraph_headers_with_rules<'ctx, C>(
     request_context: &'ctx RequestContext<C>,
     rules: impl Iterator<Item = HeaderRuleWalker<'ctx>>,
     default: http::HeaderMap,
@@ -9,7 +9,7 @@ mod response_modifier;
 mod state;
 mod walkers;
 
-use std::sync::Arc;
+use std::sync::{atomic::AtomicUsize, Arc};
 
 use crate::{
     operation::{FieldId, LogicalPlanId, PreparedOperation, ResponseModifierRule, Variables},
@@ -23,6 +23,7 @@ use crate::{
 pub(crate) use context::*;
 pub(crate) use coordinator::*;
 pub(crate) use error::*;
+pub(crate) use header_rule::create_subgraph_headers_with_rules;
 pub(crate) use hooks::RequestHooks;
 use id_newtypes::{BitSet, IdToMany};
 pub(crate) use ids::*;
@@ -51,6 +52,10 @@ pub(crate) struct ExecutableOperation {
     pub(crate) execution_plans: Vec<ExecutionPlan>,
     pub(crate) response_views: ResponseViews,
     pub(crate) response_modifier_executors: Vec<ResponseModifierExecutor>,
+    /// Retries left for the subgraph requests of this operation, unlimited if None.
+    pub(crate) remaining_retries: Option<AtomicUsize>,
+    /// Entities which may still be fetched from subgraphs by this operation, unlimited if None.
+    pub(crate) remaining_entities: Option<AtomicUsize>,
 }
 
 impl std::ops::Deref for ExecutableOperation {
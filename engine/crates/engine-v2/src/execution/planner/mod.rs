@@ -1,7 +1,10 @@
 mod builder;
 mod query_modifier;
 
-use std::{mem::take, sync::Arc};
+use std::{
+    mem::take,
+    sync::{atomic::AtomicUsize, Arc},
+};
 
 use builder::ExecutionBuilder;
 use id_newtypes::IdRange;
@@ -89,6 +92,8 @@ pub(super) async fn plan<'ctx, R: Runtime>(
         execution_plans: Default::default(),
         response_views: Default::default(),
         response_modifier_executors: Default::default(),
+        remaining_retries: ctx.schema.settings.max_retries_per_operation.map(AtomicUsize::new),
+        remaining_entities: ctx.schema.settings.max_entities_per_operation.map(AtomicUsize::new),
     };
 
     let operation = ExecutionPlanner {
@@ -110,9 +115,10 @@ pub(super) async fn plan<'ctx, R: Runtime>(
             .enumerate()
             .rev() // roots first
             .format_with("\n", |(id, plan), f| f(&format_args!(
-                "**{id}**\n  input <- {}\n  ouput -> {}",
+                "**{id}**\n  input <- {}\n  ouput -> {}\n  timeout: {:?}",
                 plan.parent_count,
-                plan.children.iter().join(",")
+                plan.children.iter().join(","),
+                plan.prepared_executor.timeout()
             ))),
     );
 
@@ -1,8 +1,8 @@
-use schema::SchemaWalker;
+use schema::{ResolverWalker, SchemaWalker};
 
 use crate::{
     operation::{
-        LogicalPlanId, LogicalPlanResponseBlueprint, OperationWalker, PreparedOperation, QueryInputValueId,
+        Field, LogicalPlanId, LogicalPlanResponseBlueprint, OperationWalker, PreparedOperation, QueryInputValueId,
         QueryInputValueWalker, ResponseBlueprint,
     },
     response::ResponseKeys,
@@ -75,6 +75,47 @@ impl<'a> PlanWalker<'a, (), ()> {
     pub fn selection_set(self) -> PlanSelectionSet<'a> {
         PlanSelectionSet::RootFields(self)
     }
+
+    pub fn id(&self) -> ExecutionPlanId {
+        self.plan_id
+    }
+
+    /// Paths of the root fields of the plan within the response, made of the response keys from
+    /// the operation root without any list indices. Comma separated if there are several, like
+    /// `topProducts.reviews,topProducts.averageRating`.
+    pub fn response_path(&self) -> String {
+        let operation = self.operation;
+        let mut paths = Vec::<String>::new();
+        for &field_id in &self
+            .logical_plan()
+            .as_ref()
+            .root_field_ids_ordered_by_parent_entity_id_then_position
+        {
+            if matches!(operation[field_id], Field::TypeName(_)) {
+                continue;
+            }
+            let mut keys = Vec::new();
+            let mut field = &operation[field_id];
+            loop {
+                keys.push(&operation.response_keys[field.response_key()]);
+                let parent_selection_set_id = field.parent_selection_set_id();
+                match operation
+                    .fields
+                    .iter()
+                    .find(|parent| parent.selection_set_id() == Some(parent_selection_set_id))
+                {
+                    Some(parent) => field = parent,
+                    None => break,
+                }
+            }
+            keys.reverse();
+            let path = keys.join(".");
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+        paths.join(",")
+    }
 }
 
 impl<'a, I, SI> PlanWalker<'a, I, SI> {
@@ -107,4 +148,8 @@ impl<'a> LogicalPlanWalker<'a> {
     pub fn response_blueprint(&self) -> &LogicalPlanResponseBlueprint {
         &self.operation.response_blueprint[self.item]
     }
+
+    pub fn resolver(&self) -> ResolverWalker<'a> {
+        self.schema_walker.walk(self.as_ref().resolver_id)
+    }
 }
@@ -23,6 +23,8 @@ pub struct HttpGraphqlResponseExtraMetadata {
     pub operation_name: Option<String>,
     pub operation_type: Option<&'static str>,
     pub has_errors: bool,
+    /// Errors caused by the gateway or the subgraphs failing, rather than by the request.
+    pub has_execution_failures: bool,
 }
 
 pub enum HttpGraphqlResponseBody {
@@ -48,7 +50,25 @@ impl HttpGraphqlResponse {
                 {
                     "message": message,
                     "extensions": {
-                        "code": ErrorCode::BadRequest
+                        "code": ErrorCode::BadRequest,
+                        "retryable": false
+                    }
+                }
+            ]
+            }),
+        )
+    }
+
+    pub fn request_too_large(message: &str) -> HttpGraphqlResponse {
+        Self::from_json(
+            GraphqlResponseStatus::RequestError { count: 1 },
+            &serde_json::json!({
+            "errors": [
+                {
+                    "message": message,
+                    "extensions": {
+                        "code": ErrorCode::RequestTooLarge,
+                        "retryable": false
                     }
                 }
             ]
@@ -72,6 +92,23 @@ impl HttpGraphqlResponse {
         )
     }
 
+    pub fn service_unavailable(message: &str) -> HttpGraphqlResponse {
+        Self::from_json(
+            GraphqlResponseStatus::RequestError { count: 1 },
+            &serde_json::json!({
+                "errors": [
+                    {
+                        "message": message,
+                        "extensions": {
+                            "code": ErrorCode::ServiceUnavailable,
+                            "retryable": true
+                        }
+                    }
+                ]
+            }),
+        )
+    }
+
     pub(crate) fn build(
         response: Response,
         format: Option<StreamingFormat>,
@@ -90,6 +127,39 @@ impl HttpGraphqlResponse {
         http_response
     }
 
+    pub(crate) fn build_json(response: &Response, metadata: HttpGraphqlResponseExtraMetadata) -> Self {
+        let mut http_response = Self::from_json(response.status(), response);
+        http_response.metadata = metadata;
+        http_response
+    }
+
+    /// Re-indents a JSON body, streamed and non-JSON responses are returned untouched.
+    pub(crate) fn into_pretty(self) -> Self {
+        if self.headers.typed_get::<headers::ContentType>() != Some(headers::ContentType::json()) {
+            return self;
+        }
+        let HttpGraphqlResponseBody::Bytes(ref bytes) = self.body else {
+            return self;
+        };
+        let pretty = serde_json::from_slice::<serde_json::Value>(bytes.as_ref())
+            .and_then(|value| serde_json::to_vec_pretty(&value));
+        match pretty {
+            Ok(pretty) => {
+                let mut headers = self.headers;
+                headers.typed_insert(headers::ContentLength(pretty.len() as u64));
+                HttpGraphqlResponse {
+                    headers,
+                    body: HttpGraphqlResponseBody::Bytes(pretty.into()),
+                    metadata: self.metadata,
+                }
+            }
+            Err(err) => {
+                tracing::error!("Failed to pretty print response: {}", err);
+                self
+            }
+        }
+    }
+
     pub(crate) fn from_stream<T>(
         format: StreamingFormat,
         status: GraphqlResponseStatus,
@@ -156,7 +226,7 @@ impl HttpGraphqlResponse {
         }
     }
 
-    fn from_json_bytes(status: GraphqlResponseStatus, bytes: OwnedOrSharedBytes) -> HttpGraphqlResponse {
+    pub(crate) fn from_json_bytes(status: GraphqlResponseStatus, bytes: OwnedOrSharedBytes) -> HttpGraphqlResponse {
         let mut response = Self::from_bytes(status, bytes);
         response.headers.typed_insert(headers::ContentType::json());
         response
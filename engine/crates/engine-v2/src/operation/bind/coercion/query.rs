@@ -265,7 +265,7 @@ impl<'binder, 'schema, 'parsed> QueryValueCoercionContext<'binder, 'schema, 'par
                     if let Some(n) = n.as_f64() {
                         QueryInputValue::Float(n)
                     } else if let Some(n) = n.as_i64() {
-                        QueryInputValue::BigInt(n)
+                        QueryInputValue::BigInt(n.into())
                     } else {
                         QueryInputValue::U64(n.as_u64().unwrap())
                     }
@@ -308,7 +308,13 @@ impl<'binder, 'schema, 'parsed> QueryValueCoercionContext<'binder, 'schema, 'par
                 Ok(QueryInputValue::Int(value))
             }
             (Value::Number(number), ScalarType::BigInt) => {
-                let Some(value) = number.as_i64() else {
+                // Integers beyond u64 have already been parsed as floats and lost their precision,
+                // those can only be provided as strings.
+                let Some(value) = number
+                    .as_i64()
+                    .map(i128::from)
+                    .or_else(|| number.as_u64().map(i128::from))
+                else {
                     return Err(InputValueError::IncorrectScalarValue {
                         actual: number.to_string(),
                         expected: scalar.name().to_string(),
@@ -318,6 +324,17 @@ impl<'binder, 'schema, 'parsed> QueryValueCoercionContext<'binder, 'schema, 'par
                 };
                 Ok(QueryInputValue::BigInt(value))
             }
+            (Value::String(value), ScalarType::BigInt) => {
+                let Ok(number) = value.parse::<i128>() else {
+                    return Err(InputValueError::IncorrectScalarValue {
+                        actual: value,
+                        expected: scalar.name().to_string(),
+                        path: self.path(),
+                        location: self.location,
+                    });
+                };
+                Ok(QueryInputValue::BigInt(number))
+            }
             (Value::Number(number), ScalarType::Float) => {
                 let Some(value) = number.as_f64() else {
                     return Err(InputValueError::IncorrectScalarValue {
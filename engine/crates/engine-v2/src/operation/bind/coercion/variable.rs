@@ -191,7 +191,7 @@ impl<'a> VariableCoercionContext<'a> {
                     if let Some(n) = n.as_f64() {
                         VariableInputValue::Float(n)
                     } else if let Some(n) = n.as_i64() {
-                        VariableInputValue::BigInt(n)
+                        VariableInputValue::BigInt(n.into())
                     } else {
                         VariableInputValue::U64(n.as_u64().unwrap())
                     }
@@ -235,7 +235,13 @@ impl<'a> VariableCoercionContext<'a> {
                 Ok(VariableInputValue::Int(value))
             }
             (ConstValue::Number(number), ScalarType::BigInt) => {
-                let Some(value) = number.as_i64() else {
+                // Integers beyond u64 have already been parsed as floats and lost their precision,
+                // those can only be provided as strings.
+                let Some(value) = number
+                    .as_i64()
+                    .map(i128::from)
+                    .or_else(|| number.as_u64().map(i128::from))
+                else {
                     return Err(InputValueError::IncorrectScalarValue {
                         actual: number.to_string(),
                         expected: scalar.name().to_string(),
@@ -245,6 +251,17 @@ impl<'a> VariableCoercionContext<'a> {
                 };
                 Ok(VariableInputValue::BigInt(value))
             }
+            (ConstValue::String(value), ScalarType::BigInt) => {
+                let Ok(number) = value.parse::<i128>() else {
+                    return Err(InputValueError::IncorrectScalarValue {
+                        actual: value,
+                        expected: scalar.name().to_string(),
+                        path: self.path(),
+                        location: self.location,
+                    });
+                };
+                Ok(VariableInputValue::BigInt(number))
+            }
             (ConstValue::Number(number), ScalarType::Float) => {
                 let Some(value) = number.as_f64() else {
                     return Err(InputValueError::IncorrectScalarValue {
@@ -15,10 +15,11 @@ use schema::Schema;
 use validation::validate_parsed_operation;
 
 use super::{
-    parse::ParsedOperation, FieldId, QueryInputValues, QueryModifierId, QueryModifierRule, ResponseModifierId,
-    ResponseModifierRule,
+    parse::ParsedOperation, DeferredFragment, DeferredFragmentId, FieldId, QueryInputValues, QueryModifierId,
+    QueryModifierRule, ResponseModifierId, ResponseModifierRule,
 };
 use crate::{
+    engine::{FeatureFlags, Tenant},
     operation::SelectionSetType,
     operation::{
         Field, FieldArgument, FieldArgumentId, Location, Operation, SelectionSet, SelectionSetId, VariableDefinition,
@@ -89,6 +90,11 @@ pub enum BindError {
     },
     #[error("Fragment cycle detected: {}", .cycle.iter().join(", "))]
     FragmentCycle { cycle: Vec<String>, location: Location },
+    #[error("Fields '{response_key}' conflict because they have differing arguments. Use different aliases on the fields to fetch both if this was intentional.")]
+    FieldMergeConflict {
+        response_key: String,
+        locations: Vec<Location>,
+    },
     #[error("Query is too big: {0}")]
     QueryTooBig(String),
     #[error("{0}")]
@@ -107,6 +113,12 @@ pub enum BindError {
     QueryContainsTooManyRootFields { count: usize, location: Location },
     #[error("Query contains too many aliases.")]
     QueryContainsTooManyAliases { count: usize, location: Location },
+    #[error("Query contains too many fragment spreads.")]
+    QueryContainsTooManyFragmentSpreads { count: usize, location: Location },
+    #[error("Fragments are nested too deep.")]
+    FragmentsNestedTooDeep { depth: usize, location: Location },
+    #[error("Query contains too many fields.")]
+    QueryContainsTooManyFields { count: usize, location: Location },
 }
 
 impl From<BindError> for GraphqlError {
@@ -129,13 +141,27 @@ impl From<BindError> for GraphqlError {
             | BindError::QueryTooComplex { location, .. }
             | BindError::QueryTooDeep { location, .. }
             | BindError::QueryContainsTooManyAliases { location, .. }
-            | BindError::QueryContainsTooManyRootFields { location, .. } => vec![location],
+            | BindError::QueryContainsTooManyRootFields { location, .. }
+            | BindError::QueryContainsTooManyFragmentSpreads { location, .. }
+            | BindError::FragmentsNestedTooDeep { location, .. }
+            | BindError::QueryContainsTooManyFields { location, .. } => vec![location],
             BindError::InvalidInputValue(ref err) => vec![err.location()],
+            BindError::FieldMergeConflict { ref locations, .. } => locations.clone(),
             BindError::NoMutationDefined | BindError::NoSubscriptionDefined | BindError::QueryTooBig { .. } => {
                 vec![]
             }
         };
-        GraphqlError::new(err.to_string(), ErrorCode::OperationValidationError).with_locations(locations)
+        let code = match err {
+            BindError::FragmentCycle { .. } => ErrorCode::FragmentCycle,
+            BindError::FieldMergeConflict { .. } => ErrorCode::FieldMergeConflict,
+            _ => ErrorCode::OperationValidationError,
+        };
+        GraphqlError::builder()
+            .message(err.to_string())
+            .code(code)
+            .locations(locations)
+            .retryable(false)
+            .build()
     }
 }
 
@@ -144,6 +170,8 @@ pub type BindResult<T> = Result<T, BindError>;
 pub(crate) struct Binder<'schema, 'p> {
     schema: &'schema Schema,
     parsed_operation: &'p ParsedOperation,
+    feature_flags: &'p FeatureFlags,
+    tenant: Option<&'p Tenant>,
     operation_name: ErrorOperationName,
     response_keys: ResponseKeys,
     field_arguments: Vec<FieldArgument>,
@@ -154,6 +182,13 @@ pub(crate) struct Binder<'schema, 'p> {
     input_values: QueryInputValues,
     query_modifiers: HashMap<QueryModifierRule, (QueryModifierId, Vec<FieldId>)>,
     response_modifiers: HashMap<ResponseModifierRule, (ResponseModifierId, Vec<FieldId>)>,
+    uses_incremental_delivery: bool,
+    // Response keys of the fields leading to the selection set being bound.
+    field_path: Vec<String>,
+    // Deferred fragment the selections being registered are part of. Deferred fragments are
+    // never nested.
+    deferred_fragment_id: Option<DeferredFragmentId>,
+    deferred_fragments: Vec<DeferredFragment>,
 }
 
 id_newtypes::index! {
@@ -161,7 +196,12 @@ id_newtypes::index! {
     Binder<'s, 'p>.selection_sets[SelectionSetId] => SelectionSet,
 }
 
-pub fn bind_operation(schema: &Schema, mut parsed_operation: ParsedOperation) -> BindResult<Operation> {
+pub fn bind_operation(
+    schema: &Schema,
+    mut parsed_operation: ParsedOperation,
+    feature_flags: &FeatureFlags,
+    tenant: Option<&Tenant>,
+) -> BindResult<Operation> {
     validate_parsed_operation(&parsed_operation, &schema.settings.operation_limits)?;
 
     let root_object_id = match parsed_operation.definition.ty {
@@ -178,6 +218,8 @@ pub fn bind_operation(schema: &Schema, mut parsed_operation: ParsedOperation) ->
     let mut binder = Binder {
         schema,
         parsed_operation: &parsed_operation,
+        feature_flags,
+        tenant,
         operation_name: ErrorOperationName(parsed_operation.name.clone()),
         response_keys: ResponseKeys::default(),
         field_arguments: Vec::new(),
@@ -188,6 +230,10 @@ pub fn bind_operation(schema: &Schema, mut parsed_operation: ParsedOperation) ->
         query_modifiers: Default::default(),
         input_values: QueryInputValues::default(),
         response_modifiers: Default::default(),
+        uses_incremental_delivery: false,
+        field_path: Vec::new(),
+        deferred_fragment_id: None,
+        deferred_fragments: Vec::new(),
     };
 
     // Must be executed before binding selection sets
@@ -219,6 +265,8 @@ pub fn bind_operation(schema: &Schema, mut parsed_operation: ParsedOperation) ->
         query_modifier_impacted_fields,
         response_modifiers,
         response_modifier_impacted_fields,
+        uses_incremental_delivery: binder.uses_incremental_delivery,
+        deferred_fragments: binder.deferred_fragments,
     })
 }
 
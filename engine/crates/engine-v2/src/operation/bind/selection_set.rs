@@ -5,7 +5,10 @@ use im::HashMap;
 use schema::{Definition, FieldDefinitionId, ObjectId};
 
 use crate::{
-    operation::{FieldId, Location, QueryPosition, SelectionSet, SelectionSetId, SelectionSetType},
+    operation::{
+        DeferredFragment, DeferredFragmentId, FieldId, Location, QueryPosition, SelectionSet, SelectionSetId,
+        SelectionSetType,
+    },
     response::SafeResponseKey,
 };
 
@@ -24,15 +27,29 @@ impl<'schema, 'p> Binder<'schema, 'p> {
 pub(super) struct SelectionSetBinder<'schema, 'parsed, 'binder> {
     binder: &'binder mut Binder<'schema, 'parsed>,
     next_query_position: usize,
+    // Each occurrence is kept with the deferred fragment it's part of, if any.
     #[allow(clippy::type_complexity)]
     fields: HashMap<
         (SafeResponseKey, FieldDefinitionId),
-        (QueryPosition, Vec<&'parsed Positioned<engine_parser::types::Field>>),
+        (
+            QueryPosition,
+            Vec<(
+                &'parsed Positioned<engine_parser::types::Field>,
+                Option<DeferredFragmentId>,
+            )>,
+        ),
     >,
     #[allow(clippy::type_complexity)]
     typename_fields: HashMap<
         SafeResponseKey,
-        HashMap<SelectionSetType, (QueryPosition, &'parsed Positioned<engine_parser::types::Field>)>,
+        HashMap<
+            SelectionSetType,
+            (
+                QueryPosition,
+                &'parsed Positioned<engine_parser::types::Field>,
+                Option<DeferredFragmentId>,
+            ),
+        >,
     >,
 }
 
@@ -85,28 +102,57 @@ impl<'schema, 'p, 'binder> SelectionSetBinder<'schema, 'p, 'binder> {
 
     fn generate_fields(&mut self, ty: SelectionSetType, id: SelectionSetId) -> BindResult<Vec<FieldId>> {
         let mut field_ids = Vec::with_capacity(self.fields.len());
+        // Within a deferred selection set, fields are deferred through their parent.
+        let within_deferred_fragment = self.deferred_fragment_id.is_some();
 
         for ((response_key, definition_id), (query_position, fields)) in std::mem::take(&mut self.fields) {
             let field: &'p Positioned<engine_parser::types::Field> = fields
                 .iter()
+                .map(|(field, _)| *field)
                 .min_by_key(|field| field.pos.line)
                 .expect("At least one occurence");
+            // Selections sharing a response key are merged into a single field, they must agree on
+            // the arguments or one of them would be silently ignored.
+            if let Some((conflicting, _)) = fields.iter().find(|(other, _)| !have_same_arguments(field, other)) {
+                return Err(BindError::FieldMergeConflict {
+                    response_key: self.response_keys[response_key].to_string(),
+                    locations: vec![field.pos.try_into()?, conflicting.pos.try_into()?],
+                });
+            }
             let bound_response_key = response_key
                 .with_position(query_position)
                 .ok_or(BindError::TooManyFields {
                     location: field.pos.try_into()?,
                 })?;
+            // A field is only deferred if it isn't also selected outside of the deferred fragment.
+            let deferred_fragment_id = fields
+                .iter()
+                .map(|(_, deferred_fragment_id)| *deferred_fragment_id)
+                .reduce(|merged, deferred_fragment_id| deferred_fragment_id.and(merged))
+                .flatten();
             let selection_set_id = SelectionSetType::maybe_from(self.schema.walk(definition_id).ty().inner().id())
                 .map(|ty| {
                     let merged_selection_sets = fields
                         .into_iter()
-                        .map(|field| &field.node.selection_set)
+                        .map(|(field, _)| &field.node.selection_set)
                         .collect::<Vec<_>>();
-                    self.binder.bind_merged_selection_sets(ty, &merged_selection_sets)
+
+                    let key = self.response_keys[response_key].to_string();
+                    let parent_deferred_fragment_id =
+                        std::mem::replace(&mut self.binder.deferred_fragment_id, deferred_fragment_id);
+                    self.binder.field_path.push(key);
+                    let result = self.binder.bind_merged_selection_sets(ty, &merged_selection_sets);
+                    self.binder.field_path.pop();
+                    self.binder.deferred_fragment_id = parent_deferred_fragment_id;
+                    result
                 })
                 .transpose()?;
 
-            field_ids.push(self.bind_field(id, bound_response_key, definition_id, field, selection_set_id)?)
+            let field_id = self.bind_field(id, bound_response_key, definition_id, field, selection_set_id)?;
+            if !within_deferred_fragment {
+                self.add_deferred_fragment_field(deferred_fragment_id, field_id);
+            }
+            field_ids.push(field_id);
         }
 
         for (response_key, typename_fields) in std::mem::take(&mut self.typename_fields) {
@@ -114,34 +160,50 @@ impl<'schema, 'p, 'binder> SelectionSetBinder<'schema, 'p, 'binder> {
             // only keep that one.
             if typename_fields
                 .get(&ty)
-                .map(|(qpos, _)| Some(qpos) == typename_fields.values().map(|(qpos, _)| qpos).min())
+                .map(|(qpos, _, _)| Some(qpos) == typename_fields.values().map(|(qpos, _, _)| qpos).min())
                 .unwrap_or_default()
             {
-                let (query_position, field) = typename_fields.get(&ty).unwrap();
+                let (query_position, field, deferred_fragment_id) = typename_fields.get(&ty).unwrap();
                 let bound_response_key =
                     response_key
                         .with_position(*query_position)
                         .ok_or(BindError::TooManyFields {
                             location: field.pos.try_into()?,
                         })?;
-                field_ids.push(self.bind_typename_field(id, ty, bound_response_key, field)?);
+                let field_id = self.bind_typename_field(id, ty, bound_response_key, field)?;
+                if !within_deferred_fragment {
+                    self.add_deferred_fragment_field(*deferred_fragment_id, field_id);
+                }
+                field_ids.push(field_id);
 
                 continue;
             }
-            for (type_condition, (query_position, field)) in typename_fields {
+            for (type_condition, (query_position, field, deferred_fragment_id)) in typename_fields {
                 let bound_response_key =
                     response_key
                         .with_position(query_position)
                         .ok_or(BindError::TooManyFields {
                             location: field.pos.try_into()?,
                         })?;
-                field_ids.push(self.bind_typename_field(id, type_condition, bound_response_key, field)?)
+                let field_id = self.bind_typename_field(id, type_condition, bound_response_key, field)?;
+                if !within_deferred_fragment {
+                    self.add_deferred_fragment_field(deferred_fragment_id, field_id);
+                }
+                field_ids.push(field_id);
             }
         }
 
         Ok(field_ids)
     }
 
+    fn add_deferred_fragment_field(&mut self, deferred_fragment_id: Option<DeferredFragmentId>, field_id: FieldId) {
+        if let Some(deferred_fragment_id) = deferred_fragment_id {
+            self.binder.deferred_fragments[usize::from(deferred_fragment_id)]
+                .field_ids
+                .push(field_id);
+        }
+    }
+
     fn register_selection_set_fields(
         &mut self,
         ty: SelectionSetType,
@@ -151,23 +213,72 @@ impl<'schema, 'p, 'binder> SelectionSetBinder<'schema, 'p, 'binder> {
             node: selection_set, ..
         } = selection_set;
 
-        for Positioned { node: selection, .. } in &selection_set.items {
-            match selection {
-                engine_parser::types::Selection::Field(field) => {
-                    self.register_field(ty, field)?;
-                }
-                engine_parser::types::Selection::FragmentSpread(spread) => {
-                    self.register_fragment_spread_fields(ty, spread)?;
-                }
-                engine_parser::types::Selection::InlineFragment(fragment) => {
-                    self.register_inline_fragment_fields(ty, fragment)?;
+        for Positioned { node: selection, pos } in &selection_set.items {
+            let directives = match selection {
+                engine_parser::types::Selection::Field(field) => &field.node.directives,
+                engine_parser::types::Selection::FragmentSpread(spread) => &spread.node.directives,
+                engine_parser::types::Selection::InlineFragment(fragment) => &fragment.node.directives,
+            };
+            if is_excluded_by_literal_condition(directives) {
+                continue;
+            }
+            if is_incrementally_delivered(directives) {
+                self.uses_incremental_delivery = true;
+            }
+            // Deferred fragments nested in another one are delivered with it.
+            if self.deferred_fragment_id.is_none() {
+                let label = match selection {
+                    engine_parser::types::Selection::Field(_) => None,
+                    _ => deferred_fragment_label(directives),
+                };
+                if let Some(label) = label {
+                    let deferred_fragment_id = self.record_deferred_fragment((*pos).try_into()?, label);
+                    self.binder.deferred_fragment_id = Some(deferred_fragment_id);
+                    let result = self.register_selection(ty, selection);
+                    self.binder.deferred_fragment_id = None;
+                    result?;
+                    continue;
                 }
             }
+            self.register_selection(ty, selection)?;
         }
 
         Ok(())
     }
 
+    fn register_selection(
+        &mut self,
+        ty: SelectionSetType,
+        selection: &'p engine_parser::types::Selection,
+    ) -> BindResult<()> {
+        match selection {
+            engine_parser::types::Selection::Field(field) => self.register_field(ty, field),
+            engine_parser::types::Selection::FragmentSpread(spread) => self.register_fragment_spread_fields(ty, spread),
+            engine_parser::types::Selection::InlineFragment(fragment) => {
+                self.register_inline_fragment_fields(ty, fragment)
+            }
+        }
+    }
+
+    fn record_deferred_fragment(&mut self, location: Location, label: Option<String>) -> DeferredFragmentId {
+        let path = self.field_path.clone();
+        // The same fragment may be reached more than once through a named fragment.
+        if let Some(i) = self
+            .deferred_fragments
+            .iter()
+            .position(|fragment| fragment.location == location && fragment.path == path)
+        {
+            return DeferredFragmentId::from(i);
+        }
+        self.binder.deferred_fragments.push(DeferredFragment {
+            label,
+            path,
+            location,
+            field_ids: Vec::new(),
+        });
+        DeferredFragmentId::from(self.deferred_fragments.len() - 1)
+    }
+
     fn register_field(
         &mut self,
         parent: SelectionSetType,
@@ -184,13 +295,15 @@ impl<'schema, 'p, 'binder> SelectionSetBinder<'schema, 'p, 'binder> {
                 .unwrap_or_else(|| name),
         );
         let query_position = self.next_query_position();
+        let deferred_fragment_id = self.deferred_fragment_id;
 
         if name == "__typename" {
-            self.typename_fields
-                .entry(response_key)
-                .or_default()
-                .entry(parent)
-                .or_insert((query_position, field));
+            let typename_fields = self.typename_fields.entry(response_key).or_default();
+            if let Some((_, _, merged)) = typename_fields.get_mut(&parent) {
+                *merged = deferred_fragment_id.and(*merged);
+            } else {
+                typename_fields.insert(parent, (query_position, field, deferred_fragment_id));
+            }
             return Ok(());
         }
 
@@ -205,6 +318,15 @@ impl<'schema, 'p, 'binder> SelectionSetBinder<'schema, 'p, 'binder> {
                 });
             }
         }
+        // Fields behind a feature flag the request didn't enable, or hidden from its tenant, don't
+        // exist as far as it's concerned.
+        .filter(|definition_id| {
+            let directives = walker.walk(*definition_id).directives();
+            directives
+                .feature_flag()
+                .map_or(true, |flag| self.feature_flags.contains(flag))
+                && self.tenant.map_or(true, |tenant| tenant.can_see(directives.tags()))
+        })
         .ok_or_else(|| BindError::UnknownField {
             container: walker.walk(Definition::from(parent)).name().to_string(),
             name: name.to_string(),
@@ -215,7 +337,7 @@ impl<'schema, 'p, 'binder> SelectionSetBinder<'schema, 'p, 'binder> {
             .entry((response_key, definition_id))
             .or_insert((query_position, Vec::new()))
             .1
-            .push(field);
+            .push((field, deferred_fragment_id));
 
         Ok(())
     }
@@ -318,3 +440,52 @@ impl<'schema, 'p, 'binder> SelectionSetBinder<'schema, 'p, 'binder> {
         QueryPosition::from(query_position)
     }
 }
+
+fn have_same_arguments(
+    left: &Positioned<engine_parser::types::Field>,
+    right: &Positioned<engine_parser::types::Field>,
+) -> bool {
+    left.node.arguments.len() == right.node.arguments.len()
+        && left.node.arguments.iter().all(|(name, value)| {
+            right
+                .node
+                .arguments
+                .iter()
+                .any(|(other_name, other_value)| name.node == other_name.node && value.node == other_value.node)
+        })
+}
+
+/// `@skip` and `@include` with a literal condition evaluate the same way for every execution of
+/// the operation, so those selections are dropped while binding.
+fn is_incrementally_delivered(directives: &[Positioned<engine_parser::types::Directive>]) -> bool {
+    directives
+        .iter()
+        .any(|Positioned { node: directive, .. }| matches!(directive.name.node.as_str(), "defer" | "stream"))
+}
+
+/// Label of the fragment if it's deferred. Fragments with `@defer(if: false)` are not, but those
+/// with a variable condition always are.
+fn deferred_fragment_label(directives: &[Positioned<engine_parser::types::Directive>]) -> Option<Option<String>> {
+    let directive = directives
+        .iter()
+        .map(|Positioned { node, .. }| node)
+        .find(|directive| directive.name.node == "defer")?;
+    if let Some(engine_value::Value::Boolean(false)) = directive.get_argument("if").map(|value| &value.node) {
+        return None;
+    }
+    let label = match directive.get_argument("label").map(|value| &value.node) {
+        Some(engine_value::Value::String(label)) => Some(label.clone()),
+        _ => None,
+    };
+    Some(label)
+}
+
+fn is_excluded_by_literal_condition(directives: &[Positioned<engine_parser::types::Directive>]) -> bool {
+    directives.iter().any(|Positioned { node: directive, .. }| {
+        let condition = directive.get_argument("if").map(|value| &value.node);
+        matches!(
+            (directive.name.node.as_str(), condition),
+            ("skip", Some(engine_value::Value::Boolean(true))) | ("include", Some(engine_value::Value::Boolean(false)))
+        )
+    })
+}
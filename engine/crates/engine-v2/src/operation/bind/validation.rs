@@ -17,6 +17,11 @@ pub(super) fn validate_parsed_operation(operation: &ParsedOperation, limits: &Op
         max_aliases_count: limits.aliases.map(Into::into).unwrap_or(usize::MAX),
         complexity: 0,
         max_complexity: limits.complexity.map(Into::into).unwrap_or(usize::MAX),
+        fragment_spreads: 0,
+        max_fragment_spreads: limits.fragment_spreads.map(Into::into).unwrap_or(usize::MAX),
+        max_fragment_depth: limits.fragment_depth.map(Into::into).unwrap_or(usize::MAX),
+        fields: 0,
+        max_fields: limits.fields.map(Into::into).unwrap_or(usize::MAX),
     }
     .visit_selection_set(&operation.definition.selection_set)
 }
@@ -32,6 +37,11 @@ struct Visitor<'p> {
     max_aliases_count: usize,
     complexity: usize,
     max_complexity: usize,
+    fragment_spreads: usize,
+    max_fragment_spreads: usize,
+    max_fragment_depth: usize,
+    fields: usize,
+    max_fields: usize,
 }
 
 impl<'p> Visitor<'p> {
@@ -71,6 +81,14 @@ impl<'p> Visitor<'p> {
     }
 
     fn visit_field(&mut self, field: &'p Positioned<engine_parser::types::Field>) -> BindResult<()> {
+        // Unlike the height, every occurrence counts: aliases and each expansion of a fragment.
+        self.fields += 1;
+        if self.fields > self.max_fields {
+            return Err(BindError::QueryContainsTooManyFields {
+                count: self.fields,
+                location: field.pos.try_into()?,
+            });
+        }
         self.aliases_count += field.alias.is_some() as usize;
         if self.aliases_count > self.max_aliases_count {
             return Err(BindError::QueryContainsTooManyAliases {
@@ -113,6 +131,21 @@ impl<'p> Visitor<'p> {
                 location: fragment_spread.pos.try_into()?,
             });
         };
+        // Fragments are expanded on every spread, so each one counts even if the same fragment
+        // was already visited. This bounds the work done on fragment bombs.
+        self.fragment_spreads += 1;
+        if self.fragment_spreads > self.max_fragment_spreads {
+            return Err(BindError::QueryContainsTooManyFragmentSpreads {
+                count: self.fragment_spreads,
+                location: fragment_spread.pos.try_into()?,
+            });
+        }
+        if self.current_fragments_stack.len() >= self.max_fragment_depth {
+            return Err(BindError::FragmentsNestedTooDeep {
+                depth: self.current_fragments_stack.len() + 1,
+                location: fragment_spread.pos.try_into()?,
+            });
+        }
 
         self.current_fragments_stack.push(fragment_name.as_str());
         self.visit_selection_set(&fragment.selection_set)?;
@@ -19,15 +19,23 @@ pub enum VariableError {
     MissingVariable { name: String, location: Location },
     #[error("Variable ${name} has an invalid value. {err}")]
     InvalidValue { name: String, err: InputValueError },
+    #[error("Variables exceed the limit of {max} values")]
+    TooComplex { max: usize },
+    #[error("Variables exceed the maximum nesting depth of {max}")]
+    TooDeep { max: usize },
 }
 
 impl From<VariableError> for GraphqlError {
     fn from(err: VariableError) -> Self {
-        let locations = match err {
-            VariableError::MissingVariable { location, .. } => vec![location],
-            VariableError::InvalidValue { ref err, .. } => vec![err.location()],
+        let (code, locations) = match err {
+            VariableError::MissingVariable { location, .. } => (ErrorCode::OperationValidationError, vec![location]),
+            VariableError::InvalidValue { ref err, .. } => (ErrorCode::OperationValidationError, vec![err.location()]),
+            VariableError::TooComplex { .. } => (ErrorCode::VariablesTooComplex, Vec::new()),
+            VariableError::TooDeep { .. } => (ErrorCode::VariablesTooDeep, Vec::new()),
         };
-        GraphqlError::new(err.to_string(), ErrorCode::OperationValidationError).with_locations(locations)
+        GraphqlError::new(err.to_string(), code)
+            .with_locations(locations)
+            .with_retryable(false)
     }
 }
 
@@ -36,6 +44,18 @@ pub fn bind_variables(
     operation: &Operation,
     mut request_variables: engine::Variables,
 ) -> Result<Variables, Vec<VariableError>> {
+    if let Some(max) = schema.settings.max_variable_values {
+        if exceeds_value_count(operation, &request_variables, max) {
+            return Err(vec![VariableError::TooComplex { max }]);
+        }
+    }
+
+    if let Some(max) = schema.settings.max_variable_depth {
+        if exceeds_depth(operation, &request_variables, max) {
+            return Err(vec![VariableError::TooDeep { max }]);
+        }
+    }
+
     let mut errors = Vec::new();
     let mut variables = Variables {
         input_values: VariableInputValues::default(),
@@ -74,6 +94,55 @@ pub fn bind_variables(
     Ok(variables)
 }
 
+/// Counts the values of the variables used by the operation, nested ones included, stopping
+/// as soon as `max` is exceeded. Iterative as deeply nested values are exactly what it guards
+/// against.
+fn exceeds_value_count(operation: &Operation, request_variables: &engine::Variables, max: usize) -> bool {
+    let mut stack = operation
+        .variable_definitions
+        .iter()
+        .filter_map(|definition| request_variables.get(&engine_value::Name::new(&definition.name)))
+        .collect::<Vec<_>>();
+
+    let mut count = 0;
+    while let Some(value) = stack.pop() {
+        count += 1;
+        if count > max {
+            return true;
+        }
+        match value {
+            engine_value::ConstValue::List(items) => stack.extend(items),
+            engine_value::ConstValue::Object(fields) => stack.extend(fields.values()),
+            _ => {}
+        }
+    }
+
+    false
+}
+
+/// Whether any variable used by the operation nests lists and objects deeper than `max`, a
+/// scalar at the top level being at depth 0. Iterative for the same reason as
+/// `exceeds_value_count`.
+fn exceeds_depth(operation: &Operation, request_variables: &engine::Variables, max: usize) -> bool {
+    let mut stack = operation
+        .variable_definitions
+        .iter()
+        .filter_map(|definition| request_variables.get(&engine_value::Name::new(&definition.name)))
+        .map(|value| (value, 0))
+        .collect::<Vec<_>>();
+
+    while let Some((value, depth)) = stack.pop() {
+        match value {
+            engine_value::ConstValue::List(_) | engine_value::ConstValue::Object(_) if depth >= max => return true,
+            engine_value::ConstValue::List(items) => stack.extend(items.iter().map(|item| (item, depth + 1))),
+            engine_value::ConstValue::Object(fields) => stack.extend(fields.values().map(|field| (field, depth + 1))),
+            _ => {}
+        }
+    }
+
+    false
+}
+
 impl<'schema, 'p> Binder<'schema, 'p> {
     pub(super) fn bind_variable_definitions(
         &mut self,
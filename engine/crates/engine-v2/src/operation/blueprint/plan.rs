@@ -336,7 +336,7 @@ where
                     fields.iter().map(|field| field.selection_set().unwrap().id()).collect(),
                 ),
             },
-            wrapping: ty.wrapping(),
+            wrapping: definition.response_wrapping(),
         }
     }
 
@@ -1,16 +1,19 @@
 use schema::Schema;
 use tracing::instrument;
 
-use crate::response::{ErrorCode, GraphqlError};
+use crate::{
+    engine::{FeatureFlags, Tenant},
+    response::{ErrorCode, GraphqlError},
+};
 
 use super::{
     bind::{bind_operation, BindError},
     blueprint::ResponseBlueprintBuilder,
-    logical_planner::{LogicalPlanner, LogicalPlanningError},
+    logical_planner::{ensure_aliased_fetches_limit, ensure_subgraph_limit, LogicalPlanner, LogicalPlanningError},
     metrics::{generate_used_fields, prepare_metrics_attributes},
     parse::{parse_operation, ParseError},
-    validation::{validate_operation, ValidationError},
-    Operation, OperationMetricsAttributes, PreparedOperation, Variables,
+    validation::{ensure_operation_is_permitted, validate_operation, ValidationError},
+    IncrementalPlan, Operation, OperationMetricsAttributes, PreparedOperation, Variables,
 };
 
 #[derive(Debug, thiserror::Error)]
@@ -65,12 +68,32 @@ impl Operation {
     ///
     /// All field names are mapped to their actual field id in the schema and respective configuration.
     /// At this stage the operation might not be resolvable but it should make sense given the schema types.
+    ///
+    /// Clients restricted to specific operation name prefixes are checked first, before doing
+    /// any binding work. Fields gated behind a feature flag are only bound if the request
+    /// enabled it, so the result must be cached per set of flags. The same goes for tagged
+    /// fields and the tenant's profile. Clients may also be limited in the number of subgraphs
+    /// an operation is planned across.
     #[instrument(skip_all)]
-    pub fn build(schema: &Schema, request: &engine::Request) -> Result<PreparedOperation, OperationError> {
-        let parsed_operation = parse_operation(request)?;
+    pub fn build(
+        schema: &Schema,
+        request: &engine::Request,
+        document: &str,
+        client_name: Option<&str>,
+        feature_flags: &FeatureFlags,
+        tenant: Option<&Tenant>,
+    ) -> Result<PreparedOperation, OperationError> {
+        let parsed_operation = parse_operation(document, request.operation_name(), schema.settings.max_parse_time)?;
         let metrics_attributes = prepare_metrics_attributes(&parsed_operation, request);
 
-        let mut operation = match bind_operation(schema, parsed_operation) {
+        if let Err(err) = ensure_operation_is_permitted(schema, client_name, parsed_operation.name.as_deref()) {
+            return Err(OperationError::Validation {
+                metrics_attributes: Box::new(metrics_attributes),
+                err,
+            });
+        }
+
+        let mut operation = match bind_operation(schema, parsed_operation, feature_flags, tenant) {
             Ok(operation) => operation,
             Err(err) => {
                 return Err(OperationError::Bind {
@@ -89,7 +112,11 @@ impl Operation {
             });
         }
 
-        let plan = match LogicalPlanner::new(schema, &variables, &mut operation).plan() {
+        let plan = match LogicalPlanner::new(schema, &variables, &mut operation)
+            .plan()
+            .and_then(|plan| ensure_subgraph_limit(schema, client_name, &plan).map(|()| plan))
+            .and_then(|plan| ensure_aliased_fetches_limit(schema, &operation, &plan, request).map(|()| plan))
+        {
             Ok(plan) => plan,
             Err(err) => {
                 return Err(OperationError::LogicalPlanning {
@@ -100,6 +127,7 @@ impl Operation {
         };
 
         let response_blueprint = ResponseBlueprintBuilder::new(schema, &variables, &operation, &plan).build();
+        let incremental = IncrementalPlan::build(&operation, &plan);
 
         let mut metrics_attributes = metrics_attributes.ok_or(OperationError::NormalizationError)?;
         metrics_attributes.used_fields = generate_used_fields(schema, &operation);
@@ -109,6 +137,7 @@ impl Operation {
             metrics_attributes,
             plan,
             response_blueprint,
+            incremental,
         })
     }
 }
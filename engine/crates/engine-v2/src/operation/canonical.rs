@@ -0,0 +1,356 @@
+use std::borrow::Cow;
+
+use super::Location;
+use crate::response::GraphqlError;
+
+/// The operation document without the tokens GraphQL ignores: whitespace, commas and comments.
+/// Variables are renamed `$v0`, `$v1`, ... in their order of appearance. Documents only differing
+/// by those are the same operation and share its cache entry. Significant tokens are kept as is,
+/// their order included, as it determines the order of the response fields.
+///
+/// Operations of documents sent as is are built from their canonical form, so the locations and
+/// variable names of their errors refer to it. [`CanonicalDocument::restore_error`] maps them back
+/// to the document of the request, and [`CanonicalDocument::canonical_variables`] renames the
+/// variables of the request.
+pub(crate) struct CanonicalDocument {
+    text: String,
+    /// Name of each variable in the original document, `$v{i}` being `variable_names[i]`.
+    variable_names: Vec<String>,
+    /// Starts of the parts of the canonical document copied as is from the original one, sorted.
+    segments: Vec<Segment>,
+}
+
+#[derive(Clone, Copy)]
+struct Segment {
+    canonical: Position,
+    original: Position,
+}
+
+/// Same convention as the parser: one-based, columns counted in characters and only `\n`
+/// starting a new line.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Position {
+    line: usize,
+    column: usize,
+}
+
+impl Position {
+    const START: Position = Position { line: 1, column: 1 };
+
+    fn advance(&mut self, text: &str) {
+        for c in text.chars() {
+            if c == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+    }
+}
+
+impl CanonicalDocument {
+    pub fn new(query: &str) -> Self {
+        let mut document = CanonicalDocument {
+            text: String::with_capacity(query.len()),
+            variable_names: Vec::new(),
+            segments: Vec::new(),
+        };
+        let mut canonical = Position::START;
+        let mut original = Position::START;
+        // Whether ignored tokens precede the current one.
+        let mut separated = false;
+        // Whether the current token directly follows the last one copied in the original document.
+        let mut contiguous = false;
+        let mut chars = query.char_indices().peekable();
+
+        while let Some((start, c)) = chars.next() {
+            let end = match c {
+                ' ' | '\t' | '\n' | '\r' | ',' | '\u{feff}' => start + c.len_utf8(),
+                // Comments run until the end of the line.
+                '#' => {
+                    let mut end = start + 1;
+                    while let Some((i, c)) = chars.next_if(|(_, c)| !matches!(c, '\n' | '\r')) {
+                        end = i + c.len_utf8();
+                    }
+                    end
+                }
+                // Strings are copied verbatim, up to their closing quote.
+                '"' if query[start..].starts_with(r#"""""#) => block_string_end(query, start),
+                '"' => string_end(query, start),
+                '$' => start + 1 + name_len(&query[start + 1..]),
+                _ if is_name_continue(c) => start + name_len(&query[start..]),
+                _ => start + c.len_utf8(),
+            };
+            while chars.next_if(|(i, _)| *i < end).is_some() {}
+            let token = &query[start..end];
+
+            if matches!(c, ' ' | '\t' | '\n' | '\r' | ',' | '\u{feff}' | '#') {
+                separated = true;
+                contiguous = false;
+                original.advance(token);
+                continue;
+            }
+
+            let needs_space = match document.text.chars().next_back() {
+                Some(previous) => {
+                    separated && ((is_name_continue(previous) && is_name_continue(c)) || (previous == '"' && c == '"'))
+                }
+                None => false,
+            };
+            if needs_space {
+                document.text.push(' ');
+                canonical.advance(" ");
+            }
+            separated = false;
+
+            if !contiguous {
+                document.segments.push(Segment { canonical, original });
+            }
+
+            if c == '$' && token.len() > 1 {
+                let name = &token[1..];
+                let index = match document.variable_names.iter().position(|known| known == name) {
+                    Some(index) => index,
+                    None => {
+                        document.variable_names.push(name.to_string());
+                        document.variable_names.len() - 1
+                    }
+                };
+                let renamed = format!("$v{index}");
+                document.text.push_str(&renamed);
+                canonical.advance(&renamed);
+                // The following token isn't at the same offset anymore.
+                contiguous = false;
+            } else {
+                document.text.push_str(token);
+                canonical.advance(token);
+                contiguous = true;
+            }
+            original.advance(token);
+        }
+
+        document
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Renames the variables of the request to their canonical name. Variables which don't appear
+    /// in the document are dropped, they could otherwise collide with a canonical name.
+    pub fn canonical_variables(&self, variables: engine::Variables) -> engine::Variables {
+        let mut renamed = engine::Variables::default();
+        for (name, value) in variables {
+            if let Some(index) = self.variable_names.iter().position(|known| known == name.as_str()) {
+                renamed.insert(engine_value::Name::new(format!("v{index}")), value);
+            }
+        }
+        renamed
+    }
+
+    /// Maps the locations and variable names of an error on the canonical document back to the
+    /// original one.
+    pub fn restore_error(&self, error: &mut GraphqlError) {
+        for location in &mut error.locations {
+            *location = self.original_location(*location);
+        }
+        if let Cow::Owned(message) = self.original_message(&error.message) {
+            error.message = Cow::Owned(message);
+        }
+    }
+
+    fn original_location(&self, location: Location) -> Location {
+        let target = Position {
+            line: location.line() as usize,
+            column: location.column() as usize,
+        };
+        let index = self.segments.partition_point(|segment| segment.canonical <= target);
+        let Some(segment) = index.checked_sub(1).map(|index| self.segments[index]) else {
+            return location;
+        };
+
+        // Within a segment both documents advance together, a line break included.
+        let original = if target.line == segment.canonical.line {
+            Position {
+                line: segment.original.line,
+                column: segment.original.column + (target.column - segment.canonical.column),
+            }
+        } else {
+            Position {
+                line: segment.original.line + (target.line - segment.canonical.line),
+                column: target.column,
+            }
+        };
+
+        Location::new(
+            original.line.try_into().unwrap_or(u16::MAX),
+            original.column.try_into().unwrap_or(u16::MAX),
+        )
+    }
+
+    fn original_message<'a>(&self, message: &'a str) -> Cow<'a, str> {
+        if !message.contains("$v") {
+            return Cow::Borrowed(message);
+        }
+
+        let mut out = String::with_capacity(message.len());
+        let mut rest = message;
+        while let Some(i) = rest.find("$v") {
+            out.push_str(&rest[..i]);
+            let digits = rest[i + 2..].bytes().take_while(u8::is_ascii_digit).count();
+            let name = rest[i + 2..i + 2 + digits]
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| self.variable_names.get(index));
+            match name {
+                Some(name) => {
+                    out.push('$');
+                    out.push_str(name);
+                }
+                None => out.push_str(&rest[i..i + 2 + digits]),
+            }
+            rest = &rest[i + 2 + digits..];
+        }
+        out.push_str(rest);
+
+        Cow::Owned(out)
+    }
+}
+
+fn is_name_continue(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn name_len(text: &str) -> usize {
+    text.bytes().take_while(|b| is_name_continue(*b as char)).count()
+}
+
+/// End of the string starting at `start`, the rest of the line if it isn't closed.
+fn string_end(query: &str, start: usize) -> usize {
+    let mut escaped = false;
+    for (i, c) in query[start + 1..].char_indices() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return start + 1 + i + 1,
+            '\n' | '\r' => return start + 1 + i,
+            _ => escaped = false,
+        }
+    }
+    query.len()
+}
+
+/// End of the block string starting at `start`, the rest of the document if it isn't closed.
+fn block_string_end(query: &str, start: usize) -> usize {
+    let content = start + 3;
+    let mut offset = 0;
+    while let Some(i) = query[content + offset..].find(r#"""""#) {
+        let position = content + offset + i;
+        // `\"""` is an escaped triple quote within the block string.
+        if query[..position].ends_with('\\') {
+            offset += i + 3;
+        } else {
+            return position + 3;
+        }
+    }
+    query.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CanonicalDocument;
+    use crate::{
+        operation::Location,
+        response::{ErrorCode, GraphqlError},
+    };
+
+    fn canonical(query: &str) -> String {
+        CanonicalDocument::new(query).as_str().to_string()
+    }
+
+    #[test]
+    fn ignored_tokens_are_removed_and_variables_renamed() {
+        let query = r#"
+            # The current user
+            query Me($first: Int = 10, $after: String) {
+              me {
+                id,
+                friends(first: $first, after: $after) { ... on User { name } }
+              }
+            }
+        "#;
+
+        assert_eq!(
+            canonical(query),
+            "query Me($v0:Int=10$v1:String){me{id friends(first:$v0 after:$v1){...on User{name}}}}"
+        );
+    }
+
+    #[test]
+    fn strings_are_kept_as_is() {
+        assert_eq!(
+            canonical(r#"{ search(text: "a,  b # $c", other: "") }"#),
+            r#"{search(text:"a,  b # $c"other:"")}"#
+        );
+        assert_eq!(
+            canonical(r#"{ search(text: "escaped \" , quote") }"#),
+            r#"{search(text:"escaped \" , quote")}"#
+        );
+        assert_eq!(
+            canonical("{ search(text: \"\"\"\n  block \\\"\"\" , \"  \n\"\"\") }"),
+            "{search(text:\"\"\"\n  block \\\"\"\" , \"  \n\"\"\")}"
+        );
+    }
+
+    #[test]
+    fn adjacent_tokens_stay_apart() {
+        assert_eq!(canonical(r#"{ f(a: ["" "b"]) }"#), r#"{f(a:["" "b"])}"#);
+        assert_eq!(canonical("{ f(a: [1 2 $x]) }"), "{f(a:[1 2$v0])}");
+    }
+
+    #[test]
+    fn equivalent_documents_are_identical() {
+        let compact = "query($id:ID!){product(id:$id){name price}}";
+        let formatted = "
+            query($productId: ID!) {
+              product(id: $productId) {
+                name
+                price
+              }
+            }
+        ";
+
+        assert_eq!(canonical(compact), canonical(formatted));
+        assert_ne!(
+            canonical(compact),
+            canonical("query($id:ID!){product(id:$id){price name}}")
+        );
+    }
+
+    #[test]
+    fn errors_are_restored_to_the_original_document() {
+        let document = CanonicalDocument::new("query(\n  $productId: ID!\n) {\n  product(id: $productId) { name }\n}");
+        assert_eq!(document.as_str(), "query($v0:ID!){product(id:$v0){name}}");
+
+        let mut error = GraphqlError::new("Variable $v0 is missing", ErrorCode::OperationValidationError)
+            .with_locations([Location::new(1, 7), Location::new(1, 16), Location::new(1, 27)]);
+        document.restore_error(&mut error);
+
+        assert_eq!(error.message, "Variable $productId is missing");
+        assert_eq!(
+            error.locations,
+            vec![Location::new(2, 3), Location::new(4, 3), Location::new(4, 15)]
+        );
+    }
+
+    #[test]
+    fn request_variables_are_renamed() {
+        let document = CanonicalDocument::new("query($b: Int, $a: Int) { f(a: $a, b: $b) }");
+        let variables = engine::Variables::from_json(serde_json::json!({"a": 1, "b": 2, "unused": 3}));
+
+        assert_eq!(
+            serde_json::to_value(document.canonical_variables(variables)).unwrap(),
+            serde_json::json!({"v0": 2, "v1": 1})
+        );
+    }
+}
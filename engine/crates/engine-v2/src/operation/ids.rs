@@ -1,6 +1,6 @@
 use super::{
-    Field, FieldArgument, LogicalPlan, LogicalPlanResponseBlueprint, Operation, OperationPlan, PreparedOperation,
-    QueryModifier, ResponseBlueprint, ResponseModifier, SelectionSet, VariableDefinition,
+    DeferredFragment, Field, FieldArgument, LogicalPlan, LogicalPlanResponseBlueprint, Operation, OperationPlan,
+    PreparedOperation, QueryModifier, ResponseBlueprint, ResponseModifier, SelectionSet, VariableDefinition,
 };
 
 id_newtypes::NonZeroU16! {
@@ -12,6 +12,7 @@ id_newtypes::NonZeroU16! {
     Operation.response_modifier_impacted_fields[ResponseModifierImpactedFieldId] => FieldId,
     Operation.query_modifiers[QueryModifierId] => QueryModifier,
     Operation.query_modifier_impacted_fields[QueryModifierImpactedFieldId] => FieldId,
+    Operation.deferred_fragments[DeferredFragmentId] => DeferredFragment,
     OperationPlan.logical_plans[LogicalPlanId] => LogicalPlan | proxy(PreparedOperation.plan),
 }
 
@@ -0,0 +1,104 @@
+//! Queries using `@defer` are executed once, like any other. Logical plans only resolving fields
+//! of deferred fragments are held back until the initial response was sent, and then executed
+//! against the same response objects. Each fragment is delivered as soon as the plans leading to
+//! it are done. Nested deferred fragments are delivered with their parent.
+
+use id_newtypes::BitSet;
+
+use super::{DeferredFragmentId, FieldId, Location, LogicalPlanId, Operation, OperationPlan, OperationType};
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub(crate) struct DeferredFragment {
+    pub label: Option<String>,
+    /// Response keys of the fields leading to the fragment.
+    pub path: Vec<String>,
+    pub location: Location,
+    /// Fields of the selection set the fragment is part of which are only selected within it.
+    /// Their subselections are deferred with them.
+    pub field_ids: Vec<FieldId>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub(crate) struct IncrementalPlan {
+    pub deferred_logical_plans: BitSet<LogicalPlanId>,
+    /// Deferred fragments each logical plan leads to, through its own fields or the plans
+    /// depending on it. A fragment is complete once none of the running plans leads to it.
+    pub fragment_ids_by_logical_plan: Vec<Vec<DeferredFragmentId>>,
+}
+
+impl IncrementalPlan {
+    /// Deferred fragments are only delivered separately for queries. A logical plan is deferred if
+    /// all of its root fields are, and none of the plans depending on it are needed for the
+    /// initial response.
+    pub(super) fn build(operation: &Operation, plan: &OperationPlan) -> Option<Self> {
+        if !matches!(operation.ty, OperationType::Query) || operation.deferred_fragments.is_empty() {
+            return None;
+        }
+
+        let mut parent_fields = vec![None; operation.selection_sets.len()];
+        for (id, field) in operation.fields.iter().enumerate() {
+            if let Some(selection_set_id) = field.selection_set_id() {
+                parent_fields[usize::from(selection_set_id)] = Some(FieldId::from(id));
+            }
+        }
+        let mut deferred_fields = BitSet::init_with(false, operation.fields.len());
+        let mut fragment_ids_by_field = vec![Vec::new(); operation.fields.len()];
+        for (id, fragment) in operation.deferred_fragments.iter().enumerate() {
+            for &field_id in &fragment.field_ids {
+                deferred_fields.set(field_id, true);
+                fragment_ids_by_field[usize::from(field_id)].push(DeferredFragmentId::from(id));
+            }
+        }
+        let is_deferred = |mut field_id: FieldId| loop {
+            if deferred_fields[field_id] {
+                return true;
+            }
+            match parent_fields[usize::from(operation[field_id].parent_selection_set_id())] {
+                Some(parent_field_id) => field_id = parent_field_id,
+                None => return false,
+            }
+        };
+
+        let mut deferred_logical_plans = BitSet::init_with(false, plan.logical_plans.len());
+        for (id, logical_plan) in plan.logical_plans.iter().enumerate() {
+            let deferred = logical_plan
+                .root_field_ids_ordered_by_parent_entity_id_then_position
+                .iter()
+                .all(|&field_id| is_deferred(field_id));
+            deferred_logical_plans.set(LogicalPlanId::from(id), deferred);
+        }
+        let mut fragment_ids_by_logical_plan = plan
+            .logical_plans
+            .iter()
+            .map(|logical_plan| {
+                let mut fragment_ids = Vec::new();
+                for &field_id in &logical_plan.root_field_ids_ordered_by_parent_entity_id_then_position {
+                    let mut field_id = Some(field_id);
+                    while let Some(id) = field_id {
+                        fragment_ids.extend_from_slice(&fragment_ids_by_field[usize::from(id)]);
+                        field_id = parent_fields[usize::from(operation[id].parent_selection_set_id())];
+                    }
+                }
+                fragment_ids
+            })
+            .collect::<Vec<_>>();
+        // Going up from the leaves, children are settled before their parents.
+        for &id in plan.in_topological_order.iter().rev() {
+            if plan.children.find_all(id).any(|&child| !deferred_logical_plans[child]) {
+                deferred_logical_plans.set(id, false);
+            }
+            let mut fragment_ids = std::mem::take(&mut fragment_ids_by_logical_plan[usize::from(id)]);
+            for &child in plan.children.find_all(id) {
+                fragment_ids.extend_from_slice(&fragment_ids_by_logical_plan[usize::from(child)]);
+            }
+            fragment_ids.sort_unstable();
+            fragment_ids.dedup();
+            fragment_ids_by_logical_plan[usize::from(id)] = fragment_ids;
+        }
+
+        Some(Self {
+            deferred_logical_plans,
+            fragment_ids_by_logical_plan,
+        })
+    }
+}
@@ -21,7 +21,10 @@ impl<'de> serde::Deserializer<'de> for QueryInputValueWalker<'de> {
             QueryInputValue::String(s) => visitor.visit_borrowed_str(s),
             QueryInputValue::EnumValue(id) => visitor.visit_borrowed_str(self.schema_walker.walk(*id).name()),
             QueryInputValue::Int(n) => visitor.visit_i32(*n),
-            QueryInputValue::BigInt(n) => visitor.visit_i64(*n),
+            QueryInputValue::BigInt(n) => match i64::try_from(*n) {
+                Ok(n) => visitor.visit_i64(n),
+                Err(_) => visitor.visit_i128(*n),
+            },
             QueryInputValue::U64(n) => visitor.visit_u64(*n),
             QueryInputValue::Float(n) => visitor.visit_f64(*n),
             QueryInputValue::Boolean(b) => visitor.visit_bool(*b),
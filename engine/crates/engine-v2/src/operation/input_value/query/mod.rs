@@ -32,7 +32,7 @@ pub(crate) enum QueryInputValue {
     String(String),
     EnumValue(EnumValueId),
     Int(i32),
-    BigInt(i64),
+    BigInt(i128),
     Float(f64),
     Boolean(bool),
     InputObject(IdRange<QueryInputObjectFieldValueId>),
@@ -179,7 +179,7 @@ impl PartialEq<SchemaInputValue> for QueryInputValueWalker<'_> {
             (QueryInputValue::String(l), SchemaInputValue::String(r)) => l == &self.schema_walker[*r],
             (QueryInputValue::EnumValue(l), SchemaInputValue::EnumValue(r)) => l == r,
             (QueryInputValue::Int(l), SchemaInputValue::Int(r)) => l == r,
-            (QueryInputValue::BigInt(l), SchemaInputValue::BigInt(r)) => l == r,
+            (QueryInputValue::BigInt(l), SchemaInputValue::BigInt(r)) => *l == i128::from(*r),
             (QueryInputValue::U64(l), SchemaInputValue::U64(r)) => l == r,
             (QueryInputValue::Float(l), SchemaInputValue::Float(r)) => l == r,
             (QueryInputValue::Boolean(l), SchemaInputValue::Boolean(r)) => l == r,
@@ -30,10 +30,12 @@ impl<'a> serde::Serialize for QueryInputValueView<'a> {
         let mut map = serializer.serialize_map(None)?;
         for item in self.selection_set.iter() {
             for (id, value) in &self.inner.operation[*fields] {
-                if *id == item.id {
+                let value = self.inner.walk(value);
+                // Omitted fields must stay omitted rather than being sent as null.
+                if *id == item.id && !value.is_undefined() {
                     map.serialize_key(self.inner.schema_walker.walk(*id).name())?;
                     map.serialize_value(&Self {
-                        inner: self.inner.walk(value),
+                        inner: value,
                         selection_set: &item.subselection,
                     })?;
                     continue;
@@ -65,10 +67,11 @@ impl<'de> serde::Deserializer<'de> for QueryInputValueView<'de> {
 
         MapDeserializer::new(self.selection_set.iter().filter_map(|item| {
             self.inner.operation[*fields].iter().find_map(|(id, value)| {
-                if *id == item.id {
+                let value = self.inner.walk(value);
+                if *id == item.id && !value.is_undefined() {
                     let name = self.inner.schema_walker.walk(*id).name();
                     let value = Self {
-                        inner: self.inner.walk(value),
+                        inner: value,
                         selection_set: &item.subselection,
                     };
                     Some((name, value))
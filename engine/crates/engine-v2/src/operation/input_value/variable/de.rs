@@ -21,7 +21,10 @@ impl<'de> serde::Deserializer<'de> for VariableInputValueWalker<'de> {
             VariableInputValue::String(s) => visitor.visit_borrowed_str(s),
             VariableInputValue::EnumValue(id) => visitor.visit_borrowed_str(self.schema_walker.walk(*id).name()),
             VariableInputValue::Int(n) => visitor.visit_i32(*n),
-            VariableInputValue::BigInt(n) => visitor.visit_i64(*n),
+            VariableInputValue::BigInt(n) => match i64::try_from(*n) {
+                Ok(n) => visitor.visit_i64(n),
+                Err(_) => visitor.visit_i128(*n),
+            },
             VariableInputValue::U64(n) => visitor.visit_u64(*n),
             VariableInputValue::Float(n) => visitor.visit_f64(*n),
             VariableInputValue::Boolean(b) => visitor.visit_bool(*b),
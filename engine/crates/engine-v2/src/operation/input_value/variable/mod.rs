@@ -29,7 +29,7 @@ pub enum VariableInputValue {
     String(String),
     EnumValue(EnumValueId),
     Int(i32),
-    BigInt(i64),
+    BigInt(i128),
     Float(f64),
     Boolean(bool),
     InputObject(IdRange<VariableInputObjectFieldValueId>),
@@ -131,7 +131,7 @@ impl PartialEq<SchemaInputValue> for VariableInputValueWalker<'_> {
             (VariableInputValue::String(l), SchemaInputValue::String(r)) => l == &self.schema_walker[*r],
             (VariableInputValue::EnumValue(l), SchemaInputValue::EnumValue(r)) => l == r,
             (VariableInputValue::Int(l), SchemaInputValue::Int(r)) => l == r,
-            (VariableInputValue::BigInt(l), SchemaInputValue::BigInt(r)) => l == r,
+            (VariableInputValue::BigInt(l), SchemaInputValue::BigInt(r)) => *l == i128::from(*r),
             (VariableInputValue::U64(l), SchemaInputValue::U64(r)) => l == r,
             (VariableInputValue::Float(l), SchemaInputValue::Float(r)) => l == r,
             (VariableInputValue::Boolean(l), SchemaInputValue::Boolean(r)) => l == r,
@@ -11,7 +11,7 @@ use tracing::instrument;
 
 use crate::{
     operation::{
-        FieldId, LogicalPlan, LogicalPlanId, Operation, OperationWalker, QueryPath, ResponseModifierRule,
+        Field, FieldId, LogicalPlan, LogicalPlanId, Operation, OperationWalker, QueryPath, ResponseModifierRule,
         SelectionSetId, SolvedRequiredFieldSet, Variables,
     },
     response::{ErrorCode, GraphqlError},
@@ -28,20 +28,131 @@ pub(crate) enum LogicalPlanningError {
         missing: Vec<String>,
         query_path: Vec<String>,
     },
+    #[error("Operation requires {count} subgraphs, but client '{client_name}' is limited to {max}")]
+    TooManySubgraphs {
+        client_name: String,
+        count: usize,
+        max: usize,
+    },
+    #[error("Operation requires {count} subgraph requests for aliased fields, but is limited to {max}")]
+    TooManyAliasedFetches { count: usize, max: usize },
 }
 
 impl From<LogicalPlanningError> for GraphqlError {
     fn from(error: LogicalPlanningError) -> Self {
         let message = error.to_string();
-        let query_path = match error {
-            LogicalPlanningError::CouldNotPlanAnyField { query_path, .. } => query_path
-                .into_iter()
-                .map(serde_json::Value::String)
-                .collect::<Vec<_>>(),
-        };
+        match error {
+            LogicalPlanningError::CouldNotPlanAnyField { query_path, .. } => {
+                let query_path = query_path
+                    .into_iter()
+                    .map(serde_json::Value::String)
+                    .collect::<Vec<_>>();
+
+                GraphqlError::builder()
+                    .message(message)
+                    .code(ErrorCode::OperationPlanningError)
+                    .extension("queryPath", query_path)
+                    .build()
+            }
+            LogicalPlanningError::TooManySubgraphs { .. } => GraphqlError::new(message, ErrorCode::TooManySubgraphs),
+            LogicalPlanningError::TooManyAliasedFetches { .. } => {
+                GraphqlError::new(message, ErrorCode::TooManyAliasedFetches)
+            }
+        }
+    }
+}
 
-        GraphqlError::new(message, ErrorCode::OperationPlanningError).with_extension("queryPath", query_path)
+/// Clients configured with a maximum number of subgraphs per operation can't execute operations
+/// planned across more distinct subgraphs. Introspection isn't counted.
+pub(super) fn ensure_subgraph_limit(
+    schema: &Schema,
+    client_name: Option<&str>,
+    plan: &OperationPlan,
+) -> LogicalPlanningResult<()> {
+    let Some((client_name, max)) = client_name.and_then(|client_name| {
+        let client = schema.settings.clients.get(client_name)?;
+        Some((client_name, client.max_subgraphs_per_operation?))
+    }) else {
+        return Ok(());
+    };
+
+    let count = plan
+        .logical_plans
+        .iter()
+        .map(|plan| schema.walker().walk(plan.resolver_id))
+        .filter(|resolver| resolver.graphql_endpoint().is_some())
+        .map(|resolver| resolver.subgraph_id())
+        .unique()
+        .count();
+
+    if count > max {
+        return Err(LogicalPlanningError::TooManySubgraphs {
+            client_name: client_name.to_string(),
+            count,
+            max,
+        });
     }
+
+    Ok(())
+}
+
+/// Aliases of a field with different arguments can't be merged into a single subgraph request
+/// when their subselections are resolved by another subgraph, each one adding a request. Plans
+/// with a root field which is aliased, or nested in an aliased field, are counted.
+pub(super) fn ensure_aliased_fetches_limit(
+    schema: &Schema,
+    operation: &Operation,
+    plan: &OperationPlan,
+    request: &engine::Request,
+) -> LogicalPlanningResult<()> {
+    let Some(max) = schema.settings.operation_limits.aliased_fetches.map(usize::from) else {
+        return Ok(());
+    };
+    if request.operation_limits_disabled() {
+        return Ok(());
+    }
+
+    let mut parent_fields = vec![None; operation.selection_sets.len()];
+    for (id, field) in operation.fields.iter().enumerate() {
+        if let Some(selection_set_id) = field.selection_set_id() {
+            parent_fields[usize::from(selection_set_id)] = Some(FieldId::from(id));
+        }
+    }
+
+    let is_aliased = |field: &Field| match field {
+        Field::Query(query_field) => operation
+            .response_keys
+            .try_resolve(field.response_key())
+            .is_some_and(|key| key != schema.walker().walk(query_field.definition_id).name()),
+        _ => false,
+    };
+
+    let count = plan
+        .logical_plans
+        .iter()
+        .filter(|logical_plan| {
+            logical_plan
+                .root_field_ids_ordered_by_parent_entity_id_then_position
+                .iter()
+                .any(|&id| {
+                    let mut field_id = Some(id);
+                    while let Some(id) = field_id {
+                        let field = &operation[id];
+                        if is_aliased(field) {
+                            return true;
+                        }
+                        field_id = parent_fields[usize::from(field.parent_selection_set_id())];
+                    }
+                    false
+                })
+        })
+        .count();
+
+    if count > max {
+        return Err(LogicalPlanningError::TooManyAliasedFetches { count, max });
+    }
+
+    Ok(())
 }
 
 pub(super) type LogicalPlanningResult<T> = Result<T, LogicalPlanningError>;
@@ -205,6 +316,20 @@ impl<'a> LogicalPlanner<'a> {
 
         if matches!(self.operation.ty, OperationType::Mutation) {
             self.plan_mutation(field_ids)?;
+        } else if matches!(self.operation.ty, OperationType::Query) {
+            // Root fields of deferred fragments are planned separately, so they can be executed once
+            // the initial response was sent. Nested ones only are when they're resolved by another
+            // subgraph than their parent.
+            let (deferred_field_ids, field_ids): (Vec<_>, Vec<_>) = field_ids.into_iter().partition(|field_id| {
+                self.operation
+                    .deferred_fragments
+                    .iter()
+                    .any(|fragment| fragment.path.is_empty() && fragment.field_ids.contains(field_id))
+            });
+            self.plan_query(field_ids)?;
+            if !deferred_field_ids.is_empty() {
+                self.plan_query(deferred_field_ids)?;
+            }
         } else {
             // Subscription are considered to be Queries for planning, they just happen to have
             // only one root field.
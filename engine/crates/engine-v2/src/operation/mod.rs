@@ -1,7 +1,9 @@
 mod bind;
 mod blueprint;
 mod build;
+mod canonical;
 pub mod ids;
+mod incremental;
 mod input_value;
 mod location;
 mod logical_planner;
@@ -15,15 +17,17 @@ mod variables;
 mod walkers;
 
 use crate::response::{ConcreteObjectShapeId, FieldShapeId, ResponseKeys, ResponseObjectSetId, Shapes};
+pub(crate) use canonical::CanonicalDocument;
 pub(crate) use engine_parser::types::OperationType;
 use grafbase_telemetry::metrics::OperationMetricsAttributes;
 use id_newtypes::{BitSet, IdRange, IdToMany};
 pub(crate) use ids::*;
+pub(crate) use incremental::*;
 pub(crate) use input_value::*;
 pub(crate) use location::Location;
 pub(crate) use modifier::*;
 pub(crate) use path::QueryPath;
-use schema::{EntityId, ObjectId, RequiredFieldId, ResolverId, SchemaWalker};
+use schema::{EntityId, ObjectId, RequiredFieldId, ResolverId, Schema, SchemaWalker};
 pub(crate) use selection_set::*;
 pub(crate) use variables::*;
 pub(crate) use walkers::*;
@@ -34,6 +38,8 @@ pub(crate) struct PreparedOperation {
     pub metrics_attributes: OperationMetricsAttributes,
     pub plan: OperationPlan,
     pub response_blueprint: ResponseBlueprint,
+    // Only for queries with deferred fragments.
+    pub incremental: Option<IncrementalPlan>,
 }
 
 impl std::ops::Deref for PreparedOperation {
@@ -72,6 +78,10 @@ pub(crate) struct Operation {
     // deduplicated by rule
     pub response_modifiers: Vec<ResponseModifier>,
     pub response_modifier_impacted_fields: Vec<FieldId>,
+    // Whether a selection uses @defer or @stream
+    pub uses_incremental_delivery: bool,
+    // Outermost ones, in the order they appear in the operation.
+    pub deferred_fragments: Vec<DeferredFragment>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -151,6 +161,49 @@ impl PreparedOperation {
         self.plan.field_to_logical_plan_id[usize::from(id)]
     }
 
+    /// Whether the plan only resolves fields of deferred fragments, to be executed after the
+    /// initial response was sent.
+    pub fn is_deferred(&self, id: LogicalPlanId) -> bool {
+        self.incremental
+            .as_ref()
+            .is_some_and(|incremental| incremental.deferred_logical_plans[id])
+    }
+
+    /// Deferred fragments which can only be delivered once the plan is done.
+    pub fn deferred_fragments_led_to_by(&self, id: LogicalPlanId) -> &[DeferredFragmentId] {
+        self.incremental.as_ref().map_or(&[], |incremental| {
+            &incremental.fragment_ids_by_logical_plan[usize::from(id)]
+        })
+    }
+
+    /// Whether all fields are resolved by the introspection, the response then only depends on
+    /// the schema and the variables.
+    pub fn is_introspection_only(&self, schema: &Schema) -> bool {
+        let introspection = schema.walker().introspection_metadata();
+        self.plan
+            .logical_plans
+            .iter()
+            .all(|plan| plan.resolver_id == introspection.resolver_id)
+    }
+
+    /// Surrogate keys of the response: the names of the types whose fields are selected, root
+    /// and introspection types aside. Sorted and deduplicated.
+    pub fn surrogate_keys(&self, schema: &Schema) -> Vec<String> {
+        let root = EntityId::Object(self.operation.root_object_id);
+        let mut keys = self
+            .operation
+            .fields
+            .iter()
+            .filter_map(|field| field.definition_id())
+            .map(|definition_id| schema.walk(definition_id).parent_entity())
+            .filter(|entity| entity.id() != root && !entity.name().starts_with("__"))
+            .map(|entity| entity.name().to_string())
+            .collect::<Vec<_>>();
+        keys.sort_unstable();
+        keys.dedup();
+        keys
+    }
+
     pub fn solved_requirements_for(&self, id: SelectionSetId) -> Option<&SolvedRequiredFieldSet> {
         self.plan
             .solved_requirements
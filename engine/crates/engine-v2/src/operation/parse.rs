@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
 use engine_parser::{
     types::{DocumentOperations, OperationDefinition},
@@ -13,6 +13,8 @@ pub enum ParseError {
     UnknowOperation(String),
     #[error("Missing operation name.")]
     MissingOperationName,
+    #[error("Parsing the operation took longer than the allowed {}ms.", .0.as_millis())]
+    Timeout(Duration),
     #[error(transparent)]
     ParserError(#[from] engine_parser::Error),
 }
@@ -23,8 +25,12 @@ impl From<ParseError> for GraphqlError {
     fn from(err: ParseError) -> Self {
         match err {
             ParseError::ParserError(err) => GraphqlError::new(err.to_string(), ErrorCode::OperationParsingError)
-                .with_locations(err.positions().filter_map(|pos| pos.try_into().ok())),
-            err => GraphqlError::new(err.to_string(), ErrorCode::OperationParsingError),
+                .with_locations(err.positions().filter_map(|pos| pos.try_into().ok()))
+                .with_retryable(false),
+            err @ ParseError::Timeout(_) => {
+                GraphqlError::new(err.to_string(), ErrorCode::QueryParseTimeout).with_retryable(false)
+            }
+            err => GraphqlError::new(err.to_string(), ErrorCode::OperationParsingError).with_retryable(false),
         }
     }
 }
@@ -41,11 +47,27 @@ impl ParsedOperation {
     }
 }
 
-/// Returns a valid GraphQL operation from the query string before.
-pub fn parse_operation(request: &engine::Request) -> ParseResult<ParsedOperation> {
-    let document = engine_parser::parse_query(request.query())?;
+/// Returns a valid GraphQL operation from the query string before. Parsing is aborted once it
+/// exceeds `max_parse_time`, if any.
+pub fn parse_operation(
+    document: &str,
+    operation_name: Option<&str>,
+    max_parse_time: Option<Duration>,
+) -> ParseResult<ParsedOperation> {
+    let document = match max_parse_time {
+        Some(max_parse_time) => {
+            let deadline = web_time::Instant::now() + max_parse_time;
+            engine_parser::parse_query_with_abort_check(document, &|| web_time::Instant::now() >= deadline).map_err(
+                |err| match err {
+                    engine_parser::Error::Aborted => ParseError::Timeout(max_parse_time),
+                    err => ParseError::ParserError(err),
+                },
+            )?
+        }
+        None => engine_parser::parse_query(document)?,
+    };
 
-    let (operation_name, operation) = if let Some(operation_name) = request.operation_name() {
+    let (operation_name, operation) = if let Some(operation_name) = operation_name {
         match document.operations {
             DocumentOperations::Single(_) => None,
             DocumentOperations::Multiple(mut operations) => operations
@@ -0,0 +1,41 @@
+use schema::Schema;
+
+use super::ValidationError;
+
+/// Clients configured with allowed operation prefixes can only execute named operations matching
+/// one of them. Other clients, including anonymous ones, aren't restricted.
+pub(in crate::operation) fn ensure_operation_is_permitted(
+    schema: &Schema,
+    client_name: Option<&str>,
+    operation_name: Option<&str>,
+) -> Result<(), ValidationError> {
+    let Some(client_name) = client_name else {
+        return Ok(());
+    };
+    let Some(prefixes) = schema
+        .settings
+        .clients
+        .get(client_name)
+        .and_then(|client| client.allowed_operation_prefixes.as_ref())
+    else {
+        return Ok(());
+    };
+
+    let Some(operation_name) = operation_name else {
+        return Err(ValidationError::AnonymousOperationNotPermitted {
+            client_name: client_name.to_string(),
+        });
+    };
+
+    if prefixes
+        .iter()
+        .any(|prefix| operation_name.starts_with(prefix.as_str()))
+    {
+        Ok(())
+    } else {
+        Err(ValidationError::OperationNotPermitted {
+            client_name: client_name.to_string(),
+            operation_name: operation_name.to_string(),
+        })
+    }
+}
@@ -1,3 +1,4 @@
+mod allowed_operations;
 mod introspection;
 mod operation_limits;
 
@@ -5,6 +6,7 @@ use crate::{
     operation::{Location, OperationWalker},
     response::{ErrorCode, GraphqlError},
 };
+pub(super) use allowed_operations::ensure_operation_is_permitted;
 use introspection::*;
 use operation_limits::*;
 use schema::Schema;
@@ -15,15 +17,33 @@ pub(crate) enum ValidationError {
     OperationLimitExceeded(#[from] OperationLimitExceededError),
     #[error("GraphQL introspection is not allowed, but the query contained __schema or __type")]
     IntrospectionWhenDisabled { location: Location },
+    #[error("Operation '{operation_name}' is not permitted for client '{client_name}'")]
+    OperationNotPermitted {
+        client_name: String,
+        operation_name: String,
+    },
+    #[error("Anonymous operations are not permitted for client '{client_name}'")]
+    AnonymousOperationNotPermitted { client_name: String },
 }
 
 impl From<ValidationError> for GraphqlError {
     fn from(err: ValidationError) -> Self {
         let locations = match &err {
             ValidationError::IntrospectionWhenDisabled { location } => vec![*location],
-            ValidationError::OperationLimitExceeded { .. } => Vec::new(),
+            _ => Vec::new(),
         };
-        GraphqlError::new(err.to_string(), ErrorCode::OperationValidationError).with_locations(locations)
+        let code = match &err {
+            ValidationError::OperationNotPermitted { .. } | ValidationError::AnonymousOperationNotPermitted { .. } => {
+                ErrorCode::OperationNotPermitted
+            }
+            _ => ErrorCode::OperationValidationError,
+        };
+        GraphqlError::builder()
+            .message(err.to_string())
+            .code(code)
+            .locations(locations)
+            .retryable(false)
+            .build()
     }
 }
 
@@ -11,7 +11,12 @@ use super::ResponsePath;
 #[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
 pub(crate) enum ErrorCode {
     BadRequest,
+    RequestTooLarge,
+    MissingRequiredHeader,
+    NotAcceptable,
     InternalServerError,
+    // The gateway isn't ready or is shutting down.
+    ServiceUnavailable,
     TrustedDocumentError,
     // Used for APQ
     PersistedQueryError,
@@ -19,22 +24,62 @@ pub(crate) enum ErrorCode {
     // Subgraph errors
     SubgraphError,
     SubgraphInvalidResponseError,
+    SubgraphMalformedEntities,
+    SubgraphMissingEntities,
     SubgraphRequestError,
+    SubgraphTypeMismatch,
     // Auth
     Unauthenticated,
     Unauthorized,
     // Operation preparation phases
+    EmptyQuery,
     OperationParsingError,
+    QueryParseTimeout,
     OperationValidationError,
+    VariablesTooComplex,
+    VariablesTooDeep,
     OperationPlanningError,
+    OperationNotPermitted,
+    ClientVersionUnsupported,
+    DuplicateOperationName,
+    FragmentCycle,
+    FieldMergeConflict,
+    TooManySubgraphs,
+    TooManyAliasedFetches,
     // Runtime
     HookError,
+    ExecutionDepthExceeded,
+    EntityLimitExceeded,
+    InvalidEntityRepresentation,
+    ResponseValidationFailed,
+    ResponseSerializationTimeout,
     // Rate limit
     RateLimited,
+    CostBudgetExceeded,
+    TooManySubscriptions,
     // Timeouts
     GatewayTimeout,
 }
 
+impl ErrorCode {
+    /// Whether the error comes from the gateway or the subgraphs failing, rather than from the
+    /// request itself.
+    pub(crate) fn is_execution_failure(self) -> bool {
+        matches!(
+            self,
+            Self::InternalServerError
+                | Self::SubgraphError
+                | Self::SubgraphInvalidResponseError
+                | Self::SubgraphMalformedEntities
+                | Self::SubgraphMissingEntities
+                | Self::SubgraphRequestError
+                | Self::SubgraphTypeMismatch
+                | Self::ResponseSerializationTimeout
+                | Self::GatewayTimeout
+        )
+    }
+}
+
 impl From<PartialErrorCode> for ErrorCode {
     fn from(code: PartialErrorCode) -> Self {
         match code {
@@ -57,6 +102,18 @@ pub(crate) struct GraphqlError {
 }
 
 impl GraphqlError {
+    /// Starts building an error. Both the message and the code must be provided before it can be
+    /// built.
+    pub fn builder() -> GraphqlErrorBuilder<(), ()> {
+        GraphqlErrorBuilder {
+            message: (),
+            code: (),
+            locations: Vec::new(),
+            path: None,
+            extensions: Vec::new(),
+        }
+    }
+
     pub fn new(message: impl Into<Cow<'static, str>>, code: ErrorCode) -> Self {
         GraphqlError {
             message: message.into(),
@@ -92,22 +149,175 @@ impl GraphqlError {
         self.extensions.push((key, value.into()));
         self
     }
+
+    /// Hints clients whether the same request may succeed if sent again.
+    #[must_use]
+    pub fn with_retryable(mut self, retryable: bool) -> Self {
+        self.set_retryable(retryable);
+        self
+    }
+
+    /// Same as [`GraphqlError::with_retryable`], replacing any previous hint.
+    pub fn set_retryable(&mut self, retryable: bool) {
+        self.extensions.retain(|(key, _)| key != "retryable");
+        self.extensions.push(("retryable".into(), retryable.into()));
+    }
 }
 
-impl From<runtime::error::PartialGraphqlError> for GraphqlError {
-    fn from(err: runtime::error::PartialGraphqlError) -> Self {
+#[must_use]
+pub(crate) struct GraphqlErrorBuilder<Message, Code> {
+    message: Message,
+    code: Code,
+    locations: Vec<Location>,
+    path: Option<ResponsePath>,
+    extensions: Vec<(Cow<'static, str>, serde_json::Value)>,
+}
+
+impl<Code> GraphqlErrorBuilder<(), Code> {
+    pub fn message(self, message: impl Into<Cow<'static, str>>) -> GraphqlErrorBuilder<Cow<'static, str>, Code> {
+        GraphqlErrorBuilder {
+            message: message.into(),
+            code: self.code,
+            locations: self.locations,
+            path: self.path,
+            extensions: self.extensions,
+        }
+    }
+}
+
+impl<Message> GraphqlErrorBuilder<Message, ()> {
+    pub fn code(self, code: ErrorCode) -> GraphqlErrorBuilder<Message, ErrorCode> {
+        GraphqlErrorBuilder {
+            message: self.message,
+            code,
+            locations: self.locations,
+            path: self.path,
+            extensions: self.extensions,
+        }
+    }
+}
+
+impl<Message, Code> GraphqlErrorBuilder<Message, Code> {
+    pub fn location(mut self, location: Location) -> Self {
+        self.locations.push(location);
+        self
+    }
+
+    pub fn locations(mut self, locations: impl IntoIterator<Item = Location>) -> Self {
+        self.locations.extend(locations);
+        self
+    }
+
+    pub fn path(mut self, path: ResponsePath) -> Self {
+        self.path = Some(path);
+        self
+    }
+
+    pub fn extension(mut self, key: impl Into<Cow<'static, str>>, value: impl Into<serde_json::Value>) -> Self {
+        let key = key.into();
+        debug_assert!(key != "code");
+        self.extensions.push((key, value.into()));
+        self
+    }
+
+    pub fn retryable(self, retryable: bool) -> Self {
+        self.extension("retryable", retryable)
+    }
+
+    fn extensions(mut self, extensions: Vec<(Cow<'static, str>, serde_json::Value)>) -> Self {
+        self.extensions.extend(extensions);
+        self
+    }
+}
+
+impl GraphqlErrorBuilder<Cow<'static, str>, ErrorCode> {
+    pub fn build(self) -> GraphqlError {
         GraphqlError {
-            message: err.message,
-            code: err.code.into(),
-            extensions: err.extensions,
-            locations: Vec::new(),
-            path: None,
+            message: self.message,
+            code: self.code,
+            locations: self.locations,
+            path: self.path,
+            extensions: self.extensions,
         }
     }
 }
 
+impl From<runtime::error::PartialGraphqlError> for GraphqlError {
+    fn from(err: runtime::error::PartialGraphqlError) -> Self {
+        GraphqlError::builder()
+            .message(err.message)
+            .code(err.code.into())
+            .extensions(err.extensions)
+            .build()
+    }
+}
+
 impl std::fmt::Display for GraphqlError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.message.fmt(f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_sets_message_and_code() {
+        let error = GraphqlError::builder()
+            .message("Something went wrong")
+            .code(ErrorCode::InternalServerError)
+            .build();
+
+        assert_eq!(error.message, "Something went wrong");
+        assert!(matches!(error.code, ErrorCode::InternalServerError));
+        assert!(error.locations.is_empty());
+        assert!(error.path.is_none());
+        assert!(error.extensions.is_empty());
+    }
+
+    #[test]
+    fn builder_order_does_not_matter() {
+        let error = GraphqlError::builder()
+            .code(ErrorCode::SubgraphError)
+            .path(ResponsePath::default())
+            .message(String::from("Subgraph error"))
+            .build();
+
+        assert_eq!(error.message, "Subgraph error");
+        assert!(matches!(error.code, ErrorCode::SubgraphError));
+        assert!(error.path.is_some());
+    }
+
+    #[test]
+    fn builder_collects_locations_and_extensions() {
+        let first = Location::new(1, 2);
+        let second = Location::new(3, 4);
+
+        let error = GraphqlError::builder()
+            .message("Invalid")
+            .location(first)
+            .locations([second])
+            .extension("upstream_path", serde_json::json!(["a", 0]))
+            .code(ErrorCode::SubgraphInvalidResponseError)
+            .build();
+
+        assert_eq!(error.locations, vec![first, second]);
+        assert_eq!(
+            error.extensions,
+            vec![("upstream_path".into(), serde_json::json!(["a", 0]))]
+        );
+    }
+
+    #[test]
+    fn builder_matches_new() {
+        let built = GraphqlError::builder()
+            .message("Gateway timeout")
+            .code(ErrorCode::GatewayTimeout)
+            .build();
+        let new = GraphqlError::new("Gateway timeout", ErrorCode::GatewayTimeout);
+
+        assert_eq!(built.message, new.message);
+        assert_eq!(built.code.as_ref(), new.code.as_ref());
+    }
+}
@@ -0,0 +1,84 @@
+//! Payloads sent after the initial response for deferred fragments, following the GraphQL
+//! incremental delivery format.
+
+use super::{
+    GraphqlError, ResponseData, ResponseEdge, ResponseListId, ResponseObjectId, ResponseValue, UnpackedResponseEdge,
+};
+use crate::operation::DeferredFragmentId;
+
+/// Built from the same response data as the initial response, once the plans leading to its
+/// deferred fragments were executed. Each object at the path of one of the fragments becomes a
+/// payload with the fields of the fragment, see the serialization.
+pub(crate) struct SubsequentResponse {
+    pub(super) data: ResponseData,
+    /// Errors raised since the previous response was sent.
+    pub(super) errors: Vec<GraphqlError>,
+    pub(super) fragment_ids: Vec<DeferredFragmentId>,
+    pub(super) has_next: bool,
+}
+
+impl ResponseData {
+    /// Objects found at the end of a path of response keys, with their path in the response. Lists
+    /// add their indices to the path.
+    pub(super) fn objects_at(&self, keys: &[String]) -> Vec<(Vec<ResponseEdge>, ResponseObjectId)> {
+        let mut objects = Vec::new();
+        if let Some(root) = self.root {
+            self.collect_objects(root, keys, &mut Vec::new(), &mut objects);
+        }
+        objects
+    }
+
+    fn collect_objects(
+        &self,
+        object_id: ResponseObjectId,
+        keys: &[String],
+        path: &mut Vec<ResponseEdge>,
+        objects: &mut Vec<(Vec<ResponseEdge>, ResponseObjectId)>,
+    ) {
+        let Some((key, rest)) = keys.split_first() else {
+            objects.push((path.clone(), object_id));
+            return;
+        };
+        let response_keys = &self.operation.response_keys;
+        let Some(field) = self[object_id].fields().find(|field| {
+            matches!(field.edge.unpack(), UnpackedResponseEdge::BoundResponseKey(bound_key) if response_keys[bound_key] == **key)
+        }) else {
+            return;
+        };
+        path.push(field.edge);
+        self.collect_value_objects(&field.value, rest, path, objects);
+        path.pop();
+    }
+
+    fn collect_value_objects(
+        &self,
+        value: &ResponseValue,
+        keys: &[String],
+        path: &mut Vec<ResponseEdge>,
+        objects: &mut Vec<(Vec<ResponseEdge>, ResponseObjectId)>,
+    ) {
+        match *value {
+            ResponseValue::Object { part_id, index, .. } => {
+                self.collect_objects(ResponseObjectId { part_id, index }, keys, path, objects)
+            }
+            ResponseValue::List {
+                part_id,
+                offset,
+                length,
+                ..
+            } => {
+                let items = &self[ResponseListId {
+                    part_id,
+                    offset,
+                    length,
+                }];
+                for (index, item) in items.iter().enumerate() {
+                    path.push(index.into());
+                    self.collect_value_objects(item, keys, path, objects);
+                    path.pop();
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -2,6 +2,7 @@ use std::{borrow::Cow, sync::Arc};
 
 pub(crate) use error::*;
 use grafbase_telemetry::gql_response_status::GraphqlResponseStatus;
+pub(crate) use incremental::*;
 pub(crate) use key::*;
 pub(crate) use object_set::*;
 pub(crate) use path::*;
@@ -11,9 +12,10 @@ pub(crate) use shape::*;
 pub(crate) use value::*;
 pub(crate) use write::*;
 
-use crate::operation::PreparedOperation;
+use crate::operation::{CanonicalDocument, PreparedOperation};
 
 mod error;
+mod incremental;
 mod key;
 mod object_set;
 mod path;
@@ -29,19 +31,28 @@ pub(crate) enum Response {
     ExecutionFailure(ExecutionFailureResponse),
     /// Invalid request
     PreExecutionError(PreExecutionErrorResponse),
+    /// Deferred fragments, sent after the initial response.
+    Subsequent(SubsequentResponse),
 }
 
 pub(crate) struct InitialResponse {
     // will be None if an error propagated up to the root.
     data: ResponseData,
     errors: Vec<GraphqlError>,
+    // Some of the data was served from expired cache entries.
+    served_stale: bool,
+    // Only set when deferred fragments are sent separately.
+    has_next: Option<bool>,
 }
 
 struct ResponseData {
     schema: Arc<Schema>,
     operation: Arc<PreparedOperation>,
+    // Client the response is sent to, its response transformations are applied during
+    // serialization.
+    client_name: Option<String>,
     root: Option<ResponseObjectId>,
-    parts: Vec<ResponseDataPart>,
+    parts: Vec<Arc<ResponseDataPart>>,
 }
 
 pub(crate) struct PreExecutionErrorResponse {
@@ -93,6 +104,50 @@ impl Response {
             Self::PreExecutionError(resp) => GraphqlResponseStatus::RequestError {
                 count: resp.errors.len() as u64,
             },
+            Self::Subsequent(resp) => {
+                if resp.errors.is_empty() {
+                    GraphqlResponseStatus::Success
+                } else {
+                    GraphqlResponseStatus::FieldError {
+                        count: resp.errors.len() as u64,
+                        data_is_null: false,
+                    }
+                }
+            }
+        }
+    }
+
+    pub(crate) fn has_data(&self) -> bool {
+        matches!(self, Response::Initial(resp) if resp.data.root.is_some())
+    }
+
+    /// Whether more responses follow the initial one.
+    pub(crate) fn with_has_next(mut self, has_next: bool) -> Self {
+        if let Response::Initial(resp) = &mut self {
+            resp.has_next = Some(has_next);
+        }
+        self
+    }
+
+    pub(crate) fn served_stale(&self) -> bool {
+        matches!(self, Response::Initial(resp) if resp.served_stale)
+    }
+
+    /// First error of a response rejecting the request before its execution.
+    pub(crate) fn rejection(&self) -> Option<&GraphqlError> {
+        match self {
+            Response::PreExecutionError(resp) => resp.errors.first(),
+            _ => None,
+        }
+    }
+
+    /// Whether any error is an execution failure, as opposed to errors caused by the request.
+    pub(crate) fn has_execution_failures(&self) -> bool {
+        match self {
+            Response::Initial(resp) => resp.errors.iter().any(|error| error.code.is_execution_failure()),
+            Response::ExecutionFailure(resp) => resp.errors.iter().any(|error| error.code.is_execution_failure()),
+            Response::PreExecutionError(_) => false,
+            Response::Subsequent(resp) => resp.errors.iter().any(|error| error.code.is_execution_failure()),
         }
     }
 
@@ -101,9 +156,24 @@ impl Response {
             Response::Initial(resp) => resp.errors.first(),
             Response::ExecutionFailure(resp) => resp.errors.first(),
             Response::PreExecutionError(resp) => resp.errors.first(),
+            Response::Subsequent(resp) => resp.errors.first(),
         }
         .map(|error| error.message.clone())
     }
+
+    /// Errors of operations built from the canonical document refer to it, rather than to the one
+    /// the client sent.
+    pub(crate) fn restore_original_document(&mut self, document: &CanonicalDocument) {
+        let errors = match self {
+            Response::Initial(resp) => &mut resp.errors,
+            Response::ExecutionFailure(resp) => &mut resp.errors,
+            Response::PreExecutionError(resp) => &mut resp.errors,
+            Response::Subsequent(resp) => &mut resp.errors,
+        };
+        for error in errors {
+            document.restore_error(error);
+        }
+    }
 }
 
 impl std::fmt::Debug for Response {
@@ -0,0 +1,113 @@
+use std::collections::{HashMap, HashSet};
+
+use config::latest::ResponseKeyCasing;
+use schema::Schema;
+
+use crate::{
+    operation::{Field, Operation, QueryField},
+    response::ResponseKey,
+};
+
+/// Response keys of an operation converted to the casing expected by a client. Only keys that are
+/// the name of their field are converted, aliases being chosen by the client itself. `__typename`
+/// is never converted.
+pub(super) struct ResponseKeyConversions {
+    keys: HashMap<ResponseKey, String>,
+}
+
+impl ResponseKeyConversions {
+    pub fn new(schema: &Schema, operation: &Operation, casing: ResponseKeyCasing) -> Self {
+        let mut keys = HashMap::new();
+        let mut aliases = HashSet::new();
+        for field in &operation.fields {
+            let Field::Query(QueryField {
+                bound_response_key,
+                definition_id,
+                ..
+            }) = field
+            else {
+                continue;
+            };
+            let key = bound_response_key.as_response_key();
+            let name = &operation.response_keys[key];
+            if name != schema.walk(*definition_id).name() {
+                aliases.insert(key);
+                continue;
+            }
+            keys.entry(key).or_insert_with(|| match casing {
+                ResponseKeyCasing::SnakeCase => to_snake_case(name),
+                ResponseKeyCasing::CamelCase => to_camel_case(name),
+            });
+        }
+        // A key used both as an alias and as a field name is left untouched everywhere.
+        keys.retain(|key, converted| !aliases.contains(key) && converted.as_str() != &operation.response_keys[*key]);
+        ResponseKeyConversions { keys }
+    }
+
+    pub fn get(&self, key: ResponseKey) -> Option<&str> {
+        self.keys.get(&key).map(String::as_str)
+    }
+}
+
+/// `firstName` -> `first_name`, acronyms being kept together: `userID` -> `user_id`.
+fn to_snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut output = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_uppercase() {
+            output.push(c);
+            continue;
+        }
+        let is_word_start = match i.checked_sub(1).map(|prev| chars[prev]) {
+            Some(prev) if prev.is_lowercase() || prev.is_ascii_digit() => true,
+            Some(prev) if prev.is_uppercase() => chars.get(i + 1).is_some_and(|next| next.is_lowercase()),
+            _ => false,
+        };
+        if is_word_start {
+            output.push('_');
+        }
+        output.extend(c.to_lowercase());
+    }
+    output
+}
+
+/// `first_name` -> `firstName`, leading underscores being kept: `_service` stays as is.
+fn to_camel_case(name: &str) -> String {
+    let mut output = String::with_capacity(name.len());
+    let mut uppercase_next = false;
+    for c in name.chars() {
+        if c == '_' && !output.trim_start_matches('_').is_empty() {
+            uppercase_next = true;
+        } else if uppercase_next {
+            output.extend(c.to_uppercase());
+            uppercase_next = false;
+        } else {
+            output.push(c);
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{to_camel_case, to_snake_case};
+
+    #[test]
+    fn snake_case() {
+        assert_eq!(to_snake_case("firstName"), "first_name");
+        assert_eq!(to_snake_case("userID"), "user_id");
+        assert_eq!(to_snake_case("HTTPServer"), "http_server");
+        assert_eq!(to_snake_case("address2Line"), "address2_line");
+        assert_eq!(to_snake_case("already_snake"), "already_snake");
+        assert_eq!(to_snake_case("_entities"), "_entities");
+    }
+
+    #[test]
+    fn camel_case() {
+        assert_eq!(to_camel_case("first_name"), "firstName");
+        assert_eq!(to_camel_case("user_id"), "userId");
+        assert_eq!(to_camel_case("alreadyCamel"), "alreadyCamel");
+        assert_eq!(to_camel_case("_service"), "_service");
+        assert_eq!(to_camel_case("__typename"), "__typename");
+    }
+}
@@ -1,8 +1,10 @@
 use std::sync::Arc;
 
 use super::{InputdResponseObjectSet, ResponseBuilder};
+mod casing;
 mod selection_set;
 mod ser;
+mod transforms;
 mod view;
 
 use schema::Schema;
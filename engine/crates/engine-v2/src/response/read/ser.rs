@@ -1,11 +1,25 @@
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    rc::Rc,
+    time::Duration,
+};
 
-use serde::ser::{SerializeMap, SerializeSeq};
+use config::latest::NonFiniteFloats;
+use serde::{
+    ser::{Error as _, SerializeMap, SerializeSeq},
+    Serialize,
+};
+use web_time::Instant;
 
-use crate::response::{
-    value::ResponseObjectField, ErrorCode, ExecutionFailureResponse, GraphqlError, InitialResponse,
-    PreExecutionErrorResponse, Response, ResponseData, ResponseKeys, ResponseListId, ResponseObject, ResponseObjectId,
-    ResponsePath, ResponseValue, UnpackedResponseEdge,
+use super::{casing::ResponseKeyConversions, transforms::ResponseTransforms};
+use crate::{
+    operation::{DeferredFragment, DeferredFragmentId},
+    response::{
+        value::ResponseObjectField, ErrorCode, ExecutionFailureResponse, GraphqlError, InitialResponse,
+        PreExecutionErrorResponse, Response, ResponseData, ResponseEdge, ResponseKeys, ResponseListId, ResponseObject,
+        ResponseObjectId, ResponsePath, ResponseValue, SubsequentResponse, UnpackedResponseEdge,
+    },
 };
 
 impl serde::Serialize for Response {
@@ -14,18 +28,44 @@ impl serde::Serialize for Response {
         S: serde::Serializer,
     {
         match self {
-            Response::Initial(InitialResponse { data, errors, .. }) => {
+            Response::Initial(InitialResponse {
+                data, errors, has_next, ..
+            }) => {
                 let mut map = serializer.serialize_map(Some(1))?;
-                map.serialize_entry("data", &SerializableResponseData { data })?;
+                let (state, transforms) = data.serialization_state();
+                map.serialize_entry(
+                    "data",
+                    &SerializableResponseData {
+                        data,
+                        state: &state,
+                        transforms: transforms.as_ref(),
+                    },
+                )?;
+                let DataSerializationState {
+                    key_conversions,
+                    errors: data_errors,
+                    ..
+                } = state;
+                let data_errors = data_errors.into_inner();
+                let errors = if data_errors.is_empty() {
+                    Cow::Borrowed(errors.as_slice())
+                } else {
+                    Cow::Owned(errors.iter().cloned().chain(data_errors).collect())
+                };
                 if !errors.is_empty() {
                     map.serialize_entry(
                         "errors",
                         &SerializableErrors {
                             keys: &data.operation.response_keys,
-                            errors,
+                            key_conversions: key_conversions.as_ref(),
+                            max_path_depth: data.schema.settings.max_error_path_depth,
+                            errors: &errors,
                         },
                     )?;
                 }
+                if let Some(has_next) = has_next {
+                    map.serialize_entry("hasNext", has_next)?;
+                }
                 map.end()
             }
             Response::PreExecutionError(PreExecutionErrorResponse { errors, .. }) => {
@@ -37,6 +77,8 @@ impl serde::Serialize for Response {
                         "errors",
                         &SerializableErrors {
                             keys: &empty_keys,
+                            key_conversions: None,
+                            max_path_depth: None,
                             errors,
                         },
                     )?;
@@ -53,18 +95,227 @@ impl serde::Serialize for Response {
                         "errors",
                         &SerializableErrors {
                             keys: &empty_keys,
+                            key_conversions: None,
+                            max_path_depth: None,
                             errors,
                         },
                     )?;
                 }
                 map.end()
             }
+            Response::Subsequent(SubsequentResponse {
+                data,
+                errors,
+                fragment_ids,
+                has_next,
+            }) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                let (state, transforms) = data.serialization_state();
+                map.serialize_entry(
+                    "incremental",
+                    &SerializableIncrementalPayloads {
+                        data,
+                        state: &state,
+                        transforms: transforms.as_ref(),
+                        fragment_ids,
+                        errors,
+                    },
+                )?;
+                map.serialize_entry("hasNext", has_next)?;
+                map.end()
+            }
+        }
+    }
+}
+
+impl ResponseData {
+    /// Serialization state and response transforms for the client the response is sent to.
+    fn serialization_state(&self) -> (DataSerializationState, Option<ResponseTransforms<'_>>) {
+        let client = self
+            .client_name
+            .as_ref()
+            .and_then(|name| self.schema.settings.clients.get(name));
+        let key_conversions = client
+            .and_then(|client| client.response_key_casing)
+            .map(|casing| ResponseKeyConversions::new(&self.schema, &self.operation, casing));
+        let state = DataSerializationState::new(self.schema.settings.non_finite_floats, key_conversions)
+            .with_max_time(self.schema.settings.max_serialization_time);
+        let transforms = client
+            .filter(|client| !client.response_transforms.is_empty())
+            .map(|client| ResponseTransforms::new(&client.response_transforms));
+        (state, transforms)
+    }
+}
+
+/// Each object at the path of one of the deferred fragments is a payload with the fields of the
+/// fragment. Errors are added to the last payload, after any raised while serializing the data.
+struct SerializableIncrementalPayloads<'a> {
+    data: &'a ResponseData,
+    state: &'a DataSerializationState,
+    transforms: Option<&'a ResponseTransforms<'a>>,
+    fragment_ids: &'a [DeferredFragmentId],
+    errors: &'a [GraphqlError],
+}
+
+struct IncrementalPayload<'a> {
+    fragment: &'a DeferredFragment,
+    edges: Rc<[ResponseEdge]>,
+    // None if the parent of the fragment is null, likely because of an error.
+    object: Option<(ResponsePath, ResponseObjectId)>,
+    transforms: Option<&'a ResponseTransforms<'a>>,
+}
+
+impl<'a> serde::Serialize for SerializableIncrementalPayloads<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let operation = &self.data.operation;
+        let mut payloads = Vec::new();
+        for &fragment_id in self.fragment_ids {
+            let fragment = &operation[fragment_id];
+            let edges: Rc<[ResponseEdge]> = fragment
+                .field_ids
+                .iter()
+                .map(|&field_id| operation[field_id].response_edge())
+                .collect();
+            // Transforms are configured with the keys as requested, list items share the ones of
+            // their list.
+            let transforms = self.transforms.and_then(|transforms| {
+                fragment
+                    .path
+                    .iter()
+                    .try_fold(transforms, |transforms, key| transforms.field(key))
+            });
+            let objects = self.data.objects_at(&fragment.path);
+            if objects.is_empty() {
+                payloads.push(IncrementalPayload {
+                    fragment,
+                    edges,
+                    object: None,
+                    transforms,
+                });
+            } else {
+                payloads.extend(objects.into_iter().map(|(path, object_id)| IncrementalPayload {
+                    fragment,
+                    edges: edges.clone(),
+                    object: Some((ResponsePath::from(path), object_id)),
+                    transforms,
+                }));
+            }
+        }
+
+        let mut seq = serializer.serialize_seq(Some(payloads.len()))?;
+        let last = payloads.len().saturating_sub(1);
+        for (i, payload) in payloads.iter().enumerate() {
+            seq.serialize_element(&SerializableIncrementalPayload {
+                data: self.data,
+                state: self.state,
+                payload,
+                errors: (i == last).then_some(self.errors),
+            })?;
+        }
+        seq.end()
+    }
+}
+
+struct SerializableIncrementalPayload<'a> {
+    data: &'a ResponseData,
+    state: &'a DataSerializationState,
+    payload: &'a IncrementalPayload<'a>,
+    errors: Option<&'a [GraphqlError]>,
+}
+
+impl<'a> serde::Serialize for SerializableIncrementalPayload<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let IncrementalPayload {
+            fragment,
+            edges,
+            object,
+            transforms,
+        } = self.payload;
+        let keys = &self.data.operation.response_keys;
+        let mut map = serializer.serialize_map(None)?;
+        match object {
+            Some((path, object_id)) => {
+                map.serialize_entry(
+                    "data",
+                    &SerializableSelectedFields {
+                        object: SerializableResponseObject {
+                            data: self.data,
+                            state: self.state,
+                            path: ValuePath::Root(path),
+                            object: &self.data[*object_id],
+                            transforms: *transforms,
+                        },
+                        edges,
+                    },
+                )?;
+                map.serialize_entry(
+                    "path",
+                    &SerializableResponsePath {
+                        keys,
+                        key_conversions: self.state.key_conversions.as_ref(),
+                        max_depth: None,
+                        path,
+                    },
+                )?;
+            }
+            None => {
+                map.serialize_entry("data", &serde_json::Value::Null)?;
+                map.serialize_entry("path", &fragment.path)?;
+            }
         }
+        if let Some(label) = &fragment.label {
+            map.serialize_entry("label", label)?;
+        }
+        if let Some(errors) = self.errors {
+            let data_errors = self.state.errors.borrow();
+            let errors = if data_errors.is_empty() {
+                Cow::Borrowed(errors)
+            } else {
+                Cow::Owned(errors.iter().chain(data_errors.iter()).cloned().collect())
+            };
+            if !errors.is_empty() {
+                map.serialize_entry(
+                    "errors",
+                    &SerializableErrors {
+                        keys,
+                        key_conversions: self.state.key_conversions.as_ref(),
+                        max_path_depth: self.data.schema.settings.max_error_path_depth,
+                        errors: &errors,
+                    },
+                )?;
+            }
+        }
+        map.end()
+    }
+}
+
+struct SerializableSelectedFields<'a> {
+    object: SerializableResponseObject<'a>,
+    edges: &'a [ResponseEdge],
+}
+
+impl<'a> serde::Serialize for SerializableSelectedFields<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        self.object
+            .serialize_selected_fields(&mut map, |edge| self.edges.contains(&edge))?;
+        map.end()
     }
 }
 
 struct SerializableErrors<'a> {
     keys: &'a ResponseKeys,
+    key_conversions: Option<&'a ResponseKeyConversions>,
+    max_path_depth: Option<usize>,
     errors: &'a [GraphqlError],
 }
 
@@ -75,7 +326,12 @@ impl<'a> serde::Serialize for SerializableErrors<'a> {
     {
         let mut seq = serializer.serialize_seq(Some(self.errors.len()))?;
         for error in self.errors {
-            seq.serialize_element(&SerializableError { keys: self.keys, error })?;
+            seq.serialize_element(&SerializableError {
+                keys: self.keys,
+                key_conversions: self.key_conversions,
+                max_path_depth: self.max_path_depth,
+                error,
+            })?;
         }
         seq.end()
     }
@@ -83,6 +339,8 @@ impl<'a> serde::Serialize for SerializableErrors<'a> {
 
 struct SerializableError<'a> {
     keys: &'a ResponseKeys,
+    key_conversions: Option<&'a ResponseKeyConversions>,
+    max_path_depth: Option<usize>,
     error: &'a GraphqlError,
 }
 
@@ -106,7 +364,15 @@ impl<'a> serde::Serialize for SerializableError<'a> {
             map.serialize_entry("locations", &self.error.locations)?;
         }
         if let Some(ref path) = self.error.path {
-            map.serialize_entry("path", &SerializableResponsePath { keys: self.keys, path })?;
+            map.serialize_entry(
+                "path",
+                &SerializableResponsePath {
+                    keys: self.keys,
+                    key_conversions: self.key_conversions,
+                    max_depth: self.max_path_depth,
+                    path,
+                },
+            )?;
         }
         map.serialize_entry(
             "extensions",
@@ -141,8 +407,13 @@ impl<'a> serde::Serialize for SerializableExtension<'a> {
     }
 }
 
+/// Written at the end of paths cut at their maximum depth.
+const TRUNCATED_PATH_INDICATOR: &str = "…";
+
 struct SerializableResponsePath<'a> {
     keys: &'a ResponseKeys,
+    key_conversions: Option<&'a ResponseKeyConversions>,
+    max_depth: Option<usize>,
     path: &'a ResponsePath,
 }
 
@@ -151,27 +422,130 @@ impl<'a> serde::Serialize for SerializableResponsePath<'a> {
     where
         S: serde::Serializer,
     {
-        let mut seq = serializer.serialize_seq(Some(self.path.len()))?;
-        for edge in self.path.iter() {
+        let depth = self.max_depth.map_or(self.path.len(), |max| max.min(self.path.len()));
+        let is_truncated = depth < self.path.len();
+        let mut seq = serializer.serialize_seq(Some(depth + is_truncated as usize))?;
+        for edge in self.path.iter().take(depth) {
             match edge.unpack() {
                 UnpackedResponseEdge::Index(index) => seq.serialize_element(&index)?,
                 // for requrest errors, keys will be empty. There shouldn't be any path within
                 // those errors to begin with, but just in case better to output somthing than
                 // crashing.
                 UnpackedResponseEdge::BoundResponseKey(key) => {
-                    seq.serialize_element(&self.keys.try_resolve(key.as_response_key()).unwrap_or("<unknown>"))?
+                    let key = key.as_response_key();
+                    let converted = self.key_conversions.and_then(|conversions| conversions.get(key));
+                    seq.serialize_element(&converted.or(self.keys.try_resolve(key)).unwrap_or("<unknown>"))?
                 }
                 UnpackedResponseEdge::ExtraFieldResponseKey(key) => {
                     seq.serialize_element(&self.keys.try_resolve(key).unwrap_or("<unknown>"))?
                 }
             }
         }
+        if is_truncated {
+            seq.serialize_element(TRUNCATED_PATH_INDICATOR)?;
+        }
         seq.end()
     }
 }
 
+/// Number of values serialized between two reads of the clock when serialization has a time
+/// budget.
+const DEADLINE_CHECK_INTERVAL: u32 = 256;
+
+/// State kept while serializing the response data. Values that can't be represented in JSON may
+/// add errors, which are serialized after the data.
+struct DataSerializationState {
+    non_finite_floats: NonFiniteFloats,
+    key_conversions: Option<ResponseKeyConversions>,
+    errors: RefCell<Vec<GraphqlError>>,
+    max_time: Option<(Instant, Duration)>,
+    values_since_check: Cell<u32>,
+    timed_out: Cell<bool>,
+}
+
+impl DataSerializationState {
+    fn new(non_finite_floats: NonFiniteFloats, key_conversions: Option<ResponseKeyConversions>) -> Self {
+        Self {
+            non_finite_floats,
+            key_conversions,
+            errors: Default::default(),
+            max_time: None,
+            values_since_check: Cell::new(0),
+            timed_out: Cell::new(false),
+        }
+    }
+
+    /// Starts the serialization time budget, if any.
+    fn with_max_time(mut self, max_time: Option<Duration>) -> Self {
+        self.max_time = max_time.map(|max_time| (Instant::now() + max_time, max_time));
+        self
+    }
+
+    /// Whether the time budget ran out, in which case the remaining fields and list items are
+    /// left out of the response. An error is added the first time.
+    fn is_out_of_time(&self) -> bool {
+        let Some((deadline, max_time)) = self.max_time else {
+            return false;
+        };
+        if self.timed_out.get() {
+            return true;
+        }
+
+        let values = self.values_since_check.get() + 1;
+        if values < DEADLINE_CHECK_INTERVAL {
+            self.values_since_check.set(values);
+            return false;
+        }
+        self.values_since_check.set(0);
+        if Instant::now() < deadline {
+            return false;
+        }
+
+        self.timed_out.set(true);
+        self.errors.borrow_mut().push(
+            GraphqlError::new(
+                format!(
+                    "Serializing the response took longer than the allowed {}ms, the data was truncated.",
+                    max_time.as_millis()
+                ),
+                ErrorCode::ResponseSerializationTimeout,
+            )
+            .with_retryable(false),
+        );
+        true
+    }
+}
+
+/// Path of the value being serialized. Each level only keeps a reference to its parent on the
+/// stack, the path being collected only when an error needs it.
+#[derive(Clone, Copy)]
+enum ValuePath<'a> {
+    Root(&'a [ResponseEdge]),
+    Edge(&'a ValuePath<'a>, ResponseEdge),
+}
+
+impl ValuePath<'_> {
+    fn to_response_path(self) -> ResponsePath {
+        let mut edges = Vec::new();
+        let mut current = self;
+        let root = loop {
+            match current {
+                ValuePath::Root(root) => break root,
+                ValuePath::Edge(parent, edge) => {
+                    edges.push(edge);
+                    current = *parent;
+                }
+            }
+        };
+        edges.reverse();
+        root.iter().copied().chain(edges).collect::<Vec<_>>().into()
+    }
+}
+
 struct SerializableResponseData<'a> {
     data: &'a ResponseData,
+    state: &'a DataSerializationState,
+    transforms: Option<&'a ResponseTransforms<'a>>,
 }
 
 impl<'a> serde::Serialize for SerializableResponseData<'a> {
@@ -184,7 +558,10 @@ impl<'a> serde::Serialize for SerializableResponseData<'a> {
             .as_ref()
             .map(|root_id| SerializableResponseObject {
                 data: self.data,
+                state: self.state,
+                path: ValuePath::Root(&[]),
                 object: &self.data[*root_id],
+                transforms: self.transforms,
             })
             .serialize(serializer)
     }
@@ -192,7 +569,10 @@ impl<'a> serde::Serialize for SerializableResponseData<'a> {
 
 struct SerializableResponseObject<'a> {
     data: &'a ResponseData,
+    state: &'a DataSerializationState,
+    path: ValuePath<'a>,
     object: &'a ResponseObject,
+    transforms: Option<&'a ResponseTransforms<'a>>,
 }
 
 impl<'a> serde::Serialize for SerializableResponseObject<'a> {
@@ -200,52 +580,94 @@ impl<'a> serde::Serialize for SerializableResponseObject<'a> {
     where
         S: serde::Serializer,
     {
-        let mut map = serializer.serialize_map(Some(self.object.len()))?;
+        // Flattened fields change the number of entries.
+        let size_hint = if self.transforms.is_some() {
+            None
+        } else {
+            Some(self.object.len())
+        };
+        let mut map = serializer.serialize_map(size_hint)?;
+        self.serialize_fields(&mut map)?;
+        map.end()
+    }
+}
+
+impl<'a> SerializableResponseObject<'a> {
+    fn serialize_fields<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        self.serialize_selected_fields(map, |_| true)
+    }
+
+    fn serialize_selected_fields<M: SerializeMap>(
+        &self,
+        map: &mut M,
+        is_selected: impl Fn(ResponseEdge) -> bool,
+    ) -> Result<(), M::Error> {
         let keys = &self.data.operation.response_keys;
         // Thanks to the BoundResponseKey starting with the position and the fields being a BTreeMap
         // we're ensuring the fields are serialized in the order they appear in the query.
         for ResponseObjectField { edge, value, .. } in self.object.fields() {
-            let UnpackedResponseEdge::BoundResponseKey(key) = edge.unpack() else {
+            let UnpackedResponseEdge::BoundResponseKey(bound_key) = edge.unpack() else {
                 // Bound response keys are always first, anything after are extra fields which
                 // don't need to be serialized.
                 break;
             };
-            map.serialize_key(&keys[key])?;
-            match value {
-                ResponseValue::Null => map.serialize_value(&())?,
-                ResponseValue::Boolean { value, .. } => map.serialize_value(value)?,
-                ResponseValue::Int { value, .. } => map.serialize_value(value)?,
-                ResponseValue::Float { value, .. } => map.serialize_value(value)?,
-                ResponseValue::String { value, .. } => map.serialize_value(&value)?,
-                ResponseValue::StringId { id, .. } => map.serialize_value(&self.data.schema[*id])?,
-                ResponseValue::BigInt { value, .. } => map.serialize_value(value)?,
-                &ResponseValue::List {
-                    part_id,
-                    offset,
-                    length,
-                    ..
-                } => map.serialize_value(&SerializableResponseList {
-                    data: self.data,
-                    value: &self.data[ResponseListId {
-                        part_id,
-                        offset,
-                        length,
-                    }],
-                })?,
-                &ResponseValue::Object { part_id, index, .. } => map.serialize_value(&SerializableResponseObject {
-                    data: self.data,
-                    object: &self.data[ResponseObjectId { part_id, index }],
-                })?,
-                ResponseValue::Json { value, .. } => map.serialize_value(value)?,
+            if !is_selected(*edge) {
+                continue;
+            }
+            if self.state.is_out_of_time() {
+                break;
+            }
+            // Transforms are configured with the keys as requested, before any casing conversion.
+            let key: &str = &keys[bound_key];
+            let transforms = self.transforms.and_then(|transforms| transforms.field(key));
+            let path = ValuePath::Edge(&self.path, *edge);
+            if let Some(transforms) = transforms.filter(|transforms| transforms.flatten) {
+                if let &ResponseValue::Object { part_id, index, .. } = value {
+                    SerializableResponseObject {
+                        data: self.data,
+                        state: self.state,
+                        path,
+                        object: &self.data[ResponseObjectId { part_id, index }],
+                        transforms: Some(transforms),
+                    }
+                    .serialize_fields(map)?;
+                    continue;
+                }
+            }
+
+            let converted_key = self
+                .state
+                .key_conversions
+                .as_ref()
+                .and_then(|conversions| conversions.get(bound_key.as_response_key()));
+            map.serialize_key(
+                transforms
+                    .and_then(|transforms| transforms.rename)
+                    .or(converted_key)
+                    .unwrap_or(key),
+            )?;
+            let value = SerializableResponseValue {
+                data: self.data,
+                state: self.state,
+                path,
+                value,
+                transforms,
+            };
+            match transforms.and_then(|transforms| transforms.wrap) {
+                Some(key) => map.serialize_value(&SerializableWrappedValue { key, value })?,
+                None => map.serialize_value(&value)?,
             }
         }
-        map.end()
+        Ok(())
     }
 }
 
 struct SerializableResponseList<'a> {
     data: &'a ResponseData,
+    state: &'a DataSerializationState,
+    path: ValuePath<'a>,
     value: &'a [ResponseValue],
+    transforms: Option<&'a ResponseTransforms<'a>>,
 }
 
 impl<'a> serde::Serialize for SerializableResponseList<'a> {
@@ -254,37 +676,199 @@ impl<'a> serde::Serialize for SerializableResponseList<'a> {
         S: serde::Serializer,
     {
         let mut seq = serializer.serialize_seq(Some(self.value.len()))?;
-        for node in self.value {
-            match node {
-                ResponseValue::Null => seq.serialize_element(&())?,
-                ResponseValue::Boolean { value, .. } => seq.serialize_element(value)?,
-                ResponseValue::Int { value, .. } => seq.serialize_element(value)?,
-                ResponseValue::Float { value, .. } => seq.serialize_element(value)?,
-                ResponseValue::String { value, .. } => seq.serialize_element(&value)?,
-                ResponseValue::StringId { id, .. } => seq.serialize_element(&self.data.schema[*id])?,
-                ResponseValue::BigInt { value, .. } => seq.serialize_element(value)?,
-                &ResponseValue::List {
+        for (index, value) in self.value.iter().enumerate() {
+            if self.state.is_out_of_time() {
+                break;
+            }
+            seq.serialize_element(&SerializableResponseValue {
+                data: self.data,
+                state: self.state,
+                path: ValuePath::Edge(&self.path, index.into()),
+                value,
+                transforms: self.transforms,
+            })?;
+        }
+        seq.end()
+    }
+}
+
+struct SerializableResponseValue<'a> {
+    data: &'a ResponseData,
+    state: &'a DataSerializationState,
+    path: ValuePath<'a>,
+    value: &'a ResponseValue,
+    transforms: Option<&'a ResponseTransforms<'a>>,
+}
+
+impl<'a> serde::Serialize for SerializableResponseValue<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self.value {
+            ResponseValue::Null => serializer.serialize_unit(),
+            ResponseValue::Boolean { value, .. } => value.serialize(serializer),
+            ResponseValue::Int { value, .. } => value.serialize(serializer),
+            &ResponseValue::Float { value, .. } => SerializableFloat {
+                state: self.state,
+                path: self.path,
+                value,
+            }
+            .serialize(serializer),
+            ResponseValue::String { value, .. } => value.serialize(serializer),
+            ResponseValue::StringId { id, .. } => self.data.schema[*id].serialize(serializer),
+            ResponseValue::BigInt { value, .. } => value.serialize(serializer),
+            ResponseValue::U64 { value, .. } => value.serialize(serializer),
+            &ResponseValue::List {
+                part_id,
+                offset,
+                length,
+                ..
+            } => SerializableResponseList {
+                data: self.data,
+                state: self.state,
+                path: self.path,
+                value: &self.data[ResponseListId {
                     part_id,
                     offset,
                     length,
-                    ..
-                } => seq.serialize_element(&SerializableResponseList {
-                    data: self.data,
-                    value: &self.data[ResponseListId {
-                        part_id,
-                        offset,
-                        length,
-                    }],
-                })?,
-                &ResponseValue::Object { part_id, index, .. } => {
-                    seq.serialize_element(&SerializableResponseObject {
-                        data: self.data,
-                        object: &self.data[ResponseObjectId { part_id, index }],
-                    })?
-                }
-                ResponseValue::Json { value, .. } => seq.serialize_element(value)?,
+                }],
+                transforms: self.transforms,
             }
+            .serialize(serializer),
+            &ResponseValue::Object { part_id, index, .. } => SerializableResponseObject {
+                data: self.data,
+                state: self.state,
+                path: self.path,
+                object: &self.data[ResponseObjectId { part_id, index }],
+                transforms: self.transforms,
+            }
+            .serialize(serializer),
+            ResponseValue::Json { value, .. } => value.serialize(serializer),
         }
-        seq.end()
+    }
+}
+
+struct SerializableWrappedValue<'a> {
+    key: &'a str,
+    value: SerializableResponseValue<'a>,
+}
+
+impl<'a> serde::Serialize for SerializableWrappedValue<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(self.key, &self.value)?;
+        map.end()
+    }
+}
+
+struct SerializableFloat<'a> {
+    state: &'a DataSerializationState,
+    path: ValuePath<'a>,
+    value: f64,
+}
+
+impl<'a> serde::Serialize for SerializableFloat<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if self.value.is_finite() {
+            return serializer.serialize_f64(self.value);
+        }
+
+        let repr = if self.value.is_nan() {
+            "NaN"
+        } else if self.value.is_sign_positive() {
+            "Infinity"
+        } else {
+            "-Infinity"
+        };
+
+        match self.state.non_finite_floats {
+            NonFiniteFloats::Null => {
+                self.state.errors.borrow_mut().push(
+                    GraphqlError::new(
+                        format!("Float value {repr} cannot be represented in JSON"),
+                        ErrorCode::InternalServerError,
+                    )
+                    .with_path(self.path.to_response_path()),
+                );
+                serializer.serialize_unit()
+            }
+            NonFiniteFloats::String => serializer.serialize_str(repr),
+            NonFiniteFloats::Error => Err(S::Error::custom(format!(
+                "Float value {repr} cannot be represented in JSON"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use config::latest::NonFiniteFloats;
+
+    use super::{DataSerializationState, SerializableFloat, ValuePath};
+    use crate::response::ResponseEdge;
+
+    fn serialize(policy: NonFiniteFloats, value: f64) -> (Result<String, serde_json::Error>, DataSerializationState) {
+        let state = DataSerializationState::new(policy, None);
+        let result = serde_json::to_string(&SerializableFloat {
+            state: &state,
+            path: ValuePath::Root(&[]),
+            value,
+        });
+        (result, state)
+    }
+
+    #[test]
+    fn nan_as_null() {
+        let (result, state) = serialize(NonFiniteFloats::Null, f64::NAN);
+
+        assert_eq!(result.unwrap(), "null");
+        let errors = state.errors.into_inner();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message, "Float value NaN cannot be represented in JSON");
+    }
+
+    #[test]
+    fn value_path_is_collected_from_the_root() {
+        let root = [ResponseEdge::from(0)];
+        let parent = ValuePath::Root(&root);
+        let child = ValuePath::Edge(&parent, 1.into());
+        let path = ValuePath::Edge(&child, 2.into()).to_response_path();
+
+        assert_eq!(*path, [0, 1, 2].map(ResponseEdge::from));
+    }
+
+    #[test]
+    fn nan_as_string() {
+        let (result, state) = serialize(NonFiniteFloats::String, f64::NAN);
+        assert_eq!(result.unwrap(), r#""NaN""#);
+        assert!(state.errors.into_inner().is_empty());
+
+        let (result, _) = serialize(NonFiniteFloats::String, f64::NEG_INFINITY);
+        assert_eq!(result.unwrap(), r#""-Infinity""#);
+    }
+
+    #[test]
+    fn nan_as_error() {
+        let (result, state) = serialize(NonFiniteFloats::Error, f64::NAN);
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Float value NaN cannot be represented in JSON"
+        );
+        assert!(state.errors.into_inner().is_empty());
+    }
+
+    #[test]
+    fn finite_floats_are_unaffected() {
+        let (result, state) = serialize(NonFiniteFloats::Error, 1.5);
+        assert_eq!(result.unwrap(), "1.5");
+        assert!(state.errors.into_inner().is_empty());
     }
 }
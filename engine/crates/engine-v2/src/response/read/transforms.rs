@@ -0,0 +1,41 @@
+use std::collections::HashMap;
+
+use config::latest::ResponseTransform;
+
+/// Response transformations of a client, organized as a tree of response keys following their
+/// path. Each node holds the transformations of the field at its path.
+#[derive(Default)]
+pub(super) struct ResponseTransforms<'a> {
+    pub rename: Option<&'a str>,
+    pub wrap: Option<&'a str>,
+    pub flatten: bool,
+    children: HashMap<&'a str, ResponseTransforms<'a>>,
+}
+
+impl<'a> ResponseTransforms<'a> {
+    pub fn new(transforms: &'a [ResponseTransform]) -> Self {
+        let mut root = Self::default();
+        for transform in transforms {
+            let path = match transform {
+                ResponseTransform::Rename { path, .. }
+                | ResponseTransform::Flatten { path }
+                | ResponseTransform::Wrap { path, .. } => path,
+            };
+            let node = path
+                .iter()
+                .fold(&mut root, |node, key| node.children.entry(key.as_str()).or_default());
+            match transform {
+                ResponseTransform::Rename { to, .. } => node.rename = Some(to.as_str()),
+                ResponseTransform::Flatten { .. } => node.flatten = true,
+                ResponseTransform::Wrap { key, .. } => node.wrap = Some(key.as_str()),
+            }
+        }
+        root
+    }
+
+    /// Transformations of the field with the given response key, within the object at the
+    /// current node.
+    pub fn field(&self, key: &str) -> Option<&Self> {
+        self.children.get(key)
+    }
+}
@@ -108,6 +108,7 @@ impl<'de> serde::Deserializer<'de> for ResponseValueWalker<'de> {
             ResponseValue::Boolean { value, .. } => visitor.visit_bool(*value),
             ResponseValue::Int { value, .. } => visitor.visit_i32(*value),
             ResponseValue::BigInt { value, .. } => visitor.visit_i64(*value),
+            ResponseValue::U64 { value, .. } => visitor.visit_u64(*value),
             ResponseValue::Float { value, .. } => visitor.visit_f64(*value),
             ResponseValue::String { value, .. } => visitor.visit_borrowed_str(value),
             ResponseValue::StringId { id, .. } => visitor.visit_borrowed_str(&self.ctx.schema[*id]),
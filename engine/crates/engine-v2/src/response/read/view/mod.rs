@@ -3,7 +3,7 @@ mod ser;
 
 use std::sync::Arc;
 
-use schema::Schema;
+use schema::{RequiredFieldId, Schema};
 
 use super::{ResponseViewSelectionSet, ResponseViews};
 use crate::response::{InputdResponseObjectSet, ResponseBuilder, ResponseObject, ResponseValue};
@@ -99,6 +99,21 @@ pub(crate) struct ResponseObjectWithExtraFieldsWalker<'a> {
     extra_constant_fields: &'a [(String, serde_json::Value)],
 }
 
+impl<'a> ResponseObjectWithExtraFieldsWalker<'a> {
+    /// Name of the first of the given fields which is absent or null in the object.
+    pub fn find_missing_field(&self, ids: &[RequiredFieldId]) -> Option<&'a str> {
+        self.ctx.response_views[self.selection_set]
+            .iter()
+            .filter(|selection| ids.contains(&selection.id))
+            .find(|selection| {
+                self.response_object
+                    .find_required_field(selection.id)
+                    .map_or(true, ResponseValue::is_null)
+            })
+            .map(|selection| self.ctx.schema[selection.name].as_str())
+    }
+}
+
 struct ResponseValueWalker<'a> {
     ctx: ViewContext<'a>,
     value: &'a ResponseValue,
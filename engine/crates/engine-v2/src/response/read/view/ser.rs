@@ -92,6 +92,7 @@ impl<'a> serde::Serialize for ResponseValueWalker<'a> {
             ResponseValue::String { value, .. } => value.serialize(serializer),
             ResponseValue::StringId { id, .. } => self.ctx.schema[*id].serialize(serializer),
             ResponseValue::BigInt { value, .. } => value.serialize(serializer),
+            ResponseValue::U64 { value, .. } => value.serialize(serializer),
             &ResponseValue::List {
                 part_id,
                 offset,
@@ -5,7 +5,7 @@ use super::{ResponseDataPartId, ResponseEdge, ResponseListId, ResponseObjectId};
 // Threshold defined a bit arbitrarily
 pub const NULL: ResponseValue = ResponseValue::Null;
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub(crate) struct ResponseObject {
     /// fields are ordered by the position they appear in the query.
     /// We use ResponseEdge here, but it'll never be an index out of the 3 possible variants.
@@ -103,6 +103,11 @@ pub(crate) enum ResponseValue {
         value: i64,
         nullable: bool,
     },
+    // BigInt above i64::MAX
+    U64 {
+        value: u64,
+        nullable: bool,
+    },
     Float {
         value: f64,
         nullable: bool,
@@ -146,6 +151,7 @@ impl ResponseValue {
             Self::Boolean { nullable, .. } => *nullable = true,
             Self::Int { nullable, .. } => *nullable = true,
             Self::BigInt { nullable, .. } => *nullable = true,
+            Self::U64 { nullable, .. } => *nullable = true,
             Self::Float { nullable, .. } => *nullable = true,
             Self::String { nullable, .. } => *nullable = true,
             Self::StringId { nullable, .. } => *nullable = true,
@@ -190,6 +196,12 @@ impl From<i64> for ResponseValue {
     }
 }
 
+impl From<u64> for ResponseValue {
+    fn from(value: u64) -> Self {
+        Self::U64 { value, nullable: false }
+    }
+}
+
 impl From<f64> for ResponseValue {
     fn from(value: f64) -> Self {
         Self::Float { value, nullable: false }
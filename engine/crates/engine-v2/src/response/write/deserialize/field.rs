@@ -1,9 +1,12 @@
-use schema::{ListWrapping, Wrapping};
-use serde::de::DeserializeSeed;
+use schema::{ListWrapping, ScalarType, Wrapping};
+use serde::{
+    de::{DeserializeSeed, IgnoredAny},
+    Deserialize,
+};
 
 use super::{
     object::{ConcreteObjectSeed, PolymorphicObjectSeed},
-    ListSeed, NullableSeed, ScalarTypeSeed, SeedContext,
+    ListSeed, NullableSeed, ScalarTypeSeed, SeedContext, StringLengthLimit,
 };
 use crate::response::{ErrorCode, FieldShape, GraphqlError, ResponseValue, Shape};
 
@@ -14,12 +17,55 @@ pub(super) struct FieldSeed<'ctx, 'parent> {
     pub wrapping: Wrapping,
 }
 
+impl FieldSeed<'_, '_> {
+    fn scalar_seed(&self, ty: ScalarType) -> ScalarTypeSeed {
+        let seed = ScalarTypeSeed::new(ty, self.ctx.lenient_scalar_coercion);
+        if ty == ScalarType::JSON {
+            // Custom scalars are JSON, unless a format was configured for them.
+            let format = self
+                .ctx
+                .plan
+                .schema()
+                .walk(self.field.definition_id)
+                .ty()
+                .inner()
+                .as_scalar()
+                .and_then(|scalar| scalar.format());
+            return seed.with_format(format);
+        }
+        if ty != ScalarType::String {
+            return seed;
+        }
+        // The field's own limit takes precedence over the global one.
+        let max = self
+            .ctx
+            .plan
+            .schema()
+            .walk(self.field.definition_id)
+            .directives()
+            .max_string_length()
+            .or(self.ctx.max_string_length);
+        seed.with_max_string_length(max.map(|max| StringLengthLimit {
+            max,
+            truncate: self.ctx.truncate_oversized_strings,
+        }))
+    }
+}
+
 impl<'de, 'ctx, 'parent> DeserializeSeed<'de> for FieldSeed<'ctx, 'parent> {
     type Value = ResponseValue;
     fn deserialize<D>(mut self, deserializer: D) -> Result<Self::Value, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
+        // List items go through this seed too, so they're checked individually.
+        if let Some(max_depth) = self.ctx.exceeded_execution_depth(0) {
+            IgnoredAny::deserialize(deserializer)?;
+            return self.ctx.abort_on_execution_depth(max_depth, self.field.id);
+        }
+
+        // List items are deserialized with this seed too, with their own wrapping.
+        let is_field_value = self.wrapping == self.field.wrapping;
         let result = if let Some(list_wrapping) = self.wrapping.pop_list_wrapping() {
             let list_seed = ListSeed {
                 ctx: self.ctx,
@@ -37,7 +83,7 @@ impl<'de, 'ctx, 'parent> DeserializeSeed<'de> for FieldSeed<'ctx, 'parent> {
             }
         } else if self.wrapping.inner_is_required() {
             match self.field.shape {
-                Shape::Scalar(ty) => ScalarTypeSeed(ty).deserialize(deserializer),
+                Shape::Scalar(ty) => self.scalar_seed(ty).deserialize(deserializer),
                 Shape::ConcreteObject(shape_id) => {
                     ConcreteObjectSeed::new(self.ctx, shape_id).deserialize(deserializer)
                 }
@@ -50,7 +96,7 @@ impl<'de, 'ctx, 'parent> DeserializeSeed<'de> for FieldSeed<'ctx, 'parent> {
                 Shape::Scalar(ty) => NullableSeed {
                     ctx: self.ctx,
                     field_id: self.field.id,
-                    seed: ScalarTypeSeed(ty),
+                    seed: self.scalar_seed(ty),
                 }
                 .deserialize(deserializer),
                 Shape::ConcreteObject(shape_id) => NullableSeed {
@@ -68,15 +114,47 @@ impl<'de, 'ctx, 'parent> DeserializeSeed<'de> for FieldSeed<'ctx, 'parent> {
             }
         };
 
+        // JSON values are nested within the response like any other.
+        if let Ok(ResponseValue::Json { value, .. }) = &result {
+            if let Some(max_depth) = self.ctx.exceeded_execution_depth(json_depth(value)) {
+                return self.ctx.abort_on_execution_depth(max_depth, self.field.id);
+            }
+        }
+
+        if is_field_value && matches!(result, Ok(ResponseValue::Null)) {
+            let definition = self.ctx.plan.schema().walk(self.field.definition_id);
+            // Only fields with a nullability override are nullable in the response shape while
+            // being required in the schema.
+            if definition.ty().wrapping().is_required() {
+                tracing::warn!(
+                    "Subgraph returned null for the non-null field {}.{}, tolerated by its nullability override",
+                    definition.parent_entity().name(),
+                    definition.name()
+                );
+            }
+        }
+
         result.map_err(move |err| {
             if self.ctx.should_create_new_graphql_error() {
                 self.ctx.writer.push_error(
-                    GraphqlError::new(err.to_string(), ErrorCode::SubgraphInvalidResponseError)
-                        .with_location(self.ctx.plan[self.field.id].location())
-                        .with_path(self.ctx.response_path()),
+                    GraphqlError::builder()
+                        .message(err.to_string())
+                        .code(ErrorCode::SubgraphInvalidResponseError)
+                        .location(self.ctx.plan[self.field.id].location())
+                        .path(self.ctx.response_path())
+                        .build(),
                 );
             }
             err
         })
     }
 }
+
+/// Number of lists and objects nested within the value, serde_json bounds the recursion.
+fn json_depth(value: &serde_json::Value) -> usize {
+    match value {
+        serde_json::Value::Array(items) => 1 + items.iter().map(json_depth).max().unwrap_or(0),
+        serde_json::Value::Object(fields) => 1 + fields.values().map(json_depth).max().unwrap_or(0),
+        _ => 0,
+    }
+}
@@ -69,9 +69,12 @@ where
                         let mut path = self.ctx.response_path();
                         path.push(index);
                         self.ctx.writer.push_error(
-                            GraphqlError::new(err.to_string(), ErrorCode::SubgraphInvalidResponseError)
-                                .with_location(self.ctx.plan[self.field_id].location())
-                                .with_path(path),
+                            GraphqlError::builder()
+                                .message(err.to_string())
+                                .code(ErrorCode::SubgraphInvalidResponseError)
+                                .location(self.ctx.plan[self.field_id].location())
+                                .path(path)
+                                .build(),
                         );
                     }
                     // Discarding the rest of the sequence.
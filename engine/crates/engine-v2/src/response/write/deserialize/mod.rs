@@ -11,6 +11,7 @@ use serde::{
 
 use crate::{
     execution::{ExecutableOperation, PlanWalker},
+    operation::FieldId,
     response::{ErrorCode, FieldShape, GraphqlError, ResponseEdge, ResponsePath, ResponseWriter},
 };
 
@@ -31,6 +32,10 @@ pub struct SeedContext<'ctx> {
     writer: ResponseWriter<'ctx>,
     propagating_error: Cell<bool>,
     path: RefCell<Vec<ResponseEdge>>,
+    max_execution_depth: Option<usize>,
+    lenient_scalar_coercion: bool,
+    max_string_length: Option<usize>,
+    truncate_oversized_strings: bool,
 }
 
 impl<'ctx> SeedContext<'ctx> {
@@ -38,6 +43,17 @@ impl<'ctx> SeedContext<'ctx> {
         let path = RefCell::new(writer.root_path().iter().copied().collect());
         Self {
             operation: plan.operation(),
+            max_execution_depth: plan.schema().settings.max_execution_depth.map(usize::from),
+            max_string_length: plan.schema().settings.max_string_length,
+            truncate_oversized_strings: matches!(
+                plan.schema().settings.oversized_strings,
+                config::latest::OversizedStringPolicy::Truncate
+            ),
+            lenient_scalar_coercion: plan
+                .logical_plan()
+                .resolver()
+                .graphql_endpoint()
+                .is_some_and(|endpoint| endpoint.lenient_scalar_coercion()),
             plan,
             writer,
             propagating_error: Cell::new(false),
@@ -76,6 +92,28 @@ impl<'ctx> SeedContext<'ctx> {
         ResponsePath::from(self.path.borrow().clone())
     }
 
+    /// Returns the configured maximum execution depth if a value `nested_depth` levels below the
+    /// current one lies beyond it. Every field and list item from the root of the response counts,
+    /// including those leading to the objects this subgraph response is written into.
+    fn exceeded_execution_depth(&self, nested_depth: usize) -> Option<usize> {
+        let max_depth = self.max_execution_depth?;
+        (self.path.borrow().len() + nested_depth > max_depth).then_some(max_depth)
+    }
+
+    /// The whole request is aborted, so the error propagates without creating any other one and
+    /// the rest of the subgraph response is only read to be discarded.
+    fn abort_on_execution_depth<V, E: serde::de::Error>(&self, max_depth: usize, field_id: FieldId) -> Result<V, E> {
+        self.writer.abort_request(
+            GraphqlError::builder()
+                .message(format!("Execution depth exceeds the configured maximum of {max_depth}"))
+                .code(ErrorCode::ExecutionDepthExceeded)
+                .location(self.plan[field_id].location())
+                .path(self.response_path())
+                .build(),
+        );
+        self.propagate_error()
+    }
+
     fn should_create_new_graphql_error(&self) -> bool {
         let is_propagating = self.propagating_error.get();
         self.propagating_error.set(true);
@@ -104,6 +142,36 @@ impl<'ctx> UpdateSeed<'ctx> {
             ctx: SeedContext::new(plan, writer),
         }
     }
+
+    /// `data: null` without any errors is a legitimate null unless the subgraph is configured to
+    /// reject it, in which case it's an invalid response if any requested field is non-null.
+    pub fn null_data_without_errors(self) {
+        let UpdateSeed { ctx } = self;
+        let blueprint = &ctx.operation.response_blueprint;
+        let shape = &blueprint[ctx.plan.logical_plan().response_blueprint().concrete_shape_id];
+        let first_required_edge = blueprint[shape.field_shape_ids]
+            .iter()
+            .filter(|field| field.wrapping.is_required())
+            .map(|field| field.edge)
+            .min();
+        let error_on_null_data = ctx
+            .plan
+            .logical_plan()
+            .resolver()
+            .graphql_endpoint()
+            .is_some_and(|endpoint| endpoint.error_on_null_data());
+
+        match first_required_edge {
+            Some(edge) if error_on_null_data => ctx.writer.propagate_error(
+                GraphqlError::new(
+                    "Subgraph returned null data without any errors",
+                    ErrorCode::SubgraphInvalidResponseError,
+                )
+                .with_path(ctx.writer.root_path().child(edge)),
+            ),
+            _ => ctx.writer.null_data(),
+        }
+    }
 }
 
 impl<'de, 'ctx> DeserializeSeed<'de> for UpdateSeed<'ctx> {
@@ -64,9 +64,12 @@ where
             Err(err) => {
                 if self.ctx.stop_propagating_and_should_create_new_graphql_error() {
                     self.ctx.writer.push_error(
-                        GraphqlError::new(err.to_string(), ErrorCode::SubgraphInvalidResponseError)
-                            .with_location(self.ctx.plan[self.field_id].location())
-                            .with_path(self.ctx.response_path()),
+                        GraphqlError::builder()
+                            .message(err.to_string())
+                            .code(ErrorCode::SubgraphInvalidResponseError)
+                            .location(self.ctx.plan[self.field_id].location())
+                            .path(self.ctx.response_path())
+                            .build(),
                     );
                 }
                 Ok(ResponseValue::Null)
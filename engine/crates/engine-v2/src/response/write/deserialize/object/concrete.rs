@@ -7,8 +7,8 @@ use serde::de::{DeserializeSeed, IgnoredAny, MapAccess, Visitor};
 use crate::response::{
     value::ResponseObjectField,
     write::deserialize::{field::FieldSeed, key::Key, SeedContext},
-    ConcreteObjectShapeId, FieldShape, FieldShapeId, GraphqlError, ObjectIdentifier, ResponseEdge, ResponseObject,
-    ResponseObjectRef, ResponseObjectSetId, ResponseValue,
+    ConcreteObjectShapeId, FieldShape, FieldShapeId, ObjectIdentifier, ResponseEdge, ResponseObject, ResponseObjectRef,
+    ResponseObjectSetId, ResponseValue,
 };
 
 pub(crate) struct ConcreteObjectSeed<'ctx, 'seed> {
@@ -197,10 +197,9 @@ impl<'de, 'ctx, 'seed> ConcreteObjectFieldsSeed<'ctx, 'seed> {
                     let mut path = self.ctx.response_path();
                     path.push(field_shape.edge);
 
-                    self.ctx.writer.push_error(GraphqlError {
-                        path: Some(path),
-                        ..self.ctx.operation[error_id].clone()
-                    });
+                    self.ctx
+                        .writer
+                        .push_error(self.ctx.operation[error_id].clone().with_path(path));
 
                     if field_shape.wrapping.is_required() {
                         required_field_error = true;
@@ -222,10 +221,8 @@ impl<'de, 'ctx, 'seed> ConcreteObjectFieldsSeed<'ctx, 'seed> {
             let field_shapes = &self.ctx.operation.response_blueprint[self.field_shape_ids];
             let n = response_fields.len();
             for field_shape in field_shapes {
-                if response_fields[0..n]
-                    .binary_search_by(|field| field.edge.cmp(&field.edge))
-                    .is_err()
-                {
+                // Fields are in the order of the subgraph response, not necessarily sorted.
+                if !response_fields[0..n].iter().any(|field| field.edge == field_shape.edge) {
                     if field_shape.wrapping.is_required() {
                         return Err(serde::de::Error::custom(
                             self.ctx.missing_field_error_message(field_shape),
@@ -272,6 +269,9 @@ impl<'de, 'ctx, 'seed> ConcreteObjectFieldsSeed<'ctx, 'seed> {
                     .binary_search_by(|probe| schema[schema[*probe].name].as_str().cmp(typename))
                     .ok();
             } else {
+                if key != "__typename" {
+                    self.ctx.writer.record_extra_field();
+                }
                 // Skipping the value.
                 map.next_value::<IgnoredAny>()?;
             }
@@ -305,6 +305,9 @@ impl<'de, 'ctx, 'seed> ConcreteObjectFieldsSeed<'ctx, 'seed> {
             {
                 self.visit_field(map, fields, response_fields)?;
             } else {
+                if key != "__typename" {
+                    self.ctx.writer.record_extra_field();
+                }
                 // Skipping the value.
                 map.next_value::<IgnoredAny>()?;
             }
@@ -1,11 +1,11 @@
 use std::{collections::VecDeque, fmt};
 
-use schema::ObjectId;
+use schema::{Definition, ObjectId};
 use serde::de::{DeserializeSeed, IgnoredAny, MapAccess, Visitor};
 
 use crate::response::{
     write::deserialize::{key::Key, SeedContext},
-    ConcreteObjectShapeId, PolymorphicObjectShapeId, ResponseObject, ResponseValue,
+    ConcreteObjectShapeId, ErrorCode, GraphqlError, PolymorphicObjectShapeId, ResponseObject, ResponseValue,
 };
 
 use super::concrete::ConcreteObjectSeed;
@@ -71,6 +71,28 @@ impl<'de, 'ctx, 'parent> Visitor<'de> for PolymorphicObjectSeed<'ctx, 'parent> {
                 // Discarding the rest of the data if it does not match any concrete shape
                 while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
 
+                let endpoint = self.ctx.plan.logical_plan().resolver().graphql_endpoint();
+                // Possibilities cover every possible type of the field, so a known object is one
+                // that doesn't belong to the interface or union. A subgraph bug.
+                if matches!(schema.definition_by_name(typename), Some(Definition::Object(_))) {
+                    if endpoint.is_some_and(|endpoint| endpoint.error_on_type_mismatch()) {
+                        if self.ctx.should_create_new_graphql_error() {
+                            let message = format!(
+                                "Subgraph returned an object of type '{typename}' which isn't a possible type of the field"
+                            );
+                            self.ctx.writer.push_error(
+                                GraphqlError::new(message, ErrorCode::SubgraphTypeMismatch)
+                                    .with_path(self.ctx.response_path()),
+                            );
+                        }
+                        return self.ctx.propagate_error();
+                    }
+                } else if endpoint.is_some_and(|endpoint| endpoint.error_on_unknown_typename()) {
+                    return Err(serde::de::Error::custom(format!(
+                        "Unknown __typename '{typename}', it isn't a possible type of the field"
+                    )));
+                }
+
                 // Adding empty object instead
                 return Ok(self
                     .ctx
@@ -1,9 +1,48 @@
+use std::fmt;
+
+use config::latest::ScalarFormat;
 use schema::ScalarType;
-use serde::{de::DeserializeSeed, Deserialize};
+use serde::{
+    de::{DeserializeSeed, Unexpected, Visitor},
+    Deserialize,
+};
+
+use crate::{response::ResponseValue, utils::truncate_at_char_boundary};
+
+pub(crate) struct ScalarTypeSeed {
+    ty: ScalarType,
+    lenient: bool,
+    max_string_length: Option<StringLengthLimit>,
+    format: Option<ScalarFormat>,
+}
 
-use crate::response::ResponseValue;
+impl ScalarTypeSeed {
+    pub fn new(ty: ScalarType, lenient: bool) -> Self {
+        Self {
+            ty,
+            lenient,
+            max_string_length: None,
+            format: None,
+        }
+    }
 
-pub(crate) struct ScalarTypeSeed(pub ScalarType);
+    pub fn with_format(mut self, format: Option<ScalarFormat>) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn with_max_string_length(mut self, limit: Option<StringLengthLimit>) -> Self {
+        self.max_string_length = limit;
+        self
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct StringLengthLimit {
+    /// In bytes.
+    pub max: usize,
+    pub truncate: bool,
+}
 
 impl<'de> DeserializeSeed<'de> for ScalarTypeSeed {
     type Value = ResponseValue;
@@ -12,13 +51,299 @@ impl<'de> DeserializeSeed<'de> for ScalarTypeSeed {
     where
         D: serde::Deserializer<'de>,
     {
-        match self.0 {
-            ScalarType::String => String::deserialize(deserializer).map(Into::into),
+        match self.ty {
+            ScalarType::String => StringSeed {
+                limit: self.max_string_length,
+            }
+            .deserialize(deserializer)
+            .map(Into::into),
             ScalarType::Float => f64::deserialize(deserializer).map(Into::into),
             ScalarType::Int => i32::deserialize(deserializer).map(Into::into),
-            ScalarType::BigInt => i64::deserialize(deserializer).map(Into::into),
-            ScalarType::JSON => Box::<serde_json::Value>::deserialize(deserializer).map(Into::into),
-            ScalarType::Boolean => bool::deserialize(deserializer).map(Into::into),
+            ScalarType::BigInt => BigIntSeed.deserialize(deserializer),
+            ScalarType::JSON => match self.format {
+                Some(format) => FormattedStringSeed { format }.deserialize(deserializer).map(Into::into),
+                None => Box::<serde_json::Value>::deserialize(deserializer).map(Into::into),
+            },
+            ScalarType::Boolean => BooleanSeed { lenient: self.lenient }
+                .deserialize(deserializer)
+                .map(Into::into),
+        }
+    }
+}
+
+const TRUNCATION_INDICATOR: &str = "…[truncated]";
+
+/// Enforces the maximum length of a string, if any, either truncating it at the closest char
+/// boundary with an indicator appended or rejecting it.
+struct StringSeed {
+    limit: Option<StringLengthLimit>,
+}
+
+impl<'de> DeserializeSeed<'de> for StringSeed {
+    type Value = String;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut value = String::deserialize(deserializer)?;
+        let Some(StringLengthLimit { max, truncate }) = self.limit else {
+            return Ok(value);
+        };
+        if value.len() <= max {
+            return Ok(value);
+        }
+        if !truncate {
+            return Err(serde::de::Error::custom(format!(
+                "String value of {} bytes exceeds the maximum length of {max} bytes",
+                value.len()
+            )));
+        }
+        truncate_at_char_boundary(&mut value, max, TRUNCATION_INDICATOR);
+        Ok(value)
+    }
+}
+
+/// Custom scalars with a configured format must be strings matching it.
+struct FormattedStringSeed {
+    format: ScalarFormat,
+}
+
+impl<'de> DeserializeSeed<'de> for FormattedStringSeed {
+    type Value = String;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        let (is_valid, expected) = match self.format {
+            ScalarFormat::DateTime => (is_rfc3339_date_time(&value), "an RFC 3339 date-time"),
+            ScalarFormat::Uuid => (is_uuid(&value), "a UUID"),
+        };
+        if !is_valid {
+            return Err(serde::de::Error::custom(format!("Value {value:?} is not {expected}")));
+        }
+        Ok(value)
+    }
+}
+
+/// `YYYY-MM-DDTHH:MM:SS`, with optional fractional seconds, followed by `Z` or a `±HH:MM` offset.
+fn is_rfc3339_date_time(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    let number = |start: usize, len: usize| -> Option<u32> {
+        bytes.get(start..start + len)?.iter().try_fold(0, |acc, byte| {
+            byte.is_ascii_digit().then(|| acc * 10 + u32::from(byte - b'0'))
+        })
+    };
+    let (Some(year), Some(month), Some(day), Some(hour), Some(minute), Some(second)) = (
+        number(0, 4),
+        number(5, 2),
+        number(8, 2),
+        number(11, 2),
+        number(14, 2),
+        number(17, 2),
+    ) else {
+        return false;
+    };
+    if bytes[4] != b'-'
+        || bytes[7] != b'-'
+        || !matches!(bytes[10], b'T' | b't')
+        || bytes[13] != b':'
+        || bytes[16] != b':'
+    {
+        return false;
+    }
+
+    let is_leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year => 29,
+        2 => 28,
+        _ => return false,
+    };
+    // 60 seconds for leap seconds.
+    if day == 0 || day > days_in_month || hour > 23 || minute > 59 || second > 60 {
+        return false;
+    }
+
+    let mut rest = &bytes[19..];
+    if let Some(fraction) = rest.strip_prefix(b".") {
+        let digits = fraction.iter().take_while(|byte| byte.is_ascii_digit()).count();
+        if digits == 0 {
+            return false;
         }
+        rest = &fraction[digits..];
+    }
+    match rest {
+        [b'Z' | b'z'] => true,
+        [b'+' | b'-', h1, h2, b':', m1, m2] => {
+            [h1, h2, m1, m2].iter().all(|byte| byte.is_ascii_digit())
+                && (h1 - b'0') * 10 + (h2 - b'0') <= 23
+                && (m1 - b'0') * 10 + (m2 - b'0') <= 59
+        }
+        _ => false,
+    }
+}
+
+/// Hyphenated form, `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx`, in either case.
+fn is_uuid(value: &str) -> bool {
+    value.len() == 36
+        && value.bytes().enumerate().all(|(index, byte)| match index {
+            8 | 13 | 18 | 23 => byte == b'-',
+            _ => byte.is_ascii_hexdigit(),
+        })
+}
+
+/// BigInt values are backed by an `i64`, or an `u64` above `i64::MAX`. Integers
+/// beyond those are rejected with an explicit error rather than the generic one of serde.
+struct BigIntSeed;
+
+impl<'de> DeserializeSeed<'de> for BigIntSeed {
+    type Value = ResponseValue;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_i64(self)
+    }
+}
+
+impl<'de> Visitor<'de> for BigIntSeed {
+    type Value = ResponseValue;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a BigInt")
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(v.into())
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(match i64::try_from(v) {
+            Ok(v) => v.into(),
+            Err(_) => v.into(),
+        })
+    }
+
+    // Integers within range are never parsed as floats, so a float within range had a fraction or
+    // an exponent and isn't a BigInt. Beyond it, integers are only parsed as floats and may have
+    // been rounded to the bounds: `i64::MIN` is exactly -2^63 and `u64::MAX as f64` is rounded up
+    // to 2^64.
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        if v.is_finite() && v.fract() == 0.0 && (v <= i64::MIN as f64 || v >= u64::MAX as f64) {
+            return Err(E::custom(format!("BigInt value {v} out of range")));
+        }
+        Err(E::invalid_type(Unexpected::Float(v), &self))
+    }
+}
+
+/// In lenient mode, also accepts `0`/`1` and `"0"`/`"1"` as booleans.
+struct BooleanSeed {
+    lenient: bool,
+}
+
+impl<'de> DeserializeSeed<'de> for BooleanSeed {
+    type Value = bool;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        if self.lenient {
+            deserializer.deserialize_any(self)
+        } else {
+            bool::deserialize(deserializer)
+        }
+    }
+}
+
+impl<'de> Visitor<'de> for BooleanSeed {
+    type Value = bool;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a boolean, 0 or 1")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(v)
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        match v {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(E::invalid_value(Unexpected::Unsigned(v), &self)),
+        }
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        match v {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(E::invalid_value(Unexpected::Signed(v), &self)),
+        }
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        match v {
+            "0" => Ok(false),
+            "1" => Ok(true),
+            _ => Err(E::invalid_value(Unexpected::Str(v), &self)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_rfc3339_date_time, is_uuid};
+
+    #[test]
+    fn rfc3339_date_times() {
+        assert!(is_rfc3339_date_time("2024-05-17T09:30:00Z"));
+        assert!(is_rfc3339_date_time("2024-02-29T23:59:60.123+02:00"));
+        assert!(is_rfc3339_date_time("2024-05-17t09:30:00-05:30"));
+
+        assert!(!is_rfc3339_date_time("2024-05-17"));
+        assert!(!is_rfc3339_date_time("2024-05-17 09:30:00Z"));
+        assert!(!is_rfc3339_date_time("2023-02-29T09:30:00Z"));
+        assert!(!is_rfc3339_date_time("2024-13-01T09:30:00Z"));
+        assert!(!is_rfc3339_date_time("2024-05-17T24:00:00Z"));
+        assert!(!is_rfc3339_date_time("2024-05-17T09:30:00"));
+        assert!(!is_rfc3339_date_time("2024-05-17T09:30:00.Z"));
+        assert!(!is_rfc3339_date_time("2024-05-17T09:30:00+0200"));
+    }
+
+    #[test]
+    fn uuids() {
+        assert!(is_uuid("123e4567-e89b-12d3-a456-426614174000"));
+        assert!(is_uuid("123E4567-E89B-12D3-A456-426614174000"));
+
+        assert!(!is_uuid("123e4567e89b12d3a456426614174000"));
+        assert!(!is_uuid("123e4567-e89b-12d3-a456-42661417400g"));
     }
 }
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use super::{ResponseBuilder, ResponseDataPart};
 use crate::response::{ResponseData, ResponseObject, ResponseValue};
 
@@ -47,7 +49,7 @@ impl std::ops::Index<ResponseObjectId> for ResponseBuilder {
 
 impl std::ops::IndexMut<ResponseObjectId> for ResponseBuilder {
     fn index_mut(&mut self, index: ResponseObjectId) -> &mut Self::Output {
-        &mut self.parts[usize::from(index.part_id)].objects[index.index as usize]
+        &mut Arc::make_mut(&mut self.parts[usize::from(index.part_id)]).objects[index.index as usize]
     }
 }
 
@@ -61,7 +63,7 @@ impl std::ops::Index<ResponseListId> for ResponseBuilder {
 
 impl std::ops::IndexMut<ResponseListId> for ResponseBuilder {
     fn index_mut(&mut self, index: ResponseListId) -> &mut Self::Output {
-        &mut self.parts[usize::from(index.part_id)][index]
+        &mut Arc::make_mut(&mut self.parts[usize::from(index.part_id)])[index]
     }
 }
 
@@ -1,5 +1,6 @@
 mod deserialize;
 mod ids;
+mod validation;
 
 use std::{
     cell::{Ref, RefCell, RefMut},
@@ -12,18 +13,19 @@ pub use ids::*;
 use itertools::Either;
 use schema::{ObjectId, Schema};
 
-use self::deserialize::UpdateSeed;
+pub(crate) use self::deserialize::UpdateSeed;
 
 use super::{
     value::ResponseObjectField, ErrorCode, GraphqlError, InitialResponse, InputdResponseObjectSet,
     OutputResponseObjectSets, Response, ResponseData, ResponseEdge, ResponseObject, ResponseObjectRef,
-    ResponseObjectSet, ResponseObjectSetId, ResponsePath, ResponseValue, UnpackedResponseEdge,
+    ResponseObjectSet, ResponseObjectSetId, ResponsePath, ResponseValue, SubsequentResponse, UnpackedResponseEdge,
 };
 use crate::{
     execution::{ExecutionError, PlanWalker},
-    operation::PreparedOperation,
+    operation::{DeferredFragmentId, PreparedOperation},
 };
 
+#[derive(Clone)]
 pub(crate) struct ResponseDataPart {
     id: ResponseDataPartId,
     objects: Vec<ResponseObject>,
@@ -47,8 +49,13 @@ impl ResponseDataPart {
 pub(crate) struct ResponseBuilder {
     // will be None if an error propagated up to the root.
     pub(super) root: Option<(ResponseObjectId, ObjectId)>,
-    parts: Vec<ResponseDataPart>,
+    // Shared with the snapshots sent before the end of the execution, copied on write.
+    parts: Vec<Arc<ResponseDataPart>>,
     errors: Vec<GraphqlError>,
+    // Whether any subgraph response came from an expired cache entry.
+    served_stale: bool,
+    // Aborts the whole request, it's the only error returned and data is null.
+    request_error: Option<GraphqlError>,
 }
 
 // Only supporting additions for the current graph. Deletion are... tricky
@@ -66,8 +73,16 @@ impl ResponseBuilder {
         let root_id = initial_part.push_object(ResponseObject::default());
         Self {
             root: Some((root_id, root_object_id)),
-            parts: vec![initial_part],
+            parts: vec![Arc::new(initial_part)],
             errors: Vec::new(),
+            served_stale: false,
+            request_error: None,
+        }
+    }
+
+    pub fn insert_root_fields(&mut self, fields: Vec<ResponseObjectField>) {
+        if let Some((root_id, _)) = self.root {
+            self[root_id].extend(fields);
         }
     }
 
@@ -93,7 +108,7 @@ impl ResponseBuilder {
         // reserving the spot until the actual data is written. It's safe as no one can reference
         // any data in this part before it's added. And a part can only be overwritten if it's
         // empty.
-        self.parts.push(ResponseDataPart::new(id));
+        self.parts.push(Arc::new(ResponseDataPart::new(id)));
         SubgraphResponse::new(
             ResponseDataPart::new(id),
             root_response_object_set,
@@ -143,9 +158,19 @@ impl ResponseBuilder {
         any_edge: ResponseEdge,
         default_fields: Option<Vec<ResponseObjectField>>,
     ) -> OutputResponseObjectSets {
+        if let Some(error) = subgraph_response.request_error {
+            self.request_error.get_or_insert(error);
+            self.root = None;
+            return OutputResponseObjectSets {
+                ids: subgraph_response.tracked_response_object_set_ids,
+                sets: Vec::new(),
+            };
+        }
+
         let reservation = &mut self.parts[usize::from(subgraph_response.data.id)];
         assert!(reservation.is_empty(), "Part already has data");
-        *reservation = subgraph_response.data;
+        *reservation = Arc::new(subgraph_response.data);
+        self.served_stale |= subgraph_response.stale;
 
         let mut invalidated_paths = Vec::<&[ResponseEdge]>::new();
         for (update, obj_ref) in subgraph_response
@@ -202,6 +227,15 @@ impl ResponseBuilder {
                 UpdateSlot::Fields(fields) => {
                     self[obj_ref.id].extend(fields);
                 }
+                UpdateSlot::NullData => {
+                    if let Some(fields) = &default_fields {
+                        self[obj_ref.id].extend(fields.clone());
+                    } else if !invalidated_paths.iter().any(|path| obj_ref.path.starts_with(path)) {
+                        if let Some(invalidated_path) = self.propagate_error(&obj_ref.path) {
+                            invalidated_paths.push(invalidated_path);
+                        }
+                    }
+                }
                 UpdateSlot::Error => {
                     if !invalidated_paths.iter().any(|path| obj_ref.path.starts_with(path)) {
                         if let Some(invalidated_path) = self.propagate_error(&obj_ref.path) {
@@ -230,15 +264,89 @@ impl ResponseBuilder {
         }
     }
 
-    pub fn build(self, schema: Arc<Schema>, operation: Arc<PreparedOperation>) -> Response {
+    /// Whether a subgraph response aborted the request, nothing else needs to be executed.
+    pub fn is_aborted(&self) -> bool {
+        self.request_error.is_some()
+    }
+
+    /// Response with the data written so far, execution can continue afterwards. Parts are
+    /// shared with the snapshot, only the ones written to afterwards are copied. Errors are moved
+    /// into it, so that each one is only sent once.
+    pub fn snapshot(
+        &mut self,
+        schema: Arc<Schema>,
+        operation: Arc<PreparedOperation>,
+        client_name: Option<String>,
+    ) -> Response {
+        Response::Initial(InitialResponse {
+            data: self.snapshot_data(schema, operation, client_name),
+            errors: self.take_errors(),
+            served_stale: self.served_stale,
+            has_next: None,
+        })
+    }
+
+    /// Same as [`ResponseBuilder::snapshot`] for the payloads of deferred fragments, with the
+    /// errors raised since the previous snapshot.
+    pub fn subsequent_snapshot(
+        &mut self,
+        schema: Arc<Schema>,
+        operation: Arc<PreparedOperation>,
+        client_name: Option<String>,
+        fragment_ids: Vec<DeferredFragmentId>,
+        has_next: bool,
+    ) -> Response {
+        Response::Subsequent(SubsequentResponse {
+            data: self.snapshot_data(schema, operation, client_name),
+            errors: self.take_errors(),
+            fragment_ids,
+            has_next,
+        })
+    }
+
+    fn snapshot_data(
+        &self,
+        schema: Arc<Schema>,
+        operation: Arc<PreparedOperation>,
+        client_name: Option<String>,
+    ) -> ResponseData {
+        ResponseData {
+            schema,
+            operation,
+            client_name,
+            root: self.root.map(|(id, _)| id),
+            parts: self.parts.clone(),
+        }
+    }
+
+    fn take_errors(&mut self) -> Vec<GraphqlError> {
+        // An aborted request only keeps the error which aborted it.
+        match &self.request_error {
+            Some(error) => vec![error.clone()],
+            None => std::mem::take(&mut self.errors),
+        }
+    }
+
+    pub fn build(
+        self,
+        schema: Arc<Schema>,
+        operation: Arc<PreparedOperation>,
+        client_name: Option<String>,
+    ) -> Response {
         Response::Initial(InitialResponse {
             data: ResponseData {
                 schema,
                 operation,
+                client_name,
                 root: self.root.map(|(id, _)| id),
                 parts: self.parts,
             },
-            errors: self.errors,
+            errors: match self.request_error {
+                Some(error) => vec![error],
+                None => self.errors,
+            },
+            served_stale: self.served_stale,
+            has_next: None,
         })
     }
 
@@ -350,6 +458,12 @@ pub(crate) struct SubgraphResponse {
     updates: Vec<UpdateSlot>,
     tracked_response_object_set_ids: IdRange<ResponseObjectSetId>,
     tracked_response_object_sets: Vec<ResponseObjectSet>,
+    /// Number of fields present in the subgraph response which weren't requested.
+    extra_fields_count: usize,
+    /// Whether the data comes from an expired cache entry, served because the subgraph failed.
+    stale: bool,
+    /// Error aborting the whole request, the data is discarded if present.
+    request_error: Option<GraphqlError>,
 }
 
 impl SubgraphResponse {
@@ -368,6 +482,9 @@ impl SubgraphResponse {
                 .into_iter()
                 .map(|_| (Vec::new()))
                 .collect(),
+            extra_fields_count: 0,
+            stale: false,
+            request_error: None,
         }
     }
 
@@ -387,6 +504,36 @@ impl SubgraphResponse {
             )
         })
     }
+
+    /// Errors sent by a subgraph answering with a server error status may go away once it
+    /// recovers.
+    pub fn mark_subgraph_errors_retryable(&mut self) {
+        for error in self.errors.iter_mut().chain(self.request_error.as_mut()) {
+            if matches!(
+                error.code,
+                ErrorCode::SubgraphError | ErrorCode::SubgraphInvalidResponseError | ErrorCode::SubgraphRequestError
+            ) {
+                error.set_retryable(true);
+            }
+        }
+    }
+
+    pub fn extra_fields_count(&self) -> usize {
+        self.extra_fields_count
+    }
+
+    pub fn mark_stale(&mut self) {
+        self.stale = true;
+    }
+
+    /// Reserves the slots of the root objects up to `count`, which have no update. Those are
+    /// reported as missing data, like the ones a subgraph didn't return.
+    pub fn reserve_updates_up_to(&mut self, count: usize) {
+        let count = count.min(self.root_response_object_set.len());
+        while self.updates.len() < count {
+            self.updates.push(UpdateSlot::Reserved);
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -472,10 +619,23 @@ impl<'resp> ResponseWriter<'resp> {
         self.part().updates[self.index] = UpdateSlot::Error;
     }
 
+    pub fn null_data(&self) {
+        self.part().updates[self.index] = UpdateSlot::NullData;
+    }
+
     pub fn push_error(&self, error: impl Into<GraphqlError>) {
         self.part().errors.push(error.into());
     }
 
+    /// Only the first error is kept, the request is aborted once the subgraph response is ingested.
+    pub fn abort_request(&self, error: impl Into<GraphqlError>) {
+        self.part().request_error.get_or_insert_with(|| error.into());
+    }
+
+    pub fn record_extra_field(&self) {
+        self.part().extra_fields_count += 1;
+    }
+
     pub fn push_response_object(&self, set_id: ResponseObjectSetId, obj: ResponseObjectRef) {
         let mut part = self.part();
         let i = part
@@ -490,4 +650,6 @@ enum UpdateSlot {
     Reserved,
     Fields(Vec<ResponseObjectField>),
     Error,
+    /// The subgraph legitimately returned null for the whole object.
+    NullData,
 }
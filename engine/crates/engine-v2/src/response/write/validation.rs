@@ -0,0 +1,156 @@
+use schema::{FieldDefinitionWalker, Schema, Wrapping};
+
+use super::ResponseBuilder;
+use crate::{
+    execution::ExecutableOperation,
+    operation::{Field, QueryField, SelectionSetId, SelectionSetType},
+    response::{ErrorCode, GraphqlError, ResponseEdge, ResponseListId, ResponseObjectId, ResponsePath, ResponseValue},
+};
+
+struct Violation {
+    /// Path of the object missing the field or of the value holding the null, where the error
+    /// propagation starts.
+    parent_path: ResponsePath,
+    error: GraphqlError,
+}
+
+impl ResponseBuilder {
+    /// Subgraph responses are validated individually while being deserialized, this checks that
+    /// the merged data still honors the non-null fields of the schema. Violations are reported
+    /// with their path and propagated like any other error.
+    pub fn validate_nullability(&mut self, schema: &Schema, operation: &ExecutableOperation) {
+        let Some((root_id, root_object_id)) = self.root else {
+            return;
+        };
+
+        let mut validator = NullabilityValidator {
+            schema,
+            operation,
+            response: self,
+            violations: Vec::new(),
+        };
+        validator.validate_object(
+            root_id,
+            SelectionSetType::Object(root_object_id),
+            operation.root_selection_set_id,
+            ResponsePath::default(),
+        );
+
+        for Violation { parent_path, error } in validator.violations {
+            // Violations within data already nulled by a previous one are irrelevant.
+            if self.propagate_error(&parent_path).is_some() {
+                self.errors.push(error);
+            }
+        }
+    }
+}
+
+struct NullabilityValidator<'a> {
+    schema: &'a Schema,
+    operation: &'a ExecutableOperation,
+    response: &'a ResponseBuilder,
+    violations: Vec<Violation>,
+}
+
+impl<'a> NullabilityValidator<'a> {
+    fn validate_object(
+        &mut self,
+        object_id: ResponseObjectId,
+        ty: SelectionSetType,
+        selection_set_id: SelectionSetId,
+        path: ResponsePath,
+    ) {
+        let (operation, response) = (self.operation, self.response);
+        let object = &response[object_id];
+        for &field_id in &operation[selection_set_id].field_ids_ordered_by_parent_entity_id_then_position {
+            let Field::Query(QueryField {
+                bound_response_key,
+                definition_id,
+                selection_set_id,
+                ..
+            }) = operation[field_id]
+            else {
+                continue;
+            };
+            if operation.query_modifications.skipped_fields[field_id] {
+                continue;
+            }
+
+            let definition = self.schema.walk(definition_id);
+            let edge = ResponseEdge::from(bound_response_key);
+            let wrapping = definition.response_wrapping();
+            let selection_set = selection_set_id.zip(SelectionSetType::maybe_from(definition.ty().inner().id()));
+            match object.field_position(edge) {
+                Some(position) => {
+                    self.validate_value(&object[position], wrapping, definition, selection_set, &path, edge)
+                }
+                // Fields within a type condition only apply to some of the possible objects, which
+                // can't be told apart here.
+                None if wrapping.is_required() && SelectionSetType::from(definition.parent_entity().id()) == ty => {
+                    self.violations.push(Violation {
+                        parent_path: path.clone(),
+                        error: violation_error(definition, "missing", path.child(edge)),
+                    })
+                }
+                None => {}
+            }
+        }
+    }
+
+    fn validate_value(
+        &mut self,
+        value: &ResponseValue,
+        mut wrapping: Wrapping,
+        definition: FieldDefinitionWalker<'_>,
+        selection_set: Option<(SelectionSetId, SelectionSetType)>,
+        parent_path: &ResponsePath,
+        edge: ResponseEdge,
+    ) {
+        let path = parent_path.child(edge);
+        if value.is_null() {
+            if wrapping.is_required() {
+                self.violations.push(Violation {
+                    parent_path: parent_path.clone(),
+                    error: violation_error(definition, "null", path),
+                });
+            }
+            return;
+        }
+
+        if wrapping.pop_list_wrapping().is_some() {
+            if let &ResponseValue::List {
+                part_id,
+                offset,
+                length,
+                ..
+            } = value
+            {
+                let response = self.response;
+                let items = &response[ResponseListId {
+                    part_id,
+                    offset,
+                    length,
+                }];
+                for (index, item) in items.iter().enumerate() {
+                    self.validate_value(item, wrapping, definition, selection_set, &path, index.into());
+                }
+            }
+        } else if let (&ResponseValue::Object { part_id, index, .. }, Some((selection_set_id, ty))) =
+            (value, selection_set)
+        {
+            self.validate_object(ResponseObjectId { part_id, index }, ty, selection_set_id, path);
+        }
+    }
+}
+
+fn violation_error(definition: FieldDefinitionWalker<'_>, state: &str, path: ResponsePath) -> GraphqlError {
+    GraphqlError::new(
+        format!(
+            "Non-null field {}.{} is {state} in the response",
+            definition.parent_entity().name(),
+            definition.name()
+        ),
+        ErrorCode::ResponseValidationFailed,
+    )
+    .with_path(path)
+}
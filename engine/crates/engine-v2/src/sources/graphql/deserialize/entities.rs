@@ -11,12 +11,15 @@ use crate::{
     sources::graphql::CacheEntry,
 };
 
-use super::errors::GraphqlErrorsSeed;
+use super::{errors::GraphqlErrorsSeed, GraphqlDataSeed};
 
 pub(in crate::sources::graphql) struct EntitiesDataSeed<'resp> {
     pub response: SubgraphResponseRefMut<'resp>,
     pub plan: PlanWalker<'resp>,
+    pub subgraph_name: &'resp str,
     pub cache_entries: Option<&'resp [CacheEntry]>,
+    /// Number of root objects covered by the entities, fewer than all of them for batches.
+    pub count: usize,
 }
 
 impl<'resp, 'de> DeserializeSeed<'de> for EntitiesDataSeed<'resp>
@@ -33,6 +36,14 @@ where
     }
 }
 
+impl<'resp, 'de> GraphqlDataSeed<'de> for EntitiesDataSeed<'resp>
+where
+    'resp: 'de,
+{
+    // Entities without data are reported as missing by the coordinator.
+    fn null_data_without_errors(self) {}
+}
+
 impl<'resp, 'de> Visitor<'de> for EntitiesDataSeed<'resp>
 where
     'resp: 'de,
@@ -47,20 +58,40 @@ where
     where
         A: MapAccess<'de>,
     {
+        let mut has_entities = false;
         while let Some(key) = map.next_key::<EntitiesKey>()? {
             match key {
                 EntitiesKey::Entities => {
-                    map.next_value_seed(EntitiesSeed {
+                    has_entities = true;
+                    let is_list = map.next_value_seed(EntitiesSeed {
                         response_part: &self.response,
                         plan: self.plan,
                         cache_entries: self.cache_entries.map(|slice| slice.iter()),
+                        count: self.count,
                     })?;
+                    if !is_list {
+                        self.response.push_error(GraphqlError::new(
+                            format!(
+                                "Subgraph '{}' returned a malformed `_entities`, it must be a list",
+                                self.subgraph_name
+                            ),
+                            ErrorCode::SubgraphMalformedEntities,
+                        ));
+                    }
                 }
                 EntitiesKey::Unknown => {
                     map.next_value::<IgnoredAny>()?;
                 }
             }
         }
+        // Most likely the subgraph doesn't implement federation. Without it the entities would
+        // only be reported as missing data, hiding the actual cause.
+        if !has_entities {
+            self.response.push_error(GraphqlError::new(
+                "Subgraph response data doesn't contain any `_entities`",
+                ErrorCode::SubgraphMissingEntities,
+            ));
+        }
         Ok(())
     }
 }
@@ -73,23 +104,26 @@ enum EntitiesKey {
     Unknown,
 }
 
+/// Returns whether `_entities` was a list. Anything else is accepted and skipped, so that the
+/// error can say what's actually wrong rather than the deserializer.
 struct EntitiesSeed<'resp, 'parent> {
     response_part: &'parent SubgraphResponseRefMut<'resp>,
     plan: PlanWalker<'resp>,
     cache_entries: Option<std::slice::Iter<'parent, CacheEntry>>,
+    count: usize,
 }
 
 impl<'resp, 'de, 'parent> DeserializeSeed<'de> for EntitiesSeed<'resp, 'parent>
 where
     'resp: 'de,
 {
-    type Value = ();
+    type Value = bool;
 
     fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_seq(self)
+        deserializer.deserialize_any(self)
     }
 }
 
@@ -97,17 +131,56 @@ impl<'resp, 'de, 'parent> Visitor<'de> for EntitiesSeed<'resp, 'parent>
 where
     'resp: 'de,
 {
-    type Value = ();
+    type Value = bool;
 
     fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
         formatter.write_str("a non null entities list")
     }
 
+    fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+        Ok(false)
+    }
+
+    fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+        Ok(false)
+    }
+
+    fn visit_bool<E: Error>(self, _: bool) -> Result<Self::Value, E> {
+        Ok(false)
+    }
+
+    fn visit_i64<E: Error>(self, _: i64) -> Result<Self::Value, E> {
+        Ok(false)
+    }
+
+    fn visit_u64<E: Error>(self, _: u64) -> Result<Self::Value, E> {
+        Ok(false)
+    }
+
+    fn visit_f64<E: Error>(self, _: f64) -> Result<Self::Value, E> {
+        Ok(false)
+    }
+
+    fn visit_str<E: Error>(self, _: &str) -> Result<Self::Value, E> {
+        Ok(false)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+        Ok(false)
+    }
+
     fn visit_seq<A>(mut self, mut seq: A) -> Result<Self::Value, A::Error>
     where
         A: SeqAccess<'de>,
     {
-        while let Some(seed) = self.response_part.next_seed(self.plan) {
+        for _ in 0..self.count {
+            let Some(seed) = self.response_part.next_seed(self.plan) else {
+                break;
+            };
             let maybe_cache_data = self
                 .cache_entries
                 .as_mut()
@@ -144,13 +217,16 @@ where
             ));
             while seq.next_element::<IgnoredAny>()?.is_some() {}
         }
-        Ok(())
+        Ok(true)
     }
 }
 
 pub(in crate::sources::graphql) struct EntitiesErrorsSeed<'resp> {
     pub response: SubgraphResponseRefMut<'resp>,
     pub response_keys: &'resp ResponseKeys,
+    pub max_extension_size: usize,
+    /// Position of the root object of the first entity, the request being one of a batch.
+    pub offset: usize,
 }
 
 impl<'resp> GraphqlErrorsSeed<'resp> for EntitiesErrorsSeed<'resp> {
@@ -158,6 +234,10 @@ impl<'resp> GraphqlErrorsSeed<'resp> for EntitiesErrorsSeed<'resp> {
         &self.response
     }
 
+    fn max_extension_size(&self) -> usize {
+        self.max_extension_size
+    }
+
     fn convert_path(&self, path: &serde_json::Value) -> Option<ResponsePath> {
         let mut path = path.as_array()?.iter();
         if path.next()?.as_str()? != "_entities" {
@@ -166,7 +246,7 @@ impl<'resp> GraphqlErrorsSeed<'resp> for EntitiesErrorsSeed<'resp> {
 
         let mut out = self
             .response
-            .get_root_response_object(path.next()?.as_u64()? as usize)?
+            .get_root_response_object(self.offset + path.next()?.as_u64()? as usize)?
             .path
             .clone();
 
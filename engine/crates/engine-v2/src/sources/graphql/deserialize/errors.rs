@@ -1,17 +1,21 @@
 use serde::{de::DeserializeSeed, Deserializer};
 
-use crate::response::{
-    ErrorCode, GraphqlError, ResponseKeys, ResponsePath, SubgraphResponseRefMut, UnpackedResponseEdge,
+use crate::{
+    response::{ErrorCode, GraphqlError, ResponseKeys, ResponsePath, SubgraphResponseRefMut, UnpackedResponseEdge},
+    utils::truncate_at_char_boundary,
 };
 
 pub(super) trait GraphqlErrorsSeed<'resp> {
     fn response(&self) -> &SubgraphResponseRefMut<'resp>;
     fn convert_path(&self, path: &serde_json::Value) -> Option<ResponsePath>;
+    /// Maximum size in bytes of each forwarded extension value.
+    fn max_extension_size(&self) -> usize;
 }
 
 pub(in crate::sources::graphql) struct RootGraphqlErrors<'resp> {
     pub response: SubgraphResponseRefMut<'resp>,
     pub response_keys: &'resp ResponseKeys,
+    pub max_extension_size: usize,
 }
 
 impl<'resp> GraphqlErrorsSeed<'resp> for RootGraphqlErrors<'resp> {
@@ -19,6 +23,10 @@ impl<'resp> GraphqlErrorsSeed<'resp> for RootGraphqlErrors<'resp> {
         &self.response
     }
 
+    fn max_extension_size(&self) -> usize {
+        self.max_extension_size
+    }
+
     fn convert_path(&self, path: &serde_json::Value) -> Option<ResponsePath> {
         let mut out = ResponsePath::default();
         for edge in path.as_array()? {
@@ -77,7 +85,9 @@ where
                     error = error.with_extension("upstream_path", subgraph_error.path);
                 }
                 if !subgraph_error.extensions.is_null() {
-                    error = error.with_extension("upstream_extensions", subgraph_error.extensions);
+                    let mut extensions = subgraph_error.extensions;
+                    truncate_extensions(&mut extensions, self.0.max_extension_size());
+                    error = error.with_extension("upstream_extensions", extensions);
                 }
                 error
             })
@@ -86,3 +96,32 @@ where
         Ok(errors_count)
     }
 }
+
+const TRUNCATION_INDICATOR: &str = "...(truncated)";
+
+/// Replaces extension values larger than `max_size` bytes with their truncated JSON text, keeping
+/// errors bounded without dropping everything the subgraph sent.
+fn truncate_extensions(extensions: &mut serde_json::Value, max_size: usize) {
+    match extensions {
+        serde_json::Value::Object(fields) => {
+            for value in fields.values_mut() {
+                truncate_value(value, max_size);
+            }
+        }
+        value => truncate_value(value, max_size),
+    }
+}
+
+fn truncate_value(value: &mut serde_json::Value, max_size: usize) {
+    let mut text = match &mut *value {
+        serde_json::Value::String(text) if text.len() <= max_size => return,
+        serde_json::Value::String(text) => std::mem::take(text),
+        other => match serde_json::to_string(other) {
+            Ok(text) if text.len() <= max_size => return,
+            Ok(text) => text,
+            Err(_) => return,
+        },
+    };
+    truncate_at_char_boundary(&mut text, max_size, TRUNCATION_INDICATOR);
+    *value = serde_json::Value::String(text);
+}
@@ -6,8 +6,24 @@ use serde::{
     Deserializer,
 };
 
+use crate::response::UpdateSeed;
+
 use super::errors::{ConcreteGraphqlErrorsSeed, GraphqlErrorsSeed};
 
+/// Seed for the `data` of a subgraph response. A null `data` without any errors is ambiguous,
+/// so it's left to the seed to decide what it means.
+pub(in crate::sources::graphql) trait GraphqlDataSeed<'de>:
+    DeserializeSeed<'de, Value = ()>
+{
+    fn null_data_without_errors(self);
+}
+
+impl<'de, 'ctx> GraphqlDataSeed<'de> for UpdateSeed<'ctx> {
+    fn null_data_without_errors(self) {
+        UpdateSeed::null_data_without_errors(self)
+    }
+}
+
 pub(in crate::sources::graphql) struct GraphqlResponseSeed<DataSeed, ErrorSeed> {
     data_seed: Option<DataSeed>,
     errors_seed: Option<ConcreteGraphqlErrorsSeed<ErrorSeed>>,
@@ -24,7 +40,7 @@ impl<DataSeed, ErrorSeed> GraphqlResponseSeed<DataSeed, ErrorSeed> {
 
 impl<'resp, 'de, DataSeed, ErrorsSeed> DeserializeSeed<'de> for GraphqlResponseSeed<DataSeed, ErrorsSeed>
 where
-    DataSeed: DeserializeSeed<'de, Value = ()>,
+    DataSeed: GraphqlDataSeed<'de>,
     ErrorsSeed: GraphqlErrorsSeed<'resp>,
     'resp: 'de,
 {
@@ -40,7 +56,7 @@ where
 
 impl<'resp, 'de, DataSeed, ErrorsSeed> Visitor<'de> for GraphqlResponseSeed<DataSeed, ErrorsSeed>
 where
-    DataSeed: DeserializeSeed<'de, Value = ()>,
+    DataSeed: GraphqlDataSeed<'de>,
     ErrorsSeed: GraphqlErrorsSeed<'resp>,
     'resp: 'de,
 {
@@ -55,12 +71,16 @@ where
         A: MapAccess<'de>,
     {
         let mut data_is_null_result = Ok(true);
+        let mut null_data_seed = None;
         let mut errors_count = 0;
         while let Some(key) = map.next_key::<ResponseKey>()? {
             match key {
                 ResponseKey::Data => {
                     if let Some(seed) = self.data_seed.take() {
-                        data_is_null_result = map.next_value_seed(NullableDataSeed { seed });
+                        data_is_null_result = map.next_value_seed(NullableDataSeed { seed }).map(|seed| {
+                            null_data_seed = seed;
+                            null_data_seed.is_some()
+                        });
                     }
                 }
                 ResponseKey::Errors => {
@@ -76,6 +96,9 @@ where
 
         let data_is_present = self.data_seed.is_some();
         let status = if errors_count == 0 {
+            if let Some(seed) = null_data_seed {
+                seed.null_data_without_errors();
+            }
             GraphqlResponseStatus::Success
         } else if data_is_present {
             GraphqlResponseStatus::FieldError {
@@ -92,6 +115,7 @@ where
     }
 }
 
+/// Gives the seed back if the data was null.
 struct NullableDataSeed<Seed> {
     seed: Seed,
 }
@@ -100,7 +124,7 @@ impl<'de, Seed> DeserializeSeed<'de> for NullableDataSeed<Seed>
 where
     Seed: DeserializeSeed<'de, Value = ()>,
 {
-    type Value = bool;
+    type Value = Option<Seed>;
 
     fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
     where
@@ -114,7 +138,7 @@ impl<'de, Seed> Visitor<'de> for NullableDataSeed<Seed>
 where
     Seed: DeserializeSeed<'de, Value = ()>,
 {
-    type Value = bool;
+    type Value = Option<Seed>;
 
     fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
         formatter.write_str("a nullable value")
@@ -131,7 +155,7 @@ where
     where
         E: serde::de::Error,
     {
-        Ok(true)
+        Ok(Some(self.seed))
     }
 
     fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
@@ -139,7 +163,7 @@ where
         D: Deserializer<'de>,
     {
         self.seed.deserialize(deserializer)?;
-        Ok(false)
+        Ok(None)
     }
 }
 
@@ -1,17 +1,21 @@
 use bytes::Bytes;
-use futures::future::join_all;
+use futures::{channel::oneshot, future::join_all, StreamExt, TryStreamExt};
 use grafbase_telemetry::{gql_response_status::GraphqlResponseStatus, span::subgraph::SubgraphRequestSpan};
 use runtime::fetch::FetchRequest;
-use schema::sources::graphql::{FederationEntityResolverWalker, GraphqlEndpointId};
+use schema::{
+    sources::graphql::{FederationEntityResolverWalker, GraphqlEndpointId},
+    RequiredFieldId,
+};
 use serde::{de::DeserializeSeed, Deserialize};
 use serde_json::value::RawValue;
-use std::{borrow::Cow, future::Future, time::Duration};
-use tracing::Instrument;
+use std::{borrow::Cow, future::Future, ops::Range, time::Duration};
+use tracing::{Instrument, Span};
+use url::Url;
 
 use crate::{
     execution::{ExecutionContext, ExecutionError, PlanWalker, PlanningResult},
     operation::OperationType,
-    response::{ResponseObjectsView, SubgraphResponse},
+    response::{ErrorCode, GraphqlError, ResponseObjectsView, SubgraphResponse},
     sources::{
         graphql::deserialize::{EntitiesErrorsSeed, GraphqlResponseSeed},
         ExecutionResult, PreparedExecutor,
@@ -21,14 +25,23 @@ use crate::{
 
 use super::{
     deserialize::EntitiesDataSeed,
+    effective_timeout, ensure_query_depth, plan_priority,
     query::PreparedFederationEntityOperation,
     request::{execute_subgraph_request, ResponseIngester},
+    request_for_log, resolve_url,
     variables::SubgraphVariables,
 };
 
+/// Batches of a single entities fetch sent at the same time, on top of the concurrency limit of
+/// the subgraph if any.
+const MAX_CONCURRENT_ENTITY_BATCHES: usize = 4;
+
 pub(crate) struct FederationEntityPreparedExecutor {
     subgraph_id: GraphqlEndpointId,
     operation: PreparedFederationEntityOperation,
+    key_fields: Vec<RequiredFieldId>,
+    timeout: Duration,
+    priority: i64,
 }
 
 impl FederationEntityPreparedExecutor {
@@ -37,14 +50,25 @@ impl FederationEntityPreparedExecutor {
         plan: PlanWalker<'_>,
     ) -> PlanningResult<PreparedExecutor> {
         let subgraph = resolver.endpoint();
-        let operation =
-            PreparedFederationEntityOperation::build(plan).map_err(|err| format!("Failed to build query: {err}"))?;
+        let operation_name = subgraph
+            .entities_operation_prefix()
+            .map(|prefix| format!("{prefix}_{}_{}", subgraph.name(), usize::from(plan.id())));
+        let operation = PreparedFederationEntityOperation::build(plan, operation_name.as_deref())
+            .map_err(|err| format!("Failed to build query: {err}"))?;
+        ensure_query_depth(subgraph, operation.depth)?;
         Ok(PreparedExecutor::FederationEntity(Self {
             subgraph_id: subgraph.id(),
             operation,
+            key_fields: resolver.requires().iter().map(|item| item.id).collect(),
+            timeout: effective_timeout(subgraph, plan),
+            priority: plan_priority(plan),
         }))
     }
 
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
     pub fn execute<'ctx, 'fut, R: Runtime>(
         &'ctx self,
         ctx: ExecutionContext<'ctx, R>,
@@ -59,44 +83,58 @@ impl FederationEntityPreparedExecutor {
             "__typename".to_string(),
             serde_json::Value::String(entity_name(ctx, plan)),
         )]);
-        let mut representations = root_response_objects
-            .iter()
-            .map(|object| serde_json::to_string(&object).and_then(RawValue::from_string))
-            .collect::<Result<Vec<_>, _>>()?;
+        let mut representations = Vec::new();
+        for object in root_response_objects.iter() {
+            // Keys are never nullable, so a subgraph couldn't make sense of a representation
+            // without one, failing with a far less helpful error.
+            if let Some(field) = object.find_missing_field(&self.key_fields) {
+                return Err(GraphqlError::new(
+                    format!("Entity representation is missing the key field '{field}'"),
+                    ErrorCode::InvalidEntityRepresentation,
+                )
+                .into());
+            }
+            representations.push(serde_json::to_string(&object).and_then(RawValue::from_string)?);
+        }
 
         let subgraph = ctx.engine.schema.walk(self.subgraph_id);
+        let url = resolve_url(subgraph, ctx.headers())?;
+        let response_path = plan.response_path();
         let span = SubgraphRequestSpan {
             name: subgraph.name(),
             operation_type: OperationType::Query.as_str(),
             // The generated query does not contain any data, everything are in the variables, so
             // it's safe to use.
             sanitized_query: &self.operation.query,
-            url: subgraph.url(),
+            url: &url,
+            plan_id: plan.id().into(),
+            response_path: &response_path,
         }
         .into_span();
 
         let cache_ttl = subgraph.entity_cache_ttl();
+        let batch_size = subgraph.entity_batch_size();
 
         let fut = {
             let span = span.clone();
             async move {
-                let mut ingester = EntityIngester {
-                    ctx,
-                    plan,
-                    cache_entries: None,
-                    subgraph_response,
-                    cache_ttl,
-                };
-
+                let mut cache_entries = None;
                 if cache_ttl.is_some() {
                     let fetches = representations
                         .iter()
                         .map(|repr| cache_fetch(ctx, subgraph.name(), repr));
 
-                    let cache_entries = join_all(fetches).await;
-                    let fully_cached = !cache_entries.iter().any(CacheEntry::is_miss);
-                    ingester.cache_entries = Some(cache_entries);
-                    if fully_cached {
+                    let entries = join_all(fetches).await;
+                    if !entries.iter().any(CacheEntry::is_miss) {
+                        let ingester = EntityIngester {
+                            ctx,
+                            plan,
+                            subgraph_id: self.subgraph_id,
+                            root_objects: 0..entries.len(),
+                            cache_entries: Some(entries),
+                            subgraph_response: ready(subgraph_response),
+                            cache_ttl,
+                        };
                         let (_, response) = ingester
                             .ingest(Bytes::from_static(br#"{"data": {"_entities": []}}"#))
                             .await?;
@@ -105,58 +143,172 @@ impl FederationEntityPreparedExecutor {
                     }
                     representations = representations
                         .into_iter()
-                        .zip(ingester.cache_entries.as_ref().unwrap())
+                        .zip(&entries)
                         .filter(|(_, cache_entry)| cache_entry.is_miss())
                         .map(|(repr, _)| repr)
                         .collect();
+                    cache_entries = Some(entries);
                 }
-                let variables = SubgraphVariables {
-                    plan,
-                    variables: &self.operation.variables,
-                    inputs: vec![(&self.operation.entities_variable_name, representations)],
-                };
-
-                tracing::debug!(
-                    "Query {}\n{}\n{}",
-                    subgraph.name(),
-                    self.operation.query,
-                    serde_json::to_string_pretty(&variables).unwrap_or_default()
-                );
-                let json_body = serde_json::to_string(&serde_json::json!({
-                    "query": self.operation.query,
-                    "variables": variables
-                }))
-                .map_err(|err| format!("Failed to serialize query: {err}"))?;
-
-                let retry_budget = ctx.engine.retry_budget_for_subgraph(self.subgraph_id);
-
-                execute_subgraph_request(
-                    ctx,
-                    span.clone(),
-                    self.subgraph_id,
-                    retry_budget,
-                    move || FetchRequest {
-                        url: subgraph.url(),
-                        headers: ctx.subgraph_headers_with_rules(subgraph.header_rules()),
-                        json_body: Bytes::from(json_body.into_bytes()),
-                        timeout: subgraph.timeout(),
-                    },
-                    ingester,
-                )
-                .await
+
+                if !ctx.withdraw_operation_entities(representations.len()) {
+                    let limit = ctx.schema().settings.max_entities_per_operation.unwrap_or_default();
+                    return Err(GraphqlError::new(
+                        format!("Operation exceeded the limit of {limit} entities fetched from subgraphs"),
+                        ErrorCode::EntityLimitExceeded,
+                    )
+                    .into());
+                }
+
+                // Batches are fetched concurrently but ingested in order, each one handing the
+                // response over to the next one once it wrote its entities.
+                let mut subgraph_response = ready(subgraph_response);
+                let mut fetches = Vec::new();
+                for batch in split_into_batches(representations, cache_entries, batch_size) {
+                    let (next_sender, next_receiver) = oneshot::channel();
+                    let ingester = EntityIngester {
+                        ctx,
+                        plan,
+                        subgraph_id: self.subgraph_id,
+                        root_objects: batch.root_objects,
+                        cache_entries: batch.cache_entries,
+                        subgraph_response: std::mem::replace(&mut subgraph_response, next_receiver),
+                        cache_ttl,
+                    };
+                    let fetch = self.fetch_entities(ctx, plan, span.clone(), &url, batch.representations, ingester);
+                    fetches.push(async move {
+                        let response = fetch.await?;
+                        // Only fails if a later batch failed, which fails the whole fetch anyway.
+                        next_sender.send(response).ok();
+                        ExecutionResult::Ok(())
+                    });
+                }
+
+                futures::stream::iter(fetches)
+                    .buffered(MAX_CONCURRENT_ENTITY_BATCHES)
+                    .try_collect::<()>()
+                    .await?;
+
+                let response = subgraph_response
+                    .await
+                    .map_err(|_| "Entities fetch ended without a response".to_string())?;
+                ExecutionResult::Ok(response)
             }
         }
         .instrument(span);
 
         Ok(fut)
     }
+
+    async fn fetch_entities<'ctx, R: Runtime>(
+        &'ctx self,
+        ctx: ExecutionContext<'ctx, R>,
+        plan: PlanWalker<'ctx, (), ()>,
+        span: Span,
+        url: &Url,
+        representations: Vec<Box<RawValue>>,
+        ingester: EntityIngester<'ctx, R>,
+    ) -> ExecutionResult<SubgraphResponse> {
+        let subgraph = ctx.engine.schema.walk(self.subgraph_id);
+        let variables = SubgraphVariables {
+            plan,
+            variables: &self.operation.variables,
+            inputs: vec![(&self.operation.entities_variable_name, representations)],
+        };
+
+        tracing::debug!(
+            "Query {}\n{}",
+            subgraph.name(),
+            request_for_log(
+                &self.operation.query,
+                &variables,
+                plan.schema().settings.max_logged_subgraph_request_size
+            )
+        );
+        let json_body = serde_json::to_string(&serde_json::json!({
+            "query": self.operation.query,
+            "variables": variables
+        }))
+        .map_err(|err| format!("Failed to serialize query: {err}"))?;
+
+        let retry_budget = ctx.engine.retry_budget_for_subgraph(self.subgraph_id);
+        let timeout = self.timeout;
+
+        execute_subgraph_request(
+            ctx,
+            span,
+            self.subgraph_id,
+            retry_budget,
+            self.priority,
+            move || FetchRequest {
+                url,
+                headers: ctx.subgraph_headers_with_rules(subgraph.header_rules()),
+                json_body: Bytes::from(json_body.into_bytes()),
+                timeout,
+                danger_accept_invalid_certs: subgraph.danger_accept_invalid_certs(),
+            },
+            ingester,
+            None,
+        )
+        .await
+    }
+}
+
+/// Entities fetched by a single subgraph request.
+struct EntityBatch<T> {
+    representations: Vec<T>,
+    // Also covers the cached entities in between, which aren't part of the representations.
+    root_objects: Range<usize>,
+    cache_entries: Option<Vec<CacheEntry>>,
+}
+
+/// Splits the representations into batches of at most `batch_size`, a single one without it.
+fn split_into_batches<T>(
+    representations: Vec<T>,
+    cache_entries: Option<Vec<CacheEntry>>,
+    batch_size: Option<usize>,
+) -> Vec<EntityBatch<T>> {
+    let batch_size = batch_size.unwrap_or(usize::MAX);
+    let mut remaining = representations.into_iter().peekable();
+    let mut cache_entries = cache_entries.map(|entries| entries.into_iter().peekable());
+    let mut batches = Vec::new();
+    let mut start = 0;
+    while remaining.peek().is_some() {
+        let representations = remaining.by_ref().take(batch_size).collect::<Vec<_>>();
+        // A batch takes the cached entities up to its last miss, and any following it.
+        let batch_cache_entries = cache_entries.as_mut().map(|entries| {
+            let mut misses = representations.len();
+            let mut batch = Vec::new();
+            while let Some(entry) = entries.next_if(|entry| misses > 0 || !entry.is_miss()) {
+                misses -= usize::from(entry.is_miss());
+                batch.push(entry);
+            }
+            batch
+        });
+        let count = batch_cache_entries.as_ref().map_or(representations.len(), Vec::len);
+        batches.push(EntityBatch {
+            representations,
+            root_objects: start..start + count,
+            cache_entries: batch_cache_entries,
+        });
+        start += count;
+    }
+    batches
+}
+
+fn ready(subgraph_response: SubgraphResponse) -> oneshot::Receiver<SubgraphResponse> {
+    let (sender, receiver) = oneshot::channel();
+    sender.send(subgraph_response).ok();
+    receiver
 }
 
 struct EntityIngester<'ctx, R: Runtime> {
     ctx: ExecutionContext<'ctx, R>,
     plan: PlanWalker<'ctx, (), ()>,
+    subgraph_id: GraphqlEndpointId,
+    root_objects: Range<usize>,
     cache_entries: Option<Vec<CacheEntry>>,
-    subgraph_response: SubgraphResponse,
+    // Received once the previous batch, if any, was ingested.
+    subgraph_response: oneshot::Receiver<SubgraphResponse>,
     cache_ttl: Option<Duration>,
 }
 
@@ -186,10 +338,15 @@ where
         let Self {
             ctx,
             plan,
+            subgraph_id,
+            root_objects,
             cache_entries,
-            mut subgraph_response,
+            subgraph_response,
             cache_ttl,
         } = self;
+        let mut subgraph_response = subgraph_response
+            .await
+            .map_err(|_| "A previous batch of entities failed".to_string())?;
 
         let status = {
             let response = subgraph_response.as_mut();
@@ -198,14 +355,20 @@ where
                     response: response.clone(),
                     cache_entries: cache_entries.as_deref(),
                     plan,
+                    subgraph_name: ctx.engine.schema.walk(subgraph_id).name(),
+                    count: root_objects.len(),
                 },
                 EntitiesErrorsSeed {
                     response,
                     response_keys: plan.response_keys(),
+                    max_extension_size: plan.schema().settings.max_error_extension_size,
+                    offset: root_objects.start,
                 },
             )
             .deserialize(&mut serde_json::Deserializer::from_slice(&bytes))?
         };
+        // Keeps the root objects of the following batches aligned with their entities.
+        subgraph_response.reserve_updates_up_to(root_objects.end);
 
         if let Some(cache_ttl) = cache_ttl {
             if let Some(cache_entries) = cache_entries.filter(|_| status.is_success()) {
@@ -303,3 +466,51 @@ fn entity_name<R: Runtime>(ctx: ExecutionContext<'_, R>, plan: PlanWalker<'_, ()
         .name()
         .to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{split_into_batches, CacheEntry};
+
+    fn miss() -> CacheEntry {
+        CacheEntry::Miss { key: String::new() }
+    }
+
+    fn hit() -> CacheEntry {
+        CacheEntry::Hit { data: Vec::new() }
+    }
+
+    #[test]
+    fn representations_are_split_into_batches() {
+        let batches = split_into_batches(vec![1, 2, 3, 4, 5], None, Some(2));
+
+        let batches = batches
+            .into_iter()
+            .map(|batch| (batch.representations, batch.root_objects))
+            .collect::<Vec<_>>();
+        assert_eq!(batches, [(vec![1, 2], 0..2), (vec![3, 4], 2..4), (vec![5], 4..5)]);
+    }
+
+    #[test]
+    fn single_batch_without_batch_size() {
+        let batches = split_into_batches(vec![1, 2, 3], None, None);
+
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].root_objects, 0..3);
+    }
+
+    #[test]
+    fn cached_entities_are_covered_by_the_batches() {
+        // Root objects: hit, miss (1), hit, miss (2), miss (3), hit
+        let cache_entries = vec![hit(), miss(), hit(), miss(), miss(), hit()];
+        let batches = split_into_batches(vec![1, 2, 3], Some(cache_entries), Some(2));
+
+        let batches = batches
+            .into_iter()
+            .map(|batch| {
+                let cache_entries = batch.cache_entries.unwrap();
+                (batch.representations, batch.root_objects, cache_entries.len())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(batches, [(vec![1, 2], 0..4, 4), (vec![3], 4..6, 2)]);
+    }
+}
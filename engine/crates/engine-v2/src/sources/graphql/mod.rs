@@ -4,19 +4,23 @@ use bytes::Bytes;
 use grafbase_telemetry::{gql_response_status::GraphqlResponseStatus, span::subgraph::SubgraphRequestSpan};
 use request::{execute_subgraph_request, ResponseIngester};
 use runtime::fetch::FetchRequest;
-use schema::sources::graphql::{GraphqlEndpointId, RootFieldResolverWalker};
+use schema::sources::graphql::{
+    GraphqlEndpointId, GraphqlEndpointWalker, RootFieldResolverWalker, UrlTemplateSegmentWalker,
+};
 use serde::de::DeserializeSeed;
 use tracing::Instrument;
+use url::Url;
 
 use self::query::PreparedGraphqlOperation;
 use self::variables::SubgraphVariables;
 
 use super::{ExecutionContext, ExecutionResult, PreparedExecutor};
 use crate::{
-    execution::{PlanWalker, PlanningResult},
+    execution::{PlanWalker, PlanningError, PlanningResult},
     operation::OperationType,
-    response::SubgraphResponse,
+    response::{ErrorCode, GraphqlError, SubgraphResponse},
     sources::graphql::deserialize::{GraphqlResponseSeed, RootGraphqlErrors},
+    utils::truncate_at_char_boundary,
     Runtime,
 };
 
@@ -29,9 +33,71 @@ mod variables;
 
 pub(crate) use federation::*;
 
+const TRUNCATION_INDICATOR: &str = "...(truncated)";
+
+/// Query and variables of a subgraph request as written to the debug logs, truncated to
+/// `max_size` bytes.
+fn request_for_log(query: &str, variables: &impl serde::Serialize, max_size: usize) -> String {
+    let mut text = format!(
+        "{query}\n{}",
+        serde_json::to_string_pretty(variables).unwrap_or_default()
+    );
+    truncate_at_char_boundary(&mut text, max_size, TRUNCATION_INDICATOR);
+    text
+}
+
+/// Subgraphs may enforce their own depth limit, better to reject the operation during planning
+/// with a clear error than let the subgraph fail it at runtime.
+fn ensure_query_depth(subgraph: GraphqlEndpointWalker<'_>, depth: usize) -> PlanningResult<()> {
+    match subgraph.max_query_depth() {
+        Some(max_depth) if depth > usize::from(max_depth) => Err(PlanningError::SubgraphQueryTooDeep {
+            subgraph_name: subgraph.name().to_string(),
+            depth,
+            max_depth,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// URL of the subgraph for the current request. Placeholders of the URL template are replaced by
+/// the value of their request header, which must be one of the allowed ones. Otherwise clients
+/// could send subgraph requests to any host.
+fn resolve_url<'a>(subgraph: GraphqlEndpointWalker<'a>, headers: &http::HeaderMap) -> ExecutionResult<Cow<'a, Url>> {
+    let Some(segments) = subgraph.url_template() else {
+        return Ok(Cow::Borrowed(subgraph.url()));
+    };
+    let unresolved = |reason: String| {
+        GraphqlError::new(
+            format!("Could not resolve the URL of subgraph '{}': {reason}", subgraph.name()),
+            ErrorCode::SubgraphRequestError,
+        )
+    };
+
+    let mut url = String::new();
+    for segment in segments {
+        match segment {
+            UrlTemplateSegmentWalker::Literal(literal) => url.push_str(literal),
+            UrlTemplateSegmentWalker::Placeholder(variable) => {
+                let value = headers
+                    .get(variable.header())
+                    .and_then(|value| value.to_str().ok())
+                    .filter(|value| variable.allows(value))
+                    .ok_or_else(|| unresolved(format!("no allowed value for '{{{}}}'", variable.name())))?;
+                url.push_str(value);
+            }
+        }
+    }
+
+    Url::parse(&url)
+        .map(Cow::Owned)
+        .map_err(|err| unresolved(err.to_string()).into())
+}
+
 pub(crate) struct GraphqlPreparedExecutor {
     subgraph_id: GraphqlEndpointId,
     operation: PreparedGraphqlOperation,
+    timeout: Duration,
+    priority: i64,
 }
 
 impl GraphqlPreparedExecutor {
@@ -44,13 +110,20 @@ impl GraphqlPreparedExecutor {
 
         let operation = query::PreparedGraphqlOperation::build(operation_type, plan)
             .map_err(|err| format!("Failed to build query: {err}"))?;
+        ensure_query_depth(subgraph, operation.depth)?;
 
         Ok(PreparedExecutor::GraphQL(Self {
             subgraph_id: subgraph.id(),
             operation,
+            timeout: effective_timeout(subgraph, plan),
+            priority: plan_priority(plan),
         }))
     }
 
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
     #[tracing::instrument(skip_all)]
     pub async fn execute<'ctx, R: Runtime>(
         &'ctx self,
@@ -59,6 +132,7 @@ impl GraphqlPreparedExecutor {
         mut subgraph_response: SubgraphResponse,
     ) -> ExecutionResult<SubgraphResponse> {
         let subgraph = plan.schema().walk(self.subgraph_id);
+        let url = resolve_url(subgraph, ctx.headers())?;
 
         let variables = SubgraphVariables::<()> {
             plan,
@@ -67,10 +141,13 @@ impl GraphqlPreparedExecutor {
         };
 
         tracing::debug!(
-            "Query {}\n{}\n{}",
+            "Query {}\n{}",
             subgraph.name(),
-            self.operation.query,
-            serde_json::to_string_pretty(&variables).unwrap_or_default()
+            request_for_log(
+                &self.operation.query,
+                &variables,
+                plan.schema().settings.max_logged_subgraph_request_size
+            )
         );
 
         let json_body = serde_json::to_string(&serde_json::json!({
@@ -79,19 +156,35 @@ impl GraphqlPreparedExecutor {
         }))
         .map_err(|err| format!("Failed to serialize query: {err}"))?;
 
+        let response_path = plan.response_path();
         let span = SubgraphRequestSpan {
             name: subgraph.name(),
             operation_type: self.operation.ty.as_str(),
             // The generated query does not contain any data, everything are in the variables, so
             // it's safe to use.
             sanitized_query: &self.operation.query,
-            url: subgraph.url(),
+            url: &url,
+            plan_id: plan.id().into(),
+            response_path: &response_path,
         }
         .into_span();
 
-        let cache_ttl_and_key = subgraph
-            .entity_cache_ttl()
-            .map(|ttl| (ttl, build_cache_key(&json_body)));
+        let headers = ctx.subgraph_headers_with_rules(subgraph.header_rules());
+
+        let cache_ttl_and_key = match subgraph.subgraph_cache_ttl() {
+            // Mutations have side effects, their responses can never be re-used.
+            Some(ttl) if matches!(self.operation.ty, OperationType::Query) => {
+                let variables = serde_json::to_vec(&variables)
+                    .map_err(|err| format!("Failed to serialize query variables: {err}"))?;
+                Some((
+                    ttl,
+                    build_subgraph_cache_key(subgraph.name(), &self.operation.query, &variables, &headers),
+                ))
+            }
+            _ => subgraph
+                .entity_cache_ttl()
+                .map(|ttl| (ttl, build_cache_key(&json_body))),
+        };
 
         if let Some((_, cache_key)) = &cache_ttl_and_key {
             let cache_entry = ctx
@@ -112,6 +205,7 @@ impl GraphqlPreparedExecutor {
                     RootGraphqlErrors {
                         response,
                         response_keys: plan.response_keys(),
+                        max_extension_size: plan.schema().settings.max_error_extension_size,
                     },
                 )
                 .deserialize(&mut serde_json::Deserializer::from_slice(&bytes))?;
@@ -128,40 +222,147 @@ impl GraphqlPreparedExecutor {
             retry_budget = None;
         }
 
+        let stale_if_error = subgraph.entity_cache_stale_if_error();
+        let stale_cache_key = cache_ttl_and_key
+            .as_ref()
+            .filter(|_| stale_if_error.is_some())
+            .map(|(_, cache_key)| build_stale_cache_key(cache_key));
+
         execute_subgraph_request(
             ctx,
             span.clone(),
             self.subgraph_id,
             retry_budget,
+            self.priority,
             || FetchRequest {
-                url: subgraph.url(),
-                headers: ctx.subgraph_headers_with_rules(subgraph.header_rules()),
+                url: &url,
+                headers,
                 json_body: Bytes::from(json_body.into_bytes()),
-                timeout: subgraph.timeout(),
+                timeout: self.timeout,
+                danger_accept_invalid_certs: subgraph.danger_accept_invalid_certs(),
             },
             GraphqlIngester {
                 ctx,
                 plan,
                 cache_ttl_and_key,
+                stale_if_error,
                 subgraph_response,
             },
+            stale_cache_key.as_deref(),
         )
         .instrument(span)
         .await
     }
 }
 
+/// Timeout of the subgraph request for a plan, computed once during planning.
+fn effective_timeout(subgraph: GraphqlEndpointWalker<'_>, plan: PlanWalker<'_>) -> Duration {
+    resolve_timeout(
+        plan.selection_set()
+            .fields()
+            .iter()
+            .filter_map(|field| subgraph.field_timeout(field.id())),
+        subgraph.timeout(),
+        plan.schema().settings.timeout,
+    )
+}
+
+/// Priority of the subgraph request for a plan, the highest `@priority` level among its root
+/// fields.
+fn plan_priority(plan: PlanWalker<'_>) -> i64 {
+    plan.selection_set()
+        .fields()
+        .iter()
+        .filter_map(|field| field.directives().priority())
+        .max()
+        .unwrap_or_default()
+}
+
+/// An explicit timeout on any of the root fields wins, the smallest one if there are several.
+/// Otherwise the subgraph timeout applies, capped by the gateway one.
+fn resolve_timeout(
+    field_timeouts: impl IntoIterator<Item = Duration>,
+    subgraph_timeout: Duration,
+    gateway_timeout: Duration,
+) -> Duration {
+    field_timeouts
+        .into_iter()
+        .min()
+        .unwrap_or_else(|| subgraph_timeout.min(gateway_timeout))
+}
+
 fn build_cache_key(json_body: &str) -> String {
     let mut hasher = blake3::Hasher::new();
     hasher.update(json_body.as_bytes());
     hasher.finalize().to_string()
 }
 
+/// Key of a subgraph response, identified by the subgraph, the query, its variables and the
+/// headers sent along. The same subgraph query may be planned for different client operations,
+/// which then share the entry. Forwarded headers, like `Authorization`, may change the response,
+/// so clients sending different ones never share it.
+fn build_subgraph_cache_key(subgraph_name: &str, query: &str, variables: &[u8], headers: &http::HeaderMap) -> String {
+    let mut pairs = headers
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_bytes()))
+        .collect::<Vec<_>>();
+    // Clients may send headers in any order.
+    pairs.sort_unstable();
+
+    let mut hasher = blake3::Hasher::new();
+    for (name, value) in pairs {
+        // Length prefixes, so that names and values can't bleed into each other.
+        hasher.update(&name.len().to_ne_bytes());
+        hasher.update(name.as_bytes());
+        hasher.update(&value.len().to_ne_bytes());
+        hasher.update(value);
+    }
+
+    format!(
+        "subgraph:{subgraph_name}:{}:{}:{}",
+        blake3::hash(query.as_bytes()),
+        blake3::hash(variables),
+        hasher.finalize()
+    )
+}
+
+/// The copy of a cache entry which outlives it by the stale-if-error window.
+fn build_stale_cache_key(cache_key: &str) -> String {
+    format!("{cache_key}:stale")
+}
+
 struct GraphqlIngester<'ctx, R: Runtime> {
     ctx: ExecutionContext<'ctx, R>,
     plan: PlanWalker<'ctx, (), ()>,
     subgraph_response: SubgraphResponse,
     cache_ttl_and_key: Option<(Duration, String)>,
+    stale_if_error: Option<Duration>,
+}
+
+impl<'ctx, R: Runtime> GraphqlIngester<'ctx, R> {
+    fn deserialize(&mut self, bytes: &[u8]) -> Result<GraphqlResponseStatus, crate::execution::ExecutionError> {
+        let response = self.subgraph_response.as_mut();
+        let status = GraphqlResponseSeed::new(
+            response.next_seed(self.plan).ok_or("No object to update")?,
+            RootGraphqlErrors {
+                response,
+                response_keys: self.plan.response_keys(),
+                max_extension_size: self.plan.schema().settings.max_error_extension_size,
+            },
+        )
+        .deserialize(&mut serde_json::Deserializer::from_slice(bytes))?;
+        Ok(status)
+    }
+}
+
+async fn put_cache_entry<R: Runtime>(ctx: ExecutionContext<'_, R>, key: &str, bytes: &[u8], ttl: Duration) {
+    ctx.engine
+        .runtime
+        .kv()
+        .put(key, Cow::Borrowed(bytes), Some(ttl))
+        .await
+        .inspect_err(|err| tracing::warn!("Failed to write the cache key {key}: {err}"))
+        .ok();
 }
 
 impl<'ctx, R> ResponseIngester for GraphqlIngester<'ctx, R>
@@ -172,31 +373,61 @@ where
         mut self,
         bytes: Bytes,
     ) -> Result<(GraphqlResponseStatus, SubgraphResponse), crate::execution::ExecutionError> {
-        let status = {
-            let response = self.subgraph_response.as_mut();
-            GraphqlResponseSeed::new(
-                response.next_seed(self.plan).ok_or("No object to update")?,
-                RootGraphqlErrors {
-                    response,
-                    response_keys: self.plan.response_keys(),
-                },
-            )
-            .deserialize(&mut serde_json::Deserializer::from_slice(&bytes))?
-        };
+        let status = self.deserialize(&bytes)?;
 
-        if let Some((cache_ttl, cache_key)) = self.cache_ttl_and_key.filter(|_| status.is_success()) {
+        if let Some((cache_ttl, cache_key)) = self.cache_ttl_and_key.take().filter(|_| status.is_success()) {
             // We could probably put this call into the background at some point, but for
             // simplicities sake I am not going to do that just now.
-            self.ctx
-                .engine
-                .runtime
-                .kv()
-                .put(&cache_key, Cow::Borrowed(bytes.as_ref()), Some(cache_ttl))
-                .await
-                .inspect_err(|err| tracing::warn!("Failed to write the cache key {cache_key}: {err}"))
-                .ok();
+            put_cache_entry(self.ctx, &cache_key, &bytes, cache_ttl).await;
+            if let Some(stale_if_error) = self.stale_if_error {
+                let stale_key = build_stale_cache_key(&cache_key);
+                put_cache_entry(self.ctx, &stale_key, &bytes, cache_ttl + stale_if_error).await;
+            }
         }
 
         Ok((status, self.subgraph_response))
     }
+
+    async fn ingest_stale(
+        mut self,
+        bytes: Bytes,
+    ) -> Result<(GraphqlResponseStatus, SubgraphResponse), crate::execution::ExecutionError> {
+        // Only the subgraph refreshes the cache, a stale entry must never be written back.
+        let status = self.deserialize(&bytes)?;
+        self.subgraph_response.mark_stale();
+        Ok((status, self.subgraph_response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::resolve_timeout;
+
+    const SECOND: Duration = Duration::from_secs(1);
+
+    #[test]
+    fn subgraph_timeout_without_field_override() {
+        assert_eq!(resolve_timeout([], 5 * SECOND, 30 * SECOND), 5 * SECOND);
+    }
+
+    #[test]
+    fn subgraph_timeout_is_capped_by_gateway_timeout() {
+        assert_eq!(resolve_timeout([], 30 * SECOND, 10 * SECOND), 10 * SECOND);
+    }
+
+    #[test]
+    fn field_override_takes_precedence() {
+        assert_eq!(resolve_timeout([20 * SECOND], 5 * SECOND, 10 * SECOND), 20 * SECOND);
+        assert_eq!(resolve_timeout([2 * SECOND], 5 * SECOND, 10 * SECOND), 2 * SECOND);
+    }
+
+    #[test]
+    fn smallest_field_override_wins() {
+        assert_eq!(
+            resolve_timeout([7 * SECOND, 3 * SECOND, 4 * SECOND], 5 * SECOND, 10 * SECOND),
+            3 * SECOND
+        );
+    }
 }
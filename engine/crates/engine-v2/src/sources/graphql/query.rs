@@ -25,6 +25,8 @@ pub(super) struct PreparedGraphqlOperation {
     pub ty: OperationType,
     pub query: String,
     pub variables: QueryVariables,
+    /// Deepest level of nested fields in the query, root fields being at depth 1.
+    pub depth: usize,
 }
 
 impl PreparedGraphqlOperation {
@@ -63,6 +65,7 @@ impl PreparedGraphqlOperation {
         Ok(PreparedGraphqlOperation {
             ty: operation_type,
             query,
+            depth: ctx.max_depth,
             variables: ctx.into_query_variables(),
         })
     }
@@ -72,12 +75,18 @@ pub(super) struct PreparedFederationEntityOperation {
     pub query: String,
     pub entities_variable_name: String,
     pub variables: QueryVariables,
+    /// Deepest level of nested fields in the query, `_entities` included.
+    pub depth: usize,
 }
 
 impl PreparedFederationEntityOperation {
-    pub(super) fn build(plan: PlanWalker<'_>) -> Result<Self, Error> {
+    pub(super) fn build(plan: PlanWalker<'_>, operation_name: Option<&str>) -> Result<Self, Error> {
         let mut ctx = QueryBuilderContext::default();
         let mut query = String::from("query");
+        if let Some(name) = operation_name {
+            query.push(' ');
+            write_sanitized_name(&mut query, name);
+        }
 
         // Generating the selection set first as this will define all the operation arguments
         let selection_set = {
@@ -105,11 +114,24 @@ impl PreparedFederationEntityOperation {
         Ok(PreparedFederationEntityOperation {
             query,
             entities_variable_name,
+            depth: ctx.max_depth + 1,
             variables: ctx.into_query_variables(),
         })
     }
 }
 
+/// Writes the name with every character not allowed in a GraphQL name replaced by `_`, also
+/// prefixed with one if it starts with a digit.
+fn write_sanitized_name(out: &mut String, name: &str) {
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        out.push('_');
+    }
+    out.extend(
+        name.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' }),
+    );
+}
+
 /// All variables associated with a subgraph query. Each one is associated with the variable name
 /// "{$VARIABLE_PREFIX}{idx}" with `idx` being the position of the input value in the inner vec.
 pub struct QueryVariables(Vec<QueryInputValueId>);
@@ -135,6 +157,8 @@ pub struct QueryVariable {
 #[derive(Default)]
 pub struct QueryBuilderContext {
     variables: HashMap<QueryInputValueId, QueryVariable>,
+    depth: usize,
+    max_depth: usize,
 }
 
 impl QueryBuilderContext {
@@ -223,11 +247,14 @@ impl QueryBuilderContext {
             indent_write!(buffer, "{response_key}: {name}")?;
         }
         self.write_arguments(buffer, field.arguments())?;
+        self.depth += 1;
+        self.max_depth = self.max_depth.max(self.depth);
         if let Some(selection_set) = field.selection_set() {
             self.write_selection_set(EntityId::maybe_from(field.ty().inner().id()), buffer, selection_set)?;
         } else {
             buffer.push('\n');
         }
+        self.depth -= 1;
         Ok(())
     }
 
@@ -1,29 +1,43 @@
 use bytes::Bytes;
-use futures::Future;
+use futures::{channel::oneshot, future::Either, Future, FutureExt};
 use grafbase_telemetry::{
     gql_response_status::{GraphqlResponseStatus, SubgraphResponseStatus},
     span::{GqlRecorderSpanExt, GRAFBASE_TARGET},
+    trace_context::TraceContext,
 };
 use runtime::{
-    fetch::{FetchRequest, FetchResponse},
+    fetch::{FetchError, FetchRequest, FetchResponse},
     rate_limiting::RateLimitKey,
 };
-use schema::sources::graphql::{GraphqlEndpointId, GraphqlEndpointWalker};
+use schema::sources::graphql::{GraphqlEndpointId, GraphqlEndpointWalker, ResponseTransform};
 use tower::retry::budget::Budget;
 use tracing::Span;
-use web_time::Duration;
+use url::Url;
+use web_time::{Duration, Instant};
 
 use crate::{
     execution::{ExecutionContext, ExecutionError, ExecutionResult},
-    response::SubgraphResponse,
+    operation::OperationType,
+    response::{ErrorCode, GraphqlError, SubgraphResponse},
     Runtime,
 };
 
 pub trait ResponseIngester: Send {
     fn ingest(
         self,
-        bytes: Bytes,
+        body: Bytes,
     ) -> impl Future<Output = Result<(GraphqlResponseStatus, SubgraphResponse), ExecutionError>> + Send;
+
+    /// Ingests an expired cache entry, served instead of failing when the subgraph request did.
+    fn ingest_stale(
+        self,
+        body: Bytes,
+    ) -> impl Future<Output = Result<(GraphqlResponseStatus, SubgraphResponse), ExecutionError>> + Send
+    where
+        Self: Sized,
+    {
+        self.ingest(body)
+    }
 }
 
 impl<T> ResponseIngester for T
@@ -40,8 +54,10 @@ pub(super) async fn execute_subgraph_request<'ctx, 'a, R: Runtime>(
     span: Span,
     subgraph_id: GraphqlEndpointId,
     retry_budget: Option<&Budget>,
+    priority: i64,
     make_request: impl FnOnce() -> FetchRequest<'a> + Send,
     ingester: impl ResponseIngester,
+    stale_cache_key: Option<&str>,
 ) -> ExecutionResult<SubgraphResponse> {
     let subgraph = ctx.schema().walk(subgraph_id);
 
@@ -56,15 +72,119 @@ pub(super) async fn execute_subgraph_request<'ctx, 'a, R: Runtime>(
         )
         .await?;
 
+    // Subgraphs tend to reject oversized headers with an opaque 431, better to fail here with
+    // a clear message.
+    if let Some(max_size) = ctx.schema().settings.max_forwarded_header_bytes {
+        let size = headers_size(&request.headers);
+        if size > max_size {
+            return Err(GraphqlError::new(
+                format!(
+                    "Headers for subgraph '{}' amount to {size} bytes, exceeding the limit of {max_size} bytes",
+                    subgraph.name()
+                ),
+                ErrorCode::SubgraphRequestError,
+            )
+            .into());
+        }
+    }
+
     request
         .headers
         .insert(http::header::ACCEPT, http::HeaderValue::from_static("application/json"));
 
-    let fetch_response = retrying_fetch(ctx, &request, subgraph_id, retry_budget).await?;
+    // For APM tools relying on their own headers rather than `traceparent`.
+    let trace_headers = Some(&ctx.schema().settings.subgraph_trace_headers)
+        .filter(|headers| !headers.is_empty())
+        .and_then(|headers| Some((headers, TraceContext::of(&span)?)));
+    if let Some((trace_headers, trace_context)) = trace_headers {
+        for (name, template) in trace_headers {
+            let Ok(name) = http::HeaderName::from_bytes(name.as_bytes()) else {
+                continue;
+            };
+            if let Ok(value) = http::HeaderValue::from_str(&trace_context.render(template)) {
+                request.headers.insert(name, value);
+            }
+        }
+    }
+
+    // Compressed once, retries re-use the same body.
+    if let Some(threshold) = subgraph.request_compression_threshold() {
+        if request.json_body.len() >= threshold {
+            request.json_body =
+                gzip(&request.json_body).map_err(|err| format!("Failed to compress the request body: {err}"))?;
+            request
+                .headers
+                .insert(http::header::CONTENT_ENCODING, http::HeaderValue::from_static("gzip"));
+        }
+    }
+
+    let fetched = match subgraph
+        .shadow_url()
+        .filter(|_| matches!(ctx.operation.ty(), OperationType::Query))
+    {
+        Some(shadow_url) => {
+            // Both requests are sent concurrently, but the comparison runs in the background so
+            // that the client never waits for the shadow subgraph.
+            let (primary_sender, primary_receiver) = oneshot::channel();
+            ctx.engine.runtime.spawn(
+                shadow_compare(
+                    ctx,
+                    &request,
+                    shadow_url.clone(),
+                    subgraph.shadow_danger_accept_invalid_certs(),
+                    subgraph.name().to_string(),
+                    primary_receiver,
+                )
+                .boxed(),
+            );
+
+            let response = retrying_fetch(ctx, &span, &request, subgraph_id, retry_budget, priority).await;
+            if let Ok(response) = &response {
+                primary_sender.send(response.bytes.clone()).ok();
+            }
+            response
+        }
+        None => retrying_fetch(ctx, &span, &request, subgraph_id, retry_budget, priority).await,
+    };
+
+    // The subgraph, or a proxy in front of it, may recover from a server error. Whatever error
+    // it causes is hinted as retryable.
+    let server_error = fetched.as_ref().is_ok_and(|response| response.status.is_server_error());
+    let retryable_if_server_error = |err: ExecutionError| {
+        if server_error {
+            ExecutionError::Graphql(GraphqlError::from(err).with_retryable(true))
+        } else {
+            err
+        }
+    };
+    let fetched = fetched.map(|response| response.bytes);
+
+    // Stale responses come from the cache, which only ever stores ingested and thus already
+    // transformed bodies.
+    let fetched = match subgraph.response_transform() {
+        Some(transform) => fetched.and_then(|body| transform_response(transform, body)),
+        None => fetched,
+    };
+
+    let fetched = fetched
+        .and_then(|body| ensure_response_is_an_object(subgraph.name(), body))
+        .map_err(retryable_if_server_error);
 
-    tracing::debug!("{}", String::from_utf8_lossy(&fetch_response.bytes));
+    let ingested = match fetched {
+        Ok(body) => {
+            tracing::debug!("{}", String::from_utf8_lossy(&body));
+            ingester.ingest(body).await
+        }
+        Err(err) => match stale_fetch(ctx, stale_cache_key).await {
+            Some(bytes) => {
+                tracing::warn!(target: GRAFBASE_TARGET, "serving a stale cached response: {err}");
+                ingester.ingest_stale(bytes).await
+            }
+            None => return Err(err),
+        },
+    };
 
-    let (status, response) = ingester.ingest(fetch_response.bytes).await.inspect_err(|err| {
+    let (status, mut response) = ingested.map_err(retryable_if_server_error).inspect_err(|err| {
         let status = SubgraphResponseStatus::InvalidResponseError;
         span.record_subgraph_status(status);
         tracing::error!(target: GRAFBASE_TARGET, "{err}");
@@ -72,6 +192,18 @@ pub(super) async fn execute_subgraph_request<'ctx, 'a, R: Runtime>(
 
     span.record_subgraph_status(SubgraphResponseStatus::GraphqlResponse(status));
 
+    if server_error {
+        response.mark_subgraph_errors_retryable();
+    }
+
+    if subgraph.warn_on_extra_fields() && response.extra_fields_count() > 0 {
+        let count = response.extra_fields_count();
+        tracing::warn!(target: GRAFBASE_TARGET, "subgraph returned {count} field(s) which weren't requested");
+        ctx.engine
+            .subgraph_metrics
+            .record_extra_fields(subgraph.name(), count as u64);
+    }
+
     match response.subgraph_errors().next().map(|e| &e.message) {
         Some(error) => {
             tracing::error!(target: GRAFBASE_TARGET, "{error}");
@@ -84,51 +216,205 @@ pub(super) async fn execute_subgraph_request<'ctx, 'a, R: Runtime>(
     Ok(response)
 }
 
+/// Extracts the GraphQL response from the subgraph response body according to the subgraph
+/// configuration.
+fn transform_response(transform: &ResponseTransform, body: Bytes) -> ExecutionResult<Bytes> {
+    let mut value = serde_json::from_slice::<serde_json::Value>(&body)?;
+
+    for key in &transform.unwrap {
+        value = match value {
+            serde_json::Value::Object(mut object) => object.remove(key),
+            _ => None,
+        }
+        .ok_or_else(|| {
+            GraphqlError::new(
+                format!("Subgraph response has no '{key}' key to unwrap"),
+                ErrorCode::SubgraphInvalidResponseError,
+            )
+        })?;
+    }
+
+    if let serde_json::Value::Object(object) = &mut value {
+        for (from, to) in &transform.rename {
+            if let Some(renamed) = object.remove(from) {
+                object.insert(to.clone(), renamed);
+            }
+        }
+    }
+
+    Ok(Bytes::from(serde_json::to_vec(&value)?))
+}
+
+/// A subgraph answering with a top-level array is most likely not a GraphQL endpoint at all, so
+/// we fail with an explicit error rather than the deserializer's.
+fn ensure_response_is_an_object(subgraph_name: &str, body: Bytes) -> ExecutionResult<Bytes> {
+    let first_significant_byte = body
+        .iter()
+        .copied()
+        .find(|byte| !matches!(byte, b' ' | b'\t' | b'\n' | b'\r'));
+    if first_significant_byte == Some(b'[') {
+        return Err(GraphqlError::new(
+            format!("Subgraph '{subgraph_name}' returned a JSON array, expected a GraphQL response object"),
+            ErrorCode::SubgraphInvalidResponseError,
+        )
+        .into());
+    }
+    Ok(body)
+}
+
+fn headers_size(headers: &http::HeaderMap) -> usize {
+    headers
+        .iter()
+        .map(|(name, value)| name.as_str().len() + value.len())
+        .sum()
+}
+
+/// Last good response of the subgraph kept in the cache beyond its expiry, if any.
+async fn stale_fetch<R: Runtime>(ctx: ExecutionContext<'_, R>, key: Option<&str>) -> Option<Bytes> {
+    let key = key?;
+    ctx.engine
+        .runtime
+        .kv()
+        .get(key, None)
+        .await
+        .inspect_err(|err| tracing::warn!("Failed to read the cache key {key}: {err}"))
+        .ok()
+        .flatten()
+        .map(Bytes::from)
+}
+
+/// Sends a copy of the request to the shadow subgraph, without retries nor rate limiting, and
+/// compares its response with the primary one once received. Only owns its data, as it outlives
+/// the request. A failure is only logged, it must never affect the response of the primary
+/// subgraph.
+fn shadow_compare<R: Runtime>(
+    ctx: ExecutionContext<'_, R>,
+    request: &FetchRequest<'_>,
+    url: Url,
+    danger_accept_invalid_certs: bool,
+    subgraph_name: String,
+    primary: oneshot::Receiver<Bytes>,
+) -> impl Future<Output = ()> + Send + 'static {
+    let fetcher = ctx.engine.runtime.fetcher().clone();
+    let metrics = ctx.engine.subgraph_metrics.clone();
+    let headers = request.headers.clone();
+    let json_body = request.json_body.clone();
+    let timeout = request.timeout;
+
+    async move {
+        let request = FetchRequest {
+            url: &url,
+            headers,
+            json_body,
+            timeout,
+            danger_accept_invalid_certs,
+        };
+        let shadow_body = match fetcher.post(&request).await {
+            Ok(response) => response.bytes,
+            Err(err) => {
+                tracing::warn!(target: GRAFBASE_TARGET, "shadow subgraph request failed: {err}");
+                return;
+            }
+        };
+
+        // The primary request failed, nothing to compare with.
+        let Ok(primary) = primary.await else {
+            return;
+        };
+
+        if !responses_match(&primary, &shadow_body) {
+            tracing::warn!(target: GRAFBASE_TARGET, "shadow subgraph response differs from the primary one");
+            metrics.record_shadow_mismatch(&subgraph_name);
+        }
+    }
+}
+
+fn responses_match(primary: &[u8], shadow: &[u8]) -> bool {
+    let primary = serde_json::from_slice::<serde_json::Value>(primary);
+    let shadow = serde_json::from_slice::<serde_json::Value>(shadow);
+    matches!((primary, shadow), (Ok(primary), Ok(shadow)) if primary == shadow)
+}
+
+fn gzip(bytes: &[u8]) -> std::io::Result<Bytes> {
+    use std::io::Write;
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(bytes)?;
+    encoder.finish().map(Bytes::from)
+}
+
 async fn retrying_fetch<'ctx, R: Runtime>(
     ctx: ExecutionContext<'ctx, R>,
+    span: &Span,
     request: &FetchRequest<'_>,
     subgraph_id: GraphqlEndpointId,
     retry_budget: Option<&Budget>,
+    priority: i64,
 ) -> ExecutionResult<FetchResponse> {
     let subgraph = ctx.engine.schema.walk(subgraph_id);
 
-    let mut result = rate_limited_fetch(ctx, subgraph, request).await;
+    let mut attempt = rate_limited_fetch(ctx, subgraph, request, priority).await;
 
     let Some(retry_budget) = retry_budget else {
-        return result;
+        return attempt;
     };
 
-    let mut counter = 0;
+    let retry_config = subgraph.retry_config();
+    let max_attempts = retry_config.and_then(|config| config.max_attempts);
+    let base_delay = retry_config
+        .and_then(|config| config.base_delay)
+        .unwrap_or(DEFAULT_RETRY_BASE_DELAY);
 
-    loop {
-        match result {
-            Ok(bytes) => {
+    let mut retries = 0;
+
+    let result = loop {
+        match attempt {
+            Ok(response) if !is_retryable_status(response.status) => {
                 retry_budget.deposit();
-                return Ok(bytes);
+                break Ok(response);
             }
-            Err(err) => {
-                if retry_budget.withdraw().is_ok() {
+            failed => {
+                // Attempts are checked before any budget, so that a subgraph which exhausted them
+                // doesn't withdraw from either. The operation wide budget is checked first, so that
+                // an operation which exhausted it doesn't drain the subgraph one.
+                if max_attempts.map_or(true, |max| retries + 1 < max)
+                    && ctx.withdraw_operation_retry()
+                    && retry_budget.withdraw().is_ok()
+                {
                     let jitter = rand::random::<f64>() * 2.0;
-                    let exp_backoff = (100 * 2u64.pow(counter)) as f64;
-                    let backoff_ms = (exp_backoff * jitter).round() as u64;
+                    let backoff = base_delay.mul_f64(2f64.powi(retries as i32) * jitter);
 
-                    ctx.engine.runtime.sleep(Duration::from_millis(backoff_ms)).await;
+                    ctx.engine.runtime.sleep(backoff).await;
 
-                    counter += 1;
+                    retries += 1;
 
-                    result = rate_limited_fetch(ctx, subgraph, request).await;
+                    attempt = rate_limited_fetch(ctx, subgraph, request, priority).await;
                 } else {
-                    return Err(err);
+                    // The body of the last unavailable response may still carry GraphQL errors.
+                    break failed;
                 }
             }
         }
-    }
+    };
+
+    span.record("subgraph.retry_count", retries);
+
+    result
+}
+
+/// Statuses of a gateway or server in front of the subgraph which is temporarily unavailable.
+fn is_retryable_status(status: http::StatusCode) -> bool {
+    matches!(
+        status,
+        http::StatusCode::BAD_GATEWAY | http::StatusCode::SERVICE_UNAVAILABLE | http::StatusCode::GATEWAY_TIMEOUT
+    )
 }
 
 async fn rate_limited_fetch<'ctx, R: Runtime>(
     ctx: ExecutionContext<'ctx, R>,
     subgraph: GraphqlEndpointWalker<'ctx>,
     request: &FetchRequest<'_>,
+    priority: i64,
 ) -> ExecutionResult<FetchResponse> {
     ctx.engine
         .runtime
@@ -136,13 +422,37 @@ async fn rate_limited_fetch<'ctx, R: Runtime>(
         .limit(&RateLimitKey::Subgraph(subgraph.name().into()))
         .await?;
 
-    ctx.engine
-        .runtime
-        .fetcher()
-        .post(request)
-        .await
-        .map_err(|error| ExecutionError::Fetch {
-            subgraph_name: subgraph.name().to_string(),
-            error,
-        })
+    // Operations outlive their subgraph requests, so their address identifies them among the
+    // ones waiting for a slot.
+    let operation = std::ptr::from_ref(ctx.operation) as usize;
+    let _permit = ctx
+        .engine
+        .acquire_subgraph_permit(subgraph.id(), operation, priority)
+        .await;
+
+    let fetch_error = |error| ExecutionError::Fetch {
+        subgraph_name: subgraph.name().to_string(),
+        error,
+    };
+
+    let start = Instant::now();
+
+    // Reading the whole body is part of the fetch, so an interrupted body is retried like any
+    // other fetch failure.
+    let fetch = std::pin::pin!(async { ctx.engine.runtime.fetcher().post(request).await.map_err(fetch_error) });
+
+    // Not every fetcher enforces the timeout on the whole body. Dropping the fetch once it
+    // expires cancels the request, so a late response can never be ingested.
+    let result = match futures::future::select(fetch, ctx.engine.runtime.sleep(request.timeout)).await {
+        Either::Left((result, _)) => result,
+        Either::Right(((), _)) => Err(fetch_error(FetchError::Timeout)),
+    };
+
+    if let Some(health) = &ctx.engine.subgraph_health {
+        health.record(subgraph.id(), start.elapsed(), result.is_err());
+    }
+
+    result
 }
+
+const DEFAULT_RETRY_BASE_DELAY: Duration = Duration::from_millis(100);
@@ -86,6 +86,7 @@ fn ingest_response(
         RootGraphqlErrors {
             response,
             response_keys: plan.response_keys(),
+            max_extension_size: plan.schema().settings.max_error_extension_size,
         },
     )
     .deserialize(subgraph_response)?;
@@ -19,6 +19,8 @@ impl IntrospectionPreparedExecutor {
         writer::IntrospectionWriter {
             schema: ctx.engine.schema.walker(),
             metadata: ctx.engine.schema.walker().introspection_metadata(),
+            feature_flags: ctx.feature_flags(),
+            tenant: ctx.tenant(),
             shapes: &plan.blueprint().shapes,
             plan,
             response: subgraph_response.as_mut().next_writer().ok_or("No objects to update")?,
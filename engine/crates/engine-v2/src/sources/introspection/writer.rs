@@ -8,6 +8,7 @@ use schema::{
 };
 
 use crate::{
+    engine::{FeatureFlags, Tenant},
     execution::{PlanField, PlanWalker},
     response::{
         ConcreteObjectShapeId, FieldShape, ResponseObject, ResponseObjectField, ResponseValue, ResponseWriter, Shapes,
@@ -17,6 +18,8 @@ use crate::{
 pub(super) struct IntrospectionWriter<'a> {
     pub schema: SchemaWalker<'a, ()>,
     pub metadata: &'a IntrospectionMetadata,
+    pub feature_flags: &'a FeatureFlags,
+    pub tenant: Option<&'a Tenant>,
     pub shapes: &'a Shapes,
     pub plan: PlanWalker<'a, (), ()>,
     pub response: ResponseWriter<'a>,
@@ -219,6 +222,13 @@ impl<'a> IntrospectionWriter<'a> {
                         .filter(|field| {
                             (!field.directives().has_deprecated() || include_deprecated)
                                 && !self.metadata.meta_fields.contains(&field.id())
+                                && field
+                                    .directives()
+                                    .feature_flag()
+                                    .map_or(true, |flag| self.feature_flags.contains(flag))
+                                && self
+                                    .tenant
+                                    .map_or(true, |tenant| tenant.can_see(field.directives().tags()))
                         })
                         .map(|field| self.__field(field, shape_id))
                         .collect::<Vec<_>>();
@@ -45,7 +45,7 @@
 use futures::{future::BoxFuture, FutureExt};
 use futures_util::stream::BoxStream;
 use schema::{Resolver, ResolverWalker};
-use std::future::Future;
+use std::{future::Future, time::Duration};
 
 use crate::{
     execution::{ExecutionContext, ExecutionError, ExecutionResult, PlanWalker, PlanningResult, SubscriptionResponse},
@@ -91,6 +91,15 @@ impl PreparedExecutor {
 }
 
 impl PreparedExecutor {
+    /// Timeout of the subgraph request, if any is made.
+    pub fn timeout(&self) -> Option<Duration> {
+        match self {
+            PreparedExecutor::GraphQL(prepared) => Some(prepared.timeout()),
+            PreparedExecutor::FederationEntity(prepared) => Some(prepared.timeout()),
+            PreparedExecutor::Introspection(_) => None,
+        }
+    }
+
     pub fn execute<'ctx, 'fut, R: Runtime>(
         &'ctx self,
         ctx: ExecutionContext<'ctx, R>,
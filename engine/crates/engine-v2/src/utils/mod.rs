@@ -1,3 +1,5 @@
 mod pool;
+mod truncate;
 
 pub(crate) use pool::*;
+pub(crate) use truncate::*;
@@ -0,0 +1,35 @@
+/// Truncates `text` to at most `max_len` bytes, moving back to the closest char boundary, and
+/// appends `indicator` if anything was cut. The indicator isn't counted in the length.
+pub(crate) fn truncate_at_char_boundary(text: &mut String, max_len: usize, indicator: &str) {
+    if text.len() <= max_len {
+        return;
+    }
+    let mut end = max_len;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text.truncate(end);
+    text.push_str(indicator);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::truncate_at_char_boundary;
+
+    #[test]
+    fn short_text_is_kept() {
+        let mut text = "abc".to_string();
+        truncate_at_char_boundary(&mut text, 3, "…");
+
+        assert_eq!(text, "abc");
+    }
+
+    #[test]
+    fn truncation_never_splits_a_char() {
+        // 'é' takes two bytes, the limit falls in its middle.
+        let mut text = "aé".to_string();
+        truncate_at_char_boundary(&mut text, 2, "…");
+
+        assert_eq!(text, "a…");
+    }
+}
@@ -19,7 +19,9 @@ use std::{
 };
 
 use engine_value::Name;
-pub use parse::{parse_const_value, parse_field, parse_query, parse_schema, parse_selection_set};
+pub use parse::{
+    parse_const_value, parse_field, parse_query, parse_query_with_abort_check, parse_schema, parse_selection_set,
+};
 use pest::{error::LineColLocation, RuleType};
 pub use pos::{Pos, Positioned};
 use serde::{Serialize, Serializer};
@@ -85,6 +87,8 @@ pub enum Error {
     },
     /// The document does not contain any operation.
     MissingOperation,
+    /// Parsing was aborted by the caller's abort check, typically because it took too long.
+    Aborted,
 }
 
 impl Error {
@@ -103,7 +107,7 @@ impl Error {
             Self::MultipleOperations { anonymous, operation } => ErrorPositions::new_2(*anonymous, *operation),
             Self::OperationDuplicated { first, second, .. } => ErrorPositions::new_2(*second, *first),
             Self::FragmentDuplicated { first, second, .. } => ErrorPositions::new_2(*second, *first),
-            Self::MissingOperation => ErrorPositions::new_0(),
+            Self::MissingOperation | Self::Aborted => ErrorPositions::new_0(),
         }
     }
 }
@@ -124,6 +128,7 @@ impl Display for Error {
                 write!(f, "fragment {fragment} is defined twice")
             }
             Self::MissingOperation => f.write_str("document does not contain an operation"),
+            Self::Aborted => f.write_str("parsing was aborted"),
         }
     }
 }
@@ -8,12 +8,29 @@ use super::*;
 ///
 /// Fails if the query is not a valid GraphQL document.
 pub fn parse_query<T: AsRef<str>>(input: T) -> Result<ExecutableDocument> {
-    let mut pc = PositionCalculator::new(input.as_ref());
+    parse_executable_document(PositionCalculator::new(input.as_ref()), input.as_ref())
+}
 
-    let items = parse_definition_items(
-        exactly_one(GraphQLParser::parse(Rule::executable_document, input.as_ref())?),
-        &mut pc,
-    )?;
+/// Parse a GraphQL query document, calling `should_abort` regularly and giving up as soon as it
+/// returns true. It isn't called while the input is tokenized, only between that and every
+/// definition, selection and value of the document being built.
+///
+/// # Errors
+///
+/// Fails if the query is not a valid GraphQL document or with [`Error::Aborted`] if the parsing was
+/// aborted.
+pub fn parse_query_with_abort_check<T: AsRef<str>>(
+    input: T,
+    should_abort: &dyn Fn() -> bool,
+) -> Result<ExecutableDocument> {
+    let input = input.as_ref();
+    parse_executable_document(PositionCalculator::with_abort_check(input, should_abort), input)
+}
+
+fn parse_executable_document(mut pc: PositionCalculator<'_>, input: &str) -> Result<ExecutableDocument> {
+    pc.check_abort()?;
+    let document = exactly_one(GraphQLParser::parse(Rule::executable_document, input)?);
+    let items = parse_definition_items(document, &mut pc)?;
 
     let mut operations = None;
     let mut fragments: HashMap<_, Positioned<FragmentDefinition>> = HashMap::new();
@@ -133,6 +150,7 @@ enum DefinitionItem {
 
 fn parse_definition_item(pair: Pair<Rule>, pc: &mut PositionCalculator) -> Result<DefinitionItem> {
     debug_assert_eq!(pair.as_rule(), Rule::executable_definition);
+    pc.check_abort()?;
 
     let pair = exactly_one(pair.into_inner());
     Ok(match pair.as_rule() {
@@ -253,6 +271,7 @@ fn parse_selection_set_internal(pair: Pair<Rule>, pc: &mut PositionCalculator) -
 
 fn parse_selection(pair: Pair<Rule>, pc: &mut PositionCalculator) -> Result<Positioned<Selection>> {
     debug_assert_eq!(pair.as_rule(), Rule::selection);
+    pc.check_abort()?;
 
     let pos = pc.step(&pair);
     let pair = exactly_one(pair.into_inner());
@@ -421,4 +440,21 @@ mod tests {
         assert!(parse_query(query_ok).is_ok());
         assert!(parse_query(query_overflow).is_ok());
     }
+
+    #[test]
+    fn test_parse_aborted_midway() {
+        let query = format!("{{ {} }}", "a: __typename ".repeat(100));
+        let checks = std::cell::Cell::new(0);
+        let should_abort = || {
+            checks.set(checks.get() + 1);
+            checks.get() > 10
+        };
+
+        assert!(matches!(
+            parse_query_with_abort_check(&query, &should_abort),
+            Err(Error::Aborted)
+        ));
+        assert_eq!(checks.get(), 11);
+        assert!(parse_query_with_abort_check(&query, &|| false).is_ok());
+    }
 }
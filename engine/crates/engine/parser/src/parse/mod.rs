@@ -22,7 +22,7 @@ mod service;
 mod utils;
 
 use engine_value::{ConstValue, Name, Number, Value};
-pub use executable::{parse_field, parse_query, parse_selection_set};
+pub use executable::{parse_field, parse_query, parse_query_with_abort_check, parse_selection_set};
 pub use service::parse_schema;
 
 #[derive(Parser)]
@@ -112,6 +112,7 @@ fn parse_const_value_impl(pair: Pair<'_, Rule>, pc: &mut PositionCalculator<'_>)
 }
 fn parse_value(pair: Pair<Rule>, pc: &mut PositionCalculator<'_>) -> Result<Positioned<Value>> {
     debug_assert_eq!(pair.as_rule(), Rule::value);
+    pc.check_abort()?;
 
     let pos = pc.step(&pair);
     let pair = exactly_one(pair.into_inner());
@@ -10,6 +10,8 @@ use std::{
 use pest::{iterators::Pair, RuleType};
 use serde::{Deserialize, Serialize};
 
+use crate::Error;
+
 /// Original position of an element in source code.
 ///
 /// You can serialize and deserialize it to the GraphQL `locations` format
@@ -148,6 +150,7 @@ pub struct PositionCalculator<'a> {
     pos: usize,
     line: usize,
     column: usize,
+    should_abort: Option<&'a dyn Fn() -> bool>,
 }
 
 impl<'a> PositionCalculator<'a> {
@@ -157,6 +160,22 @@ impl<'a> PositionCalculator<'a> {
             pos: 0,
             line: 1,
             column: 1,
+            should_abort: None,
+        }
+    }
+
+    pub(crate) fn with_abort_check(input: &'a str, should_abort: &'a dyn Fn() -> bool) -> PositionCalculator<'a> {
+        Self {
+            should_abort: Some(should_abort),
+            ..Self::new(input)
+        }
+    }
+
+    /// Called regularly while building the document, fails once the abort check returns true.
+    pub(crate) fn check_abort(&self) -> Result<(), Error> {
+        match self.should_abort {
+            Some(should_abort) if should_abort() => Err(Error::Aborted),
+            _ => Ok(()),
         }
     }
 
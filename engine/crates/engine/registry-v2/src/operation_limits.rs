@@ -6,6 +6,10 @@ pub struct OperationLimits {
     pub aliases: Option<u16>,
     pub root_fields: Option<u16>,
     pub complexity: Option<u16>,
+    pub fragment_depth: Option<u16>,
+    pub fragment_spreads: Option<u16>,
+    pub fields: Option<u16>,
+    pub aliased_fetches: Option<u16>,
 }
 
 impl OperationLimits {
@@ -22,6 +26,10 @@ impl From<gateway_config::OperationLimitsConfig> for OperationLimits {
             aliases: value.aliases,
             root_fields: value.root_fields,
             complexity: value.complexity,
+            fragment_depth: value.fragment_depth,
+            fragment_spreads: value.fragment_spreads,
+            fields: value.fields,
+            aliased_fetches: value.aliased_fetches,
         }
     }
 }
@@ -11,6 +11,8 @@ pub enum StreamingFormat {
     ///
     /// [1]: https://github.com/graphql/graphql-over-http/blob/main/rfcs/GraphQLOverSSE.md
     GraphQLOverSSE,
+    /// Newline-delimited JSON over a chunked response, for networks blocking both of the above.
+    JsonLines,
 }
 
 impl headers::Header for StreamingFormat {
@@ -37,6 +39,7 @@ impl headers::Header for StreamingFormat {
             http::HeaderValue::try_from(match self {
                 StreamingFormat::IncrementalDelivery => INCREMENTAL_MEDIA_TYPE.to_string(),
                 StreamingFormat::GraphQLOverSSE => SSE_MEDIA_TYPE.to_string(),
+                StreamingFormat::JsonLines => JSON_LINES_MEDIA_TYPE.to_string(),
             })
             .unwrap(),
         ))
@@ -47,6 +50,8 @@ const INCREMENTAL_MEDIA_TYPE: MediaType<'static> =
     MediaType::new(Name::new_unchecked("multipart"), Name::new_unchecked("mixed"));
 const SSE_MEDIA_TYPE: MediaType<'static> =
     MediaType::new(Name::new_unchecked("text"), Name::new_unchecked("event-stream"));
+const JSON_LINES_MEDIA_TYPE: MediaType<'static> =
+    MediaType::new(Name::new_unchecked("application"), Name::new_unchecked("x-ndjson"));
 
 impl StreamingFormat {
     pub fn from_accept_header(header: &str) -> Option<Self> {
@@ -56,7 +61,7 @@ impl StreamingFormat {
                 // Get the mediatype without parameters
                 let essence = mediatype.essence();
 
-                essence == INCREMENTAL_MEDIA_TYPE || essence == SSE_MEDIA_TYPE
+                essence == INCREMENTAL_MEDIA_TYPE || essence == SSE_MEDIA_TYPE || essence == JSON_LINES_MEDIA_TYPE
             })
             .map(|mediatype| {
                 let quality_value = mediatype
@@ -76,6 +81,8 @@ impl StreamingFormat {
             Some(Self::IncrementalDelivery)
         } else if mediatype == SSE_MEDIA_TYPE {
             Some(Self::GraphQLOverSSE)
+        } else if mediatype == JSON_LINES_MEDIA_TYPE {
+            Some(Self::JsonLines)
         } else {
             None
         }
@@ -126,6 +133,15 @@ mod tests {
             Some(StreamingFormat::GraphQLOverSSE)
         );
 
+        assert_eq!(
+            StreamingFormat::from_accept_header("application/x-ndjson"),
+            Some(StreamingFormat::JsonLines)
+        );
+        assert_eq!(
+            StreamingFormat::from_accept_header("application/x-ndjson,application/json;q=0.9"),
+            Some(StreamingFormat::JsonLines)
+        );
+
         assert_eq!(
             StreamingFormat::from_accept_header("text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8"),
             None
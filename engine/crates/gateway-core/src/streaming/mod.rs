@@ -43,6 +43,11 @@ where
 
             Box::pin(sse_stream(payload_stream, sse_sender, response_stream))
         }
+        StreamingFormat::JsonLines => Box::pin(payload_stream.map(|payload| {
+            let mut line = serde_json::to_vec(&payload).map_err(|e| e.to_string())?;
+            line.push(b'\n');
+            Ok(Bytes::from(line))
+        })),
     };
 
     let mut headers = http::HeaderMap::new();
@@ -52,6 +57,7 @@ where
             .parse::<mime::Mime>()
             .expect("Valid Mime"),
         StreamingFormat::GraphQLOverSSE => mime::TEXT_EVENT_STREAM,
+        StreamingFormat::JsonLines => "application/x-ndjson".parse::<mime::Mime>().expect("Valid Mime"),
     }));
 
     (headers, bytes_stream)
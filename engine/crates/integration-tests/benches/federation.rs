@@ -78,5 +78,62 @@ pub fn basic_federation(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, introspection, basic_federation);
+pub fn large_entities(c: &mut Criterion) {
+    const REVIEWS_COUNT: usize = 2_000;
+
+    let reviews = (0..REVIEWS_COUNT)
+        .map(|i| {
+            let body = format!("Review number {i}, which is long enough to not be entirely trivial to deserialize.");
+            json!({"body": body, "product": {"reviews": [{"author": {"id": "1234"}, "body": body}]}})
+        })
+        .collect::<Vec<_>>();
+
+    let bench = runtime().block_on(DeterministicEngine::new(
+        SCHEMA,
+        r#"
+        query ExampleQuery {
+            me {
+                id
+                username
+                reviews {
+                    body
+                    product {
+                        reviews {
+                            author {
+                                id
+                                username
+                            }
+                            body
+                        }
+                    }
+                }
+            }
+        }
+        "#,
+        &[
+            json!({"data":{"me":{"id":"1234","username":"Me"}}}),
+            json!({"data":{"_entities":[{"__typename":"User","reviews":reviews}]}}),
+            json!({"data":{"_entities":vec![json!({"__typename":"User","username":"Me"}); REVIEWS_COUNT]}}),
+        ],
+    ));
+    let response = runtime().block_on(bench.execute());
+
+    // Sanity check it works, the response is too big for a snapshot.
+    assert_eq!(
+        response.into_data()["me"]["reviews"].as_array().map(Vec::len),
+        Some(REVIEWS_COUNT)
+    );
+
+    c.bench_function("large_entities", |b| {
+        b.to_async(
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap(),
+        )
+        .iter(|| bench.execute());
+    });
+}
+
+criterion_group!(benches, introspection, basic_federation, large_entities);
 criterion_main!(benches);
@@ -8,6 +8,7 @@ pub use bench::*;
 use engine_config_builder::{build_with_sdl_config, build_with_toml_config};
 use federated_graph::FederatedGraphV3;
 use futures::{future::BoxFuture, stream::FuturesUnordered, FutureExt, StreamExt};
+use grafbase_telemetry::otel::opentelemetry::metrics::Meter;
 use graphql_composition::FederatedGraph;
 use graphql_mocks::MockGraphQlServer;
 use parser_sdl::{connector_parsers::MockConnectorParsers, federation::FederatedGraphConfig};
@@ -97,6 +98,11 @@ impl EngineV2Builder {
         self.runtime.fetcher = runtime::fetch::Fetcher::new(fetcher);
         self
     }
+
+    pub fn with_meter(mut self, meter: Meter) -> Self {
+        self.runtime.meter = meter;
+        self
+    }
     //-- Runtime customization --
 
     pub async fn build(mut self) -> TestEngineV2 {
@@ -170,10 +176,12 @@ impl EngineV2Builder {
         }
         .into_latest();
 
+        let background_tasks = Arc::clone(&self.runtime.background_tasks);
         let engine = engine_v2::Engine::new(Arc::new(config.try_into().unwrap()), None, self.runtime).await;
 
         TestEngineV2 {
             engine: Arc::new(engine),
+            background_tasks,
             subgraphs: subgraphs.into_iter().collect(),
         }
     }
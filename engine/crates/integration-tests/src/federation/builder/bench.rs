@@ -52,6 +52,7 @@ impl<'a> DeterministicEngineBuilder<'a> {
             self.subgraphs_json_responses
                 .into_iter()
                 .map(|resp| FetchResponse {
+                    status: http::StatusCode::OK,
                     bytes: resp.into_bytes().into(),
                 })
                 .collect(),
@@ -4,7 +4,12 @@ use runtime_local::{
     rate_limiting::in_memory::key_based::InMemoryRateLimiter, InMemoryHotCacheFactory, InMemoryKvStore, NativeFetcher,
 };
 use runtime_noop::trusted_documents::NoopTrustedDocuments;
-use tokio::sync::watch;
+use std::sync::{Arc, Mutex};
+
+use tokio::{sync::watch, task::JoinHandle};
+
+/// Futures spawned by the engine, kept so that tests can wait for them.
+pub type BackgroundTasks = Arc<Mutex<Vec<JoinHandle<()>>>>;
 
 pub struct TestRuntime {
     pub fetcher: runtime::fetch::Fetcher,
@@ -13,6 +18,7 @@ pub struct TestRuntime {
     pub meter: opentelemetry::metrics::Meter,
     pub hooks: DynamicHooks,
     pub rate_limiter: runtime::rate_limiting::RateLimiter,
+    pub background_tasks: BackgroundTasks,
 }
 
 impl Default for TestRuntime {
@@ -26,6 +32,7 @@ impl Default for TestRuntime {
             meter: metrics::meter_from_global_provider(),
             hooks: Default::default(),
             rate_limiter: InMemoryRateLimiter::runtime_with_watcher(rx),
+            background_tasks: Default::default(),
         }
     }
 }
@@ -65,4 +72,8 @@ impl engine_v2::Runtime for TestRuntime {
     fn sleep(&self, duration: std::time::Duration) -> futures::prelude::future::BoxFuture<'static, ()> {
         Box::pin(tokio::time::sleep(duration))
     }
+
+    fn spawn(&self, future: futures::prelude::future::BoxFuture<'static, ()>) {
+        self.background_tasks.lock().unwrap().push(tokio::spawn(future));
+    }
 }
@@ -20,10 +20,26 @@ use headers::HeaderMapExt;
 use http::{header::Entry, HeaderName, HeaderValue};
 use serde::de::Error;
 
-use crate::engine_v1::GraphQlRequest;
+use crate::{engine_v1::GraphQlRequest, fetch::MockFetch};
+
+/// Builds an engine from the federated SDL and TOML configuration, with subgraphs answering
+/// from the mock, and executes the query against it.
+pub fn execute_with_mock(sdl: &str, toml: &str, responses: MockFetch, query: &str) -> GraphqlResponse {
+    crate::runtime().block_on(async move {
+        let engine = engine_v2::Engine::builder()
+            .with_federated_sdl(sdl)
+            .with_toml_config(toml)
+            .with_mock_fetcher(responses)
+            .build()
+            .await;
+
+        engine.execute(query).await
+    })
+}
 
 pub struct TestEngineV2 {
     engine: Arc<engine_v2::Engine<TestRuntime>>,
+    background_tasks: BackgroundTasks,
     subgraphs: HashMap<TypeId, Subgraph>,
 }
 
@@ -54,6 +70,41 @@ impl TestEngineV2 {
         }
     }
 
+    pub async fn execute_batch(
+        &self,
+        requests: impl IntoIterator<Item = impl Into<GraphQlRequest>>,
+    ) -> GraphqlResponse {
+        let request = BatchRequest::Batch(
+            requests
+                .into_iter()
+                .map(|request| request.into().into_engine_request())
+                .collect(),
+        );
+        self.engine
+            .execute(http::HeaderMap::new(), request)
+            .await
+            .try_into()
+            .unwrap()
+    }
+
+    /// Session of a new WebSocket connection.
+    pub async fn create_websocket_session(&self) -> engine_v2::Session<TestRuntime> {
+        self.engine.create_session(http::HeaderMap::new()).await.unwrap()
+    }
+
+    /// Waits for the futures the engine ran in the background, such as shadow comparisons.
+    pub async fn wait_for_background_tasks(&self) {
+        let tasks = std::mem::take(&mut *self.background_tasks.lock().unwrap());
+        for task in tasks {
+            task.await.unwrap();
+        }
+    }
+
+    /// Purges the cached responses tagged with the surrogate key.
+    pub fn invalidate_by_key(&self, key: &str) -> usize {
+        self.engine.invalidate_by_key(key)
+    }
+
     pub fn subgraph<S: graphql_mocks::Subgraph>(&self) -> &Subgraph {
         self.subgraphs.get(&std::any::TypeId::of::<S>()).unwrap()
     }
@@ -124,6 +175,20 @@ impl ExecutionRequest {
     pub fn into_multipart_stream(self) -> MultipartStreamRequest {
         MultipartStreamRequest(self)
     }
+
+    pub fn into_json_lines_stream(self) -> JsonLinesStreamRequest {
+        JsonLinesStreamRequest(self)
+    }
+
+    /// Executes the request and returns the response body as is, for tests about its formatting.
+    pub async fn into_raw_body(self) -> String {
+        let headers = self.http_headers();
+        let request = BatchRequest::Single(self.request.into_engine_request());
+        match self.engine.execute(headers, request).await.body {
+            HttpGraphqlResponseBody::Bytes(bytes) => String::from_utf8(bytes.as_ref().to_vec()).unwrap(),
+            HttpGraphqlResponseBody::Stream(_) => panic!("Unexpected stream response body"),
+        }
+    }
 }
 
 impl IntoFuture for ExecutionRequest {
@@ -170,6 +235,51 @@ impl IntoFuture for MultipartStreamRequest {
     }
 }
 
+pub struct JsonLinesStreamRequest(ExecutionRequest);
+
+impl JsonLinesStreamRequest {
+    pub async fn collect<B>(self) -> B
+    where
+        B: Default + Extend<serde_json::Value>,
+    {
+        self.await.stream.collect().await
+    }
+}
+
+impl IntoFuture for JsonLinesStreamRequest {
+    type Output = GraphqlStreamingResponse;
+
+    type IntoFuture = BoxFuture<'static, Self::Output>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        let mut headers = self.0.http_headers();
+        headers.typed_insert(StreamingFormat::JsonLines);
+        let request = BatchRequest::Single(self.0.request.into_engine_request());
+        Box::pin(async move {
+            let response = self.0.engine.execute(headers, request).await;
+            // Lines may be split across chunks, or share one.
+            let stream = futures::stream::unfold(
+                (response.body.into_stream(), Vec::<u8>::new()),
+                |(mut chunks, mut buffer)| async move {
+                    loop {
+                        if let Some(end) = buffer.iter().position(|byte| *byte == b'\n') {
+                            let line = buffer.drain(..=end).collect::<Vec<_>>();
+                            let value = serde_json::from_slice(&line).unwrap();
+                            return Some((value, (chunks, buffer)));
+                        }
+                        let chunk = chunks.next().await?.unwrap();
+                        buffer.extend_from_slice(&chunk);
+                    }
+                },
+            );
+            GraphqlStreamingResponse {
+                stream: Box::pin(stream),
+                headers: response.headers,
+            }
+        })
+    }
+}
+
 pub struct GraphqlStreamingResponse {
     pub stream: BoxStream<'static, serde_json::Value>,
     pub headers: http::HeaderMap,
@@ -1,6 +1,10 @@
 use std::{
     collections::HashMap,
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
 };
 
 use futures::stream::BoxStream;
@@ -9,8 +13,14 @@ use runtime::fetch::{FetchError, FetchRequest, FetchResponse, FetchResult, Graph
 
 #[derive(Clone, Default)]
 pub struct MockFetch {
-    responses: Arc<Mutex<HashMap<String, crossbeam_queue::SegQueue<Vec<u8>>>>>,
+    responses: Arc<Mutex<HashMap<String, crossbeam_queue::SegQueue<(http::StatusCode, Vec<u8>)>>>>,
     requests: Arc<crossbeam_queue::SegQueue<(String, ReceivedRequest)>>,
+    bodies: Arc<crossbeam_queue::SegQueue<String>>,
+    hosts_without_tls_verification: Arc<crossbeam_queue::SegQueue<String>>,
+    in_flight: Arc<AtomicUsize>,
+    max_in_flight: Arc<AtomicUsize>,
+    delay: Option<Duration>,
+    delay_by_host: HashMap<String, Duration>,
 }
 
 impl MockFetch {
@@ -19,35 +29,112 @@ impl MockFetch {
         let mut responses_by_host = self.responses.lock().unwrap();
         let queue = responses_by_host.entry(host.to_string()).or_default();
         for response in responses {
-            queue.push(serde_json::to_vec(&response).unwrap());
+            queue.push((http::StatusCode::OK, serde_json::to_vec(&response).unwrap()));
         }
         drop(responses_by_host);
         self
     }
 
+    /// Queues a response sent with the given HTTP status, after those already queued for the host.
+    #[must_use]
+    pub fn with_status_response<R: serde::Serialize>(self, host: &str, status: u16, response: R) -> Self {
+        let status = http::StatusCode::from_u16(status).unwrap();
+        self.responses
+            .lock()
+            .unwrap()
+            .entry(host.to_string())
+            .or_default()
+            .push((status, serde_json::to_vec(&response).unwrap()));
+        self
+    }
+
+    /// Delays every response, keeping requests in flight for a while.
+    #[must_use]
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    /// Delays the responses of a single host, taking precedence over `with_delay`.
+    #[must_use]
+    pub fn with_host_delay(mut self, host: &str, delay: Duration) -> Self {
+        self.delay_by_host.insert(host.to_string(), delay);
+        self
+    }
+
     pub fn drain_received_requests(&self) -> impl Iterator<Item = (String, ReceivedRequest)> + '_ {
         std::iter::from_fn(|| self.requests.pop())
     }
+
+    /// Request bodies as sent, for what can't be observed after parsing them such as numbers
+    /// beyond 64 bits.
+    pub fn drain_received_bodies(&self) -> impl Iterator<Item = String> + '_ {
+        std::iter::from_fn(|| self.bodies.pop())
+    }
+
+    /// Largest number of requests that were awaiting their response at the same time.
+    pub fn max_in_flight_requests(&self) -> usize {
+        self.max_in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Hosts of the requests sent without verifying the TLS certificates.
+    pub fn drain_hosts_without_tls_verification(&self) -> impl Iterator<Item = String> + '_ {
+        std::iter::from_fn(|| self.hosts_without_tls_verification.pop())
+    }
+}
+
+fn decode_body(request: &FetchRequest<'_>) -> Vec<u8> {
+    let is_gzipped = request
+        .headers
+        .get(http::header::CONTENT_ENCODING)
+        .is_some_and(|encoding| encoding == "gzip");
+
+    if is_gzipped {
+        use std::io::Read;
+
+        let mut body = Vec::new();
+        flate2::read::GzDecoder::new(request.json_body.as_ref())
+            .read_to_end(&mut body)
+            .unwrap();
+        body
+    } else {
+        request.json_body.to_vec()
+    }
 }
 
 #[async_trait::async_trait]
 impl runtime::fetch::FetcherInner for MockFetch {
     async fn post(&self, request: &FetchRequest<'_>) -> FetchResult<FetchResponse> {
         let host = request.url.host_str().unwrap();
+        let body = decode_body(request);
         self.requests.push((
             host.to_string(),
             ReceivedRequest {
                 headers: request.headers.clone(),
-                body: serde_json::from_slice(&request.json_body).unwrap(),
+                body: serde_json::from_slice(&body).unwrap(),
             },
         ));
+        self.bodies.push(String::from_utf8(body).unwrap());
+        if request.danger_accept_invalid_certs {
+            self.hosts_without_tls_verification.push(host.to_string());
+        }
+
+        let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+        if let Some(delay) = self.delay_by_host.get(host).copied().or(self.delay) {
+            tokio::time::sleep(delay).await;
+        }
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
 
         self.responses
             .lock()
             .unwrap()
             .get(host)
             .and_then(|responses| responses.pop())
-            .map(|bytes| FetchResponse { bytes: bytes.into() })
+            .map(|(status, bytes)| FetchResponse {
+                status,
+                bytes: bytes.into(),
+            })
             .ok_or(FetchError::any("No more responses"))
     }
 
@@ -4,6 +4,7 @@ pub mod engine_v1;
 pub mod federation;
 pub mod fetch;
 pub mod helpers;
+pub mod metrics;
 pub mod mongodb;
 pub mod openid;
 pub mod postgres;
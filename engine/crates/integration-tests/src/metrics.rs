@@ -0,0 +1,107 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+};
+
+use grafbase_telemetry::otel::opentelemetry::{
+    metrics::{Counter, Histogram, InstrumentProvider, Meter, SyncCounter, SyncHistogram, Unit},
+    KeyValue,
+};
+
+/// Keeps track of the values added to the `u64` counters created through its meter. Other
+/// instruments are no-ops.
+#[derive(Clone, Default)]
+pub struct CountersRecorder {
+    counters: Arc<Mutex<HashMap<Cow<'static, str>, u64>>>,
+}
+
+impl CountersRecorder {
+    pub fn meter(&self) -> Meter {
+        Meter::new(Arc::new(self.clone()))
+    }
+
+    /// Total added to the counter so far, ignoring attributes.
+    pub fn get(&self, name: &str) -> u64 {
+        self.counters.lock().unwrap().get(name).copied().unwrap_or_default()
+    }
+}
+
+impl InstrumentProvider for CountersRecorder {
+    fn u64_counter(
+        &self,
+        name: Cow<'static, str>,
+        _description: Option<Cow<'static, str>>,
+        _unit: Option<Unit>,
+    ) -> grafbase_telemetry::otel::opentelemetry::metrics::Result<Counter<u64>> {
+        Ok(Counter::new(Arc::new(RecordedCounter {
+            name,
+            counters: self.counters.clone(),
+        })))
+    }
+}
+
+struct RecordedCounter {
+    name: Cow<'static, str>,
+    counters: Arc<Mutex<HashMap<Cow<'static, str>, u64>>>,
+}
+
+impl SyncCounter<u64> for RecordedCounter {
+    fn add(&self, value: u64, _attributes: &[KeyValue]) {
+        *self.counters.lock().unwrap().entry(self.name.clone()).or_default() += value;
+    }
+}
+
+type HistogramRecords = Arc<Mutex<HashMap<Cow<'static, str>, Vec<BTreeMap<String, String>>>>>;
+
+/// Keeps track of the attributes of each value recorded by the `u64` histograms created through
+/// its meter. Other instruments are no-ops.
+#[derive(Clone, Default)]
+pub struct HistogramsRecorder {
+    records: HistogramRecords,
+}
+
+impl HistogramsRecorder {
+    pub fn meter(&self) -> Meter {
+        Meter::new(Arc::new(self.clone()))
+    }
+
+    /// Attributes of every value recorded by the histogram so far, in order.
+    pub fn attributes(&self, name: &str) -> Vec<BTreeMap<String, String>> {
+        self.records.lock().unwrap().get(name).cloned().unwrap_or_default()
+    }
+}
+
+impl InstrumentProvider for HistogramsRecorder {
+    fn u64_histogram(
+        &self,
+        name: Cow<'static, str>,
+        _description: Option<Cow<'static, str>>,
+        _unit: Option<Unit>,
+    ) -> grafbase_telemetry::otel::opentelemetry::metrics::Result<Histogram<u64>> {
+        Ok(Histogram::new(Arc::new(RecordedHistogram {
+            name,
+            records: self.records.clone(),
+        })))
+    }
+}
+
+struct RecordedHistogram {
+    name: Cow<'static, str>,
+    records: HistogramRecords,
+}
+
+impl SyncHistogram<u64> for RecordedHistogram {
+    fn record(&self, _value: u64, attributes: &[KeyValue]) {
+        let attributes = attributes
+            .iter()
+            .map(|kv| (kv.key.to_string(), kv.value.to_string()))
+            .collect();
+        self.records
+            .lock()
+            .unwrap()
+            .entry(self.name.clone())
+            .or_default()
+            .push(attributes);
+    }
+}
@@ -0,0 +1,70 @@
+use engine_v2::Engine;
+use graphql_mocks::{FederatedProductsSchema, FederatedReviewsSchema};
+use integration_tests::{
+    federation::{EngineV2Ext, GraphqlResponse},
+    runtime,
+};
+
+// Products are fetched in a single request, but the reviews of each one in their own.
+const QUERY: &str = r#"
+    query {
+        first: product(upc: "top-1") { reviews { id } }
+        second: product(upc: "top-2") { reviews { id } }
+        third: product(upc: "top-3") { reviews { id } }
+    }
+"#;
+
+/// Response and number of requests received by the subgraphs.
+fn execute(config: &str) -> (GraphqlResponse, usize) {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_subgraph(FederatedReviewsSchema)
+            .with_toml_config(config)
+            .build()
+            .await;
+
+        let response = engine.execute(QUERY).await;
+        let requests = engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len()
+            + engine.drain_graphql_requests_sent_to::<FederatedReviewsSchema>().len();
+
+        (response, requests)
+    })
+}
+
+#[test]
+fn aliased_fetches_within_limit() {
+    let (response, requests) = execute(
+        r#"
+        [operation_limits]
+        aliased_fetches = 4
+        "#,
+    );
+
+    assert!(response.errors().is_empty(), "{response}");
+    assert_eq!(requests, 4);
+}
+
+#[test]
+fn too_many_aliased_fetches_are_rejected_during_planning() {
+    let (response, requests) = execute(
+        r#"
+        [operation_limits]
+        aliased_fetches = 3
+        "#,
+    );
+
+    assert_eq!(requests, 0);
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "errors": [
+        {
+          "message": "Operation requires 4 subgraph requests for aliased fields, but is limited to 3",
+          "extensions": {
+            "code": "TOO_MANY_ALIASED_FETCHES"
+          }
+        }
+      ]
+    }
+    "###);
+}
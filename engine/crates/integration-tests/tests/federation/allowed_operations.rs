@@ -0,0 +1,129 @@
+use engine_v2::Engine;
+use graphql_mocks::FakeGithubSchema;
+use integration_tests::{federation::EngineV2Ext, runtime};
+
+const CONFIG: &str = r#"
+    [clients.mobile]
+    allowed_operation_prefixes = ["Mobile_", "Shared_"]
+"#;
+
+#[test]
+fn operation_with_allowed_prefix() {
+    let response = runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FakeGithubSchema)
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        engine
+            .execute("query Shared_ServerVersion { serverVersion }")
+            .by_client("mobile", "1.0.0")
+            .await
+    });
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "serverVersion": "1"
+      }
+    }
+    "###);
+}
+
+#[test]
+fn operation_without_allowed_prefix() {
+    let response = runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FakeGithubSchema)
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        engine
+            .execute("query Web_ServerVersion { serverVersion }")
+            .by_client("mobile", "1.0.0")
+            .await
+    });
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "errors": [
+        {
+          "message": "Operation 'Web_ServerVersion' is not permitted for client 'mobile'",
+          "extensions": {
+            "retryable": false,
+            "code": "OPERATION_NOT_PERMITTED"
+          }
+        }
+      ]
+    }
+    "###);
+}
+
+#[test]
+fn anonymous_operation_for_restricted_client() {
+    let response = runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FakeGithubSchema)
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        engine
+            .execute("query { serverVersion }")
+            .by_client("mobile", "1.0.0")
+            .await
+    });
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "errors": [
+        {
+          "message": "Anonymous operations are not permitted for client 'mobile'",
+          "extensions": {
+            "retryable": false,
+            "code": "OPERATION_NOT_PERMITTED"
+          }
+        }
+      ]
+    }
+    "###);
+}
+
+#[test]
+fn cached_operation_is_not_shared_with_restricted_client() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FakeGithubSchema)
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        let query = "query Web_ServerVersion { serverVersion }";
+
+        let response = engine.execute(query).by_client("web", "1.0.0").await;
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "serverVersion": "1"
+          }
+        }
+        "###);
+
+        let response = engine.execute(query).by_client("mobile", "1.0.0").await;
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "errors": [
+            {
+              "message": "Operation 'Web_ServerVersion' is not permitted for client 'mobile'",
+              "extensions": {
+                "retryable": false,
+                "code": "OPERATION_NOT_PERMITTED"
+              }
+            }
+          ]
+        }
+        "###);
+    });
+}
@@ -0,0 +1,26 @@
+use integration_tests::{federation::execute_with_mock, fetch::MockFetch};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+    }
+
+    type Query {
+      name: String @join__field(graph: A)
+    }
+    "###;
+
+#[test]
+fn top_level_array_is_an_invalid_response() {
+    let fetcher = MockFetch::default().with_responses("a", vec![json!([{"name": "Jane"}])]);
+    let response = execute_with_mock(SDL, "", fetcher, "query { name }").into_value();
+
+    let errors = response["errors"].as_array().expect("errors");
+    assert_eq!(errors.len(), 1, "{response}");
+    assert_eq!(
+        errors[0]["message"],
+        "Subgraph 'a' returned a JSON array, expected a GraphQL response object"
+    );
+    assert_eq!(errors[0]["extensions"]["code"], "SUBGRAPH_INVALID_RESPONSE_ERROR");
+}
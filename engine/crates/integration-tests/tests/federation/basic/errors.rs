@@ -100,6 +100,7 @@ fn leaf_must_scalar_or_enum() {
             }
           ],
           "extensions": {
+            "retryable": false,
             "code": "OPERATION_VALIDATION_ERROR"
           }
         }
@@ -136,6 +137,7 @@ fn cannot_have_selection_set() {
             }
           ],
           "extensions": {
+            "retryable": false,
             "code": "OPERATION_VALIDATION_ERROR"
           }
         }
@@ -172,6 +174,7 @@ fn request_error() {
             }
           ],
           "extensions": {
+            "retryable": false,
             "code": "OPERATION_VALIDATION_ERROR"
           }
         }
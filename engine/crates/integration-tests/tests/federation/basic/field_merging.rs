@@ -0,0 +1,61 @@
+use engine_v2::Engine;
+use graphql_mocks::FakeGithubSchema;
+use integration_tests::{federation::EngineV2Ext, runtime};
+
+#[test]
+fn same_response_key_with_different_arguments_is_a_conflict() {
+    let response = runtime().block_on(async move {
+        let engine = Engine::builder().with_subgraph(FakeGithubSchema).build().await;
+
+        engine
+            .execute(
+                r#"
+                query {
+                    pr: pullRequest(id: "1") { title }
+                    pr: pullRequest(id: "2") { title }
+                }
+                "#,
+            )
+            .await
+            .into_value()
+    });
+
+    let errors = response["errors"].as_array().expect("errors");
+    assert_eq!(errors.len(), 1, "{response}");
+    assert_eq!(
+        errors[0]["message"],
+        "Fields 'pr' conflict because they have differing arguments. Use different aliases on the fields to fetch both if this was intentional."
+    );
+    assert_eq!(errors[0]["extensions"]["code"], "FIELD_MERGE_CONFLICT");
+
+    let lines = errors[0]["locations"]
+        .as_array()
+        .expect("locations")
+        .iter()
+        .map(|location| location["line"].as_u64().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(lines, [3, 4]);
+}
+
+#[test]
+fn same_response_key_with_identical_arguments_is_merged() {
+    let response = runtime().block_on(async move {
+        let engine = Engine::builder().with_subgraph(FakeGithubSchema).build().await;
+
+        engine
+            .execute(
+                r#"
+                query {
+                    pr: pullRequest(id: "1") { title }
+                    pr: pullRequest(id: "1") { checks }
+                }
+                "#,
+            )
+            .await
+            .into_value()
+    });
+
+    assert!(response.get("errors").is_none(), "{response}");
+    assert!(response["data"]["pr"]["title"].is_string(), "{response}");
+    assert!(response["data"]["pr"]["checks"].is_array(), "{response}");
+}
@@ -1,5 +1,5 @@
 use engine_v2::Engine;
-use graphql_mocks::FakeGithubSchema;
+use graphql_mocks::{FakeGithubSchema, FederatedInventorySchema, FederatedProductsSchema, FederatedReviewsSchema};
 use integration_tests::{federation::EngineV2Ext, runtime};
 
 #[test]
@@ -103,7 +103,8 @@ fn named_fragment_cycle() {
             }
           ],
           "extensions": {
-            "code": "OPERATION_VALIDATION_ERROR"
+            "retryable": false,
+            "code": "FRAGMENT_CYCLE"
           }
         }
       ]
@@ -357,3 +358,46 @@ fn named_fragments_on_polymorphic_types() {
     }
     "###);
 }
+
+#[test]
+fn same_fragment_spread_twice_is_resolved_once() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_subgraph(FederatedReviewsSchema)
+            .with_subgraph(FederatedInventorySchema)
+            .build()
+            .await;
+
+        let response = engine
+            .execute(
+                r"
+                    query {
+                        topProducts {
+                            ...ProductReviews
+                            ...ProductReviews
+                        }
+                    }
+
+                    fragment ProductReviews on Product {
+                        upc
+                        reviews {
+                            id
+                        }
+                    }
+                    ",
+            )
+            .await;
+        assert!(response.errors().is_empty(), "{response}");
+
+        // Fields of both spreads are merged into a single one, so the reviews of each product
+        // are fetched only once.
+        let requests = engine.drain_graphql_requests_sent_to::<FederatedReviewsSchema>();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].query.matches("reviews").count(), 1, "{}", requests[0].query);
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len(),
+            1
+        );
+    });
+}
@@ -987,3 +987,45 @@ fn header_regex_remove_should_remove_duplicates() {
     }
     "###);
 }
+
+#[test]
+fn oversized_headers_are_not_sent_to_subgraphs() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(EchoSchema)
+            .with_toml_config(
+                r###"
+                [gateway]
+                max_forwarded_header_bytes = 64
+
+                [[headers]]
+                rule = "forward"
+                name = "cookie"
+                "###,
+            )
+            .build()
+            .await;
+
+        let response = engine
+            .execute("query { headers { name value }}")
+            .header("cookie", "session=abc")
+            .await;
+        assert!(response.errors().is_empty(), "{response}");
+
+        let response = engine
+            .execute("query { headers { name value }}")
+            .header("cookie", format!("session={}", "a".repeat(100)))
+            .await;
+
+        let errors = response.errors();
+        assert_eq!(errors.len(), 1, "{response}");
+        assert_eq!(
+            errors[0]["message"],
+            "Headers for subgraph 'echo' amount to 114 bytes, exceeding the limit of 64 bytes"
+        );
+        assert_eq!(errors[0]["extensions"]["code"], "SUBGRAPH_REQUEST_ERROR");
+
+        // Only the first request reached the subgraph.
+        assert_eq!(engine.drain_graphql_requests_sent_to::<EchoSchema>().len(), 1);
+    });
+}
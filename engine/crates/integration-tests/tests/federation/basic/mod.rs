@@ -6,11 +6,13 @@
 // mod caching;
 mod empty_config;
 mod errors;
+mod field_merging;
 mod fragments;
 mod headers;
 mod mutation;
 mod operation_limits;
 mod scalars;
+mod skip_include;
 mod streaming;
 mod variables;
 
@@ -1,6 +1,7 @@
 use engine_v2::Engine;
 use graphql_mocks::StateMutationSchema;
-use integration_tests::{federation::EngineV2Ext, runtime};
+use integration_tests::{federation::EngineV2Ext, fetch::MockFetch, runtime};
+use serde_json::json;
 
 #[test]
 fn mutations_should_be_executed_sequentially() {
@@ -158,3 +159,172 @@ fn mutation_failure_should_stop_later_executions_if_required() {
         "###);
     });
 }
+
+#[test]
+fn mutations_on_different_subgraphs_are_executed_in_order() {
+    const SDL: &str = r###"
+        enum join__Graph {
+          A @join__graph(name: "a", url: "https://a/graphql")
+          B @join__graph(name: "b", url: "https://b/graphql")
+        }
+
+        type Query {
+          version: String @join__field(graph: A)
+        }
+
+        type Mutation {
+          createAccount(name: String!): ID! @join__field(graph: A)
+          sendWelcomeEmail(name: String!): Boolean! @join__field(graph: B)
+          activateAccount(name: String!): Boolean! @join__field(graph: A)
+        }
+        "###;
+
+    let delay = std::time::Duration::from_millis(50);
+
+    runtime().block_on(async move {
+        let fetcher = MockFetch::default()
+            .with_responses(
+                "a",
+                vec![
+                    json!({"data": {"createAccount": "1"}}),
+                    json!({"data": {"activateAccount": true}}),
+                ],
+            )
+            .with_responses("b", vec![json!({"data": {"sendWelcomeEmail": true}})])
+            .with_delay(delay);
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_mock_fetcher(fetcher.clone())
+            .build()
+            .await;
+
+        let start = std::time::Instant::now();
+        let response = engine
+            .execute(
+                r#"
+                mutation {
+                    createAccount(name: "Ada")
+                    sendWelcomeEmail(name: "Ada")
+                    activateAccount(name: "Ada")
+                }
+                "#,
+            )
+            .await;
+        let elapsed = start.elapsed();
+
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "createAccount": "1",
+            "sendWelcomeEmail": true,
+            "activateAccount": true
+          }
+        }
+        "###);
+
+        // Each mutation only starts once the previous one finished.
+        assert!(elapsed >= 3 * delay, "{elapsed:?}");
+        let requests = fetcher
+            .drain_received_requests()
+            .map(|(host, request)| (host, request.body.query))
+            .collect::<Vec<_>>();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].0, "a");
+        assert!(requests[0].1.contains("createAccount"));
+        assert_eq!(requests[1].0, "b");
+        assert!(requests[1].1.contains("sendWelcomeEmail"));
+        assert_eq!(requests[2].0, "a");
+        assert!(requests[2].1.contains("activateAccount"));
+    });
+}
+
+#[test]
+fn explicit_null_and_omitted_input_fields_are_forwarded_as_is() {
+    const SDL: &str = r###"
+        enum join__Graph {
+          A @join__graph(name: "a", url: "https://a/graphql")
+        }
+
+        type Query {
+          me: User @join__field(graph: A)
+        }
+
+        type Mutation {
+          updateUser(patch: UserPatch!): User @join__field(graph: A)
+        }
+
+        type User {
+          id: ID!
+          name: String
+          bio: String
+        }
+
+        input UserPatch {
+          id: ID!
+          name: String
+          bio: String
+        }
+        "###;
+
+    runtime().block_on(async move {
+        let fetcher = MockFetch::default().with_responses(
+            "a",
+            vec![
+                json!({"data": {"updateUser": {"id": "1"}}}),
+                json!({"data": {"updateUser": {"id": "1"}}}),
+            ],
+        );
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_mock_fetcher(fetcher.clone())
+            .build()
+            .await;
+
+        engine
+            .execute("mutation($patch: UserPatch!) { updateUser(patch: $patch) { id } }")
+            .variables(json!({"patch": {"id": "1", "name": null}}))
+            .await
+            .into_data();
+
+        engine
+            .execute(
+                r#"mutation($name: String, $bio: String) { updateUser(patch: { id: "1", name: $name, bio: $bio }) { id } }"#,
+            )
+            .variables(json!({"bio": null}))
+            .await
+            .into_data();
+
+        let bodies = fetcher
+            .drain_received_requests()
+            .map(|(_, request)| request.body)
+            .collect::<Vec<_>>();
+        insta::with_settings!({ sort_maps => true}, {
+            insta::assert_json_snapshot!(bodies, @r###"
+            [
+              {
+                "query": "mutation($var0: UserPatch!) {\n  updateUser(patch: $var0) {\n    id\n  }\n}\n",
+                "operationName": null,
+                "variables": {
+                  "var0": {
+                    "id": "1",
+                    "name": null
+                  }
+                },
+                "extensions": {}
+              },
+              {
+                "query": "mutation($var0: UserPatch!) {\n  updateUser(patch: $var0) {\n    id\n  }\n}\n",
+                "operationName": null,
+                "variables": {
+                  "var0": {
+                    "bio": null,
+                    "id": "1"
+                  }
+                },
+                "extensions": {}
+              }
+            ]
+            "###)
+        });
+    });
+}
@@ -124,6 +124,66 @@ use integration_tests::{federation::EngineV2Ext, runtime};
     }"#,
     None
 )]
+#[case( // 14
+    "@operationLimits(fragmentSpreads: 3)",
+    r#"query {
+        ...A
+        ...A
+    }
+    fragment A on Query { ...B }
+    fragment B on Query { serverVersion }"#,
+    Some("Query contains too many fragment spreads.")
+)]
+#[case( // 15
+    "@operationLimits(fragmentSpreads: 4)",
+    r#"query {
+        ...A
+        ...A
+    }
+    fragment A on Query { ...B }
+    fragment B on Query { serverVersion }"#,
+    None
+)]
+#[case( // 16
+    "@operationLimits(fragmentDepth: 1)",
+    r#"query {
+        ...A
+    }
+    fragment A on Query { ...B }
+    fragment B on Query { serverVersion }"#,
+    Some("Fragments are nested too deep.")
+)]
+#[case( // 17
+    "@operationLimits(fragmentDepth: 2)",
+    r#"query {
+        ...A
+    }
+    fragment A on Query { ...B }
+    fragment B on Query { serverVersion }"#,
+    None
+)]
+#[case( // 18
+    "@operationLimits(fields: 3)",
+    r#"query {
+        serverVersion
+        allBotPullRequests {
+            title
+            aliasedRepeateDoesCount: title
+        }
+    }"#,
+    Some("Query contains too many fields.")
+)]
+#[case( // 19
+    "@operationLimits(fields: 4)",
+    r#"query {
+        serverVersion
+        allBotPullRequests {
+            title
+            aliasedRepeateDoesCount: title
+        }
+    }"#,
+    None
+)]
 fn test_operation_limits(
     #[case] operation_limits_config: &'static str,
     #[case] query: &'static str,
@@ -154,3 +214,120 @@ fn test_operation_limits(
         error.into_iter().map(str::to_owned).collect::<Vec<_>>()
     );
 }
+
+#[test]
+fn fragment_bomb_is_rejected() {
+    let response = runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_toml_config(
+                r#"
+                [operation_limits]
+                fragment_spreads = 100
+                "#,
+            )
+            .with_subgraph(FakeGithubSchema)
+            .build()
+            .await;
+
+        engine
+            .execute(
+                r#"
+                query {
+                    ...A
+                }
+                fragment A on Query { ...B ...B ...B ...B }
+                fragment B on Query { ...C ...C ...C ...C }
+                fragment C on Query { ...D ...D ...D ...D }
+                fragment D on Query { ...E ...E ...E ...E }
+                fragment E on Query { serverVersion }
+                "#,
+            )
+            .await
+    });
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "errors": [
+        {
+          "message": "Query contains too many fragment spreads.",
+          "locations": [
+            {
+              "line": 8,
+              "column": 44
+            }
+          ],
+          "extensions": {
+            "retryable": false,
+            "code": "OPERATION_VALIDATION_ERROR"
+          }
+        }
+      ]
+    }
+    "###);
+}
+
+/// Errors of the operation along with the number of requests the subgraph received.
+fn execute_with_limits(config: &str, query: &'static str) -> (serde_json::Value, usize) {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_toml_config(config)
+            .with_subgraph(FakeGithubSchema)
+            .build()
+            .await;
+
+        let response = engine.execute(query).await.into_value();
+        let requests = engine.drain_graphql_requests_sent_to::<FakeGithubSchema>().len();
+
+        (response, requests)
+    })
+}
+
+#[test]
+fn too_deep_query_is_rejected_before_reaching_subgraphs() {
+    let (response, requests) = execute_with_limits(
+        r#"
+        [operation_limits]
+        depth = 1
+        "#,
+        "query { allBotPullRequests { title } }",
+    );
+
+    assert_eq!(requests, 0);
+    assert_eq!(response["data"], serde_json::Value::Null, "{response}");
+    let errors = response["errors"].as_array().expect("errors");
+    assert_eq!(errors.len(), 1, "{response}");
+    assert_eq!(errors[0]["message"], "Query is nested too deep.");
+    assert_eq!(errors[0]["extensions"]["code"], "OPERATION_VALIDATION_ERROR");
+}
+
+#[test]
+fn query_with_too_many_fields_is_rejected_before_reaching_subgraphs() {
+    let (response, requests) = execute_with_limits(
+        r#"
+        [operation_limits]
+        fields = 1
+        "#,
+        "query { serverVersion favoriteRepository }",
+    );
+
+    assert_eq!(requests, 0);
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "errors": [
+        {
+          "message": "Query contains too many fields.",
+          "locations": [
+            {
+              "line": 1,
+              "column": 23
+            }
+          ],
+          "extensions": {
+            "retryable": false,
+            "code": "OPERATION_VALIDATION_ERROR"
+          }
+        }
+      ]
+    }
+    "###);
+}
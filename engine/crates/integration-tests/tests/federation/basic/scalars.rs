@@ -49,6 +49,7 @@ fn supports_unused_builtin_scalars() {
             }
           ],
           "extensions": {
+            "retryable": false,
             "code": "OPERATION_VALIDATION_ERROR"
           }
         }
@@ -0,0 +1,77 @@
+use engine_v2::Engine;
+use graphql_mocks::FakeGithubSchema;
+use integration_tests::{federation::EngineV2Ext, runtime};
+
+#[test]
+fn all_root_fields_skipped() {
+    runtime().block_on(async move {
+        let engine = Engine::builder().with_subgraph(FakeGithubSchema).build().await;
+
+        let response = engine
+            .execute("query { serverVersion @skip(if: true) ... @include(if: false) { allBotPullRequests { title } } }")
+            .await;
+
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {}
+        }
+        "###);
+
+        // Nothing was left to plan.
+        let requests = engine.drain_graphql_requests_sent_to::<FakeGithubSchema>();
+        assert!(requests.is_empty(), "{requests:?}");
+    })
+}
+
+#[test]
+fn skipped_fields_are_not_requested() {
+    runtime().block_on(async move {
+        let engine = Engine::builder().with_subgraph(FakeGithubSchema).build().await;
+
+        let response = engine
+            .execute("query { serverVersion @include(if: true) allBotPullRequests @skip(if: true) { title } }")
+            .await;
+
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "serverVersion": "1"
+          }
+        }
+        "###);
+
+        let requests = engine.drain_graphql_requests_sent_to::<FakeGithubSchema>();
+        insta::assert_json_snapshot!(requests, @r###"
+        [
+          {
+            "query": "query {\n  serverVersion\n}\n",
+            "operationName": null,
+            "variables": {},
+            "extensions": {}
+          }
+        ]
+        "###);
+    })
+}
+
+#[test]
+fn typename_left_after_skipping() {
+    runtime().block_on(async move {
+        let engine = Engine::builder().with_subgraph(FakeGithubSchema).build().await;
+
+        let response = engine
+            .execute("query { __typename serverVersion @skip(if: true) }")
+            .await;
+
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "__typename": "Query"
+          }
+        }
+        "###);
+
+        let requests = engine.drain_graphql_requests_sent_to::<FakeGithubSchema>();
+        assert!(requests.is_empty(), "{requests:?}");
+    })
+}
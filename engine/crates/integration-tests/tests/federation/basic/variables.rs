@@ -632,6 +632,94 @@ fn undefined_variable() {
     "###);
 }
 
+#[test]
+fn deeply_nested_variables_beyond_the_limit() {
+    fn nested(depth: usize) -> serde_json::Value {
+        let mut input = json!({"string": "leaf"});
+        for _ in 0..depth {
+            input = json!({"recursiveObject": input});
+        }
+        input
+    }
+
+    let (shallow, deep) = runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(EchoSchema)
+            .with_toml_config(
+                r#"
+                [gateway]
+                max_variable_values = 50
+                "#,
+            )
+            .build()
+            .await;
+
+        let query = "query($input: InputObj!) { inputObject(input: $input) }";
+        let shallow = engine.execute(query).variables(json!({"input": nested(10)})).await;
+        let deep = engine.execute(query).variables(json!({"input": nested(100)})).await;
+        (shallow, deep)
+    });
+
+    assert_eq!(shallow.into_data()["inputObject"], nested(10));
+    insta::assert_json_snapshot!(deep, @r###"
+    {
+      "errors": [
+        {
+          "message": "Variables exceed the limit of 50 values",
+          "extensions": {
+            "retryable": false,
+            "code": "VARIABLES_TOO_COMPLEX"
+          }
+        }
+      ]
+    }
+    "###);
+}
+
+#[test]
+fn variables_nested_beyond_the_max_depth() {
+    fn nested(depth: usize) -> serde_json::Value {
+        let mut input = json!({"string": "leaf"});
+        for _ in 0..depth {
+            input = json!({"recursiveObject": input});
+        }
+        input
+    }
+
+    let (shallow, deep) = runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(EchoSchema)
+            .with_toml_config(
+                r#"
+                [gateway]
+                max_variable_depth = 20
+                "#,
+            )
+            .build()
+            .await;
+
+        let query = "query($input: InputObj!) { inputObject(input: $input) }";
+        let shallow = engine.execute(query).variables(json!({"input": nested(10)})).await;
+        let deep = engine.execute(query).variables(json!({"input": nested(50)})).await;
+        (shallow, deep)
+    });
+
+    assert_eq!(shallow.into_data()["inputObject"], nested(10));
+    insta::assert_json_snapshot!(deep, @r###"
+    {
+      "errors": [
+        {
+          "message": "Variables exceed the maximum nesting depth of 20",
+          "extensions": {
+            "retryable": false,
+            "code": "VARIABLES_TOO_DEEP"
+          }
+        }
+      ]
+    }
+    "###);
+}
+
 #[track_caller]
 fn roundtrip_test<T>(field: &str, ty: &str, input: T)
 where
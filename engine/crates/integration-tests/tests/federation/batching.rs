@@ -0,0 +1,120 @@
+use engine_v2::Engine;
+use integration_tests::{engine_v1::GraphQlRequest, federation::EngineV2Ext, fetch::MockFetch, runtime};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+    }
+
+    type Query {
+      me: User @join__field(graph: A)
+    }
+
+    type User {
+      id: ID!
+      name: String
+    }
+    "###;
+
+fn named(name: &str, query: &str) -> GraphQlRequest {
+    GraphQlRequest {
+        query: query.into(),
+        operation_name: Some(name.into()),
+        variables: None,
+        extensions: None,
+        doc_id: None,
+    }
+}
+
+fn execute_batch(config: &str) -> (serde_json::Value, usize) {
+    runtime().block_on(async move {
+        let fetcher = MockFetch::default().with_responses(
+            "a",
+            vec![
+                json!({"data": {"me": {"id": "1"}}}),
+                json!({"data": {"me": {"name": "Alice"}}}),
+            ],
+        );
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(config)
+            .with_mock_fetcher(fetcher.clone())
+            .build()
+            .await;
+
+        let response = engine
+            .execute_batch([
+                named("Me", "query Me { me { id } }"),
+                named("Me", "query Me { me { name } }"),
+            ])
+            .await;
+
+        (response.into_value(), fetcher.drain_received_requests().count())
+    })
+}
+
+#[test]
+fn duplicate_operation_names_are_allowed_by_default() {
+    let (response, subgraph_requests) = execute_batch("");
+
+    insta::assert_json_snapshot!(response, @r###"
+    [
+      {
+        "data": {
+          "me": {
+            "id": "1"
+          }
+        }
+      },
+      {
+        "data": {
+          "me": {
+            "name": "Alice"
+          }
+        }
+      }
+    ]
+    "###);
+    assert_eq!(subgraph_requests, 2);
+}
+
+#[test]
+fn duplicate_operation_names_can_be_rejected() {
+    let (response, subgraph_requests) = execute_batch(
+        r#"
+        [gateway.batching]
+        duplicate_operation_names = "reject"
+        "#,
+    );
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "errors": [
+        {
+          "message": "Operation name 'Me' is used more than once in the batch",
+          "extensions": {
+            "code": "DUPLICATE_OPERATION_NAME"
+          }
+        }
+      ]
+    }
+    "###);
+    assert_eq!(subgraph_requests, 0);
+}
+
+#[test]
+fn disambiguated_duplicate_operation_names_are_executed() {
+    let (response, subgraph_requests) = execute_batch(
+        r#"
+        [gateway.batching]
+        duplicate_operation_names = "disambiguate"
+        "#,
+    );
+
+    assert_eq!(
+        response,
+        json!([{"data": {"me": {"id": "1"}}}, {"data": {"me": {"name": "Alice"}}}])
+    );
+    assert_eq!(subgraph_requests, 2);
+}
@@ -0,0 +1,168 @@
+use engine_v2::Engine;
+use integration_tests::{
+    federation::{execute_with_mock, EngineV2Ext},
+    fetch::MockFetch,
+    runtime,
+};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+    }
+
+    scalar BigInt
+
+    type Query {
+      transfer(amount: BigInt!): Boolean @join__field(graph: A)
+      balance: BigInt @join__field(graph: A)
+    }
+    "###;
+
+// 2^63, one above i64::MAX.
+const ABOVE_I64: u64 = 9_223_372_036_854_775_808;
+
+fn execute(query: &str, variables: serde_json::Value) -> (serde_json::Value, String) {
+    runtime().block_on(async move {
+        let fetcher = MockFetch::default().with_responses("a", vec![json!({"data": {"transfer": true}})]);
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_mock_fetcher(fetcher.clone())
+            .build()
+            .await;
+
+        let response = engine.execute(query).variables(variables).await.into_value();
+        let sent = fetcher.drain_received_bodies().collect::<Vec<_>>().join("\n");
+        (response, sent)
+    })
+}
+
+#[test]
+fn big_int_variable_above_i64_is_forwarded_exactly() {
+    let (response, sent) = execute(
+        "query($amount: BigInt!) { transfer(amount: $amount) }",
+        json!({"amount": ABOVE_I64}),
+    );
+
+    assert_eq!(response, json!({"data": {"transfer": true}}));
+    assert!(sent.contains("9223372036854775808"), "{sent}");
+}
+
+#[test]
+fn big_int_literal_above_i64_is_forwarded_exactly() {
+    let (response, sent) = execute("query { transfer(amount: 9223372036854775808) }", json!({}));
+
+    assert_eq!(response, json!({"data": {"transfer": true}}));
+    assert!(sent.contains("9223372036854775808"), "{sent}");
+}
+
+#[test]
+fn big_int_string_variable_beyond_u64_is_forwarded_exactly() {
+    let (response, sent) = execute(
+        "query($amount: BigInt!) { transfer(amount: $amount) }",
+        json!({"amount": i128::MIN.to_string()}),
+    );
+
+    assert_eq!(response, json!({"data": {"transfer": true}}));
+    assert!(sent.contains(&format!(":{}", i128::MIN)), "{sent}");
+}
+
+#[test]
+fn big_int_string_literal_beyond_u64_is_forwarded_exactly() {
+    let (response, sent) = execute(
+        r#"query { transfer(amount: "170141183460469231731687303715884105727") }"#,
+        json!({}),
+    );
+
+    assert_eq!(response, json!({"data": {"transfer": true}}));
+    assert!(sent.contains(":170141183460469231731687303715884105727"), "{sent}");
+}
+
+#[test]
+fn big_int_string_variable_must_be_an_integer() {
+    let (response, sent) = execute(
+        "query($amount: BigInt!) { transfer(amount: $amount) }",
+        json!({"amount": "12.5"}),
+    );
+
+    assert_eq!(response["data"], json!(null));
+    assert_eq!(response["errors"].as_array().map(Vec::len), Some(1));
+    assert!(sent.is_empty());
+}
+
+#[test]
+fn big_int_variable_beyond_u64_is_rejected() {
+    let (response, sent) = execute(
+        "query($amount: BigInt!) { transfer(amount: $amount) }",
+        json!({"amount": 1e20}),
+    );
+
+    assert_eq!(response["data"], json!(null));
+    assert_eq!(response["errors"].as_array().map(Vec::len), Some(1));
+    assert!(sent.is_empty());
+}
+
+fn fetch_balance(balance: serde_json::Value) -> serde_json::Value {
+    let fetcher = MockFetch::default().with_responses("a", vec![json!({"data": {"balance": balance}})]);
+    execute_with_mock(SDL, "", fetcher, "query { balance }").into_value()
+}
+
+#[test]
+fn big_int_output_within_i64_is_returned() {
+    let response = fetch_balance(json!(i64::MIN));
+
+    assert_eq!(response, json!({"data": {"balance": i64::MIN}}));
+}
+
+#[test]
+fn big_int_output_above_i64_is_returned_exactly() {
+    let response = fetch_balance(json!(ABOVE_I64));
+
+    assert_eq!(response, json!({"data": {"balance": ABOVE_I64}}));
+}
+
+#[test]
+fn big_int_output_below_i64_is_out_of_range() {
+    // Integers below i64::MIN are only parsed as floats.
+    let response = fetch_balance(json!(-1e19));
+
+    assert_eq!(response["data"], json!({"balance": null}), "{response}");
+    let errors = response["errors"].as_array().expect("errors");
+    assert_eq!(errors.len(), 1, "{response}");
+    let message = errors[0]["message"].as_str().unwrap();
+    assert!(
+        message.starts_with("BigInt value ") && message.contains("out of range"),
+        "{response}"
+    );
+    assert_eq!(errors[0]["path"], json!(["balance"]));
+    assert_eq!(errors[0]["extensions"]["code"], "SUBGRAPH_INVALID_RESPONSE_ERROR");
+}
+
+#[test]
+fn big_int_output_with_a_fraction_is_rejected() {
+    let response = fetch_balance(json!(42.0));
+
+    assert_eq!(response["data"], json!({"balance": null}), "{response}");
+    let errors = response["errors"].as_array().expect("errors");
+    assert_eq!(errors.len(), 1, "{response}");
+    assert!(
+        !errors[0]["message"].as_str().unwrap().contains("out of range"),
+        "{response}"
+    );
+}
+
+#[test]
+fn big_int_output_beyond_i128_is_out_of_range() {
+    // Only representable as a float, but still an integer.
+    let response = fetch_balance(json!(1e40));
+
+    assert_eq!(response["data"], json!({"balance": null}), "{response}");
+    let errors = response["errors"].as_array().expect("errors");
+    assert_eq!(errors.len(), 1, "{response}");
+    let message = errors[0]["message"].as_str().unwrap();
+    assert!(
+        message.starts_with("BigInt value ") && message.contains("out of range"),
+        "{response}"
+    );
+    assert_eq!(errors[0]["path"], json!(["balance"]));
+}
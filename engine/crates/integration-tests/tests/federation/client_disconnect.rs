@@ -0,0 +1,32 @@
+use std::{future::IntoFuture, time::Duration};
+
+use engine_v2::Engine;
+use graphql_mocks::SlowSchema;
+use integration_tests::{federation::EngineV2Ext, metrics::CountersRecorder, runtime};
+
+#[test]
+fn dropped_execution_is_counted_as_cancelled() {
+    runtime().block_on(async move {
+        let counters = CountersRecorder::default();
+        let engine = Engine::builder()
+            .with_subgraph(SlowSchema)
+            .with_meter(counters.meter())
+            .build()
+            .await;
+
+        let response = engine.execute("query { delay(ms: 0) }").await;
+        assert_eq!(response["data"]["delay"], 0, "{response}");
+        assert_eq!(counters.get("requests_cancelled_total"), 0);
+
+        // The client gives up while the subgraph is still working on the request, dropping the
+        // execution.
+        let result = tokio::time::timeout(
+            Duration::from_millis(200),
+            engine.execute("query { delay(ms: 2000) }").into_future(),
+        )
+        .await;
+        assert!(result.is_err());
+
+        assert_eq!(counters.get("requests_cancelled_total"), 1);
+    })
+}
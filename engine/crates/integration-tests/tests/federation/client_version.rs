@@ -0,0 +1,70 @@
+use engine_v2::Engine;
+use graphql_mocks::FakeGithubSchema;
+use integration_tests::{
+    federation::{EngineV2Ext, GraphqlResponse},
+    runtime,
+};
+
+const CONFIG: &str = r#"
+    [clients.ios]
+    min_version = "2.3.0"
+"#;
+
+fn execute(client_name: &str, client_version: &str) -> GraphqlResponse {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FakeGithubSchema)
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        engine
+            .execute("query { serverVersion }")
+            .by_client(client_name, client_version)
+            .await
+    })
+}
+
+#[test]
+fn supported_client_version() {
+    let response = execute("ios", "2.10.1");
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "serverVersion": "1"
+      }
+    }
+    "###);
+}
+
+#[test]
+fn outdated_client_version() {
+    let response = execute("ios", "2.2.9");
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "errors": [
+        {
+          "message": "Version 2.2.9 of client 'ios' is no longer supported, please upgrade to version 2.3.0 or later",
+          "extensions": {
+            "code": "CLIENT_VERSION_UNSUPPORTED"
+          }
+        }
+      ]
+    }
+    "###);
+}
+
+#[test]
+fn other_clients_are_not_restricted() {
+    let response = execute("android", "0.1.0");
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "serverVersion": "1"
+      }
+    }
+    "###);
+}
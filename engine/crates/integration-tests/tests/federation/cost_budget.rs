@@ -0,0 +1,103 @@
+use engine_v2::Engine;
+use graphql_mocks::FakeGithubSchema;
+use integration_tests::{federation::EngineV2Ext, runtime};
+
+const CONFIG: &str = r#"
+    [clients.partner]
+    cost_budget = { capacity = 5, refill_per_second = 0 }
+
+    [clients.mobile]
+    cost_budget = { capacity = 4, refill_per_second = 1 }
+"#;
+
+const QUERY: &str = "query { a: serverVersion b: serverVersion c: serverVersion }";
+
+#[test]
+fn operations_are_rejected_once_the_budget_is_spent() {
+    let (first, second) = runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FakeGithubSchema)
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        let first = engine.execute(QUERY).by_client("partner", "1.0.0").await;
+        let second = engine.execute(QUERY).by_client("partner", "1.0.0").await;
+        (first, second)
+    });
+
+    insta::assert_json_snapshot!(first, @r###"
+    {
+      "data": {
+        "a": "1",
+        "b": "1",
+        "c": "1"
+      }
+    }
+    "###);
+
+    insta::assert_json_snapshot!(second, @r###"
+    {
+      "errors": [
+        {
+          "message": "Operation cost of 3 exceeds the remaining budget of client 'partner'",
+          "extensions": {
+            "retryable": false,
+            "code": "COST_BUDGET_EXCEEDED"
+          }
+        }
+      ]
+    }
+    "###);
+}
+
+#[test]
+fn rejection_tells_when_to_retry() {
+    let response = runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FakeGithubSchema)
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        engine.execute(QUERY).by_client("mobile", "1.0.0").await;
+        engine.execute(QUERY).by_client("mobile", "1.0.0").await
+    });
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "errors": [
+        {
+          "message": "Operation cost of 3 exceeds the remaining budget of client 'mobile'",
+          "extensions": {
+            "retryAfter": 2,
+            "retryable": true,
+            "code": "COST_BUDGET_EXCEEDED"
+          }
+        }
+      ]
+    }
+    "###);
+}
+
+#[test]
+fn clients_without_budget_are_not_limited() {
+    let responses = runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FakeGithubSchema)
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        let mut responses = Vec::new();
+        for _ in 0..3 {
+            responses.push(engine.execute(QUERY).by_client("web", "1.0.0").await);
+            responses.push(engine.execute(QUERY).await);
+        }
+        responses
+    });
+
+    for response in responses {
+        assert!(response.errors().is_empty(), "{response}");
+    }
+}
@@ -0,0 +1,45 @@
+use engine_v2::Engine;
+use graphql_mocks::FakeGithubSchema;
+use integration_tests::{federation::EngineV2Ext, runtime};
+use serde_json::json;
+
+#[test]
+fn empty_query_is_rejected_before_parsing() {
+    runtime().block_on(async move {
+        let engine = Engine::builder().with_subgraph(FakeGithubSchema).build().await;
+
+        let response = engine.execute("").await;
+
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "errors": [
+            {
+              "message": "The request doesn't contain any query.",
+              "extensions": {
+                "retryable": false,
+                "code": "EMPTY_QUERY"
+              }
+            }
+          ]
+        }
+        "###);
+
+        let response = engine.execute("  \n ").await;
+
+        assert_eq!(response["errors"][0]["extensions"]["code"], json!("EMPTY_QUERY"));
+    });
+}
+
+#[test]
+fn invalid_query_is_still_a_parsing_error() {
+    runtime().block_on(async move {
+        let engine = Engine::builder().with_subgraph(FakeGithubSchema).build().await;
+
+        let response = engine.execute("query {").await;
+
+        assert_eq!(
+            response["errors"][0]["extensions"]["code"],
+            json!("OPERATION_PARSING_ERROR")
+        );
+    });
+}
@@ -0,0 +1,55 @@
+use engine_v2::Engine;
+use graphql_mocks::{FederatedInventorySchema, FederatedProductsSchema, FederatedReviewsSchema};
+use integration_tests::{federation::EngineV2Ext, runtime};
+
+const QUERY: &str = "{ topProducts { upc reviews { id body } } }";
+
+#[test]
+fn entities_query_is_named_with_the_configured_prefix() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_subgraph(FederatedReviewsSchema)
+            .with_subgraph(FederatedInventorySchema)
+            .with_toml_config(
+                r#"
+                [subgraphs.reviews]
+                entities_operation_prefix = "Gateway-Entities"
+                "#,
+            )
+            .build()
+            .await;
+
+        engine.execute(QUERY).await.into_data();
+
+        let requests = engine.drain_graphql_requests_sent_to::<FederatedReviewsSchema>();
+        assert_eq!(requests.len(), 1);
+
+        // The plan id isn't stable across planner changes, only check that one is present.
+        let query = &requests[0].query;
+        let plan_id = query
+            .strip_prefix("query Gateway_Entities_reviews_")
+            .and_then(|rest| rest.split_once('('))
+            .map(|(plan_id, _)| plan_id)
+            .unwrap_or_else(|| panic!("unexpected entities query: {query}"));
+        assert!(plan_id.parse::<usize>().is_ok(), "unexpected entities query: {query}");
+    });
+}
+
+#[test]
+fn entities_query_is_anonymous_by_default() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_subgraph(FederatedReviewsSchema)
+            .with_subgraph(FederatedInventorySchema)
+            .build()
+            .await;
+
+        engine.execute(QUERY).await.into_data();
+
+        let requests = engine.drain_graphql_requests_sent_to::<FederatedReviewsSchema>();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].query.starts_with("query("), "{}", requests[0].query);
+    });
+}
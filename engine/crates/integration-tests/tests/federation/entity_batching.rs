@@ -0,0 +1,62 @@
+use engine_v2::Engine;
+use graphql_mocks::{FederatedProductsSchema, FederatedReviewsSchema};
+use integration_tests::{
+    federation::{EngineV2Ext, GraphqlResponse},
+    runtime,
+};
+
+// The 5 top products are fetched as entities from the reviews subgraph.
+const QUERY: &str = "{ topProducts { upc name reviews { id body } } }";
+
+/// Response and size of each entities request sent to the reviews subgraph.
+fn execute(config: &str) -> (GraphqlResponse, Vec<usize>) {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_subgraph(FederatedReviewsSchema)
+            .with_toml_config(config)
+            .build()
+            .await;
+
+        let response = engine.execute(QUERY).await;
+        let batch_sizes = engine
+            .drain_graphql_requests_sent_to::<FederatedReviewsSchema>()
+            .into_iter()
+            .filter_map(|request| {
+                let variables = serde_json::to_value(&request.variables).unwrap();
+                variables
+                    .as_object()?
+                    .values()
+                    .find_map(|value| value.as_array().map(Vec::len))
+            })
+            .collect();
+
+        (response, batch_sizes)
+    })
+}
+
+#[test]
+fn entities_are_fetched_in_a_single_request_by_default() {
+    let (response, batch_sizes) = execute("");
+
+    assert!(response.errors().is_empty(), "{response}");
+    assert_eq!(batch_sizes, [5]);
+}
+
+#[test]
+fn batched_entities_are_merged_back_in_order() {
+    let (unbatched, _) = execute("");
+    let (response, mut batch_sizes) = execute(
+        r#"
+        [subgraphs.reviews]
+        entity_batch_size = 2
+        "#,
+    );
+
+    assert!(response.errors().is_empty(), "{response}");
+    assert_eq!(response.to_string(), unbatched.to_string());
+
+    // Batches are sent concurrently, so in any order.
+    batch_sizes.sort_unstable();
+    assert_eq!(batch_sizes, [1, 2, 2]);
+}
@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use engine_v2::Engine;
 use graphql_mocks::{ErrorSchema, FederatedInventorySchema, FederatedProductsSchema, FederatedReviewsSchema};
-use integration_tests::{federation::EngineV2Ext, runtime};
+use integration_tests::{federation::EngineV2Ext, fetch::MockFetch, runtime};
 use serde_json::json;
 
 #[test]
@@ -299,3 +299,55 @@ fn entity_request_cache_partial_hit() {
         "###);
     })
 }
+
+#[test]
+fn stale_response_served_if_subgraph_fails() {
+    const SDL: &str = r###"
+        enum join__Graph {
+          A @join__graph(name: "a", url: "https://a/graphql")
+        }
+
+        type Query {
+          me: User @join__field(graph: A)
+        }
+
+        type User {
+          id: ID!
+          name: String
+        }
+        "###;
+
+    runtime().block_on(async move {
+        // A single response, every later request to the subgraph fails.
+        let fetcher =
+            MockFetch::default().with_responses("a", vec![json!({"data": {"me": {"id": "1", "name": "Alice"}}})]);
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(
+                r#"
+                [entity_caching]
+                enabled = true
+                ttl = "1s"
+                stale_if_error = "60s"
+                "#,
+            )
+            .with_mock_fetcher(fetcher.clone())
+            .build()
+            .await;
+
+        const QUERY: &str = "query { me { id name } }";
+
+        let fresh = engine.execute(QUERY).await;
+        assert_eq!(fresh.headers.get("x-grafbase-cache"), None);
+        let fresh = fresh.into_data();
+
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+
+        let stale = engine.execute(QUERY).await;
+        assert_eq!(stale.headers.get("x-grafbase-cache").unwrap(), "STALE");
+        assert_eq!(stale.into_data(), fresh);
+
+        // The subgraph was tried before falling back to the stale entry.
+        assert_eq!(fetcher.drain_received_requests().count(), 2);
+    })
+}
@@ -0,0 +1,58 @@
+use engine_v2::Engine;
+use graphql_mocks::{FederatedAccountsSchema, FederatedProductsSchema, FederatedReviewsSchema};
+use integration_tests::{
+    federation::{EngineV2Ext, GraphqlResponse},
+    runtime,
+};
+
+// The 5 top products are fetched as entities from the reviews subgraph, and the 3 authors of
+// their reviews from the accounts one, 8 entities in total.
+const QUERY: &str = "{ topProducts { upc reviews { id author { username } } } }";
+
+fn execute(max_entities_per_operation: usize) -> GraphqlResponse {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedAccountsSchema)
+            .with_subgraph(FederatedProductsSchema)
+            .with_subgraph(FederatedReviewsSchema)
+            .with_toml_config(format!(
+                r#"
+                [gateway]
+                max_entities_per_operation = {max_entities_per_operation}
+                "#
+            ))
+            .build()
+            .await;
+
+        engine.execute(QUERY).await
+    })
+}
+
+#[test]
+fn operation_within_the_entity_limit() {
+    let response = execute(8);
+
+    assert!(response.errors().is_empty(), "{response}");
+}
+
+#[test]
+fn nested_lists_exceeding_the_entity_limit() {
+    let response = execute(6);
+
+    let errors = response.errors();
+    assert!(!errors.is_empty(), "{response}");
+    assert!(
+        errors
+            .iter()
+            .all(|error| error["extensions"]["code"] == "ENTITY_LIMIT_EXCEEDED"),
+        "{response}"
+    );
+    assert_eq!(
+        errors[0]["message"],
+        "Operation exceeded the limit of 6 entities fetched from subgraphs"
+    );
+
+    // Products and their reviews, fetched within the limit, are still there.
+    assert_eq!(response["data"]["topProducts"][0]["reviews"][0]["id"], "review-1");
+    assert!(response["data"]["topProducts"][0]["reviews"][0]["author"].is_null());
+}
@@ -0,0 +1,40 @@
+use integration_tests::{federation::execute_with_mock, fetch::MockFetch};
+use serde_json::json;
+
+// The key is nullable in subgraph A, which may not return it.
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+      B @join__graph(name: "b", url: "https://b/graphql")
+    }
+
+    type Query
+      @join__type(graph: A)
+    {
+      product: Product @join__field(graph: A)
+    }
+
+    type Product
+      @join__type(graph: A, key: "id")
+      @join__type(graph: B, key: "id")
+    {
+      id: ID
+      name: String @join__field(graph: B)
+    }
+    "###;
+
+#[test]
+fn boundary_object_missing_a_key_field_is_an_error() {
+    // Nothing is mocked for B, the entities must not be requested.
+    let fetcher = MockFetch::default().with_responses("a", vec![json!({"data": {"product": {"id": null}}})]);
+    let response = execute_with_mock(SDL, "", fetcher, "query { product { id name } }").into_value();
+
+    let errors = response["errors"].as_array().expect("errors");
+    assert_eq!(errors.len(), 1, "{response}");
+    assert_eq!(
+        errors[0]["message"],
+        "Entity representation is missing the key field 'id'"
+    );
+    assert_eq!(errors[0]["extensions"]["code"], "INVALID_ENTITY_REPRESENTATION");
+    assert_eq!(response["data"]["product"]["id"], serde_json::Value::Null, "{response}");
+}
@@ -0,0 +1,94 @@
+use integration_tests::{federation::execute_with_mock, fetch::MockFetch};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+    }
+
+    type Query {
+      me: User @join__field(graph: A)
+    }
+
+    type User {
+      id: ID!
+    }
+    "###;
+
+fn execute(config: &str) -> serde_json::Value {
+    let fetcher = MockFetch::default().with_responses(
+        "a",
+        vec![json!({
+            "data": {"me": null},
+            "errors": [{
+                "message": "Internal error",
+                "path": ["me"],
+                "extensions": {
+                    "code": "INTERNAL",
+                    "details": {"a": [1, 2, 3]},
+                    "ids": [1, 2, 3, 4, 5, 6, 7, 8, 9, 10],
+                    "stacktrace": "at line 1\nat line 2\nat line 3"
+                }
+            }]
+        })],
+    );
+
+    let response = execute_with_mock(SDL, config, fetcher, "query { me { id } }").into_value();
+    response["errors"][0]["extensions"]["upstream_extensions"].clone()
+}
+
+#[test]
+fn oversized_extension_values_are_truncated() {
+    let extensions = execute(
+        r#"
+        [gateway]
+        max_error_extension_size = 16
+        "#,
+    );
+
+    insta::assert_json_snapshot!(extensions, @r###"
+    {
+      "code": "INTERNAL",
+      "details": {
+        "a": [
+          1,
+          2,
+          3
+        ]
+      },
+      "ids": "[1,2,3,4,5,6,7,8...(truncated)",
+      "stacktrace": "at line 1\nat lin...(truncated)"
+    }
+    "###);
+}
+
+#[test]
+fn extension_values_within_the_default_limit_are_forwarded_as_is() {
+    let extensions = execute("");
+
+    insta::assert_json_snapshot!(extensions, @r###"
+    {
+      "code": "INTERNAL",
+      "details": {
+        "a": [
+          1,
+          2,
+          3
+        ]
+      },
+      "ids": [
+        1,
+        2,
+        3,
+        4,
+        5,
+        6,
+        7,
+        8,
+        9,
+        10
+      ],
+      "stacktrace": "at line 1\nat line 2\nat line 3"
+    }
+    "###);
+}
@@ -0,0 +1,123 @@
+use integration_tests::{federation::execute_with_mock, fetch::MockFetch};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+    }
+
+    type Query {
+      me: User @join__field(graph: A)
+    }
+
+    type User {
+      name: String
+      friends: [User!]
+    }
+    "###;
+
+/// A user with a single friend, itself with a single friend, and so on `depth` times.
+fn user_with_friends(depth: usize) -> serde_json::Value {
+    (0..depth).fold(json!({"name": "Alice"}), |user, _| {
+        json!({
+            "name": "Alice",
+            "friends": [user]
+        })
+    })
+}
+
+fn query_with_friends(depth: usize) -> String {
+    format!(
+        "query {{ me {{ name {}name{} }} }}",
+        "friends { name ".repeat(depth),
+        " }".repeat(depth)
+    )
+}
+
+#[test]
+fn error_paths_are_cut_at_the_maximum_depth() {
+    let fetcher = MockFetch::default().with_responses("a", vec![json!({"data": {"me": user_with_friends(3)}})]);
+    let response = execute_with_mock(
+        SDL,
+        r#"
+        [gateway]
+        max_execution_depth = 5
+        max_error_path_depth = 4
+        "#,
+        fetcher,
+        "query { me { friends { friends { name } } } }",
+    );
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": null,
+      "errors": [
+        {
+          "message": "Execution depth exceeds the configured maximum of 5",
+          "locations": [
+            {
+              "line": 1,
+              "column": 34
+            }
+          ],
+          "path": [
+            "me",
+            "friends",
+            0,
+            "friends",
+            "…"
+          ],
+          "extensions": {
+            "code": "EXECUTION_DEPTH_EXCEEDED"
+          }
+        }
+      ]
+    }
+    "###);
+}
+
+#[test]
+fn very_deep_error_paths_are_cut() {
+    const DEPTH: usize = 50;
+
+    let fetcher = MockFetch::default().with_responses("a", vec![json!({"data": {"me": user_with_friends(DEPTH)}})]);
+    let response = execute_with_mock(
+        SDL,
+        r#"
+        [gateway]
+        max_execution_depth = 40
+        max_error_path_depth = 10
+        "#,
+        fetcher,
+        &query_with_friends(DEPTH),
+    );
+
+    assert!(!response.errors().is_empty(), "{response}");
+    for error in response.errors() {
+        assert_eq!(error["extensions"]["code"], "EXECUTION_DEPTH_EXCEEDED");
+        assert_eq!(
+            error["path"],
+            json!(["me", "friends", 0, "friends", 0, "friends", 0, "friends", 0, "friends", "…"]),
+            "{response}"
+        );
+    }
+}
+
+#[test]
+fn error_paths_are_complete_by_default() {
+    let fetcher = MockFetch::default().with_responses("a", vec![json!({"data": {"me": user_with_friends(3)}})]);
+    let response = execute_with_mock(
+        SDL,
+        r#"
+        [gateway]
+        max_execution_depth = 5
+        "#,
+        fetcher,
+        "query { me { friends { friends { name } } } }",
+    );
+
+    assert_eq!(
+        response["errors"][0]["path"],
+        json!(["me", "friends", 0, "friends", 0, "name"])
+    );
+}
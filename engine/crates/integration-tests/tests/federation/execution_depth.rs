@@ -0,0 +1,206 @@
+use integration_tests::{federation::execute_with_mock, fetch::MockFetch};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+    }
+
+    type Query {
+      me: User @join__field(graph: A)
+    }
+
+    scalar JSON
+
+    type User {
+      name: String
+      friends: [User!]
+      settings: JSON
+    }
+    "###;
+
+#[test]
+fn data_beyond_the_maximum_execution_depth_aborts_the_request() {
+    let fetcher = MockFetch::default().with_responses(
+        "a",
+        vec![json!({
+            "data": {
+                "me": {
+                    "name": "Alice",
+                    "friends": [{
+                        "name": "Bob",
+                        "friends": [{
+                            "name": "Alice",
+                            "friends": [{"name": "Bob"}]
+                        }]
+                    }]
+                }
+            }
+        })],
+    );
+    let response = execute_with_mock(
+        SDL,
+        r#"
+        [gateway]
+        max_execution_depth = 5
+        "#,
+        fetcher,
+        "query { me { name friends { name friends { name friends { name } } } } }",
+    );
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": null,
+      "errors": [
+        {
+          "message": "Execution depth exceeds the configured maximum of 5",
+          "locations": [
+            {
+              "line": 1,
+              "column": 44
+            }
+          ],
+          "path": [
+            "me",
+            "friends",
+            0,
+            "friends",
+            0,
+            "name"
+          ],
+          "extensions": {
+            "code": "EXECUTION_DEPTH_EXCEEDED"
+          }
+        }
+      ]
+    }
+    "###);
+}
+
+#[test]
+fn data_at_the_maximum_execution_depth_is_returned() {
+    let fetcher = MockFetch::default().with_responses(
+        "a",
+        vec![json!({
+            "data": {
+                "me": {
+                    "friends": [{
+                        "friends": [{"name": "Alice"}]
+                    }]
+                }
+            }
+        })],
+    );
+    let response = execute_with_mock(
+        SDL,
+        r#"
+        [gateway]
+        max_execution_depth = 6
+        "#,
+        fetcher,
+        "query { me { friends { friends { name } } } }",
+    );
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "me": {
+          "friends": [
+            {
+              "friends": [
+                {
+                  "name": "Alice"
+                }
+              ]
+            }
+          ]
+        }
+      }
+    }
+    "###);
+}
+
+#[test]
+fn nested_json_values_count_towards_the_execution_depth() {
+    let fetcher = MockFetch::default().with_responses(
+        "a",
+        vec![json!({"data": {"me": {"settings": {"theme": {"colors": ["red"]}}}}})],
+    );
+    let response = execute_with_mock(
+        SDL,
+        r#"
+        [gateway]
+        max_execution_depth = 4
+        "#,
+        fetcher,
+        "query { me { settings } }",
+    );
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": null,
+      "errors": [
+        {
+          "message": "Execution depth exceeds the configured maximum of 4",
+          "locations": [
+            {
+              "line": 1,
+              "column": 14
+            }
+          ],
+          "path": [
+            "me",
+            "settings"
+          ],
+          "extensions": {
+            "code": "EXECUTION_DEPTH_EXCEEDED"
+          }
+        }
+      ]
+    }
+    "###);
+}
+
+#[test]
+fn no_limit_on_execution_depth_by_default() {
+    let fetcher = MockFetch::default().with_responses(
+        "a",
+        vec![json!({
+            "data": {
+                "me": {
+                    "name": "Alice",
+                    "friends": [{
+                        "name": "Bob",
+                        "friends": [{"name": "Alice"}]
+                    }]
+                }
+            }
+        })],
+    );
+    let response = execute_with_mock(
+        SDL,
+        "",
+        fetcher,
+        "query { me { name friends { name friends { name } } } }",
+    );
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "me": {
+          "name": "Alice",
+          "friends": [
+            {
+              "name": "Bob",
+              "friends": [
+                {
+                  "name": "Alice"
+                }
+              ]
+            }
+          ]
+        }
+      }
+    }
+    "###);
+}
@@ -0,0 +1,78 @@
+use engine_v2::Engine;
+use integration_tests::{federation::EngineV2Ext, fetch::MockFetch, metrics::CountersRecorder, runtime};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+    }
+
+    type Query {
+      me: User @join__field(graph: A)
+    }
+
+    type User {
+      id: ID!
+      name: String
+    }
+    "###;
+
+fn execute(config: &str) -> (serde_json::Value, u64) {
+    runtime().block_on(async move {
+        let fetcher = MockFetch::default().with_responses(
+            "a",
+            vec![json!({"data": {"me": {"id": "1", "name": "Alice", "email": "alice@example.com", "age": 42}}})],
+        );
+        let counters = CountersRecorder::default();
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(config)
+            .with_mock_fetcher(fetcher)
+            .with_meter(counters.meter())
+            .build()
+            .await;
+
+        let response = engine.execute("query { me { id name } }").await;
+
+        (response.into_value(), counters.get("subgraph_extra_fields_total"))
+    })
+}
+
+#[test]
+fn extra_fields_are_ignored_by_default() {
+    let (response, extra_fields) = execute("");
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "me": {
+          "id": "1",
+          "name": "Alice"
+        }
+      }
+    }
+    "###);
+    assert_eq!(extra_fields, 0);
+}
+
+#[test]
+fn extra_fields_are_counted_with_warn_policy() {
+    let (response, extra_fields) = execute(
+        r#"
+        [subgraphs.a]
+        extra_fields = "warn"
+        "#,
+    );
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "me": {
+          "id": "1",
+          "name": "Alice"
+        }
+      }
+    }
+    "###);
+    assert_eq!(extra_fields, 2);
+}
@@ -0,0 +1,138 @@
+use engine_v2::Engine;
+use integration_tests::{federation::EngineV2Ext, fetch::MockFetch, runtime};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+    }
+
+    type Query {
+      me: User @join__field(graph: A)
+    }
+
+    type User {
+      id: ID!
+      nickname: String @feature(name: "nicknames")
+    }
+    "###;
+
+#[test]
+fn gated_field_is_unknown_without_the_flag() {
+    let response = runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_mock_fetcher(MockFetch::default())
+            .build()
+            .await;
+
+        engine
+            .execute("query { me { id nickname } }")
+            .header("x-grafbase-flags", "other")
+            .await
+    });
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "errors": [
+        {
+          "message": "User does not have a field named 'nickname'",
+          "locations": [
+            {
+              "line": 1,
+              "column": 17
+            }
+          ],
+          "extensions": {
+            "retryable": false,
+            "code": "OPERATION_VALIDATION_ERROR"
+          }
+        }
+      ]
+    }
+    "###);
+}
+
+#[test]
+fn gated_field_is_selectable_with_the_flag() {
+    let response = runtime().block_on(async move {
+        let fetcher =
+            MockFetch::default().with_responses("a", vec![json!({"data": {"me": {"id": "1", "nickname": "Al"}}})]);
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_mock_fetcher(fetcher)
+            .build()
+            .await;
+
+        let response = engine
+            .execute("query { me { id nickname } }")
+            .header("x-grafbase-flags", "other, nicknames")
+            .await;
+
+        // The same operation must not be served from the cache without the flag.
+        let without_flag = engine.execute("query { me { id nickname } }").await;
+        assert_eq!(
+            without_flag.into_value()["errors"][0]["message"],
+            "User does not have a field named 'nickname'"
+        );
+
+        response
+    });
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "me": {
+          "id": "1",
+          "nickname": "Al"
+        }
+      }
+    }
+    "###);
+}
+
+#[test]
+fn gated_field_is_hidden_from_introspection_without_the_flag() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_mock_fetcher(MockFetch::default())
+            .build()
+            .await;
+
+        let query = r#"query { __type(name: "User") { fields { name } } }"#;
+
+        let response = engine.execute(query).await;
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "__type": {
+              "fields": [
+                {
+                  "name": "id"
+                }
+              ]
+            }
+          }
+        }
+        "###);
+
+        let response = engine.execute(query).header("x-grafbase-flags", "nicknames").await;
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "__type": {
+              "fields": [
+                {
+                  "name": "id"
+                },
+                {
+                  "name": "nickname"
+                }
+              ]
+            }
+          }
+        }
+        "###);
+    })
+}
@@ -0,0 +1,316 @@
+use std::time::Duration;
+
+use engine_v2::Engine;
+use graphql_mocks::{FakeGithubSchema, FederatedProductsSchema, FederatedReviewsSchema};
+use integration_tests::{federation::EngineV2Ext, fetch::MockFetch, runtime};
+use serde_json::json;
+
+const DEFERRED_QUERY: &str = "query { ... @defer { serverVersion } }";
+
+#[test]
+fn deferred_operation_is_rejected_when_only_json_is_accepted() {
+    runtime().block_on(async move {
+        let engine = Engine::builder().with_subgraph(FakeGithubSchema).build().await;
+
+        let response = engine
+            .execute(DEFERRED_QUERY)
+            .header("Accept", "application/json")
+            .await;
+
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "errors": [
+            {
+              "message": "The operation uses @defer or @stream, which require a multipart or event stream response not allowed by the Accept header",
+              "extensions": {
+                "code": "NOT_ACCEPTABLE"
+              }
+            }
+          ]
+        }
+        "###);
+
+        // Rejected before anything reached the subgraph.
+        let requests = engine.drain_graphql_requests_sent_to::<FakeGithubSchema>();
+        assert!(requests.is_empty(), "{requests:?}");
+    })
+}
+
+#[test]
+fn deferred_operation_is_executed_at_once_when_configured() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FakeGithubSchema)
+            .with_toml_config(
+                r#"
+                [gateway]
+                incompatible_accept = "ignore"
+                "#,
+            )
+            .build()
+            .await;
+
+        let response = engine
+            .execute(DEFERRED_QUERY)
+            .header("Accept", "application/json")
+            .await;
+
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "serverVersion": "1"
+          }
+        }
+        "###);
+    })
+}
+
+#[test]
+fn operation_without_defer_is_executed() {
+    runtime().block_on(async move {
+        let engine = Engine::builder().with_subgraph(FakeGithubSchema).build().await;
+
+        let response = engine
+            .execute("query { ... { serverVersion } }")
+            .header("Accept", "application/json")
+            .await;
+
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "serverVersion": "1"
+          }
+        }
+        "###);
+    })
+}
+
+#[test]
+fn deferred_fragment_is_streamed_after_the_initial_response() {
+    runtime().block_on(async move {
+        let engine = Engine::builder().with_subgraph(FakeGithubSchema).build().await;
+
+        let response = engine
+            .execute(r#"query { serverVersion ... @defer(label: "repository") { favoriteRepository } }"#)
+            .into_multipart_stream()
+            .collect::<Vec<_>>()
+            .await;
+
+        insta::assert_json_snapshot!(response, @r###"
+        [
+          {
+            "data": {
+              "serverVersion": "1"
+            },
+            "hasNext": true
+          },
+          {
+            "incremental": [
+              {
+                "data": {
+                  "favoriteRepository": {
+                    "owner": "rust-lang",
+                    "name": "rust"
+                  }
+                },
+                "path": [],
+                "label": "repository"
+              }
+            ],
+            "hasNext": false
+          }
+        ]
+        "###);
+    })
+}
+
+#[test]
+fn deferred_fragment_within_a_list_is_resolved_from_the_initial_objects() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_subgraph(FederatedReviewsSchema)
+            .build()
+            .await;
+
+        let response = engine
+            .execute(r#"query { topProducts { upc ... @defer(label: "reviews") { reviews { id } } } }"#)
+            .into_multipart_stream()
+            .collect::<Vec<_>>()
+            .await;
+
+        insta::assert_json_snapshot!(response, @r###"
+        [
+          {
+            "data": {
+              "topProducts": [
+                {
+                  "upc": "top-1"
+                },
+                {
+                  "upc": "top-2"
+                },
+                {
+                  "upc": "top-3"
+                },
+                {
+                  "upc": "top-4"
+                },
+                {
+                  "upc": "top-5"
+                }
+              ]
+            },
+            "hasNext": true
+          },
+          {
+            "incremental": [
+              {
+                "data": {
+                  "reviews": [
+                    {
+                      "id": "review-1"
+                    }
+                  ]
+                },
+                "path": [
+                  "topProducts",
+                  0
+                ],
+                "label": "reviews"
+              },
+              {
+                "data": {
+                  "reviews": [
+                    {
+                      "id": "review-2"
+                    }
+                  ]
+                },
+                "path": [
+                  "topProducts",
+                  1
+                ],
+                "label": "reviews"
+              },
+              {
+                "data": {
+                  "reviews": [
+                    {
+                      "id": "review-3"
+                    }
+                  ]
+                },
+                "path": [
+                  "topProducts",
+                  2
+                ],
+                "label": "reviews"
+              },
+              {
+                "data": {
+                  "reviews": []
+                },
+                "path": [
+                  "topProducts",
+                  3
+                ],
+                "label": "reviews"
+              },
+              {
+                "data": {
+                  "reviews": [
+                    {
+                      "id": "review-5"
+                    }
+                  ]
+                },
+                "path": [
+                  "topProducts",
+                  4
+                ],
+                "label": "reviews"
+              }
+            ],
+            "hasNext": false
+          }
+        ]
+        "###);
+
+        // The products were only fetched once, for the initial response.
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len(),
+            1
+        );
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedReviewsSchema>().len(),
+            1
+        );
+    })
+}
+
+#[test]
+fn each_deferred_fragment_is_sent_once_resolved() {
+    runtime().block_on(async move {
+        let fetcher = MockFetch::default()
+            .with_responses("a", [json!({"data": {"fast": "fast"}})])
+            .with_responses("b", [json!({"data": {"slow": "slow"}})])
+            .with_host_delay("b", Duration::from_millis(200));
+        let engine = Engine::builder()
+            .with_federated_sdl(
+                r###"
+                enum join__Graph {
+                  A @join__graph(name: "a", url: "https://a/graphql")
+                  B @join__graph(name: "b", url: "https://b/graphql")
+                }
+
+                type Query {
+                  fast: String @join__field(graph: A)
+                  slow: String @join__field(graph: B)
+                }
+                "###,
+            )
+            .with_mock_fetcher(fetcher)
+            .build()
+            .await;
+
+        let response = engine
+            .execute(r#"query { ... @defer(label: "slow") { slow } ... @defer(label: "fast") { fast } }"#)
+            .into_multipart_stream()
+            .collect::<Vec<_>>()
+            .await;
+
+        insta::assert_json_snapshot!(response, @r###"
+        [
+          {
+            "data": {},
+            "hasNext": true
+          },
+          {
+            "incremental": [
+              {
+                "data": {
+                  "fast": "fast"
+                },
+                "path": [],
+                "label": "fast"
+              }
+            ],
+            "hasNext": true
+          },
+          {
+            "incremental": [
+              {
+                "data": {
+                  "slow": "slow"
+                },
+                "path": [],
+                "label": "slow"
+              }
+            ],
+            "hasNext": false
+          }
+        ]
+        "###);
+    })
+}
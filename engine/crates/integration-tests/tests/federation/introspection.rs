@@ -759,6 +759,7 @@ fn rejects_bogus_introspection_queries() {
             }
           ],
           "extensions": {
+            "retryable": false,
             "code": "OPERATION_VALIDATION_ERROR"
           }
         }
@@ -999,6 +1000,52 @@ fn default_values() {
     "###);
 }
 
+#[test]
+fn introspection_responses_are_cached_until_the_schema_changes() {
+    const CONFIG: &str = r#"
+        [graph]
+        introspection_cache_max_age = "1h"
+    "#;
+    const QUERY: &str = "query { __schema { queryType { fields { name } } } }";
+
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FakeGithubSchema)
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        let first = engine.execute(QUERY).await;
+        let cached = engine.execute(QUERY).await;
+
+        assert_eq!(
+            first.headers.get(http::header::CACHE_CONTROL).unwrap(),
+            "public, max-age=3600"
+        );
+        assert_eq!(
+            cached.headers.get(http::header::CACHE_CONTROL).unwrap(),
+            "public, max-age=3600"
+        );
+        assert_eq!(first.body, cached.body);
+
+        // Fields resolved by subgraphs are never cached.
+        let response = engine.execute("query { __typename serverVersion }").await;
+        assert!(response.headers.get(http::header::CACHE_CONTROL).is_none());
+
+        // A new schema, as after a hot reload, gets its own introspection.
+        let engine = Engine::builder()
+            .with_subgraph(EchoSchema)
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        let swapped = engine.execute(QUERY).await;
+
+        assert!(swapped.errors().is_empty(), "{swapped}");
+        assert_ne!(first.body, swapped.body);
+    });
+}
+
 #[allow(clippy::panic)]
 fn introspection_to_sdl(data: serde_json::Value) -> String {
     serde_json::from_value::<IntrospectionQuery>(data)
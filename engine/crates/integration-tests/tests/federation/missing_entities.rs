@@ -0,0 +1,78 @@
+use integration_tests::{federation::execute_with_mock, fetch::MockFetch};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+      B @join__graph(name: "b", url: "https://b/graphql")
+    }
+
+    type Query
+      @join__type(graph: A)
+    {
+      product: Product @join__field(graph: A)
+    }
+
+    type Product
+      @join__type(graph: A, key: "id")
+      @join__type(graph: B, key: "id")
+    {
+      id: ID!
+      name: String @join__field(graph: B)
+    }
+    "###;
+
+fn execute(entities_response: serde_json::Value) -> serde_json::Value {
+    let fetcher = MockFetch::default()
+        .with_responses("a", vec![json!({"data": {"product": {"id": "1"}}})])
+        .with_responses("b", vec![entities_response]);
+
+    execute_with_mock(SDL, "", fetcher, "query { product { id name } }").into_value()
+}
+
+#[test]
+fn entities_are_resolved() {
+    let response = execute(json!({"data": {"_entities": [{"__typename": "Product", "name": "Fedora"}]}}));
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "product": {
+          "id": "1",
+          "name": "Fedora"
+        }
+      }
+    }
+    "###);
+}
+
+#[test]
+fn response_without_entities_is_an_error() {
+    let response = execute(json!({"data": {"product": {"name": "Fedora"}}}));
+
+    let errors = response["errors"].as_array().expect("errors");
+    assert_eq!(errors.len(), 1, "{response}");
+    assert_eq!(
+        errors[0]["message"],
+        "Subgraph response data doesn't contain any `_entities`"
+    );
+    assert_eq!(errors[0]["extensions"]["code"], "SUBGRAPH_MISSING_ENTITIES");
+}
+
+#[test]
+fn entities_which_are_not_a_list_are_an_error() {
+    let response = execute(json!({"data": {"_entities": "not-an-array"}}));
+
+    let errors = response["errors"].as_array().expect("errors");
+    assert_eq!(errors.len(), 1, "{response}");
+    assert_eq!(
+        errors[0]["message"],
+        "Subgraph 'b' returned a malformed `_entities`, it must be a list"
+    );
+    assert_eq!(errors[0]["extensions"]["code"], "SUBGRAPH_MALFORMED_ENTITIES");
+    assert_eq!(
+        response["data"]["product"]["name"],
+        serde_json::Value::Null,
+        "{response}"
+    );
+}
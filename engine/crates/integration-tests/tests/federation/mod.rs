@@ -1,12 +1,64 @@
+mod aliased_fetches;
+mod allowed_operations;
 mod apq;
+mod array_response;
 mod auth;
 mod basic;
+mod batching;
+mod big_int;
+mod client_disconnect;
+mod client_version;
+mod cost_budget;
+mod empty_query;
+mod entities_operation_name;
+mod entity_batching;
 mod entity_caching;
+mod entity_limit;
+mod entity_representations;
+mod error_extensions;
+mod error_path_depth;
+mod execution_depth;
+mod extra_fields;
+mod feature_flags;
 mod hooks;
+mod incremental_delivery;
 mod introspection;
 mod issues;
+mod missing_entities;
+mod null_data;
+mod nullability_override;
+mod nullable_objects;
+mod operation_cache;
+mod parse_timeout;
+mod pretty_responses;
+mod request_compression;
+mod request_deduplication;
+mod required_headers;
+mod response_cache;
+mod response_key_casing;
+mod response_transforms;
+mod response_validation;
+mod root_fields_concurrency;
+mod scalar_coercion;
+mod scalar_formats;
+mod serialization_timeout;
+mod shadow_gateway;
+mod shadow_subgraph;
+mod string_length;
+mod subgraph_cache;
+mod subgraph_concurrency;
+mod subgraph_limit;
+mod subgraph_query_depth;
+mod subgraph_response_transform;
 mod subgraph_retries;
+mod subgraph_tls;
+mod subgraph_url_template;
 mod subgraphs;
+mod subscription_limits;
 mod subscriptions;
+mod tenants;
 mod timeouts;
+mod tracked_operations;
 mod trusted_documents;
+mod type_mismatch;
+mod typename;
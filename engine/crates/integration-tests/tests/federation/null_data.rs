@@ -0,0 +1,76 @@
+use integration_tests::{federation::execute_with_mock, fetch::MockFetch};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+    }
+
+    type Query {
+      nullable: String @join__field(graph: A)
+      required: String! @join__field(graph: A)
+    }
+    "###;
+
+fn execute(config: &str, query: &str) -> serde_json::Value {
+    let fetcher = MockFetch::default().with_responses("a", vec![json!({"data": null})]);
+    execute_with_mock(SDL, config, fetcher, query).into_value()
+}
+
+#[test]
+fn null_data_is_allowed_by_default() {
+    let response = execute("", "query { nullable }");
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "nullable": null
+      }
+    }
+    "###);
+
+    let response = execute("", "query { required }");
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": null
+    }
+    "###);
+}
+
+#[test]
+fn null_data_is_an_error_for_non_null_selections() {
+    let config = r#"
+        [subgraphs.a]
+        null_data = "error"
+    "#;
+
+    let response = execute(config, "query { required }");
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": null,
+      "errors": [
+        {
+          "message": "Subgraph returned null data without any errors",
+          "path": [
+            "required"
+          ],
+          "extensions": {
+            "code": "SUBGRAPH_INVALID_RESPONSE_ERROR"
+          }
+        }
+      ]
+    }
+    "###);
+
+    let response = execute(config, "query { nullable }");
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "nullable": null
+      }
+    }
+    "###);
+}
@@ -0,0 +1,77 @@
+use integration_tests::{federation::execute_with_mock, fetch::MockFetch};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+      B @join__graph(name: "b", url: "https://b/graphql")
+    }
+
+    type Query
+      @join__type(graph: A)
+    {
+      product: Product @join__field(graph: A)
+    }
+
+    type Product
+      @join__type(graph: A, key: "id")
+      @join__type(graph: B, key: "id")
+    {
+      id: ID!
+      name: String! @join__field(graph: B)
+    }
+    "###;
+
+fn execute(config: &str) -> serde_json::Value {
+    let fetcher = MockFetch::default()
+        .with_responses("a", vec![json!({"data": {"product": {"id": "1"}}})])
+        .with_responses(
+            "b",
+            vec![json!({"data": {"_entities": [{"__typename": "Product", "name": null}]}})],
+        );
+
+    execute_with_mock(SDL, config, fetcher, "query { product { id name } }").into_value()
+}
+
+#[test]
+fn null_for_non_null_field_is_propagated_by_default() {
+    let response = execute("");
+
+    assert_eq!(response["data"], json!({"product": null}));
+    assert_eq!(response["errors"][0]["path"], json!(["product", "name"]));
+}
+
+#[test]
+fn null_for_overridden_field_is_kept() {
+    let response = execute(
+        r#"
+        [gateway]
+        nullable_fields = ["Product.name"]
+        "#,
+    );
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "product": {
+          "id": "1",
+          "name": null
+        }
+      }
+    }
+    "###);
+}
+
+#[test]
+fn overridden_field_passes_response_validation() {
+    let response = execute(
+        r#"
+        [gateway]
+        nullable_fields = ["Product.name"]
+        validate_response_nullability = true
+        "#,
+    );
+
+    assert_eq!(response["data"], json!({"product": {"id": "1", "name": null}}));
+    assert!(response.get("errors").is_none());
+}
@@ -0,0 +1,124 @@
+use integration_tests::{federation::execute_with_mock, fetch::MockFetch};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+    }
+
+    type Query {
+      me: User @join__field(graph: A)
+      search: SearchResult @join__field(graph: A)
+    }
+
+    type User {
+      id: ID!
+      address: Address!
+    }
+
+    type Address {
+      street: String!
+      city: String
+      zip: String
+    }
+
+    type Product {
+      id: ID!
+    }
+
+    union SearchResult = User | Product
+    "###;
+
+#[test]
+fn non_null_object_with_null_fields_stays_an_object() {
+    let fetcher = MockFetch::default().with_responses(
+        "a",
+        vec![json!({"data": {"me": {"id": "1", "address": {"city": null}}}})],
+    );
+    let response = execute_with_mock(SDL, "", fetcher, "query { me { id address { city zip } } }");
+
+    // `zip` is missing from the subgraph response, it's null just like `city`.
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "me": {
+          "id": "1",
+          "address": {
+            "city": null,
+            "zip": null
+          }
+        }
+      }
+    }
+    "###);
+}
+
+#[test]
+fn fully_errored_non_null_object_propagates_null() {
+    let fetcher = MockFetch::default().with_responses(
+        "a",
+        vec![json!({"data": {"me": {"id": "1", "address": {"city": "Paris"}}}})],
+    );
+    let response = execute_with_mock(SDL, "", fetcher, "query { me { id address { street city } } }");
+
+    // The required `street` is missing, so `address` is invalid and being non-null, `me` is
+    // nulled instead.
+    assert_eq!(response["data"], json!({"me": null}), "{response}");
+    assert_eq!(response.errors().len(), 1, "{response}");
+    let error = &response["errors"][0];
+    assert!(
+        error["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("Error decoding response from upstream: Missing required field named 'street'"),
+        "{response}"
+    );
+    assert_eq!(error["path"], json!(["me"]));
+    assert_eq!(error["extensions"]["code"], "SUBGRAPH_INVALID_RESPONSE_ERROR");
+}
+
+#[test]
+fn unknown_typename_is_an_empty_object_by_default() {
+    let fetcher = MockFetch::default().with_responses(
+        "a",
+        vec![json!({"data": {"search": {"__typename": "Review", "id": "1"}}})],
+    );
+    let response = execute_with_mock(SDL, "", fetcher, "query { search { ... on User { id } } }");
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "search": {}
+      }
+    }
+    "###);
+}
+
+#[test]
+fn unknown_typename_error() {
+    let fetcher = MockFetch::default().with_responses(
+        "a",
+        vec![json!({"data": {"search": {"__typename": "Review", "id": "1"}}})],
+    );
+    let response = execute_with_mock(
+        SDL,
+        r#"
+        [subgraphs.a]
+        unknown_typename = "error"
+        "#,
+        fetcher,
+        "query { search { ... on User { id } } }",
+    );
+
+    assert_eq!(response["data"], json!({"search": null}), "{response}");
+    let error = &response["errors"][0];
+    assert!(
+        error["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("Unknown __typename 'Review', it isn't a possible type of the field"),
+        "{response}"
+    );
+    assert_eq!(error["path"], json!(["search"]));
+    assert_eq!(error["extensions"]["code"], "SUBGRAPH_INVALID_RESPONSE_ERROR");
+}
@@ -0,0 +1,82 @@
+use engine_v2::Engine;
+use graphql_mocks::FakeGithubSchema;
+use integration_tests::{federation::EngineV2Ext, runtime};
+use serde_json::json;
+
+#[test]
+fn equivalent_documents_report_errors_on_their_own_document() {
+    runtime().block_on(async move {
+        let engine = Engine::builder().with_subgraph(FakeGithubSchema).build().await;
+
+        let response = engine
+            .execute("query($id: ID!) { pullRequest(id: $id) { title } }")
+            .await;
+
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "errors": [
+            {
+              "message": "Variable $id is missing",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 7
+                }
+              ],
+              "extensions": {
+                "retryable": false,
+                "code": "OPERATION_VALIDATION_ERROR"
+              }
+            }
+          ]
+        }
+        "###);
+
+        // Same operation, so it's served from the cache entry of the first one.
+        let response = engine
+            .execute("query(\n  $pullRequestId: ID!\n) {\n  pullRequest(id: $pullRequestId) {\n    title\n  }\n}")
+            .await;
+
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "errors": [
+            {
+              "message": "Variable $pullRequestId is missing",
+              "locations": [
+                {
+                  "line": 2,
+                  "column": 3
+                }
+              ],
+              "extensions": {
+                "retryable": false,
+                "code": "OPERATION_VALIDATION_ERROR"
+              }
+            }
+          ]
+        }
+        "###);
+    });
+}
+
+#[test]
+fn equivalent_documents_bind_their_own_variable_names() {
+    runtime().block_on(async move {
+        let engine = Engine::builder().with_subgraph(FakeGithubSchema).build().await;
+
+        let response = engine
+            .execute("query($id: ID!) { pullRequest(id: $id) { title } }")
+            .variables(json!({"id": "1"}))
+            .await;
+        assert_eq!(
+            response["data"],
+            json!({"pullRequest": {"title": "Creating the thing"}})
+        );
+
+        let response = engine
+            .execute("query($pullRequestId: ID!) {\n  pullRequest(id: $pullRequestId) { title }\n}")
+            .variables(json!({"pullRequestId": "2"}))
+            .await;
+        assert_eq!(response["data"], json!({"pullRequest": {"title": "Some bot PR"}}));
+    });
+}
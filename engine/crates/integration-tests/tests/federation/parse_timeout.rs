@@ -0,0 +1,59 @@
+use engine_v2::Engine;
+use graphql_mocks::FakeGithubSchema;
+use integration_tests::{federation::EngineV2Ext, runtime};
+
+const CONFIG: &str = r#"
+    [gateway]
+    max_parse_time = "1ms"
+"#;
+
+#[test]
+fn slow_to_parse_operation_is_rejected() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FakeGithubSchema)
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        // Small enough for any sensible size limit, but hundreds of thousands of nodes to go
+        // through.
+        let query = format!("{{ {} }}", "a: serverVersion ".repeat(100_000));
+        let response = engine.execute(query.as_str()).await;
+
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "errors": [
+            {
+              "message": "Parsing the operation took longer than the allowed 1ms.",
+              "extensions": {
+                "retryable": false,
+                "code": "QUERY_PARSE_TIMEOUT"
+              }
+            }
+          ]
+        }
+        "###);
+    });
+}
+
+#[test]
+fn operation_parsed_within_the_budget_is_executed() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FakeGithubSchema)
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        let response = engine.execute("query { serverVersion }").await;
+
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "serverVersion": "1"
+          }
+        }
+        "###);
+    });
+}
@@ -0,0 +1,70 @@
+use engine_v2::Engine;
+use graphql_mocks::FakeGithubSchema;
+use integration_tests::{federation::EngineV2Ext, runtime};
+
+const QUERY: &str = "query { serverVersion }";
+
+#[test]
+fn pretty_header_indents_the_response() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FakeGithubSchema)
+            .with_toml_config(
+                r#"
+                [gateway]
+                pretty_responses = true
+                "#,
+            )
+            .build()
+            .await;
+
+        let body = engine
+            .execute(QUERY)
+            .header("x-grafbase-pretty", "true")
+            .into_raw_body()
+            .await;
+
+        insta::assert_snapshot!(body, @r###"
+        {
+          "data": {
+            "serverVersion": "1"
+          }
+        }
+        "###);
+    });
+}
+
+#[test]
+fn responses_are_compact_without_the_header() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FakeGithubSchema)
+            .with_toml_config(
+                r#"
+                [gateway]
+                pretty_responses = true
+                "#,
+            )
+            .build()
+            .await;
+
+        let body = engine.execute(QUERY).into_raw_body().await;
+
+        insta::assert_snapshot!(body, @r###"{"data":{"serverVersion":"1"}}"###);
+    });
+}
+
+#[test]
+fn pretty_header_is_ignored_unless_enabled() {
+    runtime().block_on(async move {
+        let engine = Engine::builder().with_subgraph(FakeGithubSchema).build().await;
+
+        let body = engine
+            .execute(QUERY)
+            .header("x-grafbase-pretty", "true")
+            .into_raw_body()
+            .await;
+
+        insta::assert_snapshot!(body, @r###"{"data":{"serverVersion":"1"}}"###);
+    });
+}
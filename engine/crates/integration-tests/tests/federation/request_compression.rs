@@ -0,0 +1,107 @@
+use engine_v2::Engine;
+use integration_tests::{federation::EngineV2Ext, fetch::MockFetch, runtime};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+    }
+
+    type Query {
+      me: User @join__field(graph: A)
+    }
+
+    type Mutation {
+      updateUser(bio: String!): User @join__field(graph: A)
+    }
+
+    type User {
+      id: ID!
+      bio: String
+    }
+    "###;
+
+const MUTATION: &str = "mutation($bio: String!) { updateUser(bio: $bio) { id } }";
+
+#[test]
+fn large_request_bodies_are_compressed() {
+    runtime().block_on(async move {
+        let fetcher = MockFetch::default().with_responses(
+            "a",
+            vec![
+                json!({"data": {"updateUser": {"id": "1"}}}),
+                json!({"data": {"updateUser": {"id": "1"}}}),
+            ],
+        );
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(
+                r#"
+                [subgraphs.a.request_compression]
+                enabled = true
+                threshold = 512
+                "#,
+            )
+            .with_mock_fetcher(fetcher.clone())
+            .build()
+            .await;
+
+        let bio = "a".repeat(1024);
+
+        engine
+            .execute(MUTATION)
+            .variables(json!({"bio": "short"}))
+            .await
+            .into_data();
+
+        engine
+            .execute(MUTATION)
+            .variables(json!({"bio": bio}))
+            .await
+            .into_data();
+
+        let requests = fetcher
+            .drain_received_requests()
+            .map(|(_, request)| request)
+            .collect::<Vec<_>>();
+
+        let [small, large] = requests.as_slice() else {
+            panic!("Expected two requests, got {}", requests.len());
+        };
+
+        assert_eq!(small.headers.get(http::header::CONTENT_ENCODING), None);
+        assert_eq!(
+            serde_json::to_value(&small.body.variables).unwrap(),
+            json!({"var0": "short"})
+        );
+
+        // The mock fetcher only decodes the body as gzip if the header is present.
+        assert_eq!(large.headers.get(http::header::CONTENT_ENCODING).unwrap(), "gzip");
+        assert_eq!(
+            serde_json::to_value(&large.body.variables).unwrap(),
+            json!({"var0": bio})
+        );
+    });
+}
+
+#[test]
+fn request_compression_is_disabled_by_default() {
+    runtime().block_on(async move {
+        let fetcher = MockFetch::default().with_responses("a", vec![json!({"data": {"updateUser": {"id": "1"}}})]);
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_mock_fetcher(fetcher.clone())
+            .build()
+            .await;
+
+        engine
+            .execute(MUTATION)
+            .variables(json!({"bio": "a".repeat(4096)}))
+            .await
+            .into_data();
+
+        let (_, request) = fetcher.drain_received_requests().next().unwrap();
+
+        assert_eq!(request.headers.get(http::header::CONTENT_ENCODING), None);
+    });
+}
@@ -0,0 +1,220 @@
+use std::{future::IntoFuture, time::Duration};
+
+use engine_v2::Engine;
+use integration_tests::{federation::EngineV2Ext, fetch::MockFetch, runtime};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+    }
+
+    type Query {
+      me: User @join__field(graph: A)
+    }
+
+    type Mutation {
+      updateUser(name: String!): User @join__field(graph: A)
+    }
+
+    type User {
+      id: ID!
+      name: String
+    }
+    "###;
+
+const CONFIG: &str = r#"
+    [gateway]
+    request_deduplication = true
+    "#;
+
+#[test]
+fn concurrent_identical_queries_are_executed_once() {
+    runtime().block_on(async move {
+        let fetcher = MockFetch::default()
+            .with_responses("a", vec![json!({"data": {"me": {"id": "1", "name": "Alice"}}})])
+            .with_delay(Duration::from_millis(50));
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(CONFIG)
+            .with_mock_fetcher(fetcher.clone())
+            .build()
+            .await;
+
+        let responses =
+            futures::future::join_all((0..5).map(|_| engine.execute("query { me { id name } }").into_future())).await;
+
+        for response in responses {
+            insta::assert_json_snapshot!(response, @r###"
+            {
+              "data": {
+                "me": {
+                  "id": "1",
+                  "name": "Alice"
+                }
+              }
+            }
+            "###);
+        }
+        assert_eq!(fetcher.drain_received_requests().count(), 1);
+    })
+}
+
+#[test]
+fn queries_with_different_variables_are_not_deduplicated() {
+    runtime().block_on(async move {
+        let fetcher = MockFetch::default()
+            .with_responses(
+                "a",
+                vec![
+                    json!({"data": {"me": {"id": "1"}}}),
+                    json!({"data": {"me": {"id": "1"}}}),
+                ],
+            )
+            .with_delay(Duration::from_millis(50));
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(CONFIG)
+            .with_mock_fetcher(fetcher.clone())
+            .build()
+            .await;
+
+        let query = "query($withName: Boolean!) { me { id name @include(if: $withName) } }";
+        futures::join!(
+            engine
+                .execute(query)
+                .variables(json!({"withName": false}))
+                .into_future(),
+            engine.execute(query).variables(json!({"withName": true})).into_future(),
+        );
+
+        assert_eq!(fetcher.drain_received_requests().count(), 2);
+    })
+}
+
+#[test]
+fn queries_with_different_forwarded_cookies_are_not_deduplicated() {
+    runtime().block_on(async move {
+        let fetcher = MockFetch::default()
+            .with_responses(
+                "a",
+                vec![
+                    json!({"data": {"me": {"id": "1"}}}),
+                    json!({"data": {"me": {"id": "2"}}}),
+                ],
+            )
+            .with_delay(Duration::from_millis(50));
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(
+                r#"
+                [gateway]
+                request_deduplication = true
+
+                [[subgraphs.a.headers]]
+                rule = "forward"
+                name = "cookie"
+                "#,
+            )
+            .with_mock_fetcher(fetcher.clone())
+            .build()
+            .await;
+
+        futures::join!(
+            engine
+                .execute("query { me { id } }")
+                .header("cookie", "session=alice")
+                .into_future(),
+            engine
+                .execute("query { me { id } }")
+                .header("cookie", "session=bob")
+                .into_future(),
+        );
+
+        assert_eq!(fetcher.drain_received_requests().count(), 2);
+    })
+}
+
+#[test]
+fn headers_which_are_not_forwarded_do_not_prevent_deduplication() {
+    runtime().block_on(async move {
+        let fetcher = MockFetch::default()
+            .with_responses("a", vec![json!({"data": {"me": {"id": "1"}}})])
+            .with_delay(Duration::from_millis(50));
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(CONFIG)
+            .with_mock_fetcher(fetcher.clone())
+            .build()
+            .await;
+
+        futures::join!(
+            engine
+                .execute("query { me { id } }")
+                .header("x-request-id", "1")
+                .into_future(),
+            engine
+                .execute("query { me { id } }")
+                .header("x-request-id", "2")
+                .into_future(),
+        );
+
+        assert_eq!(fetcher.drain_received_requests().count(), 1);
+    })
+}
+
+#[test]
+fn concurrent_identical_mutations_are_all_executed() {
+    runtime().block_on(async move {
+        let fetcher = MockFetch::default()
+            .with_responses(
+                "a",
+                vec![
+                    json!({"data": {"updateUser": {"id": "1"}}}),
+                    json!({"data": {"updateUser": {"id": "1"}}}),
+                ],
+            )
+            .with_delay(Duration::from_millis(50));
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(CONFIG)
+            .with_mock_fetcher(fetcher.clone())
+            .build()
+            .await;
+
+        let mutation = r#"mutation { updateUser(name: "Bob") { id } }"#;
+        futures::join!(
+            engine.execute(mutation).into_future(),
+            engine.execute(mutation).into_future()
+        );
+
+        assert_eq!(fetcher.drain_received_requests().count(), 2);
+    })
+}
+
+#[test]
+fn identical_queries_are_executed_concurrently_without_deduplication() {
+    runtime().block_on(async move {
+        let fetcher = MockFetch::default()
+            .with_responses(
+                "a",
+                vec![
+                    json!({"data": {"me": {"id": "1"}}}),
+                    json!({"data": {"me": {"id": "1"}}}),
+                ],
+            )
+            .with_delay(Duration::from_millis(50));
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_mock_fetcher(fetcher.clone())
+            .build()
+            .await;
+
+        futures::join!(
+            engine.execute("query { me { id } }").into_future(),
+            engine.execute("query { me { id } }").into_future()
+        );
+
+        assert_eq!(fetcher.drain_received_requests().count(), 2);
+    })
+}
@@ -0,0 +1,66 @@
+use engine_v2::Engine;
+use graphql_mocks::FakeGithubSchema;
+use integration_tests::{federation::EngineV2Ext, runtime};
+
+const CONFIG: &str = r#"
+    [gateway]
+    required_headers = ["x-api-key", "X-Tenant-Id"]
+"#;
+
+#[test]
+fn request_missing_a_required_header_is_rejected() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FakeGithubSchema)
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        let response = engine
+            .execute("query { serverVersion }")
+            .header("x-api-key", "secret")
+            .await;
+
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "errors": [
+            {
+              "message": "Missing required header 'x-tenant-id'",
+              "extensions": {
+                "code": "MISSING_REQUIRED_HEADER"
+              }
+            }
+          ]
+        }
+        "###);
+
+        // Rejected before anything reached the subgraph.
+        let requests = engine.drain_graphql_requests_sent_to::<FakeGithubSchema>();
+        assert!(requests.is_empty(), "{requests:?}");
+    })
+}
+
+#[test]
+fn request_with_all_required_headers_is_executed() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FakeGithubSchema)
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+
+        let response = engine
+            .execute("query { serverVersion }")
+            .header("x-api-key", "secret")
+            .header("x-tenant-id", "acme")
+            .await;
+
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "serverVersion": "1"
+          }
+        }
+        "###);
+    })
+}
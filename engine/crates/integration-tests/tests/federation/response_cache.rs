@@ -0,0 +1,177 @@
+use engine_v2::Engine;
+use graphql_mocks::FederatedProductsSchema;
+use integration_tests::{federation::EngineV2Ext, runtime};
+
+#[test]
+fn cached_response_is_purged_by_type_key() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_toml_config(
+                r#"
+                [response_cache]
+                enabled = true
+                "#,
+            )
+            .build()
+            .await;
+
+        let query = "query { topProducts { upc name } }";
+
+        let first = engine.execute(query).await;
+        assert_eq!(first.headers.get("surrogate-key").unwrap(), "Product");
+
+        let second = engine.execute(query).await;
+        assert_eq!(second.headers.get("x-grafbase-cache").unwrap(), "HIT");
+        assert_eq!(second.headers.get("surrogate-key").unwrap(), "Product");
+        assert_eq!(first.body, second.body);
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len(),
+            1
+        );
+
+        assert_eq!(engine.invalidate_by_key("User"), 0);
+        assert_eq!(engine.invalidate_by_key("Product"), 1);
+
+        let third = engine.execute(query).await;
+        assert!(third.headers.get("x-grafbase-cache").is_none());
+        assert_eq!(first.body, third.body);
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len(),
+            1
+        );
+    });
+}
+
+#[test]
+fn requests_with_authorization_bypass_the_cache() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_toml_config(
+                r#"
+                [response_cache]
+                enabled = true
+                "#,
+            )
+            .build()
+            .await;
+
+        let query = "query { topProducts { upc name } }";
+
+        for _ in 0..2 {
+            let response = engine.execute(query).header("Authorization", "Bearer secret").await;
+            assert!(response.headers.get("x-grafbase-cache").is_none());
+            assert!(response.errors().is_empty(), "{response}");
+        }
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len(),
+            2
+        );
+
+        // Nor did they fill the cache for anyone else.
+        engine.execute(query).await;
+        let response = engine.execute(query).await;
+        assert_eq!(response.headers.get("x-grafbase-cache").unwrap(), "HIT");
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len(),
+            1
+        );
+    });
+}
+
+#[test]
+fn requests_with_cookies_bypass_the_cache() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_toml_config(
+                r#"
+                [response_cache]
+                enabled = true
+                bypass_headers = []
+                "#,
+            )
+            .build()
+            .await;
+
+        let query = "query { topProducts { upc name } }";
+
+        for _ in 0..2 {
+            let response = engine.execute(query).header("Cookie", "session=alice").await;
+            assert!(response.headers.get("x-grafbase-cache").is_none());
+            assert!(response.errors().is_empty(), "{response}");
+        }
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len(),
+            2
+        );
+    });
+}
+
+#[test]
+fn responses_are_cached_per_forwarded_headers() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_toml_config(
+                r#"
+                [response_cache]
+                enabled = true
+
+                [[headers]]
+                rule = "forward"
+                name = "x-region"
+                "#,
+            )
+            .build()
+            .await;
+
+        let query = "query { topProducts { upc name } }";
+
+        engine.execute(query).header("x-region", "eu").await;
+        let response = engine.execute(query).header("x-region", "us").await;
+        assert!(response.headers.get("x-grafbase-cache").is_none());
+
+        let response = engine.execute(query).header("x-region", "eu").await;
+        assert_eq!(response.headers.get("x-grafbase-cache").unwrap(), "HIT");
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len(),
+            2
+        );
+    });
+}
+
+#[test]
+fn headers_which_are_not_forwarded_share_the_cached_response() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_toml_config(
+                r#"
+                [response_cache]
+                enabled = true
+                "#,
+            )
+            .build()
+            .await;
+
+        let query = "query { topProducts { upc name } }";
+
+        engine
+            .execute(query)
+            .header("traceparent", "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01")
+            .header("x-request-id", "1")
+            .await;
+        let response = engine
+            .execute(query)
+            .header("traceparent", "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01")
+            .header("x-request-id", "2")
+            .await;
+        assert_eq!(response.headers.get("x-grafbase-cache").unwrap(), "HIT");
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len(),
+            1
+        );
+    });
+}
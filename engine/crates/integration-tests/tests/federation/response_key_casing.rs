@@ -0,0 +1,168 @@
+use engine_v2::Engine;
+use integration_tests::{federation::EngineV2Ext, fetch::MockFetch, runtime};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+    }
+
+    type Query {
+      me: User @join__field(graph: A)
+    }
+
+    type User {
+      id: ID!
+      firstName: String
+      last_name: String
+      homeAddress: Address
+    }
+
+    type Address {
+      streetName: String
+    }
+    "###;
+
+const CONFIG: &str = r#"
+    [clients.legacy]
+    response_key_casing = "snake_case"
+
+    [clients.modern]
+    response_key_casing = "camel_case"
+"#;
+
+fn fetcher() -> MockFetch {
+    MockFetch::default().with_responses(
+        "a",
+        vec![json!({
+            "data": {
+                "me": {
+                    "__typename": "User",
+                    "id": "1",
+                    "firstName": "Alice",
+                    "last_name": "Liddell",
+                    "homeAddress": {"streetName": "Main Street"},
+                    "givenName": "Alice",
+                    "surname": "Liddell"
+                }
+            }
+        })],
+    )
+}
+
+#[test]
+fn camel_case_to_snake_case() {
+    let response = runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(CONFIG)
+            .with_mock_fetcher(fetcher())
+            .build()
+            .await;
+
+        engine
+            .execute("query { me { __typename id firstName last_name homeAddress { streetName } } }")
+            .by_client("legacy", "1.0.0")
+            .await
+    });
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "me": {
+          "__typename": "User",
+          "id": "1",
+          "first_name": "Alice",
+          "last_name": "Liddell",
+          "home_address": {
+            "street_name": "Main Street"
+          }
+        }
+      }
+    }
+    "###);
+}
+
+#[test]
+fn snake_case_to_camel_case() {
+    let response = runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(CONFIG)
+            .with_mock_fetcher(fetcher())
+            .build()
+            .await;
+
+        engine
+            .execute("query { me { __typename id firstName last_name } }")
+            .by_client("modern", "1.0.0")
+            .await
+    });
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "me": {
+          "__typename": "User",
+          "id": "1",
+          "firstName": "Alice",
+          "lastName": "Liddell"
+        }
+      }
+    }
+    "###);
+}
+
+#[test]
+fn aliases_are_preserved() {
+    let response = runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(CONFIG)
+            .with_mock_fetcher(fetcher())
+            .build()
+            .await;
+
+        engine
+            .execute("query { me { id givenName: firstName surname: last_name } }")
+            .by_client("legacy", "1.0.0")
+            .await
+    });
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "me": {
+          "id": "1",
+          "givenName": "Alice",
+          "surname": "Liddell"
+        }
+      }
+    }
+    "###);
+}
+
+#[test]
+fn other_clients_are_not_converted() {
+    let response = runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(CONFIG)
+            .with_mock_fetcher(fetcher())
+            .build()
+            .await;
+
+        engine.execute("query { me { firstName last_name } }").await
+    });
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "me": {
+          "firstName": "Alice",
+          "last_name": "Liddell"
+        }
+      }
+    }
+    "###);
+}
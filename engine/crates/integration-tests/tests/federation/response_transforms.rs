@@ -0,0 +1,109 @@
+use engine_v2::Engine;
+use integration_tests::{federation::EngineV2Ext, fetch::MockFetch, runtime};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+    }
+
+    type Query {
+      me: User @join__field(graph: A)
+    }
+
+    type User {
+      id: ID!
+      name: String
+      address: Address
+    }
+
+    type Address {
+      street: String
+      city: String
+    }
+    "###;
+
+const CONFIG: &str = r#"
+    [clients.legacy]
+    response_transforms = [
+        { kind = "rename", path = "me.name", to = "fullName" },
+        { kind = "flatten", path = "me.address" },
+    ]
+"#;
+
+fn fetcher() -> MockFetch {
+    MockFetch::default().with_responses(
+        "a",
+        vec![json!({
+            "data": {
+                "me": {
+                    "id": "1",
+                    "name": "Alice",
+                    "address": {"street": "Main Street", "city": "Springfield"}
+                }
+            }
+        })],
+    )
+}
+
+#[test]
+fn rename_and_flatten() {
+    let response = runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(CONFIG)
+            .with_mock_fetcher(fetcher())
+            .build()
+            .await;
+
+        engine
+            .execute("query { me { id name address { street city } } }")
+            .by_client("legacy", "1.0.0")
+            .await
+    });
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "me": {
+          "id": "1",
+          "fullName": "Alice",
+          "street": "Main Street",
+          "city": "Springfield"
+        }
+      }
+    }
+    "###);
+}
+
+#[test]
+fn other_clients_are_not_transformed() {
+    let response = runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(CONFIG)
+            .with_mock_fetcher(fetcher())
+            .build()
+            .await;
+
+        engine
+            .execute("query { me { id name address { street city } } }")
+            .by_client("web", "1.0.0")
+            .await
+    });
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "me": {
+          "id": "1",
+          "name": "Alice",
+          "address": {
+            "street": "Main Street",
+            "city": "Springfield"
+          }
+        }
+      }
+    }
+    "###);
+}
@@ -0,0 +1,82 @@
+use integration_tests::{federation::execute_with_mock, fetch::MockFetch};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+      B @join__graph(name: "b", url: "https://b/graphql")
+    }
+
+    type Query
+      @join__type(graph: A)
+    {
+      product: Product @join__field(graph: A)
+    }
+
+    type Product
+      @join__type(graph: A, key: "id")
+      @join__type(graph: B, key: "id")
+    {
+      id: ID!
+      price: Int @join__field(graph: B)
+      name: String! @join__field(graph: B)
+    }
+    "###;
+
+fn execute(config: &str) -> serde_json::Value {
+    let fetcher = MockFetch::default()
+        .with_responses("a", vec![json!({"data": {"product": {"id": "1"}}})])
+        // `name` is never provided, neither by A nor by B.
+        .with_responses(
+            "b",
+            vec![json!({"data": {"_entities": [{"__typename": "Product", "price": 10}]}})],
+        );
+
+    execute_with_mock(SDL, config, fetcher, "query { product { id price name } }").into_value()
+}
+
+#[test]
+fn missing_non_null_field_is_not_validated_by_default() {
+    let response = execute("");
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "product": {
+          "id": "1",
+          "price": 10
+        }
+      }
+    }
+    "###);
+}
+
+#[test]
+fn missing_non_null_field_fails_response_validation() {
+    let response = execute(
+        r#"
+        [gateway]
+        validate_response_nullability = true
+        "#,
+    );
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "product": null
+      },
+      "errors": [
+        {
+          "message": "Non-null field Product.name is missing in the response",
+          "path": [
+            "product",
+            "name"
+          ],
+          "extensions": {
+            "code": "RESPONSE_VALIDATION_FAILED"
+          }
+        }
+      ]
+    }
+    "###);
+}
@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use engine_v2::Engine;
+use integration_tests::{federation::EngineV2Ext, fetch::MockFetch, runtime};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+      B @join__graph(name: "b", url: "https://b/graphql")
+      C @join__graph(name: "c", url: "https://c/graphql")
+    }
+
+    type Query {
+      a: String @join__field(graph: A)
+      b: String @join__field(graph: B)
+      c: String @join__field(graph: C)
+    }
+    "###;
+
+// Keeps each request in flight long enough for the others to be sent.
+const DELAY: Duration = Duration::from_millis(100);
+
+#[test]
+fn independent_root_fields_are_executed_concurrently() {
+    runtime().block_on(async move {
+        let fetcher = MockFetch::default()
+            .with_responses("a", vec![json!({"data": {"a": "A"}})])
+            .with_responses("b", vec![json!({"data": {"b": "B"}})])
+            .with_responses("c", vec![json!({"data": {"c": "C"}})])
+            .with_delay(DELAY);
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_mock_fetcher(fetcher.clone())
+            .build()
+            .await;
+
+        let response = engine.execute("query { a b c }").await;
+
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "a": "A",
+            "b": "B",
+            "c": "C"
+          }
+        }
+        "###);
+        assert_eq!(fetcher.drain_received_requests().count(), 3);
+        assert_eq!(fetcher.max_in_flight_requests(), 3);
+    })
+}
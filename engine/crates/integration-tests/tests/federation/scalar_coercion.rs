@@ -0,0 +1,94 @@
+use integration_tests::{federation::execute_with_mock, fetch::MockFetch};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+    }
+
+    type Query {
+      flags: Flags @join__field(graph: A)
+    }
+
+    type Flags {
+      one: Boolean
+      zero: Boolean
+      oneString: Boolean
+      zeroString: Boolean
+      boolean: Boolean!
+    }
+    "###;
+
+fn execute(config: &str, subgraph_response: serde_json::Value) -> serde_json::Value {
+    let fetcher = MockFetch::default().with_responses("a", vec![subgraph_response]);
+    execute_with_mock(
+        SDL,
+        config,
+        fetcher,
+        "query { flags { one zero oneString zeroString boolean } }",
+    )
+    .into_value()
+}
+
+#[test]
+fn lenient_coercion_accepts_numeric_booleans() {
+    let response = execute(
+        r#"
+        [subgraphs.a]
+        scalar_coercion = "lenient"
+        "#,
+        json!({"data": {"flags": {"one": 1, "zero": 0, "oneString": "1", "zeroString": "0", "boolean": true}}}),
+    );
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "flags": {
+          "one": true,
+          "zero": false,
+          "oneString": true,
+          "zeroString": false,
+          "boolean": true
+        }
+      }
+    }
+    "###);
+}
+
+#[test]
+fn lenient_coercion_rejects_other_numbers() {
+    let response = execute(
+        r#"
+        [subgraphs.a]
+        scalar_coercion = "lenient"
+        "#,
+        json!({"data": {"flags": {"one": 2, "zero": 0, "oneString": "yes", "zeroString": "0", "boolean": false}}}),
+    );
+
+    assert_eq!(
+        response["data"],
+        json!({"flags": {"one": null, "zero": false, "oneString": null, "zeroString": false, "boolean": false}})
+    );
+    assert_eq!(response["errors"].as_array().unwrap().len(), 2);
+    assert_eq!(
+        response["errors"][0]["extensions"]["code"],
+        "SUBGRAPH_INVALID_RESPONSE_ERROR"
+    );
+}
+
+#[test]
+fn strict_coercion_rejects_numeric_booleans() {
+    let response = execute(
+        "",
+        json!({"data": {"flags": {"one": 1, "zero": false, "oneString": true, "zeroString": false, "boolean": true}}}),
+    );
+
+    assert_eq!(
+        response["data"],
+        json!({"flags": {"one": null, "zero": false, "oneString": true, "zeroString": false, "boolean": true}})
+    );
+    assert_eq!(
+        response["errors"][0]["extensions"]["code"],
+        "SUBGRAPH_INVALID_RESPONSE_ERROR"
+    );
+}
@@ -0,0 +1,89 @@
+use integration_tests::{
+    federation::{execute_with_mock, GraphqlResponse},
+    fetch::MockFetch,
+};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+    }
+
+    scalar DateTime
+
+    type Query {
+      me: User @join__field(graph: A)
+    }
+
+    type User {
+      name: String!
+      createdAt: DateTime
+    }
+    "###;
+
+const CONFIG: &str = r#"
+    [gateway.scalar_formats]
+    DateTime = "date-time"
+"#;
+
+fn execute(config: &str, created_at: &str) -> GraphqlResponse {
+    let subgraph = MockFetch::default().with_responses(
+        "a",
+        vec![json!({"data": {"me": {"name": "Douglas Adams", "createdAt": created_at}}})],
+    );
+    execute_with_mock(SDL, config, subgraph, "query { me { name createdAt } }")
+}
+
+#[test]
+fn custom_scalars_are_not_validated_by_default() {
+    let response = execute("", "yesterday");
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "me": {
+          "name": "Douglas Adams",
+          "createdAt": "yesterday"
+        }
+      }
+    }
+    "###);
+}
+
+#[test]
+fn values_matching_the_format_are_kept() {
+    let response = execute(CONFIG, "2024-05-17T09:30:00.250+02:00");
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "me": {
+          "name": "Douglas Adams",
+          "createdAt": "2024-05-17T09:30:00.250+02:00"
+        }
+      }
+    }
+    "###);
+}
+
+#[test]
+fn values_not_matching_the_format_are_errors() {
+    let response = execute(CONFIG, "2024-05-17");
+
+    assert_eq!(
+        response["data"],
+        json!({"me": {"name": "Douglas Adams", "createdAt": null}}),
+        "{response}"
+    );
+    assert_eq!(response.errors().len(), 1, "{response}");
+    let error = &response["errors"][0];
+    assert!(
+        error["message"]
+            .as_str()
+            .unwrap()
+            .starts_with(r#"Value "2024-05-17" is not an RFC 3339 date-time"#),
+        "{response}"
+    );
+    assert_eq!(error["path"], json!(["me", "createdAt"]));
+    assert_eq!(error["extensions"]["code"], "SUBGRAPH_INVALID_RESPONSE_ERROR");
+}
@@ -0,0 +1,49 @@
+use integration_tests::{federation::execute_with_mock, fetch::MockFetch};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+    }
+
+    type Query {
+      names: [String!]! @join__field(graph: A)
+    }
+    "###;
+
+const COUNT: usize = 200_000;
+
+fn execute(config: &str) -> serde_json::Value {
+    let names = (0..COUNT).map(|i| format!("name-{i}")).collect::<Vec<_>>();
+    let fetcher = MockFetch::default().with_responses("a", vec![json!({"data": {"names": names}})]);
+    execute_with_mock(SDL, config, fetcher, "query { names }").into_value()
+}
+
+#[test]
+fn slow_to_serialize_response_is_truncated() {
+    let response = execute(
+        r#"
+        [gateway]
+        max_serialization_time = "1ms"
+        "#,
+    );
+
+    let names = response["data"]["names"].as_array().expect("names");
+    assert!(names.len() < COUNT, "{} names", names.len());
+
+    let errors = response["errors"].as_array().expect("errors");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0]["message"],
+        "Serializing the response took longer than the allowed 1ms, the data was truncated."
+    );
+    assert_eq!(errors[0]["extensions"]["code"], "RESPONSE_SERIALIZATION_TIMEOUT");
+}
+
+#[test]
+fn response_is_complete_without_a_budget() {
+    let response = execute("");
+
+    assert_eq!(response["data"]["names"].as_array().expect("names").len(), COUNT);
+    assert!(response.get("errors").is_none());
+}
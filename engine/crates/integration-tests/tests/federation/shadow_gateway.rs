@@ -0,0 +1,230 @@
+use std::time::{Duration, Instant};
+
+use engine_v2::Engine;
+use integration_tests::{federation::EngineV2Ext, fetch::MockFetch, metrics::CountersRecorder, runtime};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+    }
+
+    type Query {
+      me: User @join__field(graph: A)
+    }
+
+    type Mutation {
+      updateUser(name: String!): User @join__field(graph: A)
+    }
+
+    type User {
+      id: ID!
+      name: String
+    }
+    "###;
+
+const CONFIG: &str = r#"
+    [gateway]
+    shadow_gateway = "https://canary/graphql"
+    "#;
+
+#[test]
+fn response_diffs_are_counted_without_altering_the_response() {
+    runtime().block_on(async move {
+        let fetcher = MockFetch::default()
+            .with_responses(
+                "a",
+                vec![
+                    json!({"data": {"me": {"id": "1", "name": "Alice"}}}),
+                    json!({"data": {"me": {"id": "1", "name": "Alice"}}}),
+                ],
+            )
+            .with_responses(
+                "canary",
+                vec![
+                    json!({"data": {"me": {"name": "Alice", "id": "1"}}}),
+                    json!({"data": {"me": {"id": "1", "name": "Bob"}}}),
+                ],
+            );
+        let counters = CountersRecorder::default();
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(CONFIG)
+            .with_mock_fetcher(fetcher.clone())
+            .with_meter(counters.meter())
+            .build()
+            .await;
+
+        let response = engine.execute("query { me { id name } }").await;
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "me": {
+              "id": "1",
+              "name": "Alice"
+            }
+          }
+        }
+        "###);
+        engine.wait_for_background_tasks().await;
+        assert_eq!(counters.get("response_diff_total"), 0);
+
+        let response = engine.execute("query { me { id name } }").await;
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "me": {
+              "id": "1",
+              "name": "Alice"
+            }
+          }
+        }
+        "###);
+        engine.wait_for_background_tasks().await;
+        assert_eq!(counters.get("response_diff_total"), 1);
+
+        let requests = fetcher
+            .drain_received_requests()
+            .filter(|(host, _)| host == "canary")
+            .map(|(_, request)| request.body.query.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(requests, ["query { me { id name } }", "query { me { id name } }"]);
+    })
+}
+
+#[test]
+fn failing_shadow_gateway_does_not_alter_the_response() {
+    runtime().block_on(async move {
+        // No responses for the shadow gateway, so its requests fail.
+        let fetcher =
+            MockFetch::default().with_responses("a", vec![json!({"data": {"me": {"id": "1", "name": "Alice"}}})]);
+        let counters = CountersRecorder::default();
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(CONFIG)
+            .with_mock_fetcher(fetcher)
+            .with_meter(counters.meter())
+            .build()
+            .await;
+
+        let response = engine.execute("query { me { id name } }").await;
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "me": {
+              "id": "1",
+              "name": "Alice"
+            }
+          }
+        }
+        "###);
+        engine.wait_for_background_tasks().await;
+        assert_eq!(counters.get("response_diff_total"), 0);
+    })
+}
+
+#[test]
+fn slow_shadow_gateway_does_not_delay_the_response() {
+    runtime().block_on(async move {
+        let fetcher = MockFetch::default()
+            .with_responses("a", vec![json!({"data": {"me": {"id": "1", "name": "Alice"}}})])
+            .with_responses("canary", vec![json!({"data": {"me": {"id": "1", "name": "Bob"}}})])
+            .with_host_delay("canary", Duration::from_secs(2));
+        let counters = CountersRecorder::default();
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(CONFIG)
+            .with_mock_fetcher(fetcher)
+            .with_meter(counters.meter())
+            .build()
+            .await;
+
+        let start = Instant::now();
+        let response = engine.execute("query { me { id name } }").await;
+        assert!(start.elapsed() < Duration::from_secs(1));
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "me": {
+              "id": "1",
+              "name": "Alice"
+            }
+          }
+        }
+        "###);
+
+        engine.wait_for_background_tasks().await;
+        assert_eq!(counters.get("response_diff_total"), 1);
+    })
+}
+
+#[test]
+fn only_allowed_headers_are_forwarded_to_the_shadow_gateway() {
+    runtime().block_on(async move {
+        let fetcher = MockFetch::default()
+            .with_responses("a", vec![json!({"data": {"me": {"id": "1", "name": "Alice"}}})])
+            .with_responses("canary", vec![json!({"data": {"me": {"id": "1", "name": "Alice"}}})]);
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(
+                r#"
+                [gateway]
+                shadow_gateway = "https://canary/graphql"
+                shadow_gateway_headers = ["x-client-id"]
+                "#,
+            )
+            .with_mock_fetcher(fetcher.clone())
+            .build()
+            .await;
+
+        engine
+            .execute("query { me { id name } }")
+            .header("x-client-id", "web")
+            .header("authorization", "Bearer secret")
+            .header("cookie", "session=alice")
+            .await;
+        engine.wait_for_background_tasks().await;
+
+        let (_, request) = fetcher
+            .drain_received_requests()
+            .find(|(host, _)| host == "canary")
+            .unwrap();
+        assert_eq!(request.headers.get("x-client-id").unwrap(), "web");
+        assert!(request.headers.get("authorization").is_none());
+        assert!(request.headers.get("cookie").is_none());
+    })
+}
+
+#[test]
+fn mutations_are_not_sent_to_the_shadow_gateway() {
+    runtime().block_on(async move {
+        let fetcher =
+            MockFetch::default().with_responses("a", vec![json!({"data": {"updateUser": {"id": "1", "name": "Bob"}}})]);
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(CONFIG)
+            .with_mock_fetcher(fetcher.clone())
+            .build()
+            .await;
+
+        let response = engine
+            .execute(r#"mutation { updateUser(name: "Bob") { id name } }"#)
+            .await;
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "updateUser": {
+              "id": "1",
+              "name": "Bob"
+            }
+          }
+        }
+        "###);
+
+        let hosts = fetcher
+            .drain_received_requests()
+            .map(|(host, _)| host)
+            .collect::<Vec<_>>();
+        assert_eq!(hosts, ["a"]);
+    })
+}
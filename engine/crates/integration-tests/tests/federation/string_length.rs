@@ -0,0 +1,98 @@
+use integration_tests::{federation::execute_with_mock, fetch::MockFetch};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+    }
+
+    type Query {
+      me: User @join__field(graph: A)
+    }
+
+    type User {
+      name: String!
+      bio: String @maxStringLength(length: 5)
+    }
+    "###;
+
+const QUERY: &str = "query { me { name bio } }";
+
+fn subgraph() -> MockFetch {
+    MockFetch::default().with_responses(
+        "a",
+        vec![json!({"data": {"me": {"name": "Douglas Adams", "bio": "Writer of the Hitchhiker's Guide"}}})],
+    )
+}
+
+#[test]
+fn strings_are_unlimited_by_default() {
+    let sdl = SDL.replace("@maxStringLength(length: 5)", "");
+    let response = execute_with_mock(&sdl, "", subgraph(), QUERY);
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "me": {
+          "name": "Douglas Adams",
+          "bio": "Writer of the Hitchhiker's Guide"
+        }
+      }
+    }
+    "###);
+}
+
+#[test]
+fn oversized_strings_are_truncated() {
+    let response = execute_with_mock(
+        SDL,
+        r#"
+        [gateway]
+        max_string_length = 8
+        "#,
+        subgraph(),
+        QUERY,
+    );
+
+    // `bio` has its own limit, overriding the global one.
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "me": {
+          "name": "Douglas …[truncated]",
+          "bio": "Write…[truncated]"
+        }
+      }
+    }
+    "###);
+}
+
+#[test]
+fn oversized_strings_are_errors_when_configured() {
+    let response = execute_with_mock(
+        SDL,
+        r#"
+        [gateway]
+        oversized_strings = "error"
+        "#,
+        subgraph(),
+        QUERY,
+    );
+
+    assert_eq!(
+        response["data"],
+        json!({"me": {"name": "Douglas Adams", "bio": null}}),
+        "{response}"
+    );
+    assert_eq!(response.errors().len(), 1, "{response}");
+    let error = &response["errors"][0];
+    assert!(
+        error["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("String value of 32 bytes exceeds the maximum length of 5 bytes"),
+        "{response}"
+    );
+    assert_eq!(error["path"], json!(["me", "bio"]));
+    assert_eq!(error["extensions"]["code"], "SUBGRAPH_INVALID_RESPONSE_ERROR");
+}
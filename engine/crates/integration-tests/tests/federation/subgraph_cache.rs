@@ -0,0 +1,152 @@
+use engine_v2::Engine;
+use graphql_mocks::FederatedProductsSchema;
+use integration_tests::{federation::EngineV2Ext, runtime};
+use serde_json::json;
+
+#[test]
+fn warm_cache_skips_the_subgraph_request() {
+    let (first, second) = runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_toml_config(
+                r#"
+                [subgraph_cache]
+                enabled = true
+                "#,
+            )
+            .build()
+            .await;
+
+        // Different client operations, sharing the same subgraph query.
+        let first = engine
+            .execute("query Home { topProducts { upc name } }")
+            .await
+            .into_data();
+        let second = engine
+            .execute("query Listing { topProducts { upc name } }")
+            .await
+            .into_data();
+
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len(),
+            1
+        );
+
+        (first, second)
+    });
+
+    assert_eq!(first, second);
+    insta::assert_json_snapshot!(first["topProducts"][0], @r###"
+    {
+      "upc": "top-1",
+      "name": "Trilby"
+    }
+    "###);
+}
+
+#[test]
+fn variables_are_part_of_the_key() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_toml_config(
+                r#"
+                [subgraph_cache]
+                enabled = true
+                "#,
+            )
+            .build()
+            .await;
+
+        const QUERY: &str = "query($upc: String!) { product(upc: $upc) { name } }";
+
+        let trilby = engine.execute(QUERY).variables(json!({"upc": "top-1"})).await;
+        let fedora = engine.execute(QUERY).variables(json!({"upc": "top-2"})).await;
+        let trilby_again = engine.execute(QUERY).variables(json!({"upc": "top-1"})).await;
+
+        assert_eq!(trilby.into_data(), json!({"product": {"name": "Trilby"}}));
+        assert_eq!(fedora.into_data(), json!({"product": {"name": "Fedora"}}));
+        assert_eq!(trilby_again.into_data(), json!({"product": {"name": "Trilby"}}));
+
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len(),
+            2
+        );
+    });
+}
+
+#[test]
+fn forwarded_headers_are_part_of_the_key() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_toml_config(
+                r#"
+                [subgraph_cache]
+                enabled = true
+
+                [[headers]]
+                rule = "forward"
+                name = "authorization"
+                "#,
+            )
+            .build()
+            .await;
+
+        const QUERY: &str = "query { topProducts { upc name } }";
+
+        engine
+            .execute(QUERY)
+            .header("authorization", "Bearer alice")
+            .await
+            .into_data();
+        engine
+            .execute(QUERY)
+            .header("authorization", "Bearer bob")
+            .await
+            .into_data();
+        engine
+            .execute(QUERY)
+            .header("authorization", "Bearer alice")
+            .await
+            .into_data();
+
+        // Each client gets its own entry, the second request of alice hits hers.
+        let authorizations = engine
+            .drain_http_requests_sent_to::<FederatedProductsSchema>()
+            .into_iter()
+            .map(|request| request.headers["authorization"].to_str().unwrap().to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(authorizations, vec!["Bearer alice", "Bearer bob"]);
+    });
+}
+
+#[test]
+fn subgraph_can_opt_out() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_toml_config(
+                r#"
+                [subgraph_cache]
+                enabled = true
+
+                [subgraphs.products.subgraph_cache]
+                enabled = false
+                "#,
+            )
+            .build()
+            .await;
+
+        const QUERY: &str = "query { topProducts { upc name } }";
+
+        engine.execute(QUERY).await.into_data();
+        engine.execute(QUERY).await.into_data();
+
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len(),
+            2
+        );
+    });
+}
@@ -0,0 +1,52 @@
+use std::{future::IntoFuture, time::Duration};
+
+use engine_v2::Engine;
+use integration_tests::{federation::EngineV2Ext, fetch::MockFetch, metrics::HistogramsRecorder, runtime};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+    }
+
+    type Query {
+      me: String @join__field(graph: A)
+    }
+    "###;
+
+#[test]
+fn permit_wait_and_queue_depth_are_recorded_per_subgraph() {
+    runtime().block_on(async move {
+        let fetcher = MockFetch::default()
+            .with_responses("a", vec![json!({"data": {"me": "Alice"}}); 2])
+            .with_delay(Duration::from_millis(100));
+        let histograms = HistogramsRecorder::default();
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(
+                r#"
+                [subgraphs.a]
+                max_concurrent_requests = 1
+                "#,
+            )
+            .with_mock_fetcher(fetcher)
+            .with_meter(histograms.meter())
+            .build()
+            .await;
+
+        // Different queries, so that the subgraph requests aren't deduplicated.
+        futures::join!(
+            engine.execute("{ first: me }").into_future(),
+            engine.execute("{ second: me }").into_future(),
+        );
+
+        for name in ["subgraph_permit_wait_ms", "subgraph_queue_depth"] {
+            let subgraph_names = histograms
+                .attributes(name)
+                .into_iter()
+                .map(|attributes| attributes.get("subgraph.name").cloned())
+                .collect::<Vec<_>>();
+            assert_eq!(subgraph_names, [Some("a".to_string()), Some("a".to_string())], "{name}");
+        }
+    })
+}
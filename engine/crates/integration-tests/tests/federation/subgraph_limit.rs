@@ -0,0 +1,107 @@
+use engine_v2::Engine;
+use integration_tests::{federation::EngineV2Ext, fetch::MockFetch, runtime};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+      B @join__graph(name: "b", url: "https://b/graphql")
+      C @join__graph(name: "c", url: "https://c/graphql")
+    }
+
+    type Query {
+      a: String @join__field(graph: A)
+      b: String @join__field(graph: B)
+      c: String @join__field(graph: C)
+    }
+    "###;
+
+const CONFIG: &str = r#"
+    [clients.free]
+    max_subgraphs_per_operation = 2
+"#;
+
+#[test]
+fn operation_within_limit() {
+    let response = runtime().block_on(async move {
+        let fetcher = MockFetch::default()
+            .with_responses("a", vec![json!({"data": {"a": "a"}})])
+            .with_responses("b", vec![json!({"data": {"b": "b"}})]);
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(CONFIG)
+            .with_mock_fetcher(fetcher)
+            .build()
+            .await;
+
+        engine
+            .execute("query { a b __typename }")
+            .by_client("free", "1.0.0")
+            .await
+    });
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "a": "a",
+        "b": "b",
+        "__typename": "Query"
+      }
+    }
+    "###);
+}
+
+#[test]
+fn operation_touching_too_many_subgraphs() {
+    let response = runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(CONFIG)
+            .with_mock_fetcher(MockFetch::default())
+            .build()
+            .await;
+
+        engine.execute("query { a b c }").by_client("free", "1.0.0").await
+    });
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "errors": [
+        {
+          "message": "Operation requires 3 subgraphs, but client 'free' is limited to 2",
+          "extensions": {
+            "code": "TOO_MANY_SUBGRAPHS"
+          }
+        }
+      ]
+    }
+    "###);
+}
+
+#[test]
+fn other_clients_are_not_limited() {
+    let response = runtime().block_on(async move {
+        let fetcher = MockFetch::default()
+            .with_responses("a", vec![json!({"data": {"a": "a"}})])
+            .with_responses("b", vec![json!({"data": {"b": "b"}})])
+            .with_responses("c", vec![json!({"data": {"c": "c"}})]);
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(CONFIG)
+            .with_mock_fetcher(fetcher)
+            .build()
+            .await;
+
+        engine.execute("query { a b c }").await
+    });
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "a": "a",
+        "b": "b",
+        "c": "c"
+      }
+    }
+    "###);
+}
@@ -0,0 +1,71 @@
+use engine_v2::Engine;
+use graphql_mocks::{FederatedInventorySchema, FederatedProductsSchema, FederatedReviewsSchema};
+use integration_tests::{federation::EngineV2Ext, runtime};
+
+const QUERY: &str = "{ topProducts { upc reviews { id body } } }";
+
+#[test]
+fn entity_query_exceeding_the_subgraph_depth_is_rejected_during_planning() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_subgraph(FederatedReviewsSchema)
+            .with_subgraph(FederatedInventorySchema)
+            .with_toml_config(
+                r#"
+                [subgraphs.reviews]
+                max_query_depth = 2
+                "#,
+            )
+            .build()
+            .await;
+
+        let response = engine.execute(QUERY).await;
+
+        // `_entities { ... on Product { reviews { id } } }` is three levels deep.
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "errors": [
+            {
+              "message": "Query for subgraph 'reviews' would have a depth of 3, exceeding its limit of 2",
+              "extensions": {
+                "code": "OPERATION_PLANNING_ERROR"
+              }
+            }
+          ]
+        }
+        "###);
+
+        assert!(engine
+            .drain_graphql_requests_sent_to::<FederatedProductsSchema>()
+            .is_empty());
+        assert!(engine
+            .drain_graphql_requests_sent_to::<FederatedReviewsSchema>()
+            .is_empty());
+    });
+}
+
+#[test]
+fn entity_query_within_the_subgraph_depth_is_executed() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_subgraph(FederatedReviewsSchema)
+            .with_subgraph(FederatedInventorySchema)
+            .with_toml_config(
+                r#"
+                [subgraphs.reviews]
+                max_query_depth = 3
+                "#,
+            )
+            .build()
+            .await;
+
+        engine.execute(QUERY).await.into_data();
+
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedReviewsSchema>().len(),
+            1
+        );
+    });
+}
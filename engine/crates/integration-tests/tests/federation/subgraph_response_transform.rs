@@ -0,0 +1,64 @@
+use integration_tests::{federation::execute_with_mock, fetch::MockFetch};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+    }
+
+    type Query {
+      name: String @join__field(graph: A)
+    }
+    "###;
+
+const CONFIG: &str = r#"
+    [subgraphs.a.response_transform]
+    unwrap = "$.result"
+    rename = { payload = "data" }
+"#;
+
+fn execute(config: &str, subgraph_response: serde_json::Value) -> serde_json::Value {
+    let fetcher = MockFetch::default().with_responses("a", vec![subgraph_response]);
+    execute_with_mock(SDL, config, fetcher, "query { name }").into_value()
+}
+
+#[test]
+fn enveloped_response_is_unwrapped() {
+    let response = execute(CONFIG, json!({"status": "ok", "result": {"payload": {"name": "Jane"}}}));
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "name": "Jane"
+      }
+    }
+    "###);
+}
+
+#[test]
+fn response_is_untouched_by_default() {
+    let response = execute("", json!({"data": {"name": "Jane"}}));
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "name": "Jane"
+      }
+    }
+    "###);
+}
+
+#[test]
+fn response_without_the_envelope_is_an_error() {
+    let response = execute(CONFIG, json!({"data": {"name": "Jane"}}));
+
+    assert_eq!(response["data"], json!({"name": null}), "{response}");
+    assert_eq!(
+        response["errors"][0]["message"],
+        "Subgraph response has no 'result' key to unwrap"
+    );
+    assert_eq!(
+        response["errors"][0]["extensions"]["code"],
+        "SUBGRAPH_INVALID_RESPONSE_ERROR"
+    );
+}
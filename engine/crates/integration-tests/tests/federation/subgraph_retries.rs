@@ -1,6 +1,6 @@
 use engine_v2::Engine;
 use graphql_mocks::{MockGraphQlServer, StateMutationSchema, Subgraph};
-use integration_tests::{federation::EngineV2Ext, runtime};
+use integration_tests::{federation::EngineV2Ext, fetch::MockFetch, runtime};
 
 struct Stateful;
 
@@ -95,3 +95,177 @@ fn subgraph_retries_mutations_enabled() {
         });
     });
 }
+
+#[test]
+fn operation_retry_budget_is_shared_across_subgraphs() {
+    runtime().block_on(async move {
+        // Without any response, every request to either subgraph fails.
+        let fetcher = MockFetch::default();
+        let engine = Engine::builder()
+            .with_federated_sdl(
+                r###"
+                enum join__Graph {
+                  A @join__graph(name: "a", url: "https://a/graphql")
+                  B @join__graph(name: "b", url: "https://b/graphql")
+                }
+
+                type Query {
+                  a: String @join__field(graph: A)
+                  b: String @join__field(graph: B)
+                }
+                "###,
+            )
+            .with_toml_config(
+                r###"
+                [gateway]
+                max_retries_per_operation = 1
+
+                [subgraphs.a.retry]
+                enabled = true
+                ttl = "10s"
+
+                [subgraphs.b.retry]
+                enabled = true
+                ttl = "10s"
+                "###,
+            )
+            .with_mock_fetcher(fetcher.clone())
+            .build()
+            .await;
+
+        let response = engine.execute("query { a b }").await;
+
+        assert_eq!(
+            response["data"],
+            serde_json::json!({"a": null, "b": null}),
+            "{response}"
+        );
+        assert_eq!(response.errors().len(), 2, "{response}");
+
+        // One request per subgraph and a single retry shared by both of them.
+        assert_eq!(fetcher.drain_received_requests().count(), 3);
+    });
+}
+
+#[test]
+fn retries_stop_after_max_attempts() {
+    runtime().block_on(async move {
+        // Without any response, every request fails.
+        let fetcher = MockFetch::default();
+        let engine = Engine::builder()
+            .with_federated_sdl(
+                r###"
+                enum join__Graph {
+                  A @join__graph(name: "a", url: "https://a/graphql")
+                }
+
+                type Query {
+                  a: String @join__field(graph: A)
+                }
+                "###,
+            )
+            .with_toml_config(
+                r###"
+                [subgraphs.a.retry]
+                enabled = true
+                ttl = "10s"
+                min_per_second = 100
+                max_attempts = 3
+                base_delay = "10ms"
+                "###,
+            )
+            .with_mock_fetcher(fetcher.clone())
+            .build()
+            .await;
+
+        let response = engine.execute("query { a }").await;
+
+        assert_eq!(response["data"], serde_json::json!({"a": null}), "{response}");
+        assert_eq!(fetcher.drain_received_requests().count(), 3);
+    });
+}
+
+#[test]
+fn unavailable_subgraph_responses_are_retried() {
+    runtime().block_on(async move {
+        let fetcher = MockFetch::default()
+            .with_status_response("a", 502, serde_json::json!({"errors": [{"message": "Bad Gateway"}]}))
+            .with_responses("a", [serde_json::json!({"data": {"a": "ok"}})]);
+        let engine = Engine::builder()
+            .with_federated_sdl(
+                r###"
+                enum join__Graph {
+                  A @join__graph(name: "a", url: "https://a/graphql")
+                }
+
+                type Query {
+                  a: String @join__field(graph: A)
+                }
+                "###,
+            )
+            .with_toml_config(
+                r###"
+                [subgraphs.a.retry]
+                enabled = true
+                ttl = "10s"
+                min_per_second = 100
+                base_delay = "10ms"
+                "###,
+            )
+            .with_mock_fetcher(fetcher.clone())
+            .build()
+            .await;
+
+        let response = engine.execute("query { a }").await;
+
+        assert_eq!(response["data"], serde_json::json!({"a": "ok"}), "{response}");
+        assert!(response.errors().is_empty(), "{response}");
+        assert_eq!(fetcher.drain_received_requests().count(), 2);
+    });
+}
+
+#[test]
+fn errors_of_subgraph_server_errors_are_hinted_as_retryable() {
+    runtime().block_on(async move {
+        // Retries are disabled, the responses are ingested as is.
+        let fetcher = MockFetch::default()
+            .with_status_response("a", 503, serde_json::json!({"errors": [{"message": "Overloaded"}]}))
+            .with_status_response("a", 502, serde_json::json!("<html>Bad Gateway</html>"))
+            .with_status_response("a", 400, serde_json::json!({"errors": [{"message": "Bad request"}]}));
+        let engine = Engine::builder()
+            .with_federated_sdl(
+                r###"
+                enum join__Graph {
+                  A @join__graph(name: "a", url: "https://a/graphql")
+                }
+
+                type Query {
+                  a: String @join__field(graph: A)
+                }
+                "###,
+            )
+            .with_mock_fetcher(fetcher)
+            .build()
+            .await;
+
+        let graphql_errors = engine.execute("query { a }").await.into_value();
+        let not_graphql = engine.execute("query { a }").await.into_value();
+        let client_error = engine.execute("query { a }").await.into_value();
+
+        assert_eq!(
+            graphql_errors["errors"][0]["extensions"]["retryable"],
+            serde_json::Value::Bool(true),
+            "{graphql_errors}"
+        );
+        assert_eq!(
+            not_graphql["errors"][0]["extensions"]["retryable"],
+            serde_json::Value::Bool(true),
+            "{not_graphql}"
+        );
+        assert_eq!(
+            client_error["errors"][0]["extensions"]["retryable"],
+            serde_json::Value::Null,
+            "{client_error}"
+        );
+    });
+}
@@ -0,0 +1,107 @@
+use engine_v2::Engine;
+use integration_tests::{federation::EngineV2Ext, fetch::MockFetch, runtime};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+      B @join__graph(name: "b", url: "https://b/graphql")
+    }
+
+    type Query
+      @join__type(graph: A)
+    {
+      product: Product @join__field(graph: A)
+    }
+
+    type Product
+      @join__type(graph: A, key: "id")
+      @join__type(graph: B, key: "id")
+    {
+      id: ID!
+      name: String @join__field(graph: B)
+    }
+    "###;
+
+fn hosts_without_tls_verification(config: &str) -> Vec<String> {
+    runtime().block_on(async move {
+        let fetcher = MockFetch::default()
+            .with_responses("a", vec![json!({"data": {"product": {"id": "1"}}})])
+            .with_responses(
+                "b",
+                vec![json!({"data": {"_entities": [{"__typename": "Product", "name": "Fork"}]}})],
+            );
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(config)
+            .with_mock_fetcher(fetcher.clone())
+            .build()
+            .await;
+
+        let response = engine.execute("query { product { id name } }").await;
+        assert_eq!(response.into_data(), json!({"product": {"id": "1", "name": "Fork"}}));
+
+        fetcher.drain_hosts_without_tls_verification().collect()
+    })
+}
+
+#[test]
+fn certificates_are_verified_by_default() {
+    assert!(hosts_without_tls_verification("").is_empty());
+}
+
+#[test]
+fn only_the_flagged_subgraph_skips_certificate_verification() {
+    let hosts = hosts_without_tls_verification(
+        r#"
+        [subgraphs.a]
+        danger_accept_invalid_certs = true
+        "#,
+    );
+
+    assert_eq!(hosts, vec!["a".to_string()]);
+}
+
+#[test]
+fn shadow_subgraph_certificates_are_verified_independently() {
+    let hosts = |config: &str| {
+        let config = config.to_string();
+        runtime().block_on(async move {
+            let fetcher = MockFetch::default()
+                .with_responses("a", vec![json!({"data": {"product": {"id": "1"}}})])
+                .with_responses("shadow", vec![json!({"data": {"product": {"id": "1"}}})]);
+            let engine = Engine::builder()
+                .with_federated_sdl(SDL)
+                .with_toml_config(config)
+                .with_mock_fetcher(fetcher.clone())
+                .build()
+                .await;
+
+            let response = engine.execute("query { product { id } }").await;
+            assert_eq!(response.into_data(), json!({"product": {"id": "1"}}));
+            engine.wait_for_background_tasks().await;
+
+            let mut hosts = fetcher.drain_hosts_without_tls_verification().collect::<Vec<_>>();
+            hosts.sort();
+            hosts
+        })
+    };
+
+    let inherited = hosts(
+        r#"
+        [subgraphs.a]
+        shadow_subgraph = "https://shadow/graphql"
+        danger_accept_invalid_certs = true
+        "#,
+    );
+    assert_eq!(inherited, vec!["a".to_string()]);
+
+    let explicit = hosts(
+        r#"
+        [subgraphs.a]
+        shadow_subgraph = "https://shadow/graphql"
+        shadow_danger_accept_invalid_certs = true
+        "#,
+    );
+    assert_eq!(explicit, vec!["shadow".to_string()]);
+}
@@ -0,0 +1,152 @@
+use engine_v2::Engine;
+use integration_tests::{federation::EngineV2Ext, fetch::MockFetch, runtime};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+      B @join__graph(name: "b", url: "https://b/graphql")
+    }
+
+    type Query
+      @join__type(graph: A)
+    {
+      product: Product @join__field(graph: A)
+    }
+
+    type Product
+      @join__type(graph: A, key: "id")
+      @join__type(graph: B, key: "id")
+    {
+      id: ID!
+      price: Int @join__field(graph: B)
+    }
+    "###;
+
+const CONFIG: &str = r#"
+    [subgraphs.b.url_template]
+    url = "https://{region}.b/graphql"
+    variables.region = { header = "x-region", allowed_values = ["eu", "us"] }
+"#;
+
+fn fetcher() -> MockFetch {
+    MockFetch::default()
+        .with_responses("a", vec![json!({"data": {"product": {"id": "1"}}}); 2])
+        .with_responses(
+            "eu.b",
+            vec![json!({"data": {"_entities": [{"__typename": "Product", "price": 10}]}})],
+        )
+}
+
+#[test]
+fn subgraph_url_is_resolved_from_request_header() {
+    runtime().block_on(async move {
+        let fetcher = fetcher();
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(CONFIG)
+            .with_mock_fetcher(fetcher.clone())
+            .build()
+            .await;
+
+        let response = engine
+            .execute("query { product { id price } }")
+            .header("x-region", "eu")
+            .await;
+
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "product": {
+              "id": "1",
+              "price": 10
+            }
+          }
+        }
+        "###);
+
+        let mut hosts = fetcher
+            .drain_received_requests()
+            .map(|(host, _)| host)
+            .collect::<Vec<_>>();
+        hosts.sort();
+        assert_eq!(hosts, vec!["a", "eu.b"]);
+    });
+}
+
+#[test]
+fn subgraph_url_template_rejects_unresolved_and_disallowed_values() {
+    runtime().block_on(async move {
+        let fetcher = fetcher();
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(CONFIG)
+            .with_mock_fetcher(fetcher.clone())
+            .build()
+            .await;
+
+        let missing = engine.execute("query { product { id price } }").await;
+        let disallowed = engine
+            .execute("query { product { id price } }")
+            .header("x-region", "evil.com/")
+            .await;
+
+        for response in [missing, disallowed] {
+            let errors = response.errors();
+            assert_eq!(errors.len(), 1, "{response}");
+            assert_eq!(
+                errors[0]["message"],
+                "Could not resolve the URL of subgraph 'b': no allowed value for '{region}'"
+            );
+        }
+
+        // Only the requests to A went out.
+        let hosts = fetcher
+            .drain_received_requests()
+            .map(|(host, _)| host)
+            .collect::<Vec<_>>();
+        assert_eq!(hosts, vec!["a", "a"]);
+    });
+}
+
+fn build_schema(config: &str) -> Result<engine_v2::Schema, String> {
+    let config: gateway_config::Config = toml::from_str(config).unwrap();
+    let graph = graphql_composition::FederatedGraph::from_sdl(SDL).unwrap();
+    let graph = serde_json::from_value(serde_json::to_value(&graph).unwrap()).unwrap();
+    let config = engine_config_builder::build_with_toml_config(&config, graph).into_latest();
+    engine_v2::Schema::try_from(config).map_err(|err| err.to_string())
+}
+
+#[test]
+fn invalid_url_templates_are_rejected_when_building_the_schema() {
+    assert!(build_schema(CONFIG).is_ok());
+
+    let templates = [
+        (
+            "https://{region.b/graphql",
+            "unbalanced braces in 'https://{region.b/graphql'",
+        ),
+        (
+            "https://region}.b/graphql",
+            "unbalanced braces in 'https://region}.b/graphql'",
+        ),
+        ("https://{zone}.b/graphql", "no variable for the placeholder '{zone}'"),
+        (
+            "{region}.b/graphql",
+            "'eu.b/graphql' is not a valid URL: relative URL without a base",
+        ),
+    ];
+    for (url, reason) in templates {
+        let config = format!(
+            r#"
+            [subgraphs.b.url_template]
+            url = "{url}"
+            variables.region = {{ header = "x-region", allowed_values = ["eu", "us"] }}
+            "#
+        );
+
+        let error = build_schema(&config).err().expect(url);
+
+        assert_eq!(error, format!("The URL template of subgraph 'b' is invalid: {reason}"));
+    }
+}
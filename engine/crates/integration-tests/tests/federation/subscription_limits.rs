@@ -0,0 +1,97 @@
+use engine_v2::{websocket::Message, Engine, Request, Session};
+use futures::Stream;
+use integration_tests::{
+    federation::{EngineV2Ext, TestRuntime},
+    runtime,
+};
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+    }
+
+    type Query {
+      version: String @join__field(graph: A)
+    }
+
+    type Subscription {
+      newProducts: String @join__field(graph: A)
+    }
+    "###;
+
+const CONFIG: &str = r#"
+    [gateway]
+    max_subscriptions_per_connection = 2
+    max_concurrent_subscriptions = 3
+"#;
+
+fn subscribe(session: &Session<TestRuntime>, id: &str) -> Result<impl Stream<Item = Message>, serde_json::Value> {
+    session
+        .execute_websocket(id.to_string(), Request::new("subscription { newProducts }"))
+        .map_err(|message| serde_json::to_value(message).unwrap())
+}
+
+#[test]
+fn subscriptions_beyond_the_connection_limit_are_rejected() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+        let session = engine.create_websocket_session().await;
+
+        let first = subscribe(&session, "1").expect("first subscription");
+        let _second = subscribe(&session, "2").expect("second subscription");
+
+        let error = subscribe(&session, "3").err().expect("third subscription is rejected");
+        insta::assert_json_snapshot!(error, @r###"
+        {
+          "type": "error",
+          "id": "3",
+          "payload": {
+            "errors": [
+              {
+                "message": "Too many subscriptions on this connection, at most 2 are allowed",
+                "extensions": {
+                  "code": "TOO_MANY_SUBSCRIPTIONS"
+                }
+              }
+            ]
+          }
+        }
+        "###);
+
+        // Once a subscription ends, its slot can be used again, with the same id too.
+        drop(first);
+        assert!(subscribe(&session, "3").is_ok());
+    })
+}
+
+#[test]
+fn subscriptions_beyond_the_global_limit_are_rejected() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(CONFIG)
+            .build()
+            .await;
+        let first_session = engine.create_websocket_session().await;
+        let second_session = engine.create_websocket_session().await;
+
+        let _first = subscribe(&first_session, "1").expect("first subscription");
+        let _second = subscribe(&first_session, "2").expect("second subscription");
+        let third = subscribe(&second_session, "1").expect("third subscription");
+
+        let error = subscribe(&second_session, "2")
+            .err()
+            .expect("fourth subscription is rejected");
+        assert_eq!(
+            error["payload"]["errors"][0]["message"],
+            "Too many concurrent subscriptions, at most 3 are allowed"
+        );
+
+        drop(third);
+        assert!(subscribe(&second_session, "2").is_ok());
+    })
+}
@@ -1,4 +1,5 @@
 use engine_v2::Engine;
+use futures::StreamExt;
 use graphql_mocks::{
     FederatedAccountsSchema, FederatedInventorySchema, FederatedProductsSchema, FederatedReviewsSchema,
 };
@@ -116,3 +117,44 @@ fn actual_federated_subscription() {
     ]
     "###);
 }
+
+#[test]
+fn subscription_over_json_lines() {
+    let (content_type, response) = runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_sdl_websocket_config()
+            .build()
+            .await;
+
+        let response = engine
+            .execute("subscription { newProducts { upc name } }")
+            .into_json_lines_stream()
+            .await;
+        let content_type = response.headers.get("content-type").cloned();
+
+        (content_type, response.stream.collect::<Vec<_>>().await)
+    });
+
+    assert_eq!(content_type.unwrap(), "application/x-ndjson");
+    insta::assert_json_snapshot!(response, @r###"
+    [
+      {
+        "data": {
+          "newProducts": {
+            "upc": "top-4",
+            "name": "Jeans"
+          }
+        }
+      },
+      {
+        "data": {
+          "newProducts": {
+            "upc": "top-5",
+            "name": "Pink Jeans"
+          }
+        }
+      }
+    ]
+    "###);
+}
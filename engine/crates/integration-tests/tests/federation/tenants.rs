@@ -0,0 +1,182 @@
+use engine_v2::Engine;
+use integration_tests::{federation::EngineV2Ext, fetch::MockFetch, runtime};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+    }
+
+    type Query {
+      me: User @join__field(graph: A)
+    }
+
+    type User {
+      id: ID!
+      invoices: [String!] @tag(name: "billing") @tag(name: "acme")
+      legacyId: String @tag(name: "globex")
+      plan: String @tag(name: "billing")
+    }
+    "###;
+
+const CONFIG: &str = r#"
+    [gateway.tenants]
+    header = "x-tenant-id"
+
+    [gateway.tenants.profiles.acme]
+    tags = ["acme"]
+
+    [gateway.tenants.profiles.globex]
+    tags = ["globex", "billing"]
+"#;
+
+const INTROSPECTION: &str = r#"query { __type(name: "User") { fields { name } } }"#;
+
+#[test]
+fn tenants_only_introspect_their_fields() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(CONFIG)
+            .with_mock_fetcher(MockFetch::default())
+            .build()
+            .await;
+
+        let response = engine.execute(INTROSPECTION).header("x-tenant-id", "acme").await;
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "__type": {
+              "fields": [
+                {
+                  "name": "id"
+                },
+                {
+                  "name": "invoices"
+                }
+              ]
+            }
+          }
+        }
+        "###);
+
+        let response = engine.execute(INTROSPECTION).header("x-tenant-id", "globex").await;
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "__type": {
+              "fields": [
+                {
+                  "name": "id"
+                },
+                {
+                  "name": "invoices"
+                },
+                {
+                  "name": "legacyId"
+                },
+                {
+                  "name": "plan"
+                }
+              ]
+            }
+          }
+        }
+        "###);
+
+        // Unknown tenants only see the fields without any tag.
+        let response = engine.execute(INTROSPECTION).header("x-tenant-id", "initech").await;
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "__type": {
+              "fields": [
+                {
+                  "name": "id"
+                }
+              ]
+            }
+          }
+        }
+        "###);
+    })
+}
+
+#[test]
+fn tenants_cannot_query_fields_of_other_tenants() {
+    runtime().block_on(async move {
+        let fetcher =
+            MockFetch::default().with_responses("a", vec![json!({"data": {"me": {"id": "1", "legacyId": "L-1"}}})]);
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(CONFIG)
+            .with_mock_fetcher(fetcher)
+            .build()
+            .await;
+
+        let query = "query { me { id legacyId } }";
+
+        let response = engine.execute(query).header("x-tenant-id", "globex").await;
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "me": {
+              "id": "1",
+              "legacyId": "L-1"
+            }
+          }
+        }
+        "###);
+
+        // The operation bound for globex must not be served from the cache to acme.
+        let response = engine.execute(query).header("x-tenant-id", "acme").await;
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "errors": [
+            {
+              "message": "User does not have a field named 'legacyId'",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 17
+                }
+              ],
+              "extensions": {
+                "retryable": false,
+                "code": "OPERATION_VALIDATION_ERROR"
+              }
+            }
+          ]
+        }
+        "###);
+    })
+}
+
+#[test]
+fn tags_are_ignored_without_tenants() {
+    let response = runtime().block_on(async move {
+        let fetcher = MockFetch::default().with_responses(
+            "a",
+            vec![json!({"data": {"me": {"id": "1", "plan": "pro", "legacyId": "L-1"}}})],
+        );
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_mock_fetcher(fetcher)
+            .build()
+            .await;
+
+        engine.execute("query { me { id plan legacyId } }").await
+    });
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "me": {
+          "id": "1",
+          "plan": "pro",
+          "legacyId": "L-1"
+        }
+      }
+    }
+    "###);
+}
@@ -1,6 +1,9 @@
+use std::time::{Duration, Instant};
+
 use engine_v2::Engine;
 use graphql_mocks::{FakeGithubSchema, SlowSchema};
-use integration_tests::{federation::EngineV2Ext, runtime};
+use integration_tests::{federation::EngineV2Ext, fetch::MockFetch, runtime};
+use serde_json::json;
 
 #[test]
 fn gateway_timeout() {
@@ -38,6 +41,7 @@ fn gateway_timeout() {
             {
               "message": "Gateway timeout",
               "extensions": {
+                "retryable": true,
                 "code": "GATEWAY_TIMEOUT"
               }
             }
@@ -92,6 +96,7 @@ fn subgraph_timeout() {
                 "verySlow"
               ],
               "extensions": {
+                "retryable": true,
                 "code": "SUBGRAPH_REQUEST_ERROR"
               }
             }
@@ -116,6 +121,107 @@ fn subgraph_timeout() {
                 "verySlow"
               ],
               "extensions": {
+                "retryable": true,
+                "code": "SUBGRAPH_REQUEST_ERROR"
+              }
+            }
+          ]
+        }
+        "###);
+    })
+}
+
+#[test]
+fn field_timeout_takes_precedence_over_a_lower_subgraph_timeout() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(SlowSchema)
+            .with_toml_config(
+                r###"
+                [subgraphs.slow]
+                timeout = "1s"
+
+                [subgraphs.slow.fields."Query.nullableDelay"]
+                timeout = "3s"
+                "###,
+            )
+            .build()
+            .await;
+
+        let response = engine.execute("query { slow: nullableDelay(ms: 1500) }").await;
+
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "slow": 1500
+          }
+        }
+        "###);
+
+        let response = engine.execute("query { verySlow: delay(ms: 1500) }").await;
+
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": null,
+          "errors": [
+            {
+              "message": "Request to subgraph 'slow' failed with: Request timeout",
+              "path": [
+                "verySlow"
+              ],
+              "extensions": {
+                "retryable": true,
+                "code": "SUBGRAPH_REQUEST_ERROR"
+              }
+            }
+          ]
+        }
+        "###);
+    })
+}
+
+#[test]
+fn field_timeout_takes_precedence_over_a_higher_subgraph_timeout() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(SlowSchema)
+            .with_toml_config(
+                r###"
+                [subgraphs.slow]
+                timeout = "10s"
+
+                [subgraphs.slow.fields."Query.nullableDelay"]
+                timeout = "1s"
+                "###,
+            )
+            .build()
+            .await;
+
+        let response = engine.execute("query { slow: delay(ms: 1500) }").await;
+
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "slow": 1500
+          }
+        }
+        "###);
+
+        let response = engine.execute("query { verySlow: nullableDelay(ms: 1500) }").await;
+
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "verySlow": null
+          },
+          "errors": [
+            {
+              "message": "Request to subgraph 'slow' failed with: Request timeout",
+              "path": [
+                "verySlow"
+              ],
+              "extensions": {
+                "retryable": true,
                 "code": "SUBGRAPH_REQUEST_ERROR"
               }
             }
@@ -124,3 +230,197 @@ fn subgraph_timeout() {
         "###);
     })
 }
+
+#[test]
+fn smallest_field_timeout_applies_to_the_whole_subgraph_request() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(SlowSchema)
+            .with_toml_config(
+                r###"
+                [subgraphs.slow.fields."Query.delay"]
+                timeout = "3s"
+
+                [subgraphs.slow.fields."Query.nullableDelay"]
+                timeout = "1s"
+                "###,
+            )
+            .build()
+            .await;
+
+        let response = engine
+            .execute("query { verySlow: delay(ms: 1500) fast: nullableDelay(ms: 0) }")
+            .await;
+
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": null,
+          "errors": [
+            {
+              "message": "Request to subgraph 'slow' failed with: Request timeout",
+              "path": [
+                "verySlow"
+              ],
+              "extensions": {
+                "retryable": true,
+                "code": "SUBGRAPH_REQUEST_ERROR"
+              }
+            }
+          ]
+        }
+        "###);
+    })
+}
+
+#[test]
+fn retryable_hint_on_timeouts_but_not_validation_errors() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_subgraph(SlowSchema)
+            .with_toml_config(
+                r###"
+                [gateway]
+                timeout = "1s"
+                "###,
+            )
+            .build()
+            .await;
+
+        let timeout = engine.execute("query { verySlow: delay(ms: 1500) }").await.into_value();
+        let invalid = engine.execute("query { unknown }").await.into_value();
+
+        assert_eq!(
+            timeout["errors"][0]["extensions"]["retryable"],
+            serde_json::Value::Bool(true)
+        );
+        assert_eq!(
+            invalid["errors"][0]["extensions"]["code"],
+            serde_json::Value::from("OPERATION_VALIDATION_ERROR")
+        );
+        assert_eq!(
+            invalid["errors"][0]["extensions"]["retryable"],
+            serde_json::Value::Bool(false)
+        );
+    })
+}
+
+#[test]
+fn late_subgraph_response_is_discarded() {
+    runtime().block_on(async move {
+        // The mock fetcher ignores the timeout, only the engine can cancel the request.
+        let engine = Engine::builder()
+            .with_federated_sdl(
+                r###"
+                enum join__Graph {
+                  A @join__graph(name: "a", url: "https://a/graphql")
+                }
+
+                type Query {
+                  version: String @join__field(graph: A)
+                }
+                "###,
+            )
+            .with_toml_config(
+                r###"
+                [subgraphs.a]
+                timeout = "1s"
+                "###,
+            )
+            .with_mock_fetcher(
+                MockFetch::default()
+                    .with_responses("a", vec![json!({"data": {"version": "late"}})])
+                    .with_delay(Duration::from_secs(3)),
+            )
+            .build()
+            .await;
+
+        let start = Instant::now();
+        let response = engine.execute("query { version }").await;
+        assert!(start.elapsed() < Duration::from_secs(3), "{:?}", start.elapsed());
+
+        insta::assert_json_snapshot!(response, @r###"
+        {
+          "data": {
+            "version": null
+          },
+          "errors": [
+            {
+              "message": "Request to subgraph 'a' failed with: Request timeout",
+              "path": [
+                "version"
+              ],
+              "extensions": {
+                "retryable": true,
+                "code": "SUBGRAPH_REQUEST_ERROR"
+              }
+            }
+          ]
+        }
+        "###);
+    })
+}
+
+#[test]
+fn entity_fetch_timeout_keeps_the_rest_of_the_response() {
+    runtime().block_on(async move {
+        let engine = Engine::builder()
+            .with_federated_sdl(
+                r###"
+                enum join__Graph {
+                  A @join__graph(name: "a", url: "https://a/graphql")
+                  B @join__graph(name: "b", url: "https://b/graphql")
+                }
+
+                type Query
+                  @join__type(graph: A)
+                {
+                  product: Product @join__field(graph: A)
+                }
+
+                type Product
+                  @join__type(graph: A, key: "id")
+                  @join__type(graph: B, key: "id")
+                {
+                  id: ID!
+                  name: String @join__field(graph: B)
+                }
+                "###,
+            )
+            .with_toml_config(
+                r###"
+                [subgraphs.b]
+                timeout = "1s"
+                "###,
+            )
+            .with_mock_fetcher(
+                MockFetch::default()
+                    .with_responses("a", vec![json!({"data": {"product": {"id": "1"}}})])
+                    .with_responses(
+                        "b",
+                        vec![json!({"data": {"_entities": [{"__typename": "Product", "name": "Fedora"}]}})],
+                    )
+                    .with_host_delay("b", Duration::from_secs(3)),
+            )
+            .build()
+            .await;
+
+        let start = Instant::now();
+        let response = engine.execute("query { product { id name } }").await.into_value();
+        assert!(start.elapsed() < Duration::from_secs(3), "{:?}", start.elapsed());
+
+        assert_eq!(response["data"]["product"]["id"], "1", "{response}");
+        assert_eq!(
+            response["data"]["product"]["name"],
+            serde_json::Value::Null,
+            "{response}"
+        );
+
+        let errors = response["errors"].as_array().expect("errors");
+        assert_eq!(errors.len(), 1, "{response}");
+        assert_eq!(
+            errors[0]["message"],
+            "Request to subgraph 'b' failed with: Request timeout"
+        );
+        assert_eq!(errors[0]["extensions"]["code"], "SUBGRAPH_REQUEST_ERROR");
+    })
+}
@@ -0,0 +1,75 @@
+use engine_v2::Engine;
+use integration_tests::{federation::EngineV2Ext, fetch::MockFetch, metrics::HistogramsRecorder, runtime};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+    }
+
+    type Query {
+      me: String @join__field(graph: A)
+    }
+    "###;
+
+/// Executes the queries in order and returns the `gql.operation.name` attribute recorded for each.
+fn recorded_operation_names(config: &str, queries: &[&str]) -> Vec<Option<String>> {
+    runtime().block_on(async move {
+        let fetcher = MockFetch::default().with_responses("a", vec![json!({"data": {"me": "Alice"}}); queries.len()]);
+        let histograms = HistogramsRecorder::default();
+        let engine = Engine::builder()
+            .with_federated_sdl(SDL)
+            .with_toml_config(config)
+            .with_mock_fetcher(fetcher)
+            .with_meter(histograms.meter())
+            .build()
+            .await;
+
+        for query in queries {
+            engine.execute(*query).await;
+        }
+
+        histograms
+            .attributes("gql_operation_latency")
+            .into_iter()
+            .map(|attributes| attributes.get("gql.operation.name").cloned())
+            .collect()
+    })
+}
+
+#[test]
+fn operation_names_are_recorded_as_is_by_default() {
+    let names = recorded_operation_names("", &["query First { me }", "query Second { me }", "{ me }"]);
+
+    assert_eq!(names, [Some("First".to_string()), Some("Second".to_string()), None]);
+}
+
+#[test]
+fn unknown_operations_are_bucketed_into_other() {
+    let config = r#"
+        [telemetry]
+        service_name = "test"
+
+        [telemetry.metrics]
+        max_tracked_operations = 2
+        tracked_operations = ["Allowed"]
+    "#;
+
+    let names = recorded_operation_names(
+        config,
+        &[
+            "query First { me }",
+            "query Second { me }",
+            "query Allowed { me }",
+            "query First { me }",
+            "{ me }",
+        ],
+    );
+
+    assert_eq!(
+        names,
+        ["First", "other", "Allowed", "First", "other"]
+            .map(|name| Some(name.to_string()))
+            .to_vec()
+    );
+}
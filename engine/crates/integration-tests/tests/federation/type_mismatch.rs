@@ -0,0 +1,104 @@
+use integration_tests::{federation::execute_with_mock, fetch::MockFetch};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+    }
+
+    type Query {
+      pet: Pet @join__field(graph: A)
+    }
+
+    interface Pet {
+      name: String!
+    }
+
+    type Dog implements Pet {
+      name: String!
+      barks: Boolean!
+    }
+
+    type Cat implements Pet {
+      name: String!
+      meows: Boolean!
+    }
+
+    type Car {
+      name: String!
+    }
+    "###;
+
+const QUERY: &str = "query { pet { name ... on Dog { barks } ... on Cat { meows } } }";
+
+fn subgraph_returning_a_car() -> MockFetch {
+    MockFetch::default().with_responses(
+        "a",
+        vec![json!({"data": {"pet": {"__typename": "Car", "name": "Beetle"}}})],
+    )
+}
+
+#[test]
+fn object_not_implementing_the_interface_is_a_type_mismatch() {
+    let response = execute_with_mock(SDL, "", subgraph_returning_a_car(), QUERY);
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "pet": null
+      },
+      "errors": [
+        {
+          "message": "Subgraph returned an object of type 'Car' which isn't a possible type of the field",
+          "path": [
+            "pet"
+          ],
+          "extensions": {
+            "code": "SUBGRAPH_TYPE_MISMATCH"
+          }
+        }
+      ]
+    }
+    "###);
+}
+
+#[test]
+fn type_mismatch_can_be_ignored() {
+    let response = execute_with_mock(
+        SDL,
+        r#"
+        [subgraphs.a]
+        type_mismatch = "ignore"
+        "#,
+        subgraph_returning_a_car(),
+        QUERY,
+    );
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "pet": {}
+      }
+    }
+    "###);
+}
+
+#[test]
+fn possible_type_is_not_a_type_mismatch() {
+    let fetcher = MockFetch::default().with_responses(
+        "a",
+        vec![json!({"data": {"pet": {"__typename": "Dog", "name": "Rex", "barks": true}}})],
+    );
+    let response = execute_with_mock(SDL, "", fetcher, QUERY);
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "pet": {
+          "name": "Rex",
+          "barks": true
+        }
+      }
+    }
+    "###);
+}
@@ -0,0 +1,50 @@
+use integration_tests::{federation::execute_with_mock, fetch::MockFetch};
+use serde_json::json;
+
+const SDL: &str = r###"
+    enum join__Graph {
+      A @join__graph(name: "a", url: "https://a/graphql")
+      B @join__graph(name: "b", url: "https://b/graphql")
+    }
+
+    type Query
+      @join__type(graph: A)
+    {
+      product: Product @join__field(graph: A)
+    }
+
+    type Product
+      @join__type(graph: A, key: "id")
+      @join__type(graph: B, key: "id")
+    {
+      id: ID!
+      name: String @join__field(graph: B)
+    }
+    "###;
+
+#[test]
+fn nested_typename_only_selection_does_not_fetch_entities() {
+    let fetcher = MockFetch::default()
+        .with_responses("a", vec![json!({"data": {"product": {"__typename": "Product"}}})])
+        .with_responses(
+            "b",
+            vec![json!({"data": {"_entities": [{"__typename": "Product", "name": "Fedora"}]}})],
+        );
+    let response = execute_with_mock(SDL, "", fetcher.clone(), "query { product { __typename } }");
+
+    insta::assert_json_snapshot!(response, @r###"
+    {
+      "data": {
+        "product": {
+          "__typename": "Product"
+        }
+      }
+    }
+    "###);
+
+    let hosts = fetcher
+        .drain_received_requests()
+        .map(|(host, _)| host)
+        .collect::<Vec<_>>();
+    assert_eq!(hosts, ["a"]);
+}
@@ -1,9 +1,28 @@
-use tracing::Level;
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Level, Subscriber,
+};
 use tracing_mock::{expect, subscriber};
+use tracing_subscriber::{
+    layer::{Context, SubscriberExt},
+    registry::LookupSpan,
+    Layer,
+};
 
 use engine_v2::Engine;
-use grafbase_telemetry::span::gql::GRAPHQL_SPAN_NAME;
-use graphql_mocks::FakeGithubSchema;
+use grafbase_telemetry::{
+    otel::{
+        opentelemetry::trace::TracerProvider as _, opentelemetry_sdk::trace::TracerProvider, tracing_opentelemetry,
+    },
+    span::{gql::GRAPHQL_SPAN_NAME, subgraph::SUBGRAPH_SPAN_NAME},
+};
+use graphql_mocks::{FakeGithubSchema, FederatedProductsSchema, FederatedReviewsSchema, StateMutationSchema};
 use integration_tests::{federation::EngineV2Ext, runtime};
 
 #[test]
@@ -42,3 +61,228 @@ fn query_bad_request() {
         handle.assert_finished();
     })
 }
+
+#[test]
+fn subgraph_spans_identify_their_plan_and_response_path() {
+    let spans = SubgraphSpans::default();
+
+    runtime().block_on(async {
+        let subscriber = tracing_subscriber::registry().with(spans.clone());
+        let _default = tracing::subscriber::set_default(subscriber);
+
+        let engine = Engine::builder()
+            .with_subgraph(FederatedProductsSchema)
+            .with_subgraph(FederatedReviewsSchema)
+            .build()
+            .await;
+
+        engine
+            .execute("{ topProducts { upc reviews { id body } } }")
+            .await
+            .into_data();
+    });
+
+    let spans = spans.0.lock().unwrap();
+    let [products, reviews] = spans.as_slice() else {
+        panic!("expected two subgraph spans, got {spans:#?}");
+    };
+
+    assert_eq!(products["subgraph.name"], "products");
+    assert_eq!(products["gql.response.path"], "topProducts");
+    assert_eq!(reviews["subgraph.name"], "reviews");
+    assert_eq!(reviews["gql.response.path"], "topProducts.reviews");
+    assert_ne!(products["gql.plan.id"], reviews["gql.plan.id"]);
+}
+
+#[test]
+fn subgraph_span_records_the_retries() {
+    let spans = SubgraphSpans::default();
+
+    runtime().block_on(async {
+        let subscriber = tracing_subscriber::registry().with(spans.clone());
+        let _default = tracing::subscriber::set_default(subscriber);
+
+        let engine = Engine::builder()
+            .with_subgraph(StateMutationSchema::default())
+            .with_sdl_config(
+                r#"
+                extend schema @subgraph(
+                    name: "state-mutation",
+                    retry: {
+                        baseDelay: "10ms"
+                    }
+                )
+                "#,
+            )
+            .build()
+            .await;
+
+        // Fails twice before succeeding.
+        engine
+            .execute("query { incrementAndFailIfLessThan(n: 2) }")
+            .await
+            .into_data();
+    });
+
+    let spans = spans.0.lock().unwrap();
+    let [subgraph] = spans.as_slice() else {
+        panic!("expected a single subgraph span, got {spans:#?}");
+    };
+
+    assert_eq!(subgraph["subgraph.retry_count"], "2");
+}
+
+#[test]
+fn blocked_operation_is_logged_as_rejection() {
+    let rejections = RejectionEvents::default();
+
+    runtime().block_on(async {
+        let subscriber = tracing_subscriber::registry().with(rejections.clone());
+        let _default = tracing::subscriber::set_default(subscriber);
+
+        let engine = Engine::builder()
+            .with_subgraph(FakeGithubSchema)
+            .with_toml_config(
+                r#"
+                [gateway]
+                log_rejected_operations = true
+
+                [clients.mobile]
+                allowed_operation_prefixes = ["Mobile_"]
+                "#,
+            )
+            .build()
+            .await;
+
+        engine
+            .execute("query Mobile_ServerVersion { serverVersion }")
+            .by_client("mobile", "1.0.0")
+            .await
+            .into_data();
+
+        let response = engine
+            .execute("query Web_ServerVersion { serverVersion }")
+            .by_client("mobile", "1.0.0")
+            .header("x-forwarded-for", "203.0.113.7, 10.0.0.1")
+            .await;
+        assert_eq!(response.errors()[0]["extensions"]["code"], "OPERATION_NOT_PERMITTED");
+    });
+
+    let events = rejections.0.lock().unwrap();
+    let [rejection] = events.as_slice() else {
+        panic!("expected a single rejection, got {events:#?}");
+    };
+
+    assert_eq!(rejection["message"], "operation rejected");
+    assert_eq!(rejection["reason"], "OPERATION_NOT_PERMITTED");
+    assert_eq!(rejection["client.name"], "mobile");
+    assert_eq!(rejection["client.version"], "1.0.0");
+    assert_eq!(rejection["source.ip"], "203.0.113.7");
+    assert_eq!(rejection["operation.hash"].len(), 64);
+}
+
+#[test]
+fn datadog_trace_headers_are_sent_to_subgraphs() {
+    runtime().block_on(async {
+        let tracer = TracerProvider::builder().build().tracer("test");
+        let subscriber = tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(tracer));
+        let _default = tracing::subscriber::set_default(subscriber);
+
+        let engine = Engine::builder()
+            .with_subgraph(FakeGithubSchema)
+            .with_toml_config(
+                r#"
+                [telemetry]
+                service_name = "test"
+
+                [telemetry.tracing.subgraph_headers]
+                x-datadog-trace-id = "{trace_id_decimal}"
+                x-datadog-parent-id = "{span_id_decimal}"
+                x-datadog-sampling-priority = "{sampled}"
+                "#,
+            )
+            .build()
+            .await;
+
+        engine.execute("query { serverVersion }").await.into_data();
+
+        let requests = engine.drain_http_requests_sent_to::<FakeGithubSchema>();
+        let [request] = requests.as_slice() else {
+            panic!("expected a single subgraph request, got {}", requests.len());
+        };
+
+        let header = |name: &str| {
+            request
+                .headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_else(|| panic!("missing {name} header"))
+                .to_string()
+        };
+        assert!(header("x-datadog-trace-id").parse::<u64>().is_ok());
+        assert!(header("x-datadog-parent-id").parse::<u64>().is_ok());
+        assert_eq!(header("x-datadog-sampling-priority"), "1");
+    });
+}
+
+/// Fields of every rejection event.
+#[derive(Clone, Default)]
+struct RejectionEvents(Arc<Mutex<Vec<BTreeMap<String, String>>>>);
+
+impl<S: Subscriber> Layer<S> for RejectionEvents {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if event.metadata().target() != "grafbase::rejections" {
+            return;
+        }
+        let mut fields = SpanFields::default();
+        event.record(&mut fields);
+        self.0.lock().unwrap().push(fields.0);
+    }
+}
+
+/// Fields of every subgraph span created, including the ones recorded later on.
+#[derive(Clone, Default)]
+struct SubgraphSpans(Arc<Mutex<Vec<BTreeMap<String, String>>>>);
+
+/// Position of the subgraph span in [SubgraphSpans].
+struct SubgraphSpanIndex(usize);
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for SubgraphSpans {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if attrs.metadata().name() != SUBGRAPH_SPAN_NAME {
+            return;
+        }
+        let mut fields = SpanFields::default();
+        attrs.record(&mut fields);
+        let mut spans = self.0.lock().unwrap();
+        spans.push(fields.0);
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SubgraphSpanIndex(spans.len() - 1));
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(index) = ctx
+            .span(id)
+            .and_then(|span| span.extensions().get::<SubgraphSpanIndex>().map(|index| index.0))
+        else {
+            return;
+        };
+        let mut fields = SpanFields::default();
+        values.record(&mut fields);
+        self.0.lock().unwrap()[index].extend(fields.0);
+    }
+}
+
+#[derive(Default)]
+struct SpanFields(BTreeMap<String, String>);
+
+impl Visit for SpanFields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(field.name().to_string(), format!("{value:?}"));
+    }
+}
@@ -17,9 +17,230 @@ pub struct FederatedGraphConfig {
     pub global_cache_rules: GlobalCacheRules<'static>,
     pub auth: Option<AuthV2Directive>,
     pub disable_introspection: bool,
+    pub introspection_cache_max_age: Option<Duration>,
+    pub response_cache_ttl: Option<Duration>,
+    pub response_cache_bypass_headers: Vec<String>,
     pub rate_limit: Option<RateLimitConfig>,
     pub timeout: Option<Duration>,
+    pub max_execution_depth: Option<u16>,
+    pub request_deduplication: bool,
+    pub validate_response_nullability: bool,
+    pub max_error_extension_size: Option<usize>,
+    pub max_logged_subgraph_request_size: Option<usize>,
+    pub max_forwarded_header_bytes: Option<usize>,
+    pub max_variable_values: Option<usize>,
+    pub max_variable_depth: Option<usize>,
+    pub operation_cache_limit: Option<usize>,
+    pub max_subscriptions_per_connection: Option<usize>,
+    pub max_concurrent_subscriptions: Option<usize>,
+    pub max_retries_per_operation: Option<usize>,
+    pub required_headers: Vec<String>,
+    pub pretty_responses: bool,
+    pub max_parse_time: Option<Duration>,
+    pub max_serialization_time: Option<Duration>,
+    pub max_string_length: Option<usize>,
+    pub oversized_strings: OversizedStringPolicy,
+    pub max_error_path_depth: Option<usize>,
+    pub incompatible_accept: IncompatibleAcceptPolicy,
+    pub log_rejected_operations: bool,
+    pub max_entities_per_operation: Option<usize>,
+    pub subgraph_trace_headers: BTreeMap<String, String>,
+    pub nullable_fields: Vec<String>,
+    pub scalar_formats: BTreeMap<String, ScalarFormat>,
+    pub shadow_gateway_url: Option<String>,
+    pub shadow_gateway_headers: Vec<String>,
+    pub duplicate_operation_names: DuplicateOperationNames,
+    pub non_finite_floats: NonFiniteFloats,
     pub entity_caching: EntityCachingConfig,
+    pub subgraph_cache: SubgraphCacheConfig,
+    pub clients: BTreeMap<String, ClientConfig>,
+    pub client_identification: ClientIdentification,
+    pub tracked_operations: Option<TrackedOperations>,
+    pub tenants: Option<Tenants>,
+}
+
+/// How to handle operations sharing the same name within a batch
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DuplicateOperationNames {
+    #[default]
+    Allow,
+    Reject,
+    Disambiguate,
+}
+
+impl From<gateway_config::DuplicateOperationNames> for DuplicateOperationNames {
+    fn from(policy: gateway_config::DuplicateOperationNames) -> Self {
+        match policy {
+            gateway_config::DuplicateOperationNames::Allow => DuplicateOperationNames::Allow,
+            gateway_config::DuplicateOperationNames::Reject => DuplicateOperationNames::Reject,
+            gateway_config::DuplicateOperationNames::Disambiguate => DuplicateOperationNames::Disambiguate,
+        }
+    }
+}
+
+/// How `Infinity` and `NaN` Float values are written in responses
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NonFiniteFloats {
+    #[default]
+    Null,
+    String,
+    Error,
+}
+
+impl From<gateway_config::NonFiniteFloats> for NonFiniteFloats {
+    fn from(policy: gateway_config::NonFiniteFloats) -> Self {
+        match policy {
+            gateway_config::NonFiniteFloats::Null => NonFiniteFloats::Null,
+            gateway_config::NonFiniteFloats::String => NonFiniteFloats::String,
+            gateway_config::NonFiniteFloats::Error => NonFiniteFloats::Error,
+        }
+    }
+}
+
+/// Format validated for the values of a custom scalar
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScalarFormat {
+    DateTime,
+    Uuid,
+}
+
+impl From<gateway_config::ScalarFormat> for ScalarFormat {
+    fn from(format: gateway_config::ScalarFormat) -> Self {
+        match format {
+            gateway_config::ScalarFormat::DateTime => ScalarFormat::DateTime,
+            gateway_config::ScalarFormat::Uuid => ScalarFormat::Uuid,
+        }
+    }
+}
+
+/// Headers identifying the client, besides the `x-grafbase-client-*` ones
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ClientIdentification {
+    pub name_headers: Vec<String>,
+    pub version_headers: Vec<String>,
+}
+
+impl From<gateway_config::ClientIdentificationConfig> for ClientIdentification {
+    fn from(config: gateway_config::ClientIdentificationConfig) -> Self {
+        ClientIdentification {
+            name_headers: config.name_headers.into_iter().map(|name| name.to_string()).collect(),
+            version_headers: config
+                .version_headers
+                .into_iter()
+                .map(|name| name.to_string())
+                .collect(),
+        }
+    }
+}
+
+/// Bounds the distinct operation names used as metric attribute
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TrackedOperations {
+    pub max: Option<usize>,
+    pub allow_list: Vec<String>,
+}
+
+impl TrackedOperations {
+    /// Returns `None` if the metrics config doesn't limit operation names.
+    pub fn from_config(config: &gateway_config::MetricsConfig) -> Option<Self> {
+        (config.max_tracked_operations.is_some() || !config.tracked_operations.is_empty()).then(|| TrackedOperations {
+            max: config.max_tracked_operations,
+            allow_list: config.tracked_operations.clone(),
+        })
+    }
+}
+
+/// Tags visible to each tenant, identified by a header
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Tenants {
+    pub header: String,
+    pub profiles: BTreeMap<String, Vec<String>>,
+}
+
+impl From<gateway_config::TenantsConfig> for Tenants {
+    fn from(config: gateway_config::TenantsConfig) -> Self {
+        Tenants {
+            header: config.header.to_string(),
+            profiles: config
+                .profiles
+                .into_iter()
+                .map(|(tenant, profile)| (tenant, profile.tags))
+                .collect(),
+        }
+    }
+}
+
+/// Configuration for a client of the federated graph, identified by its name
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ClientConfig {
+    /// Prefixes of the operation names this client is allowed to execute, if restricted.
+    pub allowed_operation_prefixes: Option<Vec<String>>,
+    /// Transformations applied to the response data returned to this client.
+    pub response_transforms: Vec<ResponseTransform>,
+    /// Maximum number of distinct subgraphs a single operation of this client may touch.
+    pub max_subgraphs_per_operation: Option<usize>,
+    /// Token bucket consumed by the cost of each operation of this client.
+    pub cost_budget: Option<CostBudget>,
+    /// Casing the response keys are converted to for this client.
+    pub response_key_casing: Option<ResponseKeyCasing>,
+    /// Oldest version of this client allowed to send requests.
+    pub min_version: Option<String>,
+}
+
+/// Token bucket limiting the total cost of the operations of a client
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CostBudget {
+    pub capacity: u64,
+    pub refill_per_second: u64,
+}
+
+impl From<gateway_config::ClientConfig> for ClientConfig {
+    fn from(config: gateway_config::ClientConfig) -> Self {
+        ClientConfig {
+            allowed_operation_prefixes: config.allowed_operation_prefixes,
+            response_transforms: config.response_transforms.into_iter().map(Into::into).collect(),
+            max_subgraphs_per_operation: config.max_subgraphs_per_operation,
+            cost_budget: config.cost_budget.map(|budget| CostBudget {
+                capacity: budget.capacity,
+                refill_per_second: budget.refill_per_second,
+            }),
+            response_key_casing: config.response_key_casing.map(Into::into),
+            min_version: config.min_version,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResponseKeyCasing {
+    SnakeCase,
+    CamelCase,
+}
+
+impl From<gateway_config::ResponseKeyCasing> for ResponseKeyCasing {
+    fn from(casing: gateway_config::ResponseKeyCasing) -> Self {
+        match casing {
+            gateway_config::ResponseKeyCasing::SnakeCase => ResponseKeyCasing::SnakeCase,
+            gateway_config::ResponseKeyCasing::CamelCase => ResponseKeyCasing::CamelCase,
+        }
+    }
+}
+
+/// A transformation of the response data, for the field at the dot-separated path of response keys
+#[derive(Clone, Debug, PartialEq)]
+pub enum ResponseTransform {
+    Rename { path: String, to: String },
+    Flatten { path: String },
+    Wrap { path: String, key: String },
+}
+
+impl From<gateway_config::ResponseTransform> for ResponseTransform {
+    fn from(transform: gateway_config::ResponseTransform) -> Self {
+        match transform {
+            gateway_config::ResponseTransform::Rename { path, to } => ResponseTransform::Rename { path, to },
+            gateway_config::ResponseTransform::Flatten { path } => ResponseTransform::Flatten { path },
+            gateway_config::ResponseTransform::Wrap { path, key } => ResponseTransform::Wrap { path, key },
+        }
+    }
 }
 
 /// Configuration for a subgraph of the current federated graph
@@ -39,6 +260,12 @@ pub struct SubgraphConfig {
     /// This will default to the normal URL if not present.
     pub websocket_url: Option<String>,
 
+    /// URL of a subgraph receiving a copy of the queries for comparison.
+    pub shadow_url: Option<String>,
+
+    /// Whether invalid TLS certificates of the shadow subgraph are accepted
+    pub shadow_danger_accept_invalid_certs: bool,
+
     /// Rules for passing headers forward to the subgraph
     pub header_rules: Vec<SubgraphHeaderRule>,
 
@@ -51,8 +278,187 @@ pub struct SubgraphConfig {
     /// Retry configuration
     pub retry: Option<RetryConfig>,
 
+    /// Compression of the request bodies, disabled if not present
+    pub request_compression: Option<RequestCompressionConfig>,
+
     /// Optional entity caching config for this subgraph.
     pub entity_caching: Option<EntityCachingConfig>,
+
+    /// Optional query response caching config for this subgraph.
+    pub subgraph_cache: Option<SubgraphCacheConfig>,
+
+    /// How to handle fields returned by the subgraph that weren't requested
+    pub extra_fields: ExtraFieldsPolicy,
+
+    /// How strictly scalars returned by the subgraph must match their type
+    pub scalar_coercion: ScalarCoercion,
+
+    /// How to handle responses with `data: null` and no errors
+    pub null_data: NullDataPolicy,
+
+    /// How to handle objects with a `__typename` that isn't part of the schema
+    pub unknown_typename: UnknownTypenamePolicy,
+
+    /// How to handle objects with a `__typename` that isn't a possible type of their field
+    pub type_mismatch: TypeMismatchPolicy,
+
+    /// Maximum depth of the queries sent to the subgraph
+    pub max_query_depth: Option<u16>,
+
+    /// URL with placeholders resolved from the request headers, overriding the URL of the graph
+    pub url_template: Option<SubgraphUrlTemplate>,
+
+    /// Whether invalid TLS certificates of the subgraph are accepted
+    pub danger_accept_invalid_certs: bool,
+
+    /// Prefix of the operation name of the `_entities` queries, anonymous if absent
+    pub entities_operation_prefix: Option<String>,
+
+    /// Maximum number of requests in flight to the subgraph
+    pub max_concurrent_requests: Option<usize>,
+
+    /// Order in which requests waiting for a concurrency slot get one
+    pub concurrency_fairness: ConcurrencyFairness,
+
+    /// Reshaping of the subgraph responses before they're processed
+    pub response_transform: Option<SubgraphResponseTransform>,
+
+    /// Weight of the subgraph fields when apportioning the cost of operations
+    pub cost_weight: Option<f64>,
+
+    /// Maximum number of entities resolved by a single subgraph request
+    pub entity_batch_size: Option<usize>,
+
+    /// Timeouts overriding the subgraph one for specific fields, keyed by `Type.field`
+    pub field_timeouts: BTreeMap<String, Duration>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ExtraFieldsPolicy {
+    #[default]
+    Ignore,
+    Warn,
+}
+
+impl From<gateway_config::ExtraFieldsPolicy> for ExtraFieldsPolicy {
+    fn from(policy: gateway_config::ExtraFieldsPolicy) -> Self {
+        match policy {
+            gateway_config::ExtraFieldsPolicy::Ignore => ExtraFieldsPolicy::Ignore,
+            gateway_config::ExtraFieldsPolicy::Warn => ExtraFieldsPolicy::Warn,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ScalarCoercion {
+    #[default]
+    Strict,
+    Lenient,
+}
+
+impl From<gateway_config::ScalarCoercion> for ScalarCoercion {
+    fn from(coercion: gateway_config::ScalarCoercion) -> Self {
+        match coercion {
+            gateway_config::ScalarCoercion::Strict => ScalarCoercion::Strict,
+            gateway_config::ScalarCoercion::Lenient => ScalarCoercion::Lenient,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NullDataPolicy {
+    #[default]
+    Allow,
+    Error,
+}
+
+impl From<gateway_config::NullDataPolicy> for NullDataPolicy {
+    fn from(policy: gateway_config::NullDataPolicy) -> Self {
+        match policy {
+            gateway_config::NullDataPolicy::Allow => NullDataPolicy::Allow,
+            gateway_config::NullDataPolicy::Error => NullDataPolicy::Error,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum UnknownTypenamePolicy {
+    #[default]
+    Ignore,
+    Error,
+}
+
+impl From<gateway_config::UnknownTypenamePolicy> for UnknownTypenamePolicy {
+    fn from(policy: gateway_config::UnknownTypenamePolicy) -> Self {
+        match policy {
+            gateway_config::UnknownTypenamePolicy::Ignore => UnknownTypenamePolicy::Ignore,
+            gateway_config::UnknownTypenamePolicy::Error => UnknownTypenamePolicy::Error,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OversizedStringPolicy {
+    #[default]
+    Truncate,
+    Error,
+}
+
+impl From<gateway_config::OversizedStringPolicy> for OversizedStringPolicy {
+    fn from(policy: gateway_config::OversizedStringPolicy) -> Self {
+        match policy {
+            gateway_config::OversizedStringPolicy::Truncate => OversizedStringPolicy::Truncate,
+            gateway_config::OversizedStringPolicy::Error => OversizedStringPolicy::Error,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IncompatibleAcceptPolicy {
+    #[default]
+    Reject,
+    Ignore,
+}
+
+impl From<gateway_config::IncompatibleAcceptPolicy> for IncompatibleAcceptPolicy {
+    fn from(policy: gateway_config::IncompatibleAcceptPolicy) -> Self {
+        match policy {
+            gateway_config::IncompatibleAcceptPolicy::Reject => IncompatibleAcceptPolicy::Reject,
+            gateway_config::IncompatibleAcceptPolicy::Ignore => IncompatibleAcceptPolicy::Ignore,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConcurrencyFairness {
+    #[default]
+    Fifo,
+    Fair,
+}
+
+impl From<gateway_config::ConcurrencyFairness> for ConcurrencyFairness {
+    fn from(fairness: gateway_config::ConcurrencyFairness) -> Self {
+        match fairness {
+            gateway_config::ConcurrencyFairness::Fifo => ConcurrencyFairness::Fifo,
+            gateway_config::ConcurrencyFairness::Fair => ConcurrencyFairness::Fair,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TypeMismatchPolicy {
+    #[default]
+    Error,
+    Ignore,
+}
+
+impl From<gateway_config::TypeMismatchPolicy> for TypeMismatchPolicy {
+    fn from(policy: gateway_config::TypeMismatchPolicy) -> Self {
+        match policy {
+            gateway_config::TypeMismatchPolicy::Error => TypeMismatchPolicy::Error,
+            gateway_config::TypeMismatchPolicy::Ignore => TypeMismatchPolicy::Ignore,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
@@ -62,6 +468,8 @@ pub enum EntityCachingConfig {
     Enabled {
         ttl: Option<Duration>,
         storage: EntityCacheStorage,
+        /// Expired entries may be served for this long if the subgraph request fails
+        stale_if_error: Option<Duration>,
     },
 }
 
@@ -79,16 +487,37 @@ impl From<gateway_config::EntityCachingConfig> for EntityCachingConfig {
             (Some(true), ttl) => EntityCachingConfig::Enabled {
                 ttl,
                 storage: entity_cache_storage(config.storage, config.redis),
+                stale_if_error: config.stale_if_error,
             },
             (_, Some(ttl)) => EntityCachingConfig::Enabled {
                 ttl: Some(ttl),
                 storage: entity_cache_storage(config.storage, config.redis),
+                stale_if_error: config.stale_if_error,
             },
             _ => EntityCachingConfig::Disabled,
         }
     }
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SubgraphCacheConfig {
+    #[default]
+    Disabled,
+    Enabled {
+        ttl: Option<Duration>,
+    },
+}
+
+impl From<gateway_config::SubgraphCacheConfig> for SubgraphCacheConfig {
+    fn from(config: gateway_config::SubgraphCacheConfig) -> Self {
+        if config.enabled {
+            SubgraphCacheConfig::Enabled { ttl: config.ttl }
+        } else {
+            SubgraphCacheConfig::Disabled
+        }
+    }
+}
+
 fn entity_cache_storage(
     storage: gateway_config::EntityCachingStorage,
     redis: Option<gateway_config::EntityCachingRedisConfig>,
@@ -223,6 +652,41 @@ pub struct RetryConfig {
     pub retry_percent: Option<f32>,
     /// Whether mutations should be retried at all. False by default.
     pub retry_mutations: Option<bool>,
+    /// Maximum number of attempts, the first one included. Only bounded by the retry budget by default.
+    pub max_attempts: Option<u32>,
+    /// Delay before the first retry, doubled for every subsequent one and randomized by a jitter. Default: 100ms.
+    pub base_delay: Option<Duration>,
+}
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct SubgraphUrlTemplate {
+    /// The URL with `{name}` placeholders
+    pub url: String,
+    /// Source of each placeholder value, keyed by placeholder name
+    pub variables: BTreeMap<String, SubgraphUrlVariable>,
+}
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct SubgraphUrlVariable {
+    /// The request header providing the value
+    pub header: String,
+    /// The only values the header may take
+    pub allowed_values: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd)]
+pub struct SubgraphResponseTransform {
+    /// Dot separated path of the GraphQL response within the subgraph response, `$` being the
+    /// whole response
+    pub unwrap: Option<String>,
+    /// Top-level keys of the response to rename
+    pub rename: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd)]
+pub struct RequestCompressionConfig {
+    /// Bodies smaller than this size in bytes are sent uncompressed.
+    pub threshold: Option<usize>,
 }
 
 #[cfg(test)]
@@ -245,6 +709,7 @@ mod tests {
             EntityCachingConfig::Enabled {
                 ttl: Some(Duration::from_secs(60)),
                 storage: Default::default(),
+                stale_if_error: None,
             }
         )
     }
@@ -262,7 +727,8 @@ mod tests {
             EntityCachingConfig::from(config.subgraphs.remove("products").unwrap().entity_caching.unwrap()),
             EntityCachingConfig::Enabled {
                 ttl: Some(Duration::from_secs(60)),
-                storage: Default::default()
+                storage: Default::default(),
+                stale_if_error: None,
             }
         )
     }
@@ -280,7 +746,8 @@ mod tests {
             EntityCachingConfig::from(config.subgraphs.remove("products").unwrap().entity_caching.unwrap()),
             EntityCachingConfig::Enabled {
                 ttl: None,
-                storage: Default::default()
+                storage: Default::default(),
+                stale_if_error: None,
             }
         )
     }
@@ -300,4 +767,27 @@ mod tests {
             EntityCachingConfig::Disabled
         )
     }
+
+    #[test]
+    fn subgraph_cache() {
+        let input = indoc! {r#"
+            [subgraph_cache]
+            enabled = true
+
+            [subgraphs.products.subgraph_cache]
+            enabled = false
+            ttl = "60s"
+        "#};
+
+        let mut config = toml::from_str::<gateway_config::Config>(input).unwrap();
+
+        assert_eq!(
+            SubgraphCacheConfig::from(config.subgraph_cache),
+            SubgraphCacheConfig::Enabled { ttl: None }
+        );
+        assert_eq!(
+            SubgraphCacheConfig::from(config.subgraphs.remove("products").unwrap().subgraph_cache.unwrap()),
+            SubgraphCacheConfig::Disabled
+        );
+    }
 }
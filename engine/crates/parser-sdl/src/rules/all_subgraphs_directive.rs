@@ -116,6 +116,8 @@ mod tests {
                         name: "Products",
                         development_url: None,
                         websocket_url: None,
+                        shadow_url: None,
+                        shadow_danger_accept_invalid_certs: false,
                         header_rules: [
                             Insert(
                                 SubgraphHeaderInsert {
@@ -127,7 +129,24 @@ mod tests {
                         rate_limit: None,
                         timeout: None,
                         retry: None,
+                        request_compression: None,
                         entity_caching: None,
+                        subgraph_cache: None,
+                        extra_fields: Ignore,
+                        scalar_coercion: Strict,
+                        null_data: Allow,
+                        unknown_typename: Ignore,
+                        type_mismatch: Error,
+                        max_query_depth: None,
+                        url_template: None,
+                        danger_accept_invalid_certs: false,
+                        entities_operation_prefix: None,
+                        max_concurrent_requests: None,
+                        concurrency_fairness: Fifo,
+                        response_transform: None,
+                        cost_weight: None,
+                        entity_batch_size: None,
+                        field_timeouts: {},
                     },
                 },
                 header_rules: [
@@ -155,6 +174,10 @@ mod tests {
                     aliases: None,
                     root_fields: None,
                     complexity: None,
+                    fragment_depth: None,
+                    fragment_spreads: None,
+                    fields: None,
+                    aliased_fetches: None,
                 },
                 global_cache_rules: GlobalCacheRules(
                     {
@@ -171,9 +194,49 @@ mod tests {
                 ),
                 auth: None,
                 disable_introspection: false,
+                introspection_cache_max_age: None,
+                response_cache_ttl: None,
+                response_cache_bypass_headers: [],
                 rate_limit: None,
                 timeout: None,
+                max_execution_depth: None,
+                request_deduplication: false,
+                validate_response_nullability: false,
+                max_error_extension_size: None,
+                max_logged_subgraph_request_size: None,
+                max_forwarded_header_bytes: None,
+                max_variable_values: None,
+                max_variable_depth: None,
+                operation_cache_limit: None,
+                max_subscriptions_per_connection: None,
+                max_concurrent_subscriptions: None,
+                max_retries_per_operation: None,
+                required_headers: [],
+                pretty_responses: false,
+                max_parse_time: None,
+                max_serialization_time: None,
+                max_string_length: None,
+                oversized_strings: Truncate,
+                max_error_path_depth: None,
+                incompatible_accept: Reject,
+                log_rejected_operations: false,
+                max_entities_per_operation: None,
+                subgraph_trace_headers: {},
+                nullable_fields: [],
+                scalar_formats: {},
+                shadow_gateway_url: None,
+                shadow_gateway_headers: [],
+                duplicate_operation_names: Allow,
+                non_finite_floats: Null,
                 entity_caching: Disabled,
+                subgraph_cache: Disabled,
+                clients: {},
+                client_identification: ClientIdentification {
+                    name_headers: [],
+                    version_headers: [],
+                },
+                tracked_operations: None,
+                tenants: None,
             },
         )
         "###);
@@ -16,6 +16,10 @@ pub struct OperationLimitsDirective {
     aliases: Option<u16>,
     root_fields: Option<u16>,
     complexity: Option<u16>,
+    fragment_depth: Option<u16>,
+    fragment_spreads: Option<u16>,
+    fields: Option<u16>,
+    aliased_fetches: Option<u16>,
 }
 
 impl From<OperationLimitsDirective> for OperationLimits {
@@ -26,6 +30,10 @@ impl From<OperationLimitsDirective> for OperationLimits {
             aliases,
             root_fields,
             complexity,
+            fragment_depth,
+            fragment_spreads,
+            fields,
+            aliased_fetches,
         }: OperationLimitsDirective,
     ) -> Self {
         OperationLimits {
@@ -34,6 +42,10 @@ impl From<OperationLimitsDirective> for OperationLimits {
             aliases,
             root_fields,
             complexity,
+            fragment_depth,
+            fragment_spreads,
+            fields,
+            aliased_fetches,
         }
     }
 }
@@ -68,6 +80,26 @@ impl Directive for OperationLimitsDirective {
           The maximum total complexity limit.
           """
           complexity: Int
+
+          """
+          The maximum nesting of fragment spreads.
+          """
+          fragmentDepth: Int
+
+          """
+          The maximum number of fragment spreads.
+          """
+          fragmentSpreads: Int
+
+          """
+          The maximum number of fields, counting every occurrence.
+          """
+          fields: Int
+
+          """
+          The maximum number of subgraph requests made for aliased fields.
+          """
+          aliasedFetches: Int
         ) on SCHEMA
         "#
         .to_string()
@@ -152,6 +184,10 @@ mod tests {
             complexity: Some(
                 100,
             ),
+            fragment_depth: None,
+            fragment_spreads: None,
+            fields: None,
+            aliased_fetches: None,
         }
         "###);
     }
@@ -72,6 +72,12 @@ pub struct RetryConfig {
     /// Whether mutations should be retried at all. False by default.
     #[serde(default)]
     pub retry_mutations: Option<bool>,
+    /// Maximum number of attempts, the first one included. Only bounded by the retry budget by default.
+    #[serde(default)]
+    pub max_attempts: Option<u32>,
+    /// Delay before the first retry, doubled for every subsequent one and randomized by a jitter. Default: 100ms.
+    #[serde(default, deserialize_with = "duration_str::deserialize_option_duration")]
+    pub base_delay: Option<Duration>,
 }
 
 impl Directive for SubgraphDirective {
@@ -129,6 +135,10 @@ impl Directive for SubgraphDirective {
             retryPercent: Float
             "Whether mutations should be retried at all. False by default."
             retryMutations: Boolean
+            "Maximum number of attempts, the first one included. Only bounded by the retry budget by default."
+            maxAttempts: Int
+            "Delay before the first retry, doubled for every subsequent one and randomized by a jitter. Default: 100ms."
+            baseDelay: String
         }
         "#
         .to_string()
@@ -195,10 +205,12 @@ impl Visitor<'_> for SubgraphDirectiveVisitor {
                 (Some(true), ttl) => Some(EntityCachingConfig::Enabled {
                     ttl,
                     storage: Default::default(),
+                    stale_if_error: None,
                 }),
                 (_, Some(ttl)) => Some(EntityCachingConfig::Enabled {
                     ttl: Some(ttl),
                     storage: Default::default(),
+                    stale_if_error: None,
                 }),
                 _ => None,
             };
@@ -219,11 +231,15 @@ impl Visitor<'_> for SubgraphDirectiveVisitor {
                      ttl,
                      retry_percent,
                      retry_mutations,
+                     max_attempts,
+                     base_delay,
                  }| SubgraphRetryConfig {
                     min_per_second,
                     ttl,
                     retry_percent,
                     retry_mutations,
+                    max_attempts,
+                    base_delay,
                 },
             );
         }
@@ -265,6 +281,8 @@ mod tests {
                         name: "Products",
                         development_url: None,
                         websocket_url: None,
+                        shadow_url: None,
+                        shadow_danger_accept_invalid_certs: false,
                         header_rules: [
                             Forward(
                                 SubgraphHeaderForward {
@@ -287,12 +305,31 @@ mod tests {
                         rate_limit: None,
                         timeout: None,
                         retry: None,
+                        request_compression: None,
                         entity_caching: None,
+                        subgraph_cache: None,
+                        extra_fields: Ignore,
+                        scalar_coercion: Strict,
+                        null_data: Allow,
+                        unknown_typename: Ignore,
+                        type_mismatch: Error,
+                        max_query_depth: None,
+                        url_template: None,
+                        danger_accept_invalid_certs: false,
+                        entities_operation_prefix: None,
+                        max_concurrent_requests: None,
+                        concurrency_fairness: Fifo,
+                        response_transform: None,
+                        cost_weight: None,
+                        entity_batch_size: None,
+                        field_timeouts: {},
                     },
                     "Reviews": SubgraphConfig {
                         name: "Reviews",
                         development_url: None,
                         websocket_url: None,
+                        shadow_url: None,
+                        shadow_danger_accept_invalid_certs: false,
                         header_rules: [
                             Insert(
                                 SubgraphHeaderInsert {
@@ -304,7 +341,24 @@ mod tests {
                         rate_limit: None,
                         timeout: None,
                         retry: None,
+                        request_compression: None,
                         entity_caching: None,
+                        subgraph_cache: None,
+                        extra_fields: Ignore,
+                        scalar_coercion: Strict,
+                        null_data: Allow,
+                        unknown_typename: Ignore,
+                        type_mismatch: Error,
+                        max_query_depth: None,
+                        url_template: None,
+                        danger_accept_invalid_certs: false,
+                        entities_operation_prefix: None,
+                        max_concurrent_requests: None,
+                        concurrency_fairness: Fifo,
+                        response_transform: None,
+                        cost_weight: None,
+                        entity_batch_size: None,
+                        field_timeouts: {},
                     },
                 },
                 header_rules: [],
@@ -314,15 +368,59 @@ mod tests {
                     aliases: None,
                     root_fields: None,
                     complexity: None,
+                    fragment_depth: None,
+                    fragment_spreads: None,
+                    fields: None,
+                    aliased_fetches: None,
                 },
                 global_cache_rules: GlobalCacheRules(
                     {},
                 ),
                 auth: None,
                 disable_introspection: false,
+                introspection_cache_max_age: None,
+                response_cache_ttl: None,
+                response_cache_bypass_headers: [],
                 rate_limit: None,
                 timeout: None,
+                max_execution_depth: None,
+                request_deduplication: false,
+                validate_response_nullability: false,
+                max_error_extension_size: None,
+                max_logged_subgraph_request_size: None,
+                max_forwarded_header_bytes: None,
+                max_variable_values: None,
+                max_variable_depth: None,
+                operation_cache_limit: None,
+                max_subscriptions_per_connection: None,
+                max_concurrent_subscriptions: None,
+                max_retries_per_operation: None,
+                required_headers: [],
+                pretty_responses: false,
+                max_parse_time: None,
+                max_serialization_time: None,
+                max_string_length: None,
+                oversized_strings: Truncate,
+                max_error_path_depth: None,
+                incompatible_accept: Reject,
+                log_rejected_operations: false,
+                max_entities_per_operation: None,
+                subgraph_trace_headers: {},
+                nullable_fields: [],
+                scalar_formats: {},
+                shadow_gateway_url: None,
+                shadow_gateway_headers: [],
+                duplicate_operation_names: Allow,
+                non_finite_floats: Null,
                 entity_caching: Disabled,
+                subgraph_cache: Disabled,
+                clients: {},
+                client_identification: ClientIdentification {
+                    name_headers: [],
+                    version_headers: [],
+                },
+                tracked_operations: None,
+                tenants: None,
             },
         )
         "###);
@@ -1,6 +1,6 @@
 mod websockets;
 
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::OnceLock};
 
 use futures_util::stream::BoxStream;
 use runtime::fetch::{FetchError, FetchRequest, FetchResponse, FetchResult, Fetcher, FetcherInner, GraphqlRequest};
@@ -10,13 +10,38 @@ use self::websockets::StreamingRequest;
 
 pub struct NativeFetcher {
     client: reqwest::Client,
+    /// Skips the TLS certificate verification, only used for the subgraphs explicitly allowing
+    /// it. Built on first use.
+    insecure_client: OnceLock<reqwest::Client>,
 }
 
 impl NativeFetcher {
     pub fn runtime_fetcher() -> Fetcher {
-        Fetcher::new(Self {
+        Fetcher::new(Self::new())
+    }
+
+    fn new() -> Self {
+        Self {
             client: reqwest::Client::new(),
-        })
+            insecure_client: OnceLock::new(),
+        }
+    }
+
+    fn client(&self, danger_accept_invalid_certs: bool) -> FetchResult<&reqwest::Client> {
+        if !danger_accept_invalid_certs {
+            return Ok(&self.client);
+        }
+
+        if let Some(client) = self.insecure_client.get() {
+            return Ok(client);
+        }
+
+        let client = reqwest::ClientBuilder::new()
+            .danger_accept_invalid_certs(true)
+            .build()
+            .map_err(|err| FetchError::AnyError(format!("Failed to build the HTTP client: {err}")))?;
+
+        Ok(self.insecure_client.get_or_init(|| client))
     }
 }
 
@@ -26,7 +51,7 @@ impl FetcherInner for NativeFetcher {
         let n = request.json_body.len();
 
         let response = self
-            .client
+            .client(request.danger_accept_invalid_certs)?
             .post(request.url.clone())
             .body(request.json_body.clone())
             .headers(request.headers.clone())
@@ -43,12 +68,13 @@ impl FetcherInner for NativeFetcher {
                 }
             })?;
 
+        let status = response.status();
         let bytes = response
             .bytes()
             .await
             .map_err(|e| FetchError::AnyError(e.to_string()))?;
 
-        Ok(FetchResponse { bytes })
+        Ok(FetchResponse { status, bytes })
     }
 
     async fn stream(
@@ -86,3 +112,24 @@ impl FetcherInner for NativeFetcher {
             .boxed())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::NativeFetcher;
+
+    #[test]
+    fn certificates_are_only_ignored_when_requested() {
+        let fetcher = NativeFetcher::new();
+
+        let client = fetcher.client(false).unwrap();
+        assert!(std::ptr::eq(client, &fetcher.client));
+        assert!(fetcher.insecure_client.get().is_none());
+
+        let insecure_client = fetcher.client(true).unwrap();
+        assert!(!std::ptr::eq(insecure_client, &fetcher.client));
+        assert!(std::ptr::eq(insecure_client, fetcher.insecure_client.get().unwrap()));
+
+        // The default client is left untouched.
+        assert!(std::ptr::eq(fetcher.client(false).unwrap(), &fetcher.client));
+    }
+}
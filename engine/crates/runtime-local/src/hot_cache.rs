@@ -3,7 +3,8 @@ use runtime::hot_cache::{CachedDataKind, HotCache, HotCacheFactory};
 pub struct InMemoryHotCacheFactory;
 
 impl HotCacheFactory for InMemoryHotCacheFactory {
-    type Cache<V> = InMemoryHotCache<V>
+    type Cache<V>
+        = InMemoryHotCache<V>
     where
         V: Clone + Send + Sync + 'static + serde::Serialize + serde::de::DeserializeOwned;
 
@@ -17,6 +18,7 @@ impl HotCacheFactory for InMemoryHotCacheFactory {
             inner: match kind {
                 CachedDataKind::PersistedQuery => builder.max_capacity(100).build(),
                 CachedDataKind::Operation => builder.max_capacity(1000).build(),
+                CachedDataKind::IntrospectionResponse => builder.max_capacity(100).build(),
             },
         }
     }
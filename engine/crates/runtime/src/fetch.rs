@@ -26,10 +26,13 @@ pub struct FetchRequest<'a> {
     pub headers: http::HeaderMap,
     pub json_body: Bytes,
     pub timeout: Duration,
+    /// The TLS certificate of the server isn't verified, explicitly allowed for this subgraph.
+    pub danger_accept_invalid_certs: bool,
 }
 
 #[derive(Clone)]
 pub struct FetchResponse {
+    pub status: http::StatusCode,
     pub bytes: Bytes,
 }
 
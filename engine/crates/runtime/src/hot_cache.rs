@@ -4,6 +4,7 @@ use std::future::Future;
 pub enum CachedDataKind {
     PersistedQuery,
     Operation,
+    IntrospectionResponse,
 }
 
 pub trait HotCacheFactory: Send + Sync + 'static {
@@ -42,9 +43,10 @@ where
 // -- No-op implementation -- //
 // ---------------------------//
 impl HotCacheFactory for () {
-    type Cache<V> = ()
-where
-    V: Clone + Send + Sync + 'static + serde::Serialize + serde::de::DeserializeOwned;
+    type Cache<V>
+        = ()
+    where
+        V: Clone + Send + Sync + 'static + serde::Serialize + serde::de::DeserializeOwned;
 
     async fn create<V>(&self, _: CachedDataKind) -> Self::Cache<V>
     where
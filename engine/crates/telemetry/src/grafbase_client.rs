@@ -13,11 +13,48 @@ impl Client {
     }
 
     pub fn extract_from(headers: &http::HeaderMap) -> Option<Self> {
-        let name = headers.get(&X_GRAFBASE_CLIENT_NAME).and_then(|v| v.to_str().ok())?;
-        let version = headers
-            .get(&X_GRAFBASE_CLIENT_VERSION)
-            .and_then(|v| v.to_str().ok())
-            .map(str::to_string);
+        ClientIdentification::default().extract_from(headers)
+    }
+}
+
+/// Headers identifying the client besides `x-grafbase-client-name` and
+/// `x-grafbase-client-version`, for clients which can't be changed to send those. The grafbase
+/// headers always take precedence, the others are checked in order.
+#[derive(Debug, Clone, Default)]
+pub struct ClientIdentification {
+    name_headers: Vec<http::HeaderName>,
+    version_headers: Vec<http::HeaderName>,
+}
+
+impl ClientIdentification {
+    /// Invalid header names are ignored.
+    pub fn new<S: AsRef<str>>(
+        name_headers: impl IntoIterator<Item = S>,
+        version_headers: impl IntoIterator<Item = S>,
+    ) -> Self {
+        fn parse<S: AsRef<str>>(names: impl IntoIterator<Item = S>) -> Vec<http::HeaderName> {
+            names
+                .into_iter()
+                .filter_map(|name| http::HeaderName::from_bytes(name.as_ref().as_bytes()).ok())
+                .collect()
+        }
+
+        Self {
+            name_headers: parse(name_headers),
+            version_headers: parse(version_headers),
+        }
+    }
+
+    pub fn extract_from(&self, headers: &http::HeaderMap) -> Option<Client> {
+        let find = |grafbase_header: &http::HeaderName, others: &[http::HeaderName]| {
+            std::iter::once(grafbase_header)
+                .chain(others)
+                .find_map(|name| headers.get(name).and_then(|v| v.to_str().ok()))
+        };
+
+        let name = find(&X_GRAFBASE_CLIENT_NAME, &self.name_headers)?;
+        let version = find(&X_GRAFBASE_CLIENT_VERSION, &self.version_headers).map(str::to_string);
+
         Some(Client {
             name: name.to_string(),
             version,
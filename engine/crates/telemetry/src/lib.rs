@@ -14,6 +14,8 @@ pub mod span;
 /// [Tower](https://docs.rs/tower/latest/tower/) integration
 #[cfg(feature = "tower")]
 pub mod tower;
+/// Trace identifiers propagated to subgraphs
+pub mod trace_context;
 
 pub(crate) const SCOPE: &str = "grafbase";
 pub(crate) const SCOPE_VERSION: &str = "1.0";
@@ -1,11 +1,17 @@
 mod operation;
+mod registry;
 mod request;
+mod response_diff;
+mod subgraph;
 
 use std::borrow::Cow;
 
 use opentelemetry::metrics::{Meter, MeterProvider};
 pub use operation::*;
+pub use registry::*;
 pub use request::*;
+pub use response_diff::*;
+pub use subgraph::*;
 
 pub fn meter_from_global_provider() -> Meter {
     meter(&opentelemetry::global::meter_provider())
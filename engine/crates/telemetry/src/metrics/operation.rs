@@ -1,3 +1,8 @@
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
+
 use opentelemetry::{
     metrics::{Histogram, Meter},
     KeyValue,
@@ -5,9 +10,44 @@ use opentelemetry::{
 
 use crate::{gql_response_status::GraphqlResponseStatus, grafbase_client::Client};
 
+/// Operation name attribute of anonymous and untracked operations.
+pub const OTHER_OPERATIONS: &str = "other";
+
 #[derive(Clone)]
 pub struct GraphqlOperationMetrics {
     latency: Histogram<u64>,
+    tracked_operations: Option<Arc<TrackedOperations>>,
+}
+
+/// Operation names are chosen by clients, so each of them becoming a distinct time series could
+/// overwhelm the metrics backend. Allowed names are always kept, others on a first come basis
+/// until the maximum is reached.
+struct TrackedOperations {
+    allow_list: HashSet<String>,
+    max: usize,
+    tracked: Mutex<HashSet<String>>,
+}
+
+impl TrackedOperations {
+    fn label(&self, name: Option<String>) -> String {
+        let Some(name) = name else {
+            return OTHER_OPERATIONS.to_string();
+        };
+
+        if self.allow_list.contains(&name) {
+            return name;
+        }
+
+        let mut tracked = self.tracked.lock().unwrap();
+        if tracked.contains(&name) {
+            name
+        } else if self.allow_list.len() + tracked.len() < self.max {
+            tracked.insert(name.clone());
+            name
+        } else {
+            OTHER_OPERATIONS.to_string()
+        }
+    }
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -72,9 +112,22 @@ impl GraphqlOperationMetrics {
     pub fn build(meter: &Meter) -> Self {
         Self {
             latency: meter.u64_histogram("gql_operation_latency").init(),
+            tracked_operations: None,
         }
     }
 
+    /// Bounds the distinct values of the `gql.operation.name` attribute to `max`. Anonymous
+    /// operations, and any other not in the allow-list once the maximum is reached, are recorded
+    /// as [OTHER_OPERATIONS].
+    pub fn with_tracked_operations(mut self, allow_list: impl IntoIterator<Item = String>, max: Option<usize>) -> Self {
+        self.tracked_operations = Some(Arc::new(TrackedOperations {
+            allow_list: allow_list.into_iter().collect(),
+            max: max.unwrap_or_default(),
+            tracked: Default::default(),
+        }));
+        self
+    }
+
     pub fn record(
         &self,
         GraphqlRequestMetricsAttributes {
@@ -100,6 +153,10 @@ impl GraphqlOperationMetrics {
             KeyValue::new("gql.operation.type", ty.as_str()),
             KeyValue::new("gql.operation.used_fields", used_fields),
         ];
+        let name = match &self.tracked_operations {
+            Some(tracked_operations) => Some(tracked_operations.label(name)),
+            None => name,
+        };
         if let Some(name) = name {
             attributes.push(KeyValue::new("gql.operation.name", name));
         }
@@ -0,0 +1,28 @@
+use opentelemetry::{
+    metrics::{Counter, Meter},
+    KeyValue,
+};
+
+#[derive(Clone)]
+pub struct RegistryMetrics {
+    update_failures: Counter<u64>,
+    rollbacks: Counter<u64>,
+}
+
+impl RegistryMetrics {
+    pub fn build(meter: &Meter) -> Self {
+        Self {
+            update_failures: meter.u64_counter("graph_registry_update_failures_total").init(),
+            rollbacks: meter.u64_counter("graph_rollbacks_total").init(),
+        }
+    }
+
+    pub fn record_update_failure(&self, reason: &'static str) {
+        self.update_failures.add(1, &[KeyValue::new("reason", reason)]);
+    }
+
+    /// A newly deployed schema was replaced by the previous one after producing too many errors.
+    pub fn record_rollback(&self) {
+        self.rollbacks.add(1, &[]);
+    }
+}
@@ -1,5 +1,5 @@
 use opentelemetry::{
-    metrics::{Histogram, Meter},
+    metrics::{Counter, Histogram, Meter},
     KeyValue,
 };
 
@@ -51,3 +51,22 @@ impl RequestMetrics {
         self.latency.record(latency.as_millis() as u64, &attributes);
     }
 }
+
+#[derive(Clone)]
+pub struct RequestCancellationMetrics {
+    cancelled: Counter<u64>,
+}
+
+impl RequestCancellationMetrics {
+    pub fn build(meter: &Meter) -> Self {
+        Self {
+            cancelled: meter.u64_counter("requests_cancelled_total").init(),
+        }
+    }
+
+    /// The request was abandoned before its response was ready, typically because the client
+    /// disconnected.
+    pub fn record_cancelled(&self) {
+        self.cancelled.add(1, &[]);
+    }
+}
@@ -0,0 +1,18 @@
+use opentelemetry::metrics::{Counter, Meter};
+
+#[derive(Clone)]
+pub struct ResponseDiffMetrics {
+    diffs: Counter<u64>,
+}
+
+impl ResponseDiffMetrics {
+    pub fn build(meter: &Meter) -> Self {
+        Self {
+            diffs: meter.u64_counter("response_diff_total").init(),
+        }
+    }
+
+    pub fn record_diff(&self) {
+        self.diffs.add(1, &[]);
+    }
+}
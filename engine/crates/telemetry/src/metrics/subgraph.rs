@@ -0,0 +1,49 @@
+use opentelemetry::{
+    metrics::{Counter, Histogram, Meter},
+    KeyValue,
+};
+
+#[derive(Clone)]
+pub struct SubgraphMetrics {
+    extra_fields: Counter<u64>,
+    shadow_mismatches: Counter<u64>,
+    cost: Counter<f64>,
+    permit_wait: Histogram<u64>,
+    queue_depth: Histogram<u64>,
+}
+
+impl SubgraphMetrics {
+    pub fn build(meter: &Meter) -> Self {
+        Self {
+            extra_fields: meter.u64_counter("subgraph_extra_fields_total").init(),
+            shadow_mismatches: meter.u64_counter("subgraph_shadow_mismatch_total").init(),
+            cost: meter.f64_counter("subgraph_cost_total").init(),
+            permit_wait: meter.u64_histogram("subgraph_permit_wait_ms").init(),
+            queue_depth: meter.u64_histogram("subgraph_queue_depth").init(),
+        }
+    }
+
+    pub fn record_extra_fields(&self, subgraph_name: &str, count: u64) {
+        self.extra_fields
+            .add(count, &[KeyValue::new("subgraph.name", subgraph_name.to_string())]);
+    }
+
+    pub fn record_shadow_mismatch(&self, subgraph_name: &str) {
+        self.shadow_mismatches
+            .add(1, &[KeyValue::new("subgraph.name", subgraph_name.to_string())]);
+    }
+
+    /// Share of the cost of an operation attributed to the subgraph.
+    pub fn record_cost(&self, subgraph_name: &str, cost: f64) {
+        self.cost
+            .add(cost, &[KeyValue::new("subgraph.name", subgraph_name.to_string())]);
+    }
+
+    /// Time a request waited for a slot of a subgraph limiting its concurrent requests, and the
+    /// number of requests already waiting when it asked for one.
+    pub fn record_permit_wait(&self, subgraph_name: &str, wait: std::time::Duration, queue_depth: usize) {
+        let attributes = [KeyValue::new("subgraph.name", subgraph_name.to_string())];
+        self.permit_wait.record(wait.as_millis() as u64, &attributes);
+        self.queue_depth.record(queue_depth as u64, &attributes);
+    }
+}
@@ -10,6 +10,10 @@ pub struct SubgraphRequestSpan<'a> {
     pub operation_type: &'a str,
     pub sanitized_query: &'a str,
     pub url: &'a Url,
+    /// Execution plan the request was made for, the same for all the requests of a plan.
+    pub plan_id: usize,
+    /// Response paths of the fields resolved by the request, like `topProducts.reviews`.
+    pub response_path: &'a str,
 }
 
 impl<'a> SubgraphRequestSpan<'a> {
@@ -22,11 +26,14 @@ impl<'a> SubgraphRequestSpan<'a> {
             "subgraph.url" = self.url.as_str(),
             "gql.operation.type" = self.operation_type,
             "gql.operation.query" = self.sanitized_query,
+            "gql.plan.id" = self.plan_id,
+            "gql.response.path" = self.response_path,
             "gql.response.status" = Empty,
             "gql.response.field_errors_count" = Empty,
             "gql.response.data_is_null" = Empty,
             "gql.response.request_errors_count" = Empty,
             "gql.response.error" = Empty,
+            "subgraph.retry_count" = Empty,
         )
     }
 }
@@ -1,5 +1,6 @@
 use std::{
     future::Future,
+    sync::Arc,
     task::{ready, Context, Poll},
     time::Instant,
 };
@@ -14,7 +15,7 @@ use tracing::Span;
 
 use crate::{
     gql_response_status::GraphqlResponseStatus,
-    grafbase_client::Client,
+    grafbase_client::{Client, ClientIdentification},
     metrics::{RequestMetrics, RequestMetricsAttributes},
     span::{request::HttpRequestSpan, GqlRecorderSpanExt, HttpRecorderSpanExt, GRAFBASE_TARGET},
 };
@@ -22,12 +23,22 @@ use crate::{
 pub fn layer(meter: Meter) -> TelemetryLayer {
     TelemetryLayer {
         metrics: RequestMetrics::build(&meter),
+        client_identification: Default::default(),
     }
 }
 
 #[derive(Clone)]
 pub struct TelemetryLayer {
     metrics: RequestMetrics,
+    client_identification: Arc<ClientIdentification>,
+}
+
+impl TelemetryLayer {
+    /// Headers identifying the client in the request metrics, besides the grafbase ones.
+    pub fn with_client_identification(mut self, client_identification: ClientIdentification) -> Self {
+        self.client_identification = Arc::new(client_identification);
+        self
+    }
 }
 
 impl<S> Layer<S> for TelemetryLayer {
@@ -36,6 +47,7 @@ impl<S> Layer<S> for TelemetryLayer {
         TelemetryService {
             inner,
             metrics: self.metrics.clone(),
+            client_identification: self.client_identification.clone(),
         }
     }
 }
@@ -48,6 +60,7 @@ impl<S> Layer<S> for TelemetryLayer {
 pub struct TelemetryService<S> {
     inner: S,
     metrics: RequestMetrics,
+    client_identification: Arc<ClientIdentification>,
 }
 
 impl<S> TelemetryService<S> {
@@ -108,7 +121,7 @@ where
 
     fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
         let start = Instant::now();
-        let client = Client::extract_from(req.headers());
+        let client = self.client_identification.extract_from(req.headers());
         let metrics = self.metrics.clone();
         let span = self.make_span(&req);
         ResponseFuture {
@@ -0,0 +1,39 @@
+use opentelemetry::trace::TraceContextExt;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Identifiers of the OpenTelemetry span backing a tracing span, for APM tools correlating
+/// traces with their own headers rather than W3C `traceparent`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceContext {
+    pub trace_id: u128,
+    pub span_id: u64,
+    pub sampled: bool,
+}
+
+impl TraceContext {
+    /// None if the span isn't recorded by OpenTelemetry.
+    pub fn of(span: &tracing::Span) -> Option<Self> {
+        let context = span.context();
+        let span_context = context.span().span_context().clone();
+        span_context.is_valid().then(|| TraceContext {
+            trace_id: u128::from_be_bytes(span_context.trace_id().to_bytes()),
+            span_id: u64::from_be_bytes(span_context.span_id().to_bytes()),
+            sampled: span_context.is_sampled(),
+        })
+    }
+
+    /// Substitutes the placeholders of a header value template:
+    ///
+    /// - `{trace_id}` and `{span_id}`: lowercase hexadecimal, as in `traceparent`.
+    /// - `{trace_id_decimal}`: the lower 64 bits of the trace id in decimal, as Datadog expects.
+    /// - `{span_id_decimal}`: the span id in decimal.
+    /// - `{sampled}`: `1` if sampled, `0` otherwise.
+    pub fn render(&self, template: &str) -> String {
+        template
+            .replace("{trace_id_decimal}", &(self.trace_id as u64).to_string())
+            .replace("{span_id_decimal}", &self.span_id.to_string())
+            .replace("{trace_id}", &format!("{:032x}", self.trace_id))
+            .replace("{span_id}", &format!("{:016x}", self.span_id))
+            .replace("{sampled}", if self.sampled { "1" } else { "0" })
+    }
+}
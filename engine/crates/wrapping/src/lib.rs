@@ -215,6 +215,15 @@ impl Wrapping {
         self
     }
 
+    /// Same wrapping with a nullable outermost type, `[String!]!` becoming `[String!]`.
+    #[must_use]
+    pub fn with_nullable_outermost(mut self) -> Self {
+        match self.pop_list_wrapping() {
+            Some(_) => self.wrapped_by_nullable_list(),
+            None => Wrapping(self.0 & !INNER_IS_REQUIRED_FLAG),
+        }
+    }
+
     /// Outermost wrapping
     pub fn pop_list_wrapping(&mut self) -> Option<ListWrapping> {
         self.next_back()
@@ -317,4 +326,25 @@ mod tests {
 
         assert_eq!(wrapping.pop_list_wrapping(), None);
     }
+
+    #[test]
+    fn test_with_nullable_outermost() {
+        let wrapping = Wrapping::required().with_nullable_outermost();
+        assert!(!wrapping.inner_is_required());
+        assert!(!wrapping.is_list());
+
+        let wrapping = Wrapping::required()
+            .wrapped_by_required_list()
+            .wrapped_by_required_list()
+            .with_nullable_outermost();
+        assert!(wrapping.inner_is_required());
+        assert!(!wrapping.is_required());
+        assert_eq!(
+            wrapping.list_wrappings().collect::<Vec<_>>(),
+            vec![ListWrapping::RequiredList, ListWrapping::NullableList]
+        );
+
+        let wrapping = Wrapping::nullable().wrapped_by_nullable_list();
+        assert_eq!(wrapping.with_nullable_outermost(), wrapping);
+    }
 }
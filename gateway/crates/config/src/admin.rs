@@ -0,0 +1,15 @@
+use std::time::Duration;
+
+use serde_dynamic_string::DynamicString;
+
+/// Admin endpoints configuration.
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AdminConfig {
+    /// Secret to send as a bearer token in the `Authorization` header to access the admin endpoints.
+    pub secret: DynamicString<String>,
+    /// Window over which the subgraph health served at `/admin/subgraphs` is computed. Defaults
+    /// to one minute.
+    #[serde(deserialize_with = "duration_str::deserialize_option_duration", default)]
+    pub subgraph_health_window: Option<Duration>,
+}
@@ -13,6 +13,11 @@ pub struct EntityCachingConfig {
     /// The ttl to store cache entries with.  Defaults to 60s
     #[serde(deserialize_with = "duration_str::deserialize_option_duration", default)]
     pub ttl: Option<Duration>,
+
+    /// How long after their expiry cached subgraph responses may still be served if the
+    /// subgraph request fails. Disabled by default.
+    #[serde(deserialize_with = "duration_str::deserialize_option_duration", default)]
+    pub stale_if_error: Option<Duration>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, serde::Deserialize)]
@@ -1,3 +1,4 @@
+pub mod admin;
 pub mod authentication;
 pub mod cors;
 pub mod entity_caching;
@@ -9,6 +10,7 @@ pub mod telemetry;
 
 use std::{collections::BTreeMap, net::SocketAddr, path::PathBuf, time::Duration};
 
+pub use admin::*;
 use ascii::AsciiString;
 pub use authentication::*;
 pub use cors::*;
@@ -67,6 +69,22 @@ pub struct Config {
     /// Global configuration for entity caching
     #[serde(default)]
     pub entity_caching: EntityCachingConfig,
+
+    /// Global configuration for caching the responses of subgraph queries
+    #[serde(default)]
+    pub subgraph_cache: SubgraphCacheConfig,
+
+    /// Global configuration for caching whole query responses
+    #[serde(default)]
+    pub response_cache: ResponseCacheConfig,
+
+    /// Client specific configuration, keyed by the client name sent in the
+    /// `x-grafbase-client-name` header
+    #[serde(default)]
+    pub clients: BTreeMap<String, ClientConfig>,
+
+    /// Admin endpoints configuration, the endpoints are disabled if not present
+    pub admin: Option<AdminConfig>,
 }
 
 impl Config {
@@ -97,6 +115,221 @@ pub struct GatewayConfig {
     /// Global rate limiting configuration
     #[serde(default)]
     pub rate_limit: Option<RateLimitConfig>,
+    /// Maximum nesting of the response data, counting fields, list items and the values within
+    /// JSON scalars. A response nested any deeper aborts the request with a single error.
+    #[serde(default)]
+    pub max_execution_depth: Option<u16>,
+    /// Identical queries received concurrently, with the same variables and authentication, are
+    /// executed only once and all of them receive the same response.
+    #[serde(default)]
+    pub request_deduplication: bool,
+    /// Verifies that responses honor the non-null fields of the schema once the subgraph
+    /// responses are merged. Violations are reported as `RESPONSE_VALIDATION_FAILED` errors.
+    /// Disabled by default as it walks through the whole response.
+    #[serde(default)]
+    pub validate_response_nullability: bool,
+    /// Maximum size in bytes of each extension value forwarded from subgraph errors. Larger
+    /// values are truncated. Defaults to 64 KiB.
+    #[serde(default)]
+    pub max_error_extension_size: Option<usize>,
+    /// Maximum size in bytes of the subgraph requests written to the debug logs. Larger requests
+    /// are truncated. Defaults to 4 KiB.
+    #[serde(default)]
+    pub max_logged_subgraph_request_size: Option<usize>,
+    /// Maximum total size in bytes of the headers sent to a subgraph, names included. Requests
+    /// exceeding it fail before reaching the subgraph. Unlimited by default.
+    #[serde(default)]
+    pub max_forwarded_header_bytes: Option<usize>,
+    /// Maximum size in bytes of the body of a request, rejected with a `REQUEST_TOO_LARGE` error
+    /// before being parsed if larger. Can be overridden per client. Default: 2MiB.
+    #[serde(default)]
+    pub max_request_body_bytes: Option<usize>,
+    /// Maximum number of values in the variables of a request, counting every nested list item
+    /// and input object field. Bounds the work of coercing them, operations exceeding it are
+    /// rejected with `VARIABLES_TOO_COMPLEX`. Unlimited by default.
+    #[serde(default)]
+    pub max_variable_values: Option<usize>,
+    /// Maximum nesting of lists and objects in the variables of a request. Bounds the recursion
+    /// of their coercion, operations exceeding it are rejected with `VARIABLES_TOO_DEEP`.
+    /// Unlimited by default.
+    pub max_variable_depth: Option<usize>,
+    /// Maximum number of prepared operations kept in memory, so that repeated operations skip
+    /// parsing, validation and planning. The least recently used ones are evicted first.
+    /// Defaults to 1000.
+    pub operation_cache_limit: Option<usize>,
+    /// Maximum number of subscriptions a single WebSocket connection may have running. Further
+    /// `subscribe` messages are answered with an error. Unlimited by default.
+    pub max_subscriptions_per_connection: Option<usize>,
+    /// Maximum number of subscriptions running over WebSocket connections, across all of them.
+    /// Unlimited by default.
+    pub max_concurrent_subscriptions: Option<usize>,
+    /// Maximum number of subgraph request retries within a single operation, all subgraphs
+    /// included. Once exhausted, failing requests aren't retried anymore even if their subgraph
+    /// retry budget would allow it. Unlimited by default.
+    #[serde(default)]
+    pub max_retries_per_operation: Option<usize>,
+    /// Headers every request must carry, such as an API key or a tenant id. Requests missing
+    /// any of them are rejected with `MISSING_REQUIRED_HEADER` before anything else is done.
+    #[serde(default)]
+    pub required_headers: Vec<String>,
+    /// Lets clients ask for pretty-printed JSON responses with the `x-grafbase-pretty: true`
+    /// header, handy when inspecting responses by hand. Meant for debugging, responses are
+    /// always compact when disabled, which is the default.
+    #[serde(default)]
+    pub pretty_responses: bool,
+    /// Time budget for parsing an operation document. Documents taking longer are rejected with
+    /// `QUERY_PARSE_TIMEOUT`, catching inputs that are slow to parse despite being small enough.
+    /// Unlimited by default.
+    #[serde(deserialize_with = "duration_str::deserialize_option_duration", default)]
+    pub max_parse_time: Option<Duration>,
+    /// Time budget for serializing a response. Once exceeded, the rest of the data is left out
+    /// and a `RESPONSE_SERIALIZATION_TIMEOUT` error is added, a last resort for enormous
+    /// responses. Unlimited by default.
+    #[serde(deserialize_with = "duration_str::deserialize_option_duration", default)]
+    pub max_serialization_time: Option<Duration>,
+    /// Maximum size in bytes of the string values returned by subgraphs. Fields annotated with
+    /// `@maxStringLength(length:)` use their own limit instead. Unlimited by default.
+    #[serde(default)]
+    pub max_string_length: Option<usize>,
+    /// What happens to strings exceeding their maximum length.
+    #[serde(default)]
+    pub oversized_strings: OversizedStringPolicy,
+    /// Maximum number of elements in the `path` of the errors. Deeper paths are cut, ending with
+    /// a `"…"` element. Unlimited by default.
+    #[serde(default)]
+    pub max_error_path_depth: Option<usize>,
+    /// What happens to operations using `@defer` or `@stream` when the `Accept` header of the
+    /// request doesn't allow a multipart or event stream response.
+    #[serde(default)]
+    pub incompatible_accept: IncompatibleAcceptPolicy,
+    /// Logs every operation rejected before execution under the `grafbase::rejections` target,
+    /// with the reason, client, source IP and operation hash. Disabled by default.
+    #[serde(default)]
+    pub log_rejected_operations: bool,
+    /// Maximum number of entities fetched from subgraphs, across all their requests, while
+    /// executing a single operation. Once exceeded, further entity fetches fail with an
+    /// `ENTITY_LIMIT_EXCEEDED` error. Unlimited by default.
+    pub max_entities_per_operation: Option<usize>,
+    /// Non-null fields, as `Type.field`, for which subgraphs may return null while they are
+    /// being migrated to a nullable type. Such nulls are kept and logged instead of being
+    /// propagated as errors.
+    #[serde(default)]
+    pub nullable_fields: Vec<String>,
+    /// Formats validated for the values of custom scalars returned by subgraphs, by scalar name.
+    /// Values which don't match are invalid, the error propagating to the closest nullable field.
+    #[serde(default)]
+    pub scalar_formats: BTreeMap<String, ScalarFormat>,
+    /// URL of another gateway receiving a copy of every query, typically a newer version being
+    /// validated. Both responses are compared in the background and differences counted and
+    /// logged, the client only ever gets the response of this gateway.
+    pub shadow_gateway: Option<Url>,
+    /// Client headers forwarded to the shadow gateway. None are by default, credentials in
+    /// particular should only be forwarded to a trusted gateway.
+    #[serde(default)]
+    pub shadow_gateway_headers: Vec<String>,
+    /// What happens to requests still executing when their client disconnects.
+    #[serde(default)]
+    pub client_disconnect: ClientDisconnectPolicy,
+    /// Batched requests configuration
+    #[serde(default)]
+    pub batching: BatchingConfig,
+    /// How `Infinity` and `NaN` Float values are written in responses, as JSON cannot represent
+    /// them.
+    #[serde(default)]
+    pub non_finite_floats: NonFiniteFloats,
+    /// Additional headers identifying the client in metrics and for the client specific
+    /// configuration.
+    #[serde(default)]
+    pub client_identification: ClientIdentificationConfig,
+    /// Per-tenant visibility of the fields annotated with `@tag(name:)`, disabled by default.
+    #[serde(default)]
+    pub tenants: Option<TenantsConfig>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClientDisconnectPolicy {
+    /// The execution is dropped, cancelling the subgraph requests in flight. Counted by the
+    /// `requests_cancelled_total` metric.
+    #[default]
+    Cancel,
+    /// The execution carries on in the background until completion, its response being
+    /// discarded. Useful if mutations must not be interrupted half-way.
+    Complete,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ScalarFormat {
+    /// An RFC 3339 date-time, such as `2024-05-17T09:30:00Z`.
+    DateTime,
+    /// A hyphenated UUID, such as `123e4567-e89b-12d3-a456-426614174000`.
+    Uuid,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NonFiniteFloats {
+    /// Write `null` and add an error for the field.
+    #[default]
+    Null,
+    /// Write them as the strings `"Infinity"`, `"-Infinity"` and `"NaN"`.
+    String,
+    /// Fail the response serialization.
+    Error,
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClientIdentificationConfig {
+    /// Headers holding the client name, such as `apollographql-client-name`. Checked in order,
+    /// after `x-grafbase-client-name`.
+    #[serde(default)]
+    pub name_headers: Vec<AsciiString>,
+    /// Headers holding the client version, such as `apollographql-client-version`. Checked in
+    /// order, after `x-grafbase-client-version`.
+    #[serde(default)]
+    pub version_headers: Vec<AsciiString>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TenantsConfig {
+    /// Header holding the tenant identifier.
+    pub header: AsciiString,
+    /// Visibility profile of each tenant, keyed by their identifier. Requests without a known
+    /// tenant only see the fields without any `@tag`.
+    #[serde(default)]
+    pub profiles: BTreeMap<String, TenantProfileConfig>,
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TenantProfileConfig {
+    /// Tags visible to the tenant. A field with `@tag` directives is visible if one of them is
+    /// listed, fields without any are visible to all.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BatchingConfig {
+    /// How to handle operations sharing the same name within a batch
+    #[serde(default)]
+    pub duplicate_operation_names: DuplicateOperationNames,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateOperationNames {
+    /// Execute them as any other operation.
+    #[default]
+    Allow,
+    /// Reject the whole batch with a `DUPLICATE_OPERATION_NAME` error.
+    Reject,
+    /// Execute them, but suffix their name in traces and metrics with their index in the batch.
+    Disambiguate,
 }
 
 #[derive(Debug, serde::Deserialize, Clone)]
@@ -106,6 +339,13 @@ pub struct SubgraphConfig {
     pub headers: Vec<HeaderRule>,
     /// The URL to use for GraphQL websocket calls.
     pub websocket_url: Option<Url>,
+    /// URL of a subgraph receiving a copy of every query sent to this one. Responses are
+    /// compared and mismatches counted, the client only ever gets the response of this subgraph.
+    pub shadow_subgraph: Option<Url>,
+    /// DANGER: skips the verification of the TLS certificates presented by the shadow subgraph.
+    /// Independent from `danger_accept_invalid_certs`, certificates are verified by default.
+    #[serde(default)]
+    pub shadow_danger_accept_invalid_certs: bool,
     /// Rate limiting configuration specifically for this Subgraph
     #[serde(default)]
     pub rate_limit: Option<GraphRateLimit>,
@@ -114,10 +354,309 @@ pub struct SubgraphConfig {
     pub timeout: Option<Duration>,
     #[serde(default)]
     pub retry: SubgraphRetryConfig,
+    /// Compression of the request bodies sent to the subgraph.
+    #[serde(default)]
+    pub request_compression: SubgraphRequestCompressionConfig,
 
     /// Subgraph specific entity caching config  this overrides the global config if there
     /// is any
     pub entity_caching: Option<EntityCachingConfig>,
+
+    /// Subgraph specific query response caching config, overriding the global one if present.
+    pub subgraph_cache: Option<SubgraphCacheConfig>,
+
+    /// What to do with fields returned by the subgraph that weren't requested by the gateway.
+    #[serde(default)]
+    pub extra_fields: ExtraFieldsPolicy,
+
+    /// How strictly scalars returned by the subgraph must match their type.
+    #[serde(default)]
+    pub scalar_coercion: ScalarCoercion,
+
+    /// What to do with responses holding `data: null` without any errors.
+    #[serde(default)]
+    pub null_data: NullDataPolicy,
+
+    /// What to do with objects whose `__typename` isn't a type of the schema at all.
+    #[serde(default)]
+    pub unknown_typename: UnknownTypenamePolicy,
+
+    /// What to do with objects whose `__typename` is a type of the schema, but not one of the
+    /// possible types of their interface or union field.
+    #[serde(default)]
+    pub type_mismatch: TypeMismatchPolicy,
+
+    /// Maximum depth of the queries sent to this subgraph. Operations which would require a
+    /// deeper subgraph query are rejected during planning.
+    #[serde(default)]
+    pub max_query_depth: Option<u16>,
+
+    /// URL of the subgraph with placeholders substituted for each request, taking precedence
+    /// over the URL of the federated graph.
+    pub url_template: Option<SubgraphUrlTemplateConfig>,
+
+    /// DANGER: skips the verification of the TLS certificates presented by this subgraph,
+    /// accepting self-signed, expired or mismatched ones. Only meant for trusted internal
+    /// networks, it leaves the connection open to man-in-the-middle attacks. A warning is logged
+    /// at startup for every subgraph enabling it. It never applies to other subgraphs.
+    #[serde(default)]
+    pub danger_accept_invalid_certs: bool,
+
+    /// Names the `_entities` queries sent to this subgraph `{prefix}_{subgraph}_{plan id}`, like
+    /// `GatewayEntities_reviews_3`, to correlate them in the subgraph logs. Characters which
+    /// aren't allowed in a GraphQL name are replaced by `_`. Anonymous by default.
+    pub entities_operation_prefix: Option<String>,
+
+    /// Maximum number of requests in flight to this subgraph, further ones wait for a slot to
+    /// free up. Unlimited by default.
+    pub max_concurrent_requests: Option<usize>,
+
+    /// Order in which requests waiting on `max_concurrent_requests` get a slot.
+    #[serde(default)]
+    pub concurrency_fairness: ConcurrencyFairness,
+
+    /// Reshapes the responses of subgraphs which don't return a standard GraphQL response, like
+    /// ones wrapping it in an envelope, before they're processed. None by default.
+    pub response_transform: Option<SubgraphResponseTransformConfig>,
+
+    /// Weight of the fields resolved by this subgraph when apportioning the cost of operations
+    /// between subgraphs, reported by the `subgraph_cost_total` metric. Defaults to 1.
+    pub cost_weight: Option<f64>,
+
+    /// Maximum number of entities resolved by a single request to this subgraph. Larger sets of
+    /// representations are split into batches, fetched concurrently. Unbounded by default.
+    pub entity_batch_size: Option<usize>,
+
+    /// Field specific configuration, keyed by `Type.field`.
+    #[serde(default)]
+    pub fields: BTreeMap<String, SubgraphFieldConfig>,
+}
+
+/// Caching of whole subgraph responses, keyed by the subgraph, the query and its variables.
+/// Unlike entity caching it also applies to the root fields, whichever client operation they
+/// were planned for.
+#[derive(Debug, Default, serde::Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SubgraphCacheConfig {
+    /// Whether query responses are cached. Mutations never are.
+    #[serde(default)]
+    pub enabled: bool,
+    /// How long responses are cached. Defaults to 60s.
+    #[serde(deserialize_with = "duration_str::deserialize_option_duration", default)]
+    pub ttl: Option<Duration>,
+}
+
+/// Caching of whole query responses in memory. Each response is tagged with surrogate keys, the
+/// names of the types it contains, which are sent in the `Surrogate-Key` header and can be used
+/// to purge cached responses once a mutation changed them.
+#[derive(Debug, Default, serde::Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ResponseCacheConfig {
+    /// Whether query responses are cached. Mutations never are.
+    #[serde(default)]
+    pub enabled: bool,
+    /// How long responses are cached. Defaults to 60s.
+    #[serde(deserialize_with = "duration_str::deserialize_option_duration", default)]
+    pub ttl: Option<Duration>,
+    /// Requests with any of these headers are neither served from the cache nor cached.
+    /// Defaults to `Authorization`. Authenticated requests and those with an `Authorization`,
+    /// `Proxy-Authorization` or `Cookie` header always bypass the cache.
+    #[serde(default)]
+    pub bypass_headers: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default, serde::Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct SubgraphFieldConfig {
+    /// Timeout for subgraph requests resolving this field. Takes precedence over both the
+    /// subgraph and the gateway timeouts.
+    #[serde(deserialize_with = "duration_str::deserialize_option_duration", default)]
+    pub timeout: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExtraFieldsPolicy {
+    /// Silently skip them.
+    #[default]
+    Ignore,
+    /// Skip them, but log a warning and increment the `subgraph_extra_fields_total` counter.
+    Warn,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScalarCoercion {
+    /// Values must be of the exact JSON type of the scalar.
+    #[default]
+    Strict,
+    /// Also accept `0`/`1` and `"0"`/`"1"` for booleans, as some legacy subgraphs return them.
+    Lenient,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NullDataPolicy {
+    /// The null is legitimate: requested fields are null, without any error.
+    #[default]
+    Allow,
+    /// If any of the requested fields is non-null, the subgraph likely swallowed an error and
+    /// the response is treated as invalid.
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnknownTypenamePolicy {
+    /// The object is written without any field, as `{}`.
+    #[default]
+    Ignore,
+    /// The response is invalid, the error propagating to the closest nullable field as for any
+    /// other invalid value.
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OversizedStringPolicy {
+    /// The string is cut at the limit and suffixed with `…[truncated]`.
+    #[default]
+    Truncate,
+    /// The value is invalid, the error propagating to the closest nullable field.
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IncompatibleAcceptPolicy {
+    /// The operation isn't executed, the response has a `NOT_ACCEPTABLE` error.
+    #[default]
+    Reject,
+    /// The operation is executed, all the data being returned at once.
+    Ignore,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConcurrencyFairness {
+    /// First come, first served. An operation sending many requests at once may keep the
+    /// others waiting until all of them went through.
+    #[default]
+    Fifo,
+    /// Operations with waiting requests take turns, each getting one slot at a time, so a cheap
+    /// operation isn't stuck behind a large one.
+    Fair,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TypeMismatchPolicy {
+    /// A `SUBGRAPH_TYPE_MISMATCH` error is raised at the object path, propagating to the closest
+    /// nullable field.
+    #[default]
+    Error,
+    /// The object is written without any field, as `{}`.
+    Ignore,
+}
+
+#[derive(Debug, serde::Deserialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ClientConfig {
+    /// If set, the client may only execute named operations starting with one of these prefixes.
+    pub allowed_operation_prefixes: Option<Vec<String>>,
+    /// Transformations applied to the response data returned to the client.
+    #[serde(default)]
+    pub response_transforms: Vec<ResponseTransform>,
+    /// If set, operations of the client may not be planned across more distinct subgraphs.
+    pub max_subgraphs_per_operation: Option<usize>,
+    /// If set, every operation of the client consumes as many tokens from this budget as it
+    /// selects fields. Operations are rejected once the budget is exhausted, until it refills.
+    /// The budget is shared by all gateway instances through the KV store.
+    pub cost_budget: Option<CostBudgetConfig>,
+    /// If set, the response keys returned to the client are converted to this casing. Aliases
+    /// and `__typename` are kept as they are.
+    pub response_key_casing: Option<ResponseKeyCasing>,
+    /// If set, requests of the client with an older `x-grafbase-client-version`, or without any,
+    /// are rejected with a `CLIENT_VERSION_UNSUPPORTED` error asking to upgrade. Versions are
+    /// compared by their dot separated numbers, like `2.10.0` > `2.9.1`.
+    pub min_version: Option<String>,
+    /// If set, overrides `gateway.max_request_body_bytes` for the requests of the client, such as
+    /// trusted internal clients sending large batches.
+    pub max_request_body_bytes: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseKeyCasing {
+    /// `firstName` becomes `first_name`.
+    SnakeCase,
+    /// `first_name` becomes `firstName`.
+    CamelCase,
+}
+
+#[derive(Debug, serde::Deserialize, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CostBudgetConfig {
+    /// Maximum number of tokens, also available initially.
+    pub capacity: u64,
+    /// Number of tokens added back every second.
+    pub refill_per_second: u64,
+}
+
+/// A transformation of the response data. The path is the dot-separated list of response keys,
+/// aliases included, leading to the field as requested. List items are traversed implicitly.
+#[derive(Debug, serde::Deserialize, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum ResponseTransform {
+    /// Renames the field.
+    Rename { path: String, to: String },
+    /// Replaces the field with the fields of its object value.
+    Flatten { path: String },
+    /// Replaces the field value with an object holding it under the given key.
+    Wrap { path: String, key: String },
+}
+
+#[derive(Debug, serde::Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct SubgraphUrlTemplateConfig {
+    /// The URL with `{name}` placeholders, e.g. `https://{region}.reviews.internal/graphql`.
+    pub url: String,
+    /// Where the value of each placeholder comes from, keyed by placeholder name.
+    #[serde(default)]
+    pub variables: BTreeMap<String, SubgraphUrlVariableConfig>,
+}
+
+#[derive(Debug, serde::Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct SubgraphUrlVariableConfig {
+    /// The request header providing the value.
+    pub header: String,
+    /// Values the header may take. Requests with any other value are rejected, so clients
+    /// can't send subgraph requests to arbitrary hosts.
+    pub allowed_values: Vec<String>,
+}
+
+#[derive(Debug, serde::Deserialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct SubgraphResponseTransformConfig {
+    /// JSONPath of the GraphQL response within the subgraph response, like `$.result`. Only
+    /// object keys separated by dots are supported.
+    pub unwrap: Option<String>,
+    /// Top-level keys of the response to rename, like `{ payload = "data" }`. Applied after
+    /// `unwrap`.
+    #[serde(default)]
+    pub rename: BTreeMap<String, String>,
+}
+
+#[derive(Debug, serde::Deserialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct SubgraphRequestCompressionConfig {
+    /// Whether request bodies should be gzip compressed. Only enable it for subgraphs accepting
+    /// `Content-Encoding: gzip` requests.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Bodies smaller than this size in bytes are sent uncompressed. Default: 1024.
+    pub threshold: Option<usize>,
 }
 
 #[derive(Debug, serde::Deserialize, Clone, Default)]
@@ -134,6 +673,12 @@ pub struct SubgraphRetryConfig {
     /// Whether mutations should be retried at all. False by default.
     #[serde(default)]
     pub retry_mutations: Option<bool>,
+    /// Maximum number of attempts, the first one included. Only bounded by the retry budget by default.
+    #[serde(default)]
+    pub max_attempts: Option<u32>,
+    /// Delay before the first retry, doubled for every subsequent one and randomized by a jitter. Default: 100ms.
+    #[serde(deserialize_with = "duration_str::deserialize_option_duration", default)]
+    pub base_delay: Option<Duration>,
 }
 
 #[derive(Clone, Debug, Default, serde::Deserialize)]
@@ -142,6 +687,53 @@ pub struct GraphConfig {
     pub path: Option<String>,
     #[serde(default)]
     pub introspection: bool,
+    /// If present, responses to queries only made of introspection fields are cached until the
+    /// schema changes, and are sent with this `Cache-Control` max age.
+    #[serde(deserialize_with = "duration_str::deserialize_option_duration", default)]
+    pub introspection_cache_max_age: Option<Duration>,
+    /// Serves the GraphQL explorer on GET requests from browsers without any query. Meant for
+    /// development, it should stay disabled in production.
+    #[serde(default)]
+    pub explorer: bool,
+    /// Rolls back to the previous schema if a newly deployed one fails too many requests right
+    /// after the swap. Disabled if not present.
+    pub rollback: Option<GraphRollbackConfig>,
+    /// How long to wait for further schema updates before rebuilding the gateway. Updates
+    /// arriving within that delay are coalesced, only the latest one being applied. Rebuilds
+    /// never run concurrently either way. No delay by default.
+    #[serde(deserialize_with = "duration_str::deserialize_option_duration", default)]
+    pub reload_debounce: Option<Duration>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GraphRollbackConfig {
+    /// Share of the responses within the window after a schema swap, greater than 0 and at
+    /// most 1, with gateway or subgraph failures triggering the rollback. Errors caused by the
+    /// requests themselves, invalid queries for example, aren't counted.
+    #[serde(deserialize_with = "deserialize_error_ratio")]
+    pub error_ratio: f64,
+    /// Responses needed within the window before the ratio is considered. Defaults to 20.
+    pub min_responses: Option<usize>,
+    /// How long a new schema is watched after the swap. Defaults to one minute.
+    #[serde(deserialize_with = "duration_str::deserialize_option_duration", default)]
+    pub window: Option<Duration>,
+}
+
+fn deserialize_error_ratio<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let ratio = <f64 as serde::Deserialize>::deserialize(deserializer)?;
+
+    // A ratio of zero would roll back on any response, failed or not.
+    if ratio.is_nan() || ratio <= 0.0 || ratio > 1.0 {
+        return Err(serde::de::Error::custom(
+            "error_ratio must be greater than 0 and at most 1",
+        ));
+    }
+
+    Ok(ratio)
 }
 
 #[derive(Clone, Debug, Default, serde::Deserialize)]
@@ -208,6 +800,18 @@ pub struct OperationLimitsConfig {
     /// every nested field adds 2 points, and every pagination argument multiplies
     /// the nested objects score by the number of records fetched.
     pub complexity: Option<u16>,
+    /// Limits how deeply fragment spreads can be nested within each other.
+    pub fragment_depth: Option<u16>,
+    /// Limits the total number of fragment spreads in an operation, counting
+    /// every spread each time its enclosing fragment is expanded.
+    pub fragment_spreads: Option<u16>,
+    /// Limits the total number of fields in an operation, counting every
+    /// occurrence including aliases and each expansion of a fragment.
+    pub fields: Option<u16>,
+    /// Limits the number of subgraph requests made for aliased fields, or
+    /// fields nested in them. Aliasing a field with different arguments
+    /// usually sends a request for each alias.
+    pub aliased_fetches: Option<u16>,
 }
 
 #[cfg(test)]
@@ -255,6 +859,9 @@ mod tests {
 
         assert!(!config.graph.introspection);
         assert_eq!(None, config.graph.path.as_deref());
+        assert_eq!(None, config.graph.introspection_cache_max_age);
+        assert_eq!(None, config.graph.rollback);
+        assert_eq!(None, config.graph.reload_debounce);
     }
 
     #[test]
@@ -263,12 +870,56 @@ mod tests {
             [graph]
             path = "/enterprise"
             introspection = true
+            introspection_cache_max_age = "1h"
+            reload_debounce = "2s"
         "#};
 
         let config: Config = toml::from_str(input).unwrap();
 
         assert!(config.graph.introspection);
         assert_eq!(Some("/enterprise"), config.graph.path.as_deref());
+        assert_eq!(
+            Some(Duration::from_secs(3600)),
+            config.graph.introspection_cache_max_age
+        );
+        assert_eq!(Some(Duration::from_secs(2)), config.graph.reload_debounce);
+    }
+
+    #[test]
+    fn graph_rollback() {
+        let input = indoc! {r#"
+            [graph.rollback]
+            error_ratio = 0.5
+            min_responses = 10
+            window = "30s"
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(
+            Some(GraphRollbackConfig {
+                error_ratio: 0.5,
+                min_responses: Some(10),
+                window: Some(Duration::from_secs(30)),
+            }),
+            config.graph.rollback
+        );
+    }
+
+    #[test]
+    fn graph_rollback_error_ratio_out_of_range() {
+        for ratio in ["0", "0.0", "-0.5", "1.5", "nan"] {
+            let input = format!("[graph.rollback]\nerror_ratio = {ratio}\n");
+
+            let error = toml::from_str::<Config>(&input).unwrap_err();
+
+            assert!(
+                error
+                    .to_string()
+                    .contains("error_ratio must be greater than 0 and at most 1"),
+                "{ratio}: {error}"
+            );
+        }
     }
 
     #[test]
@@ -592,6 +1243,10 @@ mod tests {
             aliases = 100
             root_fields = 10
             complexity = 1000
+            fragment_depth = 5
+            fragment_spreads = 50
+            fields = 500
+            aliased_fetches = 10
         "#};
 
         let config: Config = toml::from_str(input).unwrap();
@@ -603,6 +1258,10 @@ mod tests {
             aliases: Some(100),
             root_fields: Some(10),
             complexity: Some(1000),
+            fragment_depth: Some(5),
+            fragment_spreads: Some(50),
+            fields: Some(500),
+            aliased_fetches: Some(10),
         };
 
         assert_eq!(expected, operation_limits);
@@ -1318,6 +1977,8 @@ mod tests {
                     ),
                 ],
                 websocket_url: None,
+                shadow_subgraph: None,
+                shadow_danger_accept_invalid_certs: false,
                 rate_limit: None,
                 timeout: None,
                 retry: SubgraphRetryConfig {
@@ -1326,26 +1987,848 @@ mod tests {
                     ttl: None,
                     retry_percent: None,
                     retry_mutations: None,
+                    max_attempts: None,
+                    base_delay: None,
+                },
+                request_compression: SubgraphRequestCompressionConfig {
+                    enabled: false,
+                    threshold: None,
                 },
                 entity_caching: None,
+                subgraph_cache: None,
+                extra_fields: Ignore,
+                scalar_coercion: Strict,
+                null_data: Allow,
+                unknown_typename: Ignore,
+                type_mismatch: Error,
+                max_query_depth: None,
+                url_template: None,
+                danger_accept_invalid_certs: false,
+                entities_operation_prefix: None,
+                max_concurrent_requests: None,
+                concurrency_fairness: Fifo,
+                response_transform: None,
+                cost_weight: None,
+                entity_batch_size: None,
+                fields: {},
             },
         }
         "###);
     }
 
     #[test]
-    fn subgraph_ws_valid_url() {
+    fn batching_duplicate_operation_names() {
         let input = indoc! {r#"
-            [subgraphs.products]
-            websocket_url = "https://example.com"
+            [gateway.batching]
+            duplicate_operation_names = "disambiguate"
         "#};
 
         let result: Config = toml::from_str(input).unwrap();
-        let subgraph = result.subgraphs.get("products").unwrap();
 
-        insta::assert_debug_snapshot!(&subgraph.websocket_url.as_ref().map(|u| u.to_string()), @r###"
+        assert_eq!(
+            DuplicateOperationNames::Disambiguate,
+            result.gateway.batching.duplicate_operation_names
+        );
+    }
+
+    #[test]
+    fn gateway_non_finite_floats() {
+        let input = indoc! {r#"
+            [gateway]
+            non_finite_floats = "string"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(NonFiniteFloats::String, result.gateway.non_finite_floats);
+    }
+
+    #[test]
+    fn gateway_client_identification() {
+        let input = indoc! {r#"
+            [gateway.client_identification]
+            name_headers = ["apollographql-client-name", "user-agent"]
+            version_headers = ["apollographql-client-version"]
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&result.gateway.client_identification, @r###"
+        ClientIdentificationConfig {
+            name_headers: [
+                "apollographql-client-name",
+                "user-agent",
+            ],
+            version_headers: [
+                "apollographql-client-version",
+            ],
+        }
+        "###);
+    }
+
+    #[test]
+    fn gateway_tenants() {
+        let input = indoc! {r#"
+            [gateway.tenants]
+            header = "x-tenant-id"
+
+            [gateway.tenants.profiles.acme]
+            tags = ["public", "acme"]
+
+            [gateway.tenants.profiles.globex]
+            tags = ["public"]
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&result.gateway.tenants, @r###"
         Some(
-            "https://example.com/",
+            TenantsConfig {
+                header: "x-tenant-id",
+                profiles: {
+                    "acme": TenantProfileConfig {
+                        tags: [
+                            "public",
+                            "acme",
+                        ],
+                    },
+                    "globex": TenantProfileConfig {
+                        tags: [
+                            "public",
+                        ],
+                    },
+                },
+            },
+        )
+        "###);
+    }
+
+    #[test]
+    fn gateway_client_disconnect() {
+        let input = indoc! {r#"
+            [gateway]
+            client_disconnect = "complete"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(ClientDisconnectPolicy::Complete, result.gateway.client_disconnect);
+    }
+
+    #[test]
+    fn gateway_required_headers() {
+        let input = indoc! {r#"
+            [gateway]
+            required_headers = ["x-api-key", "X-Tenant-Id"]
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(vec!["x-api-key", "X-Tenant-Id"], result.gateway.required_headers);
+    }
+
+    #[test]
+    fn gateway_pretty_responses() {
+        let input = indoc! {r#"
+            [gateway]
+            pretty_responses = true
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert!(result.gateway.pretty_responses);
+    }
+
+    #[test]
+    fn gateway_max_serialization_time() {
+        let input = indoc! {r#"
+            [gateway]
+            max_serialization_time = "200ms"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(Some(Duration::from_millis(200)), result.gateway.max_serialization_time);
+    }
+
+    #[test]
+    fn gateway_max_parse_time() {
+        let input = indoc! {r#"
+            [gateway]
+            max_parse_time = "50ms"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(Some(Duration::from_millis(50)), result.gateway.max_parse_time);
+    }
+
+    #[test]
+    fn gateway_scalar_formats() {
+        let input = indoc! {r#"
+            [gateway.scalar_formats]
+            DateTime = "date-time"
+            UUID = "uuid"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        let expected = BTreeMap::from([
+            ("DateTime".to_string(), ScalarFormat::DateTime),
+            ("UUID".to_string(), ScalarFormat::Uuid),
+        ]);
+        assert_eq!(expected, result.gateway.scalar_formats);
+    }
+
+    #[test]
+    fn gateway_max_string_length() {
+        let input = indoc! {r#"
+            [gateway]
+            max_string_length = 1048576
+            oversized_strings = "error"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(Some(1048576), result.gateway.max_string_length);
+        assert_eq!(OversizedStringPolicy::Error, result.gateway.oversized_strings);
+    }
+
+    #[test]
+    fn gateway_incompatible_accept() {
+        let input = indoc! {r#"
+            [gateway]
+            incompatible_accept = "ignore"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(IncompatibleAcceptPolicy::Ignore, result.gateway.incompatible_accept);
+    }
+
+    #[test]
+    fn gateway_log_rejected_operations() {
+        let input = indoc! {r#"
+            [gateway]
+            log_rejected_operations = true
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert!(result.gateway.log_rejected_operations);
+    }
+
+    #[test]
+    fn gateway_max_entities_per_operation() {
+        let input = indoc! {r#"
+            [gateway]
+            max_entities_per_operation = 1000
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(Some(1000), result.gateway.max_entities_per_operation);
+    }
+
+    #[test]
+    fn gateway_max_variable_depth() {
+        let input = indoc! {r#"
+            [gateway]
+            max_variable_depth = 16
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(Some(16), result.gateway.max_variable_depth);
+    }
+
+    #[test]
+    fn gateway_operation_cache_limit() {
+        let input = indoc! {r#"
+            [gateway]
+            operation_cache_limit = 5000
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(Some(5000), result.gateway.operation_cache_limit);
+    }
+
+    #[test]
+    fn gateway_max_error_path_depth() {
+        let input = indoc! {r#"
+            [gateway]
+            max_error_path_depth = 32
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(Some(32), result.gateway.max_error_path_depth);
+    }
+
+    #[test]
+    fn subgraph_extra_fields_warn() {
+        let input = indoc! {r#"
+            [subgraphs.products]
+            extra_fields = "warn"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(ExtraFieldsPolicy::Warn, result.subgraphs["products"].extra_fields);
+    }
+
+    #[test]
+    fn subgraph_scalar_coercion_lenient() {
+        let input = indoc! {r#"
+            [subgraphs.products]
+            scalar_coercion = "lenient"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(ScalarCoercion::Lenient, result.subgraphs["products"].scalar_coercion);
+    }
+
+    #[test]
+    fn subgraph_null_data_error() {
+        let input = indoc! {r#"
+            [subgraphs.products]
+            null_data = "error"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(NullDataPolicy::Error, result.subgraphs["products"].null_data);
+    }
+
+    #[test]
+    fn subgraph_unknown_typename_error() {
+        let input = indoc! {r#"
+            [subgraphs.products]
+            unknown_typename = "error"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(
+            UnknownTypenamePolicy::Error,
+            result.subgraphs["products"].unknown_typename
+        );
+    }
+
+    #[test]
+    fn subgraph_type_mismatch_ignore() {
+        let input = indoc! {r#"
+            [subgraphs.products]
+            type_mismatch = "ignore"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(TypeMismatchPolicy::Ignore, result.subgraphs["products"].type_mismatch);
+    }
+
+    #[test]
+    fn subgraph_max_query_depth() {
+        let input = indoc! {r#"
+            [subgraphs.products]
+            max_query_depth = 5
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(Some(5), result.subgraphs["products"].max_query_depth);
+    }
+
+    #[test]
+    fn subgraph_danger_accept_invalid_certs() {
+        let input = indoc! {r#"
+            [subgraphs.products]
+            danger_accept_invalid_certs = true
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert!(result.subgraphs["products"].danger_accept_invalid_certs);
+        assert!(!result.subgraphs["products"].retry.enabled);
+    }
+
+    #[test]
+    fn subgraph_shadow_danger_accept_invalid_certs() {
+        let input = indoc! {r#"
+            [subgraphs.products]
+            shadow_subgraph = "https://shadow.example.com/graphql"
+            danger_accept_invalid_certs = true
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert!(!result.subgraphs["products"].shadow_danger_accept_invalid_certs);
+
+        let input = indoc! {r#"
+            [subgraphs.products]
+            shadow_subgraph = "https://shadow.example.com/graphql"
+            shadow_danger_accept_invalid_certs = true
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert!(result.subgraphs["products"].shadow_danger_accept_invalid_certs);
+        assert!(!result.subgraphs["products"].danger_accept_invalid_certs);
+    }
+
+    #[test]
+    fn subgraph_retry_attempts_and_delay() {
+        let input = indoc! {r#"
+            [subgraphs.products.retry]
+            enabled = true
+            ttl = "10s"
+            max_attempts = 3
+            base_delay = "50ms"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        let retry = &result.subgraphs["products"].retry;
+        assert_eq!(Some(3), retry.max_attempts);
+        assert_eq!(Some(Duration::from_millis(50)), retry.base_delay);
+    }
+
+    #[test]
+    fn subgraph_entities_operation_prefix() {
+        let input = indoc! {r#"
+            [subgraphs.reviews]
+            entities_operation_prefix = "GatewayEntities"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(
+            Some("GatewayEntities"),
+            result.subgraphs["reviews"].entities_operation_prefix.as_deref()
+        );
+    }
+
+    #[test]
+    fn subgraph_cost_weight() {
+        let input = indoc! {r#"
+            [subgraphs.products]
+            cost_weight = 2.5
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(Some(2.5), result.subgraphs["products"].cost_weight);
+    }
+
+    #[test]
+    fn subgraph_entity_batch_size() {
+        let input = indoc! {r#"
+            [subgraphs.products]
+            entity_batch_size = 50
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(Some(50), result.subgraphs["products"].entity_batch_size);
+    }
+
+    #[test]
+    fn subgraph_fair_concurrency() {
+        let input = indoc! {r#"
+            [subgraphs.products]
+            max_concurrent_requests = 16
+            concurrency_fairness = "fair"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(Some(16), result.subgraphs["products"].max_concurrent_requests);
+        assert_eq!(
+            ConcurrencyFairness::Fair,
+            result.subgraphs["products"].concurrency_fairness
+        );
+    }
+
+    #[test]
+    fn subgraph_response_transform() {
+        let input = indoc! {r#"
+            [subgraphs.legacy.response_transform]
+            unwrap = "$.result"
+            rename = { payload = "data", problems = "errors" }
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&result.subgraphs["legacy"].response_transform, @r###"
+        Some(
+            SubgraphResponseTransformConfig {
+                unwrap: Some(
+                    "$.result",
+                ),
+                rename: {
+                    "payload": "data",
+                    "problems": "errors",
+                },
+            },
+        )
+        "###);
+    }
+
+    #[test]
+    fn subgraph_cache() {
+        let input = indoc! {r#"
+            [subgraph_cache]
+            enabled = true
+
+            [subgraphs.products.subgraph_cache]
+            enabled = true
+            ttl = "30s"
+
+            [subgraphs.inventory.subgraph_cache]
+            enabled = false
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(
+            result.subgraph_cache,
+            SubgraphCacheConfig {
+                enabled: true,
+                ttl: None
+            }
+        );
+        assert_eq!(
+            result.subgraphs["products"].subgraph_cache,
+            Some(SubgraphCacheConfig {
+                enabled: true,
+                ttl: Some(Duration::from_secs(30))
+            })
+        );
+        assert_eq!(
+            result.subgraphs["inventory"].subgraph_cache,
+            Some(SubgraphCacheConfig {
+                enabled: false,
+                ttl: None
+            })
+        );
+    }
+
+    #[test]
+    fn response_cache() {
+        let input = indoc! {r#"
+            [response_cache]
+            enabled = true
+            ttl = "5m"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(
+            result.response_cache,
+            ResponseCacheConfig {
+                enabled: true,
+                ttl: Some(Duration::from_secs(300)),
+                bypass_headers: None,
+            }
+        );
+    }
+
+    #[test]
+    fn response_cache_bypass_headers() {
+        let input = indoc! {r#"
+            [response_cache]
+            enabled = true
+            bypass_headers = ["Authorization", "X-Api-Key"]
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(
+            result.response_cache.bypass_headers,
+            Some(vec!["Authorization".to_string(), "X-Api-Key".to_string()])
+        );
+    }
+
+    #[test]
+    fn shadow_gateway_headers() {
+        let input = indoc! {r#"
+            [gateway]
+            shadow_gateway = "https://canary/graphql"
+            shadow_gateway_headers = ["x-client-id"]
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(result.gateway.shadow_gateway_headers, vec!["x-client-id".to_string()]);
+    }
+
+    #[test]
+    fn subgraph_url_template() {
+        let input = indoc! {r#"
+            [subgraphs.reviews.url_template]
+            url = "https://{region}.reviews.internal/graphql"
+            variables.region = { header = "x-region", allowed_values = ["eu", "us"] }
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&result.subgraphs["reviews"].url_template, @r###"
+        Some(
+            SubgraphUrlTemplateConfig {
+                url: "https://{region}.reviews.internal/graphql",
+                variables: {
+                    "region": SubgraphUrlVariableConfig {
+                        header: "x-region",
+                        allowed_values: [
+                            "eu",
+                            "us",
+                        ],
+                    },
+                },
+            },
+        )
+        "###);
+    }
+
+    #[test]
+    fn subgraph_request_compression() {
+        let input = indoc! {r#"
+            [subgraphs.products.request_compression]
+            enabled = true
+            threshold = 4096
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&result.subgraphs["products"].request_compression, @r###"
+        SubgraphRequestCompressionConfig {
+            enabled: true,
+            threshold: Some(
+                4096,
+            ),
+        }
+        "###);
+    }
+
+    #[test]
+    fn subgraph_field_timeout() {
+        let input = indoc! {r#"
+            [subgraphs.products]
+            timeout = "5s"
+
+            [subgraphs.products.fields."Query.topProducts"]
+            timeout = "10s"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&result.subgraphs["products"].fields, @r###"
+        {
+            "Query.topProducts": SubgraphFieldConfig {
+                timeout: Some(
+                    10s,
+                ),
+            },
+        }
+        "###);
+    }
+
+    #[test]
+    fn client_allowed_operation_prefixes() {
+        let input = indoc! {r#"
+            [clients.mobile]
+            allowed_operation_prefixes = ["Mobile_", "Shared_"]
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&result.clients, @r###"
+        {
+            "mobile": ClientConfig {
+                allowed_operation_prefixes: Some(
+                    [
+                        "Mobile_",
+                        "Shared_",
+                    ],
+                ),
+                response_transforms: [],
+                max_subgraphs_per_operation: None,
+                cost_budget: None,
+                response_key_casing: None,
+                min_version: None,
+                max_request_body_bytes: None,
+            },
+        }
+        "###);
+    }
+
+    #[test]
+    fn client_min_version() {
+        let input = indoc! {r#"
+            [clients.ios]
+            min_version = "2.3.0"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(Some("2.3.0"), result.clients["ios"].min_version.as_deref());
+    }
+
+    #[test]
+    fn client_max_request_body_bytes() {
+        let input = indoc! {r#"
+            [gateway]
+            max_request_body_bytes = 1024
+
+            [clients.internal]
+            max_request_body_bytes = 1048576
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(Some(1024), result.gateway.max_request_body_bytes);
+        assert_eq!(Some(1048576), result.clients["internal"].max_request_body_bytes);
+    }
+
+    #[test]
+    fn client_cost_budget() {
+        let input = indoc! {r#"
+            [clients.partner]
+            cost_budget = { capacity = 1000, refill_per_second = 10 }
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(
+            result.clients["partner"].cost_budget,
+            Some(CostBudgetConfig {
+                capacity: 1000,
+                refill_per_second: 10
+            })
+        );
+    }
+
+    #[test]
+    fn client_response_key_casing() {
+        let input = indoc! {r#"
+            [clients.legacy]
+            response_key_casing = "snake_case"
+
+            [clients.modern]
+            response_key_casing = "camel_case"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(
+            result.clients["legacy"].response_key_casing,
+            Some(ResponseKeyCasing::SnakeCase)
+        );
+        assert_eq!(
+            result.clients["modern"].response_key_casing,
+            Some(ResponseKeyCasing::CamelCase)
+        );
+    }
+
+    #[test]
+    fn client_response_transforms() {
+        let input = indoc! {r#"
+            [clients.legacy]
+            response_transforms = [
+                { kind = "rename", path = "me.name", to = "fullName" },
+                { kind = "flatten", path = "me.address" },
+                { kind = "wrap", path = "me.id", key = "value" },
+            ]
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&result.clients["legacy"].response_transforms, @r###"
+        [
+            Rename {
+                path: "me.name",
+                to: "fullName",
+            },
+            Flatten {
+                path: "me.address",
+            },
+            Wrap {
+                path: "me.id",
+                key: "value",
+            },
+        ]
+        "###);
+    }
+
+    #[test]
+    fn admin_secret() {
+        let input = indoc! {r#"
+            [admin]
+            secret = "super-secret"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&result.admin, @r###"
+        Some(
+            AdminConfig {
+                secret: DynamicString(
+                    "super-secret",
+                ),
+                subgraph_health_window: None,
+            },
+        )
+        "###);
+    }
+
+    #[test]
+    fn admin_subgraph_health_window() {
+        let input = indoc! {r#"
+            [admin]
+            secret = "super-secret"
+            subgraph_health_window = "5m"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(
+            Some(Duration::from_secs(300)),
+            result.admin.unwrap().subgraph_health_window
+        );
+    }
+
+    #[test]
+    fn subgraph_ws_valid_url() {
+        let input = indoc! {r#"
+            [subgraphs.products]
+            websocket_url = "https://example.com"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+        let subgraph = result.subgraphs.get("products").unwrap();
+
+        insta::assert_debug_snapshot!(&subgraph.websocket_url.as_ref().map(|u| u.to_string()), @r###"
+        Some(
+            "https://example.com/",
+        )
+        "###);
+    }
+
+    #[test]
+    fn subgraph_shadow() {
+        let input = indoc! {r#"
+            [subgraphs.products]
+            shadow_subgraph = "https://shadow.example.com/graphql"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+        let subgraph = result.subgraphs.get("products").unwrap();
+
+        insta::assert_debug_snapshot!(&subgraph.shadow_subgraph.as_ref().map(|u| u.to_string()), @r###"
+        Some(
+            "https://shadow.example.com/graphql",
         )
         "###);
     }
@@ -7,4 +7,10 @@ pub struct MetricsConfig {
     /// Exporters configurations
     #[serde(default)]
     pub exporters: ExportersConfig,
+    /// Maximum number of distinct operation names used for the `gql.operation.name` attribute.
+    /// Further operations, and anonymous ones, are recorded as `other`.
+    pub max_tracked_operations: Option<usize>,
+    /// Operation names always recorded as-is. They count towards the maximum.
+    #[serde(default)]
+    pub tracked_operations: Vec<String>,
 }
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use super::ExportersConfig;
 
 use serde::de::Error as DeserializeError;
@@ -20,6 +22,12 @@ pub struct TracingConfig {
     /// Exporters configurations
     #[serde(default)]
     pub exporters: ExportersConfig,
+    /// Headers added to the subgraph requests for APM tools correlating traces with their own
+    /// headers, like Datadog. Values are templates with the `{trace_id}`, `{span_id}`,
+    /// `{trace_id_decimal}`, `{span_id_decimal}` and `{sampled}` placeholders, taken from the
+    /// subgraph request span.
+    #[serde(default)]
+    pub subgraph_headers: BTreeMap<String, String>,
 }
 
 impl Default for TracingConfig {
@@ -28,6 +36,7 @@ impl Default for TracingConfig {
             sampling: DEFAULT_SAMPLING,
             collect: Default::default(),
             exporters: Default::default(),
+            subgraph_headers: Default::default(),
         }
     }
 }
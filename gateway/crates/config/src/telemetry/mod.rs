@@ -174,6 +174,28 @@ mod tests {
         "###);
     }
 
+    #[test]
+    fn subgraph_headers() {
+        // prepare
+        let input = indoc! {r#"
+            [subgraph_headers]
+            x-datadog-trace-id = "{trace_id_decimal}"
+            x-datadog-parent-id = "{span_id_decimal}"
+        "#};
+
+        // act
+        let config: TracingConfig = toml::from_str(input).unwrap();
+
+        // assert
+        assert_eq!(
+            std::collections::BTreeMap::from([
+                ("x-datadog-parent-id".to_string(), "{span_id_decimal}".to_string()),
+                ("x-datadog-trace-id".to_string(), "{trace_id_decimal}".to_string()),
+            ]),
+            config.subgraph_headers
+        );
+    }
+
     #[test]
     fn custom_collect() {
         // prepare
@@ -860,6 +882,24 @@ mod tests {
         assert!(expected.is_some());
     }
 
+    #[test]
+    fn metrics_tracked_operations() {
+        let input = indoc! {r#"
+            service_name = "kekw"
+
+            [metrics]
+            max_tracked_operations = 100
+            tracked_operations = ["GetUser"]
+        "#};
+
+        let config: TelemetryConfig = toml::from_str(input).unwrap();
+        let metrics = config.metrics.unwrap();
+
+        assert_eq!(Some(100), metrics.max_tracked_operations);
+        assert_eq!(vec!["GetUser".to_string()], metrics.tracked_operations);
+        assert_eq!(None, config.metrics_stdout_config());
+    }
+
     #[test]
     fn logs_stdout_defaults() {
         let input = indoc! {r#"
@@ -1,3 +1,5 @@
+mod admin;
+mod body_limit;
 mod cors;
 mod csrf;
 mod engine;
@@ -6,7 +8,13 @@ mod graph_fetch_method;
 #[cfg(not(feature = "lambda"))]
 mod graph_updater;
 mod health;
+mod methods;
 mod otel;
+#[cfg(not(feature = "lambda"))]
+mod registry_updater;
+#[cfg(not(feature = "lambda"))]
+mod reloader;
+mod rollback;
 mod state;
 mod trusted_documents_client;
 
@@ -21,11 +29,14 @@ use axum::{routing::get, Router};
 use axum_server as _;
 use engine_v2_axum::websocket::{WebsocketAccepter, WebsocketService};
 use gateway_config::{Config, TlsConfig};
+use grafbase_telemetry::grafbase_client::ClientIdentification;
 use grafbase_telemetry::span::GRAFBASE_TARGET;
+use rollback::EngineSender;
 use state::ServerState;
 use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr},
     path::PathBuf,
+    sync::Arc,
     time::Duration,
 };
 use tokio::sync::mpsc;
@@ -80,13 +91,14 @@ pub async fn serve(
 
     let (sender, mut gateway) = watch::channel(None);
     gateway.mark_unchanged();
+    let sender = Arc::new(EngineSender::new(sender, config.graph.rollback.clone()));
 
     fetch_method
         .start(
             &config,
             config_hot_reload.then_some(config_path).flatten(),
             otel_reload,
-            sender,
+            sender.clone(),
         )
         .await?;
 
@@ -100,7 +112,18 @@ pub async fn serve(
         None => CorsLayer::permissive(),
     };
 
-    let state = ServerState::new(gateway.clone(), otel_tracer_provider);
+    let explorer_page = config
+        .graph
+        .explorer
+        .then(|| ::engine::http::pathfinder_source(::engine::http::PathfinderConfig::new(path)));
+
+    let state = ServerState::new(
+        gateway.clone(),
+        sender,
+        otel_tracer_provider,
+        explorer_page,
+        config.gateway.client_disconnect,
+    );
 
     // HACK: Wait for the engine to be ready. This ensures we did reload OTEL providers if necessary
     // as we need all resources attributes to be present before creating the tracing layer.
@@ -108,11 +131,15 @@ pub async fn serve(
     gateway.changed().await.ok();
 
     let mut router = Router::new()
-        .route(path, get(engine::get).post(engine::post))
+        .route(path, get(engine::get).post(engine::post).fallback(methods::not_allowed))
         .route_service("/ws", WebsocketService::new(websocket_sender))
-        .layer(grafbase_telemetry::tower::layer(
-            grafbase_telemetry::metrics::meter_from_global_provider(),
-        ))
+        .layer(
+            grafbase_telemetry::tower::layer(grafbase_telemetry::metrics::meter_from_global_provider())
+                .with_client_identification(ClientIdentification::new(
+                    &config.gateway.client_identification.name_headers,
+                    &config.gateway.client_identification.version_headers,
+                )),
+        )
         .layer(tower_http::timeout::RequestBodyTimeoutLayer::new(
             config.gateway.timeout.unwrap_or(DEFAULT_GATEWAY_TIMEOUT),
         ))
@@ -137,12 +164,18 @@ pub async fn serve(
         }
     }
 
-    let mut router = router.with_state(state);
+    if let Some(admin_config) = &config.admin {
+        router = admin::route(router, admin_config);
+    }
+
+    let mut router = methods::inject_layer(router.with_state(state), path);
 
     if config.csrf.enabled {
         router = csrf::inject_layer(router);
     }
 
+    router = body_limit::inject_layer(router, &config.gateway, &config.clients);
+
     bind(addr, path, router, config.tls.as_ref()).await?;
 
     Ok(())
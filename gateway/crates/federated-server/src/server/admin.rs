@@ -0,0 +1,100 @@
+use axum::{
+    extract::State,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use engine_v2::SubgraphHealth;
+use gateway_config::AdminConfig;
+use http::{header, HeaderMap, StatusCode};
+
+use super::state::ServerState;
+
+const SDL_PATH: &str = "/admin/sdl";
+const SUBGRAPHS_PATH: &str = "/admin/subgraphs";
+const SCHEMA_WARNINGS_PATH: &str = "/admin/schema-warnings";
+
+/// Adds the admin endpoints to the router. All of them require the configured secret as a bearer token.
+pub(super) fn route(router: Router<ServerState>, config: &AdminConfig) -> Router<ServerState> {
+    // Comparing blake3 hashes is done in constant time, which avoids leaking the secret through timing.
+    let secret = blake3::hash(config.secret.as_bytes());
+
+    router
+        .route(
+            SDL_PATH,
+            get(move |state: State<ServerState>, headers: HeaderMap| sdl(state, headers, secret)),
+        )
+        .route(
+            SUBGRAPHS_PATH,
+            get(move |state: State<ServerState>, headers: HeaderMap| subgraphs(state, headers, secret)),
+        )
+        .route(
+            SCHEMA_WARNINGS_PATH,
+            get(move |state: State<ServerState>, headers: HeaderMap| schema_warnings(state, headers, secret)),
+        )
+}
+
+async fn sdl(State(state): State<ServerState>, headers: HeaderMap, secret: blake3::Hash) -> Response {
+    if !is_authorized(&headers, &secret) {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+
+    let Some(engine) = state.gateway().borrow().clone() else {
+        return StatusCode::SERVICE_UNAVAILABLE.into_response();
+    };
+
+    match engine.sdl() {
+        Some(sdl) => ([(header::CONTENT_TYPE, "text/plain; charset=utf-8")], sdl.to_owned()).into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+#[derive(serde::Serialize)]
+struct SubgraphsResponse {
+    subgraphs: Vec<SubgraphHealth>,
+}
+
+/// Error rate and latency of each subgraph over the configured window.
+async fn subgraphs(State(state): State<ServerState>, headers: HeaderMap, secret: blake3::Hash) -> Response {
+    if !is_authorized(&headers, &secret) {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+
+    let Some(engine) = state.gateway().borrow().clone() else {
+        return StatusCode::SERVICE_UNAVAILABLE.into_response();
+    };
+
+    match engine.subgraph_health() {
+        Some(subgraphs) => Json(SubgraphsResponse { subgraphs }).into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+#[derive(serde::Serialize)]
+struct SchemaWarningsResponse<'a> {
+    warnings: &'a [String],
+}
+
+/// Parts of the configuration ignored when building the running schema.
+async fn schema_warnings(State(state): State<ServerState>, headers: HeaderMap, secret: blake3::Hash) -> Response {
+    if !is_authorized(&headers, &secret) {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+
+    let Some(engine) = state.gateway().borrow().clone() else {
+        return StatusCode::SERVICE_UNAVAILABLE.into_response();
+    };
+
+    Json(SchemaWarningsResponse {
+        warnings: engine.schema_warnings(),
+    })
+    .into_response()
+}
+
+fn is_authorized(headers: &HeaderMap, secret: &blake3::Hash) -> bool {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|token| blake3::hash(token.as_bytes()) == *secret)
+}
@@ -0,0 +1,100 @@
+//! Bounds the size of request bodies before they're parsed, with an optional larger limit for
+//! some clients. Oversized bodies get a GraphQL error with a `413` status rather than a bare one.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use axum::{
+    body::Body,
+    extract::{DefaultBodyLimit, Request},
+    middleware::{self, Next},
+    response::Response,
+    Router,
+};
+use gateway_config::{ClientConfig, GatewayConfig};
+use grafbase_telemetry::grafbase_client::ClientIdentification;
+use http::header;
+
+/// Same as the default limit of axum, which we replace.
+const DEFAULT_MAX_REQUEST_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+struct BodyLimits {
+    default: usize,
+    by_client: HashMap<String, usize>,
+    client_identification: ClientIdentification,
+}
+
+impl BodyLimits {
+    fn for_request(&self, request: &Request) -> usize {
+        self.client_identification
+            .extract_from(request.headers())
+            .and_then(|client| self.by_client.get(&client.name).copied())
+            .unwrap_or(self.default)
+    }
+}
+
+/// Nothing is added unless a limit was configured, leaving the default one of axum.
+pub(super) fn inject_layer(
+    router: Router,
+    gateway: &GatewayConfig,
+    clients: &BTreeMap<String, ClientConfig>,
+) -> Router {
+    let by_client = clients
+        .iter()
+        .filter_map(|(name, client)| Some((name.clone(), client.max_request_body_bytes?)))
+        .collect::<HashMap<_, _>>();
+
+    if gateway.max_request_body_bytes.is_none() && by_client.is_empty() {
+        return router;
+    }
+
+    let limits = Arc::new(BodyLimits {
+        default: gateway.max_request_body_bytes.unwrap_or(DEFAULT_MAX_REQUEST_BODY_BYTES),
+        by_client,
+        client_identification: ClientIdentification::new(
+            &gateway.client_identification.name_headers,
+            &gateway.client_identification.version_headers,
+        ),
+    });
+
+    router
+        .layer(middleware::from_fn(move |request: Request, next: Next| {
+            let limits = limits.clone();
+            async move { limit_body(&limits, request, next).await }
+        }))
+        // Bodies are already bounded by the middleware, with a limit which may be larger.
+        .layer(DefaultBodyLimit::disable())
+}
+
+async fn limit_body(limits: &BodyLimits, request: Request, next: Next) -> Response {
+    if request.method() != http::Method::POST {
+        return next.run(request).await;
+    }
+
+    let limit = limits.for_request(&request);
+
+    let content_length = request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<usize>().ok());
+    if content_length.is_some_and(|length| length > limit) {
+        return too_large(limit);
+    }
+
+    // Bodies sent without a length, or lying about it, are only known to be too large once read.
+    // Failing to read it for another reason means the client went away and won't see this
+    // response anyway.
+    let (parts, body) = request.into_parts();
+    let Ok(bytes) = axum::body::to_bytes(body, limit).await else {
+        return too_large(limit);
+    };
+
+    next.run(Request::from_parts(parts, Body::from(bytes))).await
+}
+
+fn too_large(limit: usize) -> Response {
+    engine_v2_axum::request_too_large(&format!("Request body exceeds the limit of {limit} bytes"))
+}
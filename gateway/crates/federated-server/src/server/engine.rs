@@ -1,20 +1,39 @@
-use super::{gateway::EngineWatcher, ServerState};
+use super::ServerState;
 use axum::{
     extract::{Query, State},
-    response::IntoResponse,
+    response::{Html, IntoResponse, Response},
     Json,
 };
 use engine::BatchRequest;
-use grafbase_telemetry::otel::opentelemetry_sdk::trace::TracerProvider;
-use http::HeaderMap;
+use gateway_config::ClientDisconnectPolicy;
+use http::{header, HeaderMap};
 
 pub(super) async fn get(
     Query(request): Query<engine::QueryParamRequest>,
     headers: HeaderMap,
     State(state): State<ServerState>,
-) -> impl IntoResponse {
-    let request = engine::BatchRequest::Single(request.into());
-    traced(headers, request, state.gateway().clone(), state.tracer_provider()).await
+) -> Response {
+    let request: engine::Request = request.into();
+
+    // Browsers opening the endpoint get the explorer instead of a GraphQL error.
+    if let Some(page) = state.explorer_page() {
+        if request.query.is_empty() && accepts_html(&headers) {
+            return Html(page.to_string()).into_response();
+        }
+    }
+
+    let request = engine::BatchRequest::Single(request);
+    traced(headers, request, &state).await.into_response()
+}
+
+fn accepts_html(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|media_range| media_range.split(';').next())
+        .any(|media_type| media_type.trim().eq_ignore_ascii_case("text/html"))
 }
 
 pub(super) async fn post(
@@ -22,24 +41,19 @@ pub(super) async fn post(
     headers: HeaderMap,
     Json(request): Json<engine::BatchRequest>,
 ) -> impl IntoResponse {
-    traced(headers, request, state.gateway().clone(), state.tracer_provider()).await
+    traced(headers, request, &state).await
 }
 
 #[cfg(feature = "lambda")]
-async fn traced(
-    headers: HeaderMap,
-    request: BatchRequest,
-    engine: EngineWatcher,
-    provider: Option<TracerProvider>,
-) -> impl IntoResponse {
-    let response = handle(headers, request, engine).await;
+async fn traced(headers: HeaderMap, request: BatchRequest, state: &ServerState) -> impl IntoResponse {
+    let response = handle(headers, request, state).await;
 
     // lambda must flush the trace events here, otherwise the
     // function might fall asleep and the events are pending until
     // the next wake-up.
     //
     // read more: https://github.com/open-telemetry/opentelemetry-lambda/blob/main/docs/design_proposal.md
-    if let Some(provider) = provider {
+    if let Some(provider) = state.tracer_provider() {
         for result in provider.force_flush() {
             if let Err(e) = result {
                 println!("error flushing events: {e}");
@@ -51,18 +65,31 @@ async fn traced(
 }
 
 #[cfg(not(feature = "lambda"))]
-async fn traced(
-    headers: HeaderMap,
-    request: BatchRequest,
-    engine: EngineWatcher,
-    _: Option<TracerProvider>,
-) -> impl IntoResponse {
-    handle(headers, request, engine).await
+async fn traced(headers: HeaderMap, request: BatchRequest, state: &ServerState) -> impl IntoResponse {
+    handle(headers, request, state).await
 }
 
-async fn handle(headers: HeaderMap, request: BatchRequest, engine: EngineWatcher) -> impl IntoResponse {
-    let Some(engine) = engine.borrow().clone() else {
-        return engine_v2_axum::internal_server_error("there are no subgraphs registered currently");
+/// If the client disconnects, axum drops this future and with it the execution, unless it was
+/// configured to complete anyway.
+async fn handle(headers: HeaderMap, request: BatchRequest, state: &ServerState) -> impl IntoResponse {
+    let Some(engine) = state.gateway().borrow().clone() else {
+        return engine_v2_axum::service_unavailable("Gateway not ready");
+    };
+    let response = match state.client_disconnect() {
+        ClientDisconnectPolicy::Cancel => engine.execute(headers, request).await,
+        ClientDisconnectPolicy::Complete => {
+            let engine = engine.clone();
+            match tokio::spawn(async move { engine.execute(headers, request).await }).await {
+                Ok(response) => response,
+                Err(err) => match err.try_into_panic() {
+                    Ok(panic) => std::panic::resume_unwind(panic),
+                    Err(_) => return engine_v2_axum::service_unavailable("Gateway shutting down"),
+                },
+            }
+        }
     };
-    engine_v2_axum::into_response(engine.execute(headers, request).await)
+    state
+        .engine_sender()
+        .record_response(&engine, response.metadata.has_execution_failures);
+    engine_v2_axum::into_response(response)
 }
@@ -1,5 +1,6 @@
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use runtime_local::rate_limiting::in_memory::key_based::InMemoryRateLimiter;
 use runtime_local::rate_limiting::redis::RedisRateLimiter;
@@ -12,12 +13,13 @@ use runtime_local::{ComponentLoader, HooksWasi, InMemoryKvStore};
 use runtime_noop::trusted_documents::NoopTrustedDocuments;
 
 use gateway_config::Config;
+use grafbase_telemetry::span::GRAFBASE_TARGET;
 
 use crate::hot_reload::ConfigWatcher;
 
 /// Send half of the gateway watch channel
 #[cfg(not(feature = "lambda"))]
-pub(crate) type GatewaySender = watch::Sender<Option<Arc<Engine<GatewayRuntime>>>>;
+pub(crate) type GatewaySender = Arc<super::rollback::EngineSender>;
 
 /// Receive half of the gateway watch channel.
 ///
@@ -34,6 +36,12 @@ pub(super) async fn generate(
     let schema_version = blake3::hash(federated_schema.as_bytes());
     let graph =
         FederatedGraph::from_sdl(federated_schema).map_err(|e| crate::Error::SchemaValidationError(e.to_string()))?;
+
+    let warnings = engine_config_builder::config_warnings(gateway_config, &graph);
+    for warning in &warnings {
+        tracing::warn!(target: GRAFBASE_TARGET, "{warning}");
+    }
+
     let config = engine_config_builder::build_with_toml_config(gateway_config, graph).into_latest();
 
     // TODO: https://linear.app/grafbase/issue/GB-6168/support-trusted-documents-in-air-gapped-mode
@@ -113,9 +121,22 @@ pub(super) async fn generate(
         .try_into()
         .map_err(|err| crate::Error::InternalError(format!("Failed to generate engine Schema: {err}")))?;
 
-    Ok(Engine::new(Arc::new(config), Some(schema_version.as_bytes()), runtime).await)
+    let mut engine = Engine::new(Arc::new(config), Some(schema_version.as_bytes()), runtime)
+        .await
+        .with_sdl(federated_schema)
+        .with_schema_warnings(warnings);
+
+    // Only the admin endpoints use the subgraph health.
+    if let Some(admin) = &gateway_config.admin {
+        engine = engine
+            .with_subgraph_health_tracking(admin.subgraph_health_window.unwrap_or(DEFAULT_SUBGRAPH_HEALTH_WINDOW));
+    }
+
+    Ok(engine)
 }
 
+const DEFAULT_SUBGRAPH_HEALTH_WINDOW: Duration = Duration::from_secs(60);
+
 pub struct GatewayRuntime {
     fetcher: runtime::fetch::Fetcher,
     trusted_documents: runtime::trusted_documents_client::Client,
@@ -155,4 +176,8 @@ impl engine_v2::Runtime for GatewayRuntime {
     fn sleep(&self, duration: std::time::Duration) -> futures_util::future::BoxFuture<'static, ()> {
         Box::pin(tokio::time::sleep(duration))
     }
+
+    fn spawn(&self, future: futures_util::future::BoxFuture<'static, ()>) {
+        tokio::spawn(future);
+    }
 }
@@ -1,9 +1,9 @@
-use super::gateway::{self, GatewayRuntime};
+use super::{gateway, rollback::EngineSender};
 use crate::OtelReload;
-use engine_v2::Engine;
 use gateway_config::Config;
-use std::{path::PathBuf, sync::Arc};
-use tokio::sync::{oneshot, watch};
+use std::{path::PathBuf, sync::Arc, time::Duration};
+use tokio::sync::oneshot;
+use url::Url;
 
 /// The method of running the gateway.
 pub enum GraphFetchMethod {
@@ -21,6 +21,14 @@ pub enum GraphFetchMethod {
         /// Static federated graph from a file
         federated_schema: String,
     },
+    /// The schema is fetched in regular intervals from a schema registry, responding with the
+    /// federated SDL.
+    FromRegistry {
+        /// The URL of the latest federated SDL
+        url: Url,
+        /// How often the registry is polled for updates
+        poll_interval: Duration,
+    },
 }
 
 impl GraphFetchMethod {
@@ -34,7 +42,7 @@ impl GraphFetchMethod {
         config: &Config,
         hot_reload_config_path: Option<PathBuf>,
         otel_reload: Option<(oneshot::Sender<OtelReload>, oneshot::Receiver<()>)>,
-        sender: watch::Sender<Option<Arc<Engine<GatewayRuntime>>>>,
+        sender: Arc<EngineSender>,
     ) -> crate::Result<()> {
         match self {
             GraphFetchMethod::FromApi {
@@ -61,10 +69,21 @@ impl GraphFetchMethod {
                     Ok::<_, crate::Error>(())
                 });
             }
+            GraphFetchMethod::FromRegistry { url, poll_interval } => {
+                let config = config.clone();
+                #[cfg(not(feature = "lambda"))]
+                tokio::spawn(async move {
+                    use super::registry_updater::RegistryUpdater;
+
+                    RegistryUpdater::new(url, poll_interval, sender, config)?.poll().await;
+
+                    Ok::<_, crate::Error>(())
+                });
+            }
             GraphFetchMethod::FromLocal { federated_schema } => {
                 let gateway = gateway::generate(&federated_schema, None, config, hot_reload_config_path).await?;
 
-                sender.send(Some(Arc::new(gateway)))?;
+                sender.send(Arc::new(gateway))?;
             }
         }
 
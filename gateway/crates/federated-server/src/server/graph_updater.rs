@@ -1,6 +1,6 @@
 use std::{borrow::Cow, sync::Arc, time::Duration};
 
-use super::gateway::GatewaySender;
+use super::{gateway::GatewaySender, reloader::SchemaReloader};
 use crate::OtelReload;
 use ascii::AsciiString;
 use gateway_config::Config;
@@ -43,9 +43,8 @@ pub(super) struct GraphUpdater {
     gdn_url: Url,
     gdn_client: reqwest::Client,
     access_token: AsciiString,
-    sender: GatewaySender,
+    reloader: SchemaReloader<(String, Ulid)>,
     current_id: Option<Ulid>,
-    gateway_config: Config,
     otel_reload: Option<(oneshot::Sender<OtelReload>, oneshot::Receiver<()>)>,
 }
 
@@ -82,13 +81,18 @@ impl GraphUpdater {
             .parse::<Url>()
             .map_err(|e| crate::Error::InternalError(e.to_string()))?;
 
+        let debounce = gateway_config.graph.reload_debounce.unwrap_or_default();
+        let reloader = SchemaReloader::spawn(debounce, {
+            let gateway_config = Arc::new(gateway_config);
+            move |(sdl, branch_id): (String, Ulid)| rebuild(sdl, branch_id, sender.clone(), gateway_config.clone())
+        });
+
         Ok(Self {
             gdn_url,
             gdn_client,
             access_token,
-            sender,
+            reloader,
             current_id: None,
-            gateway_config,
             otel_reload,
         })
     }
@@ -180,27 +184,20 @@ impl GraphUpdater {
                 ack_receiver.await.ok();
             }
 
-            let gateway = match super::gateway::generate(
-                &response.sdl,
-                Some(response.branch_id),
-                &self.gateway_config,
-                None,
-            )
-            .await
-            {
-                Ok(gateway) => gateway,
-                Err(e) => {
-                    tracing::event!(target: GRAFBASE_TARGET, Level::ERROR, message = "error parsing graph", error = e.to_string());
-
-                    continue;
-                }
-            };
-
             self.current_id = Some(response.version_id);
 
-            self.sender
-                .send(Some(Arc::new(gateway)))
-                .expect("internal error: channel closed");
+            self.reloader.reload((response.sdl, response.branch_id));
+        }
+    }
+}
+
+async fn rebuild(sdl: String, branch_id: Ulid, sender: GatewaySender, gateway_config: Arc<Config>) {
+    match super::gateway::generate(&sdl, Some(branch_id), &gateway_config, None).await {
+        Ok(gateway) => {
+            sender.send(Arc::new(gateway)).expect("internal error: channel closed");
+        }
+        Err(e) => {
+            tracing::event!(target: GRAFBASE_TARGET, Level::ERROR, message = "error parsing graph", error = e.to_string());
         }
     }
 }
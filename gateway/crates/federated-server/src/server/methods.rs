@@ -0,0 +1,50 @@
+//! Explicit handling of the HTTP methods of the GraphQL endpoint. Only `GET`, `POST` and `OPTIONS`
+//! are supported, and responses to `OPTIONS` requests and to unsupported methods carry an `Allow`
+//! header listing them, as strict browser environments and security scanners expect.
+//!
+//! `OPTIONS` requests are all answered by the CORS layer with the configured allowed methods and
+//! headers, we only add the `Allow` header to its responses.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::Request,
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    Router,
+};
+use http::{header, HeaderValue, StatusCode};
+
+const ALLOWED_METHODS: &str = "GET, POST, OPTIONS";
+
+/// Any other method, such as `PUT` or `DELETE`.
+pub(super) async fn not_allowed() -> impl IntoResponse {
+    (
+        StatusCode::METHOD_NOT_ALLOWED,
+        [(header::ALLOW, HeaderValue::from_static(ALLOWED_METHODS))],
+    )
+}
+
+/// Adds the `Allow` header to the responses of `OPTIONS` requests to the GraphQL endpoint.
+pub(super) fn inject_layer(router: Router, path: &str) -> Router {
+    let path: Arc<str> = path.into();
+
+    router.layer(middleware::from_fn(move |request: Request, next: Next| {
+        let path = path.clone();
+        async move { allow_header(&path, request, next).await }
+    }))
+}
+
+async fn allow_header(path: &str, request: Request, next: Next) -> Response {
+    let is_graphql_options = request.method() == http::Method::OPTIONS && request.uri().path() == path;
+    let mut response = next.run(request).await;
+
+    if is_graphql_options {
+        response
+            .headers_mut()
+            .entry(header::ALLOW)
+            .or_insert(HeaderValue::from_static(ALLOWED_METHODS));
+    }
+
+    response
+}
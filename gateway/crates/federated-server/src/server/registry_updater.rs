@@ -0,0 +1,145 @@
+use std::{sync::Arc, time::Duration};
+
+use super::{gateway::GatewaySender, reloader::SchemaReloader};
+use gateway_config::Config;
+use grafbase_telemetry::{metrics::RegistryMetrics, span::GRAFBASE_TARGET};
+use http::{header, HeaderValue, StatusCode};
+use tokio::time::MissedTickBehavior;
+use tracing::Level;
+use url::Url;
+
+/// How long we wait for a response from the schema registry.
+const REGISTRY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long we wait until a connection is successfully opened.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The HTTP user-agent header we sent to the schema registry.
+const USER_AGENT: &str = "grafbase-gateway";
+
+/// An updater thread polling a schema registry for new versions of the federated SDL.
+pub(super) struct RegistryUpdater {
+    url: Url,
+    poll_interval: Duration,
+    client: reqwest::Client,
+    reloader: SchemaReloader<String>,
+    metrics: RegistryMetrics,
+    /// ETag of the running schema, if the registry provided one.
+    current_etag: Option<HeaderValue>,
+    /// Hash of the running schema, for registries without ETag support.
+    current_version: Option<blake3::Hash>,
+}
+
+impl RegistryUpdater {
+    pub fn new(
+        url: Url,
+        poll_interval: Duration,
+        sender: GatewaySender,
+        gateway_config: Config,
+    ) -> crate::Result<Self> {
+        let client = reqwest::ClientBuilder::new()
+            .timeout(REGISTRY_TIMEOUT)
+            .connect_timeout(CONNECT_TIMEOUT)
+            .user_agent(USER_AGENT)
+            .build()
+            .map_err(|e| crate::Error::InternalError(e.to_string()))?;
+
+        let metrics = RegistryMetrics::build(&grafbase_telemetry::metrics::meter_from_global_provider());
+        let debounce = gateway_config.graph.reload_debounce.unwrap_or_default();
+        let reloader = SchemaReloader::spawn(debounce, {
+            let gateway_config = Arc::new(gateway_config);
+            let metrics = metrics.clone();
+            move |sdl: String| rebuild(sdl, sender.clone(), gateway_config.clone(), metrics.clone())
+        });
+
+        Ok(Self {
+            url,
+            poll_interval,
+            client,
+            reloader,
+            metrics,
+            current_etag: None,
+            current_version: None,
+        })
+    }
+
+    /// A poll loop fetching the latest federated SDL from the registry. When started, fetches
+    /// the SDL immediately and after that at every poll interval. If the SDL changed, it's handed
+    /// over to the reloader and, if valid, a new gateway replaces the running one. On any failure
+    /// the running gateway is kept.
+    pub async fn poll(&mut self) {
+        let mut interval = tokio::time::interval(self.poll_interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        loop {
+            interval.tick().await;
+
+            if let Err(reason) = self.update().await {
+                self.metrics.record_update_failure(reason);
+            }
+        }
+    }
+
+    async fn update(&mut self) -> Result<(), &'static str> {
+        let mut request = self.client.get(self.url.as_str());
+
+        if let Some(ref etag) = self.current_etag {
+            request = request.header(header::IF_NONE_MATCH, etag);
+        }
+
+        let response = request.send().await.map_err(|e| {
+            tracing::event!(target: GRAFBASE_TARGET, Level::ERROR, message = "error fetching graph from the registry", error = e.to_string());
+            "request"
+        })?;
+
+        if response.status() == StatusCode::NOT_MODIFIED {
+            tracing::debug!(target: GRAFBASE_TARGET, "no updates to the graph");
+            return Ok(());
+        }
+
+        if let Err(e) = response.error_for_status_ref() {
+            tracing::event!(target: GRAFBASE_TARGET, Level::ERROR, message = "error fetching graph from the registry", error = e.to_string());
+            return Err("status");
+        }
+
+        let etag = response.headers().get(header::ETAG).cloned();
+
+        let sdl = response.text().await.map_err(|e| {
+            tracing::event!(target: GRAFBASE_TARGET, Level::ERROR, message = "error fetching graph from the registry", error = e.to_string());
+            "request"
+        })?;
+
+        let version = blake3::hash(sdl.as_bytes());
+
+        if self.current_version == Some(version) {
+            tracing::debug!(target: GRAFBASE_TARGET, "no updates to the graph");
+            self.current_etag = etag;
+            return Ok(());
+        }
+
+        tracing::event!(
+            target: GRAFBASE_TARGET,
+            Level::INFO,
+            message = "Graph fetched from the registry",
+        );
+
+        self.current_etag = etag;
+        self.current_version = Some(version);
+
+        self.reloader.reload(sdl);
+
+        Ok(())
+    }
+}
+
+async fn rebuild(sdl: String, sender: GatewaySender, gateway_config: Arc<Config>, metrics: RegistryMetrics) {
+    match super::gateway::generate(&sdl, None, &gateway_config, None).await {
+        Ok(gateway) => {
+            sender.send(Arc::new(gateway)).expect("internal error: channel closed");
+        }
+        Err(e) => {
+            tracing::event!(target: GRAFBASE_TARGET, Level::ERROR, message = "error parsing graph", error = e.to_string());
+            metrics.record_update_failure("invalid_schema");
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use std::{future::Future, time::Duration};
+
+use tokio::sync::watch;
+
+/// Rebuilds the gateway from schema updates in a single background task, so that only one
+/// rebuild runs at a time.
+///
+/// Updates arriving in quick succession are coalesced: the task waits until no new update came
+/// in for the debounce duration, and then only builds the latest one.
+pub(super) struct SchemaReloader<T> {
+    sender: watch::Sender<Option<T>>,
+}
+
+impl<T> SchemaReloader<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    pub fn spawn<F, Fut>(debounce: Duration, mut rebuild: F) -> Self
+    where
+        F: FnMut(T) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send,
+    {
+        let (sender, mut receiver) = watch::channel(None);
+
+        tokio::spawn(async move {
+            while receiver.changed().await.is_ok() {
+                // The timer restarts with every new update.
+                while let Ok(Ok(())) = tokio::time::timeout(debounce, receiver.changed()).await {}
+
+                let Some(update) = receiver.borrow_and_update().clone() else {
+                    continue;
+                };
+
+                rebuild(update).await;
+            }
+        });
+
+        Self { sender }
+    }
+
+    /// Schedules a rebuild, replacing any pending update.
+    pub fn reload(&self, update: T) {
+        self.sender.send_replace(Some(update));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use super::SchemaReloader;
+
+    #[tokio::test(start_paused = true)]
+    async fn rapid_updates_trigger_a_single_rebuild_to_the_latest_schema() {
+        let rebuilds = Arc::new(Mutex::new(Vec::new()));
+
+        let reloader = SchemaReloader::spawn(Duration::from_millis(500), {
+            let rebuilds = rebuilds.clone();
+            move |sdl: String| {
+                rebuilds.lock().unwrap().push(sdl);
+                async {}
+            }
+        });
+
+        for version in 1..=5 {
+            reloader.reload(format!("v{version}"));
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        assert_eq!(*rebuilds.lock().unwrap(), ["v5"]);
+    }
+}
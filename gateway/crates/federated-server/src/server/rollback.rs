@@ -0,0 +1,127 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use engine_v2::Engine;
+use gateway_config::GraphRollbackConfig;
+use grafbase_telemetry::{metrics::RegistryMetrics, span::GRAFBASE_TARGET};
+use tokio::sync::watch;
+use tracing::Level;
+
+use super::gateway::GatewayRuntime;
+
+const DEFAULT_ROLLBACK_WINDOW: Duration = Duration::from_secs(60);
+const DEFAULT_MIN_RESPONSES: usize = 20;
+
+/// Sends new engines to the gateway watch channel.
+///
+/// With rollback enabled, the replaced engine is kept for a while after each swap. If too large
+/// a share of the responses of the new one have execution failures during that window, the
+/// previous engine is restored. Only failures of the gateway or the subgraphs are counted, so
+/// that clients sending invalid requests can't trigger a rollback.
+pub(crate) struct EngineSender {
+    sender: watch::Sender<Option<Arc<Engine<GatewayRuntime>>>>,
+    rollback: Option<GraphRollbackConfig>,
+    probation: Mutex<Option<Probation>>,
+    metrics: RegistryMetrics,
+}
+
+/// A freshly swapped engine, being watched for errors.
+struct Probation {
+    engine: Arc<Engine<GatewayRuntime>>,
+    previous: Arc<Engine<GatewayRuntime>>,
+    swapped_at: Instant,
+    responses: usize,
+    failures: usize,
+}
+
+impl EngineSender {
+    pub fn new(
+        sender: watch::Sender<Option<Arc<Engine<GatewayRuntime>>>>,
+        rollback: Option<GraphRollbackConfig>,
+    ) -> Self {
+        Self {
+            sender,
+            rollback,
+            probation: Mutex::new(None),
+            metrics: RegistryMetrics::build(&grafbase_telemetry::metrics::meter_from_global_provider()),
+        }
+    }
+
+    /// Replaces the running engine.
+    pub fn send(&self, engine: Arc<Engine<GatewayRuntime>>) -> crate::Result<()> {
+        let mut probation = self.probation.lock().unwrap();
+        let previous = self.sender.borrow().clone();
+
+        self.sender.send(Some(Arc::clone(&engine)))?;
+
+        if self.rollback.is_some() {
+            *probation = previous.map(|previous| Probation {
+                engine,
+                previous,
+                swapped_at: Instant::now(),
+                responses: 0,
+                failures: 0,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Records a response produced by `engine`, rolling back to the previous engine if it's
+    /// still watched and the share of failed responses reached the configured ratio.
+    pub fn record_response(&self, engine: &Arc<Engine<GatewayRuntime>>, has_execution_failures: bool) {
+        let Some(config) = &self.rollback else {
+            return;
+        };
+
+        let mut probation = self.probation.lock().unwrap();
+
+        let Some(current) = probation.as_mut() else {
+            return;
+        };
+
+        if current.swapped_at.elapsed() >= config.window.unwrap_or(DEFAULT_ROLLBACK_WINDOW) {
+            // Past the window the new engine is considered healthy, no need to keep the old one.
+            *probation = None;
+            return;
+        }
+
+        if !Arc::ptr_eq(&current.engine, engine) {
+            return;
+        }
+
+        current.responses += 1;
+        if has_execution_failures {
+            current.failures += 1;
+        }
+
+        if current.responses < config.min_responses.unwrap_or(DEFAULT_MIN_RESPONSES)
+            || (current.failures as f64) < config.error_ratio * current.responses as f64
+        {
+            return;
+        }
+
+        let Some(Probation {
+            previous,
+            responses,
+            failures,
+            ..
+        }) = probation.take()
+        else {
+            return;
+        };
+
+        tracing::event!(
+            target: GRAFBASE_TARGET,
+            Level::ERROR,
+            message = "rolling back to the previous graph",
+            responses,
+            failures,
+        );
+
+        self.metrics.record_rollback();
+        self.sender.send_replace(Some(previous));
+    }
+}
@@ -1,13 +1,17 @@
+use gateway_config::ClientDisconnectPolicy;
 use std::sync::Arc;
 use tokio::sync::watch;
 
 use grafbase_telemetry::otel::opentelemetry_sdk::trace::TracerProvider;
 
-use super::gateway::EngineWatcher;
+use super::{gateway::EngineWatcher, rollback::EngineSender};
 
 struct ServerStateInner {
     gateway: EngineWatcher,
+    engine_sender: Arc<EngineSender>,
     tracer_provider: Option<watch::Receiver<TracerProvider>>,
+    explorer_page: Option<String>,
+    client_disconnect: ClientDisconnectPolicy,
 }
 
 #[derive(Clone)]
@@ -16,11 +20,20 @@ pub(super) struct ServerState {
 }
 
 impl ServerState {
-    pub(super) fn new(gateway: EngineWatcher, tracer_provider: Option<watch::Receiver<TracerProvider>>) -> Self {
+    pub(super) fn new(
+        gateway: EngineWatcher,
+        engine_sender: Arc<EngineSender>,
+        tracer_provider: Option<watch::Receiver<TracerProvider>>,
+        explorer_page: Option<String>,
+        client_disconnect: ClientDisconnectPolicy,
+    ) -> Self {
         Self {
             inner: Arc::new(ServerStateInner {
                 gateway,
+                engine_sender,
                 tracer_provider,
+                explorer_page,
+                client_disconnect,
             }),
         }
     }
@@ -29,6 +42,19 @@ impl ServerState {
         &self.inner.gateway
     }
 
+    pub(crate) fn engine_sender(&self) -> &EngineSender {
+        &self.inner.engine_sender
+    }
+
+    /// The GraphQL explorer HTML page, if enabled.
+    pub(crate) fn explorer_page(&self) -> Option<&str> {
+        self.inner.explorer_page.as_deref()
+    }
+
+    pub(crate) fn client_disconnect(&self) -> ClientDisconnectPolicy {
+        self.inner.client_disconnect
+    }
+
     pub(crate) fn tracer_provider(&self) -> Option<TracerProvider> {
         // notes on the clone:
         // - avoid long borrows that could block the producer
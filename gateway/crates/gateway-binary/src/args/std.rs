@@ -2,6 +2,7 @@ use std::{
     fs,
     net::SocketAddr,
     path::{Path, PathBuf},
+    time::Duration,
 };
 
 use anyhow::Context;
@@ -16,6 +17,7 @@ use grafbase_telemetry::{
 use graph_ref::GraphRef;
 use tracing::Subscriber;
 use tracing_subscriber::{registry::LookupSpan, Layer};
+use url::Url;
 
 use super::{log::LogStyle, LogLevel};
 
@@ -24,7 +26,7 @@ use super::{log::LogStyle, LogLevel};
     group(
         ArgGroup::new("hybrid-or-airgapped")
             .required(true)
-            .args(["graph_ref", "schema"])
+            .args(["graph_ref", "schema", "registry_url"])
     ),
     group(
         ArgGroup::new("graph-ref-with-access-token")
@@ -51,6 +53,18 @@ pub struct Args {
     /// to the Grafbase API.
     #[arg(long, short, env = "GRAFBASE_SCHEMA_PATH")]
     pub schema: Option<PathBuf>,
+    /// URL of a schema registry returning the federated SDL. It is polled for updates, and the
+    /// graph is reloaded whenever it changes.
+    #[arg(long, env = "GRAFBASE_REGISTRY_URL")]
+    pub registry_url: Option<Url>,
+    /// How often the schema registry is polled for updates, in seconds. At least one.
+    #[arg(
+        long,
+        env = "GRAFBASE_REGISTRY_POLL_INTERVAL",
+        default_value_t = 10,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub registry_poll_interval: u64,
     /// Set the logging level
     #[arg(long = "log", env = "GRAFBASE_LOG")]
     pub log_level: Option<LogLevel>,
@@ -65,6 +79,13 @@ pub struct Args {
 impl super::Args for Args {
     /// The method of fetching a graph
     fn fetch_method(&self) -> anyhow::Result<GraphFetchMethod> {
+        if let Some(ref url) = self.registry_url {
+            return Ok(GraphFetchMethod::FromRegistry {
+                url: url.clone(),
+                poll_interval: Duration::from_secs(self.registry_poll_interval),
+            });
+        }
+
         match self.graph_ref.as_ref() {
             Some(graph_ref) => Ok(GraphFetchMethod::FromApi {
                 access_token: self
@@ -75,9 +96,12 @@ impl super::Args for Args {
                 branch: graph_ref.branch().map(ToString::to_string),
             }),
             None => {
-                let federated_graph =
-                    fs::read_to_string(self.schema.as_ref().expect("must exist if graph-ref is not defined"))
-                        .context("could not read federated schema file")?;
+                let federated_graph = fs::read_to_string(
+                    self.schema
+                        .as_ref()
+                        .expect("must exist if neither graph-ref nor registry-url are defined"),
+                )
+                .context("could not read federated schema file")?;
 
                 Ok(GraphFetchMethod::FromLocal {
                     federated_schema: federated_graph,
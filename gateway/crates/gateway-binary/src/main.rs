@@ -117,7 +117,7 @@ fn init_global_tracing(args: &impl Args, config: Option<TelemetryConfig>) -> any
 
     let filter = args.log_level().map(|l| l.as_filter_str()).unwrap_or("info");
     let env_filter = EnvFilter::new(filter);
-    let will_reload_otel = !matches!(args.fetch_method()?, GraphFetchMethod::FromLocal { .. });
+    let will_reload_otel = matches!(args.fetch_method()?, GraphFetchMethod::FromApi { .. });
 
     let ReloadableOtelLayers {
         tracer,
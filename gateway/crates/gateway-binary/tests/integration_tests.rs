@@ -446,6 +446,7 @@ fn static_schema() {
                 "me"
               ],
               "extensions": {
+                "retryable": true,
                 "code": "SUBGRAPH_REQUEST_ERROR"
               }
             }
@@ -568,6 +569,7 @@ fn custom_path() {
                 "me"
               ],
               "extensions": {
+                "retryable": true,
                 "code": "SUBGRAPH_REQUEST_ERROR"
               }
             }
@@ -633,6 +635,7 @@ fn csrf_with_header() {
                 "me"
               ],
               "extensions": {
+                "retryable": true,
                 "code": "SUBGRAPH_REQUEST_ERROR"
               }
             }
@@ -642,6 +645,177 @@ fn csrf_with_header() {
     })
 }
 
+#[test]
+fn options_preflight() {
+    let config = indoc! {r#"
+        [cors]
+        allow_origins = ["https://example.com"]
+        allow_methods = ["GET", "POST"]
+        allow_headers = ["content-type"]
+    "#};
+
+    let schema = load_schema("big");
+
+    with_static_server(config, &schema, None, None, |client| async move {
+        let response = client
+            .client()
+            .request(http::Method::OPTIONS, client.endpoint())
+            .header(http::header::ORIGIN, "https://example.com")
+            .header(http::header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+            .header(http::header::ACCESS_CONTROL_REQUEST_HEADERS, "content-type")
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(http::StatusCode::OK, response.status());
+
+        let headers = response.headers();
+        assert_eq!(headers[http::header::ALLOW], "GET, POST, OPTIONS");
+        assert_eq!(headers[http::header::ACCESS_CONTROL_ALLOW_METHODS], "GET,POST");
+        assert_eq!(headers[http::header::ACCESS_CONTROL_ALLOW_HEADERS], "content-type");
+        assert_eq!(
+            headers[http::header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://example.com"
+        );
+    })
+}
+
+#[test]
+fn disallowed_method() {
+    let schema = load_schema("big");
+
+    with_static_server("", &schema, None, None, |client| async move {
+        for method in [http::Method::PUT, http::Method::DELETE] {
+            let response = client.client().request(method, client.endpoint()).send().await.unwrap();
+
+            assert_eq!(http::StatusCode::METHOD_NOT_ALLOWED, response.status());
+            assert_eq!(response.headers()[http::header::ALLOW], "GET, POST, OPTIONS");
+        }
+    })
+}
+
+#[test]
+fn request_body_limit() {
+    let config = indoc! {r#"
+        [gateway]
+        max_request_body_bytes = 1024
+
+        [clients.internal]
+        max_request_body_bytes = 1048576
+    "#};
+
+    let schema = load_schema("big");
+
+    with_static_server(config, &schema, None, None, |client| async move {
+        // Padded with a comment, ignored by GraphQL.
+        let body = serde_json::json!({"query": format!("query {{ __typename }} # {}", "a".repeat(4096))});
+
+        let response = client
+            .client()
+            .post(client.endpoint())
+            .json(&body)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(http::StatusCode::PAYLOAD_TOO_LARGE, response.status());
+
+        let response: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(response["errors"][0]["extensions"]["code"], "REQUEST_TOO_LARGE");
+
+        let response = client
+            .client()
+            .post(client.endpoint())
+            .header("x-grafbase-client-name", "internal")
+            .json(&body)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(http::StatusCode::OK, response.status());
+
+        let response: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(response["data"]["__typename"], "Query");
+    })
+}
+
+#[test]
+fn explorer() {
+    let config = indoc! {r#"
+        [graph]
+        explorer = true
+    "#};
+
+    let schema = load_schema("big");
+
+    with_static_server(config, &schema, None, None, |client| async move {
+        let html = "text/html,application/xhtml+xml;q=0.9,*/*;q=0.8";
+
+        let response = client
+            .client()
+            .get(client.endpoint())
+            .header(http::header::ACCEPT, html)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(http::StatusCode::OK, response.status());
+        assert!(response.headers()[http::header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .starts_with("text/html"));
+        assert!(response
+            .text()
+            .await
+            .unwrap()
+            .contains("window.GRAPHQL_URL = '/graphql'"));
+
+        // Requests with a query are executed as usual.
+        let response = client
+            .client()
+            .get(client.endpoint())
+            .query(&[("query", "{ __typename }")])
+            .header(http::header::ACCEPT, html)
+            .send()
+            .await
+            .unwrap();
+
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body, serde_json::json!({ "data": { "__typename": "Query" } }));
+
+        // So are requests not accepting HTML.
+        let response = client
+            .client()
+            .get(client.endpoint())
+            .header(http::header::ACCEPT, "application/json")
+            .send()
+            .await
+            .unwrap();
+
+        assert!(response.headers()[http::header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .starts_with("application/json"));
+    })
+}
+
+#[test]
+fn explorer_disabled_by_default() {
+    let schema = load_schema("big");
+
+    with_static_server("", &schema, None, None, |client| async move {
+        let response = client
+            .client()
+            .get(client.endpoint())
+            .header(http::header::ACCEPT, "text/html")
+            .send()
+            .await
+            .unwrap();
+
+        assert!(!response.text().await.unwrap().contains("<html"));
+    })
+}
+
 #[test]
 fn hybrid_graph() {
     let schema = load_schema("big");
@@ -668,6 +842,7 @@ fn hybrid_graph() {
                 "me"
               ],
               "extensions": {
+                "retryable": true,
                 "code": "SUBGRAPH_REQUEST_ERROR"
               }
             }
@@ -677,6 +852,376 @@ fn hybrid_graph() {
     });
 }
 
+#[test]
+fn gateway_not_ready() {
+    let temp_dir = tempdir().unwrap();
+
+    let config_path = temp_dir.path().join("grafbase.toml");
+    fs::write(&config_path, "").unwrap();
+
+    let addr = listen_address();
+
+    let res = runtime().block_on(async {
+        // Nothing is mounted, so the graph is never found and the engine never loaded.
+        let server = wiremock::MockServer::start().await;
+
+        let command = cmd!(
+            cargo_bin("grafbase-gateway"),
+            "--listen-address",
+            &addr.to_string(),
+            "--config",
+            &config_path.to_str().unwrap(),
+            "--graph-ref",
+            "test_graph",
+        )
+        .stdout_null()
+        .stderr_null()
+        .env("GRAFBASE_GDN_URL", format!("http://{}", server.address()))
+        .env("GRAFBASE_ACCESS_TOKEN", ACCESS_TOKEN);
+
+        let mut commands = CommandHandles::new();
+        commands.push(command.start().unwrap());
+
+        let client = Client::new(format!("http://{addr}/graphql"), commands);
+
+        let res = AssertUnwindSafe(async {
+            let start = Instant::now();
+
+            let response = loop {
+                let response = client
+                    .client()
+                    .post(client.endpoint())
+                    .json(&serde_json::json!({ "query": "query { __typename }" }))
+                    .send()
+                    .await;
+
+                if let Ok(response) = response {
+                    break response;
+                }
+
+                assert!(start.elapsed() < Duration::from_secs(30), "timeout");
+
+                tokio::time::sleep(Duration::from_millis(300)).await;
+            };
+
+            assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+            let body: serde_json::Value = response.json().await.unwrap();
+
+            insta::assert_json_snapshot!(&body, @r###"
+            {
+              "errors": [
+                {
+                  "message": "Gateway not ready",
+                  "extensions": {
+                    "code": "SERVICE_UNAVAILABLE",
+                    "retryable": true
+                  }
+                }
+              ]
+            }
+            "###);
+        })
+        .catch_unwind()
+        .await;
+
+        client.kill_handles();
+
+        res
+    });
+
+    res.unwrap();
+}
+
+#[test]
+fn registry_graph_update() {
+    let temp_dir = tempdir().unwrap();
+
+    let config_path = temp_dir.path().join("grafbase.toml");
+    fs::write(&config_path, "[graph]\nintrospection = true").unwrap();
+
+    let addr = listen_address();
+
+    let v1 = load_schema("big");
+    let v2 = v1.replace(
+        "type Query {\n",
+        "type Query {\n  version: String @join__field(graph: ACCOUNTS)\n",
+    );
+
+    let query = r#"query { __type(name: "Query") { fields { name } } }"#;
+
+    let res = runtime().block_on(async {
+        let server = wiremock::MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/supergraph"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(v1)
+                    .insert_header("ETag", "\"v1\""),
+            )
+            .mount(&server)
+            .await;
+
+        // Until the first version is checked, the registry has no updates.
+        let not_modified = Mock::given(method("GET"))
+            .and(path("/supergraph"))
+            .and(header("If-None-Match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .with_priority(1)
+            .mount_as_scoped(&server)
+            .await;
+
+        let command = cmd!(
+            cargo_bin("grafbase-gateway"),
+            "--listen-address",
+            &addr.to_string(),
+            "--config",
+            &config_path.to_str().unwrap(),
+            "--registry-url",
+            format!("http://{}/supergraph", server.address()),
+            "--registry-poll-interval",
+            "1",
+        )
+        .stdout_null()
+        .stderr_null();
+
+        let mut commands = CommandHandles::new();
+        commands.push(command.start().unwrap());
+
+        let client = Arc::new(Client::new(format!("http://{addr}/graphql"), commands));
+
+        client.poll_endpoint(30, 300).await;
+
+        let res = AssertUnwindSafe(async {
+            let result: serde_json::Value = client.gql(query).send().await;
+            insta::assert_json_snapshot!(&result, @r###"
+            {
+              "data": {
+                "__type": {
+                  "fields": [
+                    {
+                      "name": "me"
+                    },
+                    {
+                      "name": "topProducts"
+                    }
+                  ]
+                }
+              }
+            }
+            "###);
+
+            drop(not_modified);
+
+            Mock::given(method("GET"))
+                .and(path("/supergraph"))
+                .and(header("If-None-Match", "\"v1\""))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_string(v2)
+                        .insert_header("ETag", "\"v2\""),
+                )
+                .with_priority(1)
+                .mount(&server)
+                .await;
+
+            Mock::given(method("GET"))
+                .and(path("/supergraph"))
+                .and(header("If-None-Match", "\"v2\""))
+                .respond_with(ResponseTemplate::new(304))
+                .with_priority(1)
+                .mount(&server)
+                .await;
+
+            let start = Instant::now();
+
+            loop {
+                let result: serde_json::Value = client.gql(query).send().await;
+                let fields = result["data"]["__type"]["fields"].as_array().unwrap().len();
+
+                if fields == 3 {
+                    break;
+                }
+
+                assert!(start.elapsed() < Duration::from_secs(30), "timeout");
+
+                tokio::time::sleep(Duration::from_millis(300)).await;
+            }
+        })
+        .catch_unwind()
+        .await;
+
+        client.kill_handles();
+
+        res
+    });
+
+    res.unwrap();
+}
+
+#[test]
+fn registry_graph_rollback() {
+    let temp_dir = tempdir().unwrap();
+
+    let config_path = temp_dir.path().join("grafbase.toml");
+    let config = indoc! {r#"
+        [graph]
+        introspection = true
+
+        [graph.rollback]
+        error_ratio = 0.75
+        min_responses = 4
+    "#};
+    fs::write(&config_path, config).unwrap();
+
+    let addr = listen_address();
+
+    let query = r#"query { __type(name: "Query") { fields { name } } }"#;
+
+    let res = runtime().block_on(async {
+        let server = wiremock::MockServer::start().await;
+
+        // The first version works, while the second one has a broken subgraph URL.
+        let v1 = load_schema("big").replace("http://127.0.0.1:46697", &format!("{}/accounts", server.uri()));
+        let v2 = v1
+            .replace(
+                "type Query {\n",
+                "type Query {\n  version: String @join__field(graph: ACCOUNTS)\n",
+            )
+            .replace("/accounts", "/broken");
+
+        Mock::given(method("POST"))
+            .and(path("/accounts"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"data": {"me": {"id": "1"}}})))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path("/broken"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/supergraph"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(v1)
+                    .insert_header("ETag", "\"v1\""),
+            )
+            .mount(&server)
+            .await;
+
+        let not_modified = Mock::given(method("GET"))
+            .and(path("/supergraph"))
+            .and(header("If-None-Match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .with_priority(1)
+            .mount_as_scoped(&server)
+            .await;
+
+        let command = cmd!(
+            cargo_bin("grafbase-gateway"),
+            "--listen-address",
+            &addr.to_string(),
+            "--config",
+            &config_path.to_str().unwrap(),
+            "--registry-url",
+            format!("http://{}/supergraph", server.address()),
+            "--registry-poll-interval",
+            "1",
+        )
+        .stdout_null()
+        .stderr_null();
+
+        let mut commands = CommandHandles::new();
+        commands.push(command.start().unwrap());
+
+        let client = Arc::new(Client::new(format!("http://{addr}/graphql"), commands));
+
+        client.poll_endpoint(30, 300).await;
+
+        let res = AssertUnwindSafe(async {
+            let result: serde_json::Value = client.gql("query { me { id } }").send().await;
+            insta::assert_json_snapshot!(&result, @r###"
+            {
+              "data": {
+                "me": {
+                  "id": "1"
+                }
+              }
+            }
+            "###);
+
+            drop(not_modified);
+
+            Mock::given(method("GET"))
+                .and(path("/supergraph"))
+                .and(header("If-None-Match", "\"v1\""))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_string(v2)
+                        .insert_header("ETag", "\"v2\""),
+                )
+                .with_priority(1)
+                .mount(&server)
+                .await;
+
+            Mock::given(method("GET"))
+                .and(path("/supergraph"))
+                .and(header("If-None-Match", "\"v2\""))
+                .respond_with(ResponseTemplate::new(304))
+                .with_priority(1)
+                .mount(&server)
+                .await;
+
+            let start = Instant::now();
+
+            loop {
+                let result: serde_json::Value = client.gql(query).send().await;
+                let fields = result["data"]["__type"]["fields"].as_array().unwrap().len();
+
+                if fields == 3 {
+                    break;
+                }
+
+                assert!(start.elapsed() < Duration::from_secs(30), "timeout");
+
+                tokio::time::sleep(Duration::from_millis(300)).await;
+            }
+
+            // The second version fails, until reaching the ratio: the introspection query above
+            // succeeded, but not the three following ones.
+            for _ in 0..3 {
+                let result: serde_json::Value = client.gql("query { me { id } }").send().await;
+                assert!(
+                    result["errors"].as_array().is_some_and(|errors| !errors.is_empty()),
+                    "{result}"
+                );
+            }
+
+            let result: serde_json::Value = client.gql(query).send().await;
+            assert_eq!(
+                result["data"]["__type"]["fields"].as_array().unwrap().len(),
+                2,
+                "{result}"
+            );
+
+            let result: serde_json::Value = client.gql("query { me { id } }").send().await;
+            assert_eq!(result["data"]["me"]["id"], "1", "{result}");
+        })
+        .catch_unwind()
+        .await;
+
+        client.kill_handles();
+
+        res
+    });
+
+    res.unwrap();
+}
+
 #[test]
 fn health_default_config() {
     let config = "";
@@ -792,6 +1337,167 @@ fn health_custom_listener() {
     });
 }
 
+#[test]
+fn admin_sdl() {
+    let config = r#"
+        [admin]
+        secret = "admin-secret"
+    "#;
+
+    let schema = load_schema("big");
+
+    with_static_server(config, &schema, None, None, |client| async move {
+        let mut url: reqwest::Url = client.endpoint().parse().unwrap();
+        url.set_path("/admin/sdl");
+
+        let response = client.client().get(url.clone()).send().await.unwrap();
+        assert_eq!(response.status(), 401);
+
+        let response = client
+            .client()
+            .get(url.clone())
+            .bearer_auth("wrong-secret")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 401);
+
+        let response = client
+            .client()
+            .get(url)
+            .bearer_auth("admin-secret")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.text().await.unwrap(), load_schema("big"));
+    });
+}
+
+#[test]
+fn admin_subgraphs() {
+    let config = r#"
+        [admin]
+        secret = "admin-secret"
+    "#;
+
+    let schema = load_schema("big");
+
+    let query = indoc! {r#"
+        query Me {
+          me {
+            id
+          }
+        }
+    "#};
+
+    with_static_server(config, &schema, None, None, |client| async move {
+        // The accounts subgraph isn't running, so the request fails.
+        let _: serde_json::Value = client.gql(query).send().await;
+
+        let mut url: reqwest::Url = client.endpoint().parse().unwrap();
+        url.set_path("/admin/subgraphs");
+
+        let response = client.client().get(url.clone()).send().await.unwrap();
+        assert_eq!(response.status(), 401);
+
+        let response = client
+            .client()
+            .get(url)
+            .bearer_auth("admin-secret")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+
+        let body: serde_json::Value = response.json().await.unwrap();
+        let accounts = body["subgraphs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|subgraph| subgraph["name"] == "accounts")
+            .unwrap();
+
+        insta::assert_json_snapshot!(accounts, { ".p99_latency_ms" => "[latency]" }, @r###"
+        {
+          "name": "accounts",
+          "requests": 1,
+          "error_rate": 1.0,
+          "p99_latency_ms": "[latency]"
+        }
+        "###);
+
+        let idle = body["subgraphs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|subgraph| subgraph["name"] != "accounts")
+            .unwrap();
+
+        assert_eq!(idle["requests"], 0);
+        assert_eq!(idle["error_rate"], 0.0);
+        assert!(idle["p99_latency_ms"].is_null());
+    });
+}
+
+#[test]
+fn admin_schema_warnings() {
+    let config = indoc! {r#"
+        [admin]
+        secret = "admin-secret"
+
+        [subgraphs.unknown]
+        timeout = "1s"
+
+        [subgraphs.accounts.fields."User.unknown"]
+        timeout = "1s"
+    "#};
+
+    let schema = load_schema("big");
+
+    with_static_server(config, &schema, None, None, |client| async move {
+        let mut url: reqwest::Url = client.endpoint().parse().unwrap();
+        url.set_path("/admin/schema-warnings");
+
+        let response = client.client().get(url.clone()).send().await.unwrap();
+        assert_eq!(response.status(), 401);
+
+        let response = client
+            .client()
+            .get(url)
+            .bearer_auth("admin-secret")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+
+        let body: serde_json::Value = response.json().await.unwrap();
+        insta::assert_json_snapshot!(body, @r###"
+        {
+          "warnings": [
+            "Field 'User.unknown' configured for subgraph 'accounts' doesn't exist in the federated graph",
+            "Subgraph 'unknown' is configured but isn't part of the federated graph"
+          ]
+        }
+        "###);
+    });
+}
+
+#[test]
+fn admin_sdl_disabled_by_default() {
+    let config = "";
+    let schema = load_schema("big");
+
+    with_static_server(config, &schema, None, None, |client| async move {
+        let mut url: reqwest::Url = client.endpoint().parse().unwrap();
+        url.set_path("/admin/sdl");
+
+        let response = client.client().get(url).send().await.unwrap();
+
+        assert_eq!(response.status(), 404);
+    });
+}
+
 #[test]
 fn global_rate_limiting() {
     let config = indoc! {r#"
@@ -810,7 +1516,8 @@ fn global_rate_limiting() {
         }
     "#};
 
-    let expected_response = r#"{"errors":[{"message":"Too many requests","extensions":{"code":"RATE_LIMITED"}}]}"#;
+    let expected_response =
+        r#"{"errors":[{"message":"Too many requests","extensions":{"retryable":true,"code":"RATE_LIMITED"}}]}"#;
 
     with_static_server(config, &schema, None, None, |client| async move {
         expect_rate_limiting(|| client.gql(query).send().boxed(), expected_response).await;
@@ -835,7 +1542,7 @@ fn subgraph_rate_limiting() {
         }
     "#};
 
-    let expected_response = r#"{"data":null,"errors":[{"message":"Too many requests","path":["me"],"extensions":{"code":"RATE_LIMITED"}}]}"#;
+    let expected_response = r#"{"data":null,"errors":[{"message":"Too many requests","path":["me"],"extensions":{"retryable":true,"code":"RATE_LIMITED"}}]}"#;
 
     with_static_server(config, &schema, None, None, |client| async move {
         expect_rate_limiting(|| client.gql(query).send().boxed(), expected_response).await;
@@ -863,7 +1570,8 @@ fn global_redis_rate_limiting() {
         }
     "#};
 
-    let expected_response = r#"{"errors":[{"message":"Too many requests","extensions":{"code":"RATE_LIMITED"}}]}"#;
+    let expected_response =
+        r#"{"errors":[{"message":"Too many requests","extensions":{"retryable":true,"code":"RATE_LIMITED"}}]}"#;
 
     with_static_server(config, &schema, None, None, |client| async move {
         expect_rate_limiting(|| client.gql(query).send().boxed(), expected_response).await;
@@ -891,7 +1599,7 @@ fn subgraph_redis_rate_limiting() {
         }
     "#};
 
-    let expected_response = r#"{"data":null,"errors":[{"message":"Too many requests","path":["me"],"extensions":{"code":"RATE_LIMITED"}}]}"#;
+    let expected_response = r#"{"data":null,"errors":[{"message":"Too many requests","path":["me"],"extensions":{"retryable":true,"code":"RATE_LIMITED"}}]}"#;
 
     with_static_server(config, &schema, None, None, |client| async move {
         expect_rate_limiting(|| client.gql(query).send().boxed(), expected_response).await;
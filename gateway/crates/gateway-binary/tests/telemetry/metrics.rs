@@ -36,6 +36,14 @@ struct ExponentialHistogramRow {
 }
 
 fn with_gateway<T, F>(test: T)
+where
+    T: FnOnce(String, u64, Arc<Client>, &'static clickhouse::Client) -> F,
+    F: Future<Output = ()>,
+{
+    with_custom_gateway("", test)
+}
+
+fn with_custom_gateway<T, F>(extra_config: &str, test: T)
 where
     T: FnOnce(String, u64, Arc<Client>, &'static clickhouse::Client) -> F,
     F: Future<Output = ()>,
@@ -59,6 +67,8 @@ where
         [telemetry.exporters.otlp.batch_export]
         scheduled_delay = 1
         max_export_batch_size = 1
+
+        {extra_config}
     "#};
 
     let schema = load_schema("big");
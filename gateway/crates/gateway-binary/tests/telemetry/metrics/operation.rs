@@ -131,6 +131,7 @@ fn used_fields_should_be_unique() {
                 "me"
               ],
               "extensions": {
+                "retryable": true,
                 "code": "SUBGRAPH_REQUEST_ERROR"
               }
             }
@@ -189,6 +190,7 @@ fn generate_operation_name() {
                 }
               ],
               "extensions": {
+                "retryable": false,
                 "code": "OPERATION_VALIDATION_ERROR"
               }
             }
@@ -247,6 +249,7 @@ fn request_error() {
                 }
               ],
               "extensions": {
+                "retryable": false,
                 "code": "OPERATION_VALIDATION_ERROR"
               }
             }
@@ -303,6 +306,7 @@ fn field_error() {
                 "me"
               ],
               "extensions": {
+                "retryable": true,
                 "code": "SUBGRAPH_REQUEST_ERROR"
               }
             }
@@ -359,6 +363,7 @@ fn field_error_data_null() {
                 "me"
               ],
               "extensions": {
+                "retryable": true,
                 "code": "SUBGRAPH_REQUEST_ERROR"
               }
             }
@@ -1,6 +1,6 @@
 use crate::telemetry::metrics::METRICS_DELAY;
 
-use super::{with_gateway, ExponentialHistogramRow};
+use super::{with_custom_gateway, with_gateway, ExponentialHistogramRow};
 
 #[test]
 fn basic() {
@@ -58,6 +58,7 @@ fn request_error() {
                 }
               ],
               "extensions": {
+                "retryable": false,
                 "code": "OPERATION_PARSING_ERROR"
               }
             }
@@ -110,6 +111,7 @@ fn field_error() {
                 "me"
               ],
               "extensions": {
+                "retryable": true,
                 "code": "SUBGRAPH_REQUEST_ERROR"
               }
             }
@@ -159,6 +161,7 @@ fn field_error_data_null() {
                 "me"
               ],
               "extensions": {
+                "retryable": true,
                 "code": "SUBGRAPH_REQUEST_ERROR"
               }
             }
@@ -240,3 +243,59 @@ fn client() {
         "###);
     });
 }
+
+#[test]
+fn client_from_configured_headers() {
+    let config = indoc::indoc! {r#"
+        [gateway.client_identification]
+        name_headers = ["apollographql-client-name"]
+        version_headers = ["apollographql-client-version"]
+    "#};
+
+    with_custom_gateway(
+        config,
+        |service_name, start_time_unix, gateway, clickhouse| async move {
+            let resp = gateway
+                .gql::<serde_json::Value>("{ __typename }")
+                .header("apollographql-client-name", "test")
+                .header("apollographql-client-version", "1.0.0")
+                .send()
+                .await;
+            insta::assert_json_snapshot!(resp, @r###"
+        {
+          "data": {
+            "__typename": "Query"
+          }
+        }
+        "###);
+            tokio::time::sleep(METRICS_DELAY).await;
+
+            let row = clickhouse
+                .query(
+                    r#"
+                SELECT Count, Attributes
+                FROM otel_metrics_exponential_histogram
+                WHERE ServiceName = ? AND StartTimeUnix >= ?
+                    AND ScopeName = 'grafbase'
+                    AND MetricName = 'request_latency'
+                "#,
+                )
+                .bind(&service_name)
+                .bind(start_time_unix)
+                .fetch_optional::<ExponentialHistogramRow>()
+                .await
+                .unwrap();
+            insta::assert_json_snapshot!(row, @r###"
+        {
+          "Count": 1,
+          "Attributes": {
+            "gql.response.status": "SUCCESS",
+            "http.headers.x-grafbase-client-name": "test",
+            "http.headers.x-grafbase-client-version": "1.0.0",
+            "http.response.status_code": "200"
+          }
+        }
+        "###);
+        },
+    );
+}
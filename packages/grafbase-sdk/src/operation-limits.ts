@@ -2,18 +2,26 @@
  * Defines operation limits.
  */
 export interface OperationLimitsParams {
+  aliasedFetches?: number
   aliases?: number
   complexity?: number
   depth?: number
+  fields?: number
+  fragmentDepth?: number
+  fragmentSpreads?: number
   height?: number
   rootFields?: number
 }
 
 // FIXME: Find a way to "reflect" the keys of the interface above.
 const OPERATION_LIMITS_PARAMS_KEYS: (keyof OperationLimitsParams)[] = [
+  'aliasedFetches',
   'aliases',
   'complexity',
   'depth',
+  'fields',
+  'fragmentDepth',
+  'fragmentSpreads',
   'height',
   'rootFields'
 ] as (keyof OperationLimitsParams)[]